@@ -0,0 +1,62 @@
+//! Replay — re-flies a recorded flight through the FC and compares its decisions.
+//!
+//! Usage: `replay <records.ndjson> [tick hz]`
+//!
+//! Reads the NDJSON records of the GS backend (`logs/gs_records/<ts>/records.ndjson`),
+//! or an on-board log decoded by `log-decode`, and feeds their samples to the FSM at
+//! the recorded times. `tick hz` is the tick frequency of the recording FC, the host's
+//! by default. The replayed flight is logged to `logs/replay/`.
+//!
+//! Prints when each state was entered on the original flight and on the replay, and
+//! exits with an error if any decision differs.
+
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context};
+
+use flight_computer::interfaces::impls::{host::filesystem::HostFileSystem, replay::ReplayLog};
+use flight_computer::tasks::replay::replay_flight_computer;
+use utils::logging::{LogConfig, UiConfig};
+use utils::workspace;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let input = PathBuf::from(args.next().context("usage: replay <records.ndjson> [tick hz]")?);
+    let tick_hz = args.next().map_or(Ok(embassy_time::TICK_HZ), |hz| hz.parse()).context("tick hz must be an integer")?;
+    ensure!(tick_hz > 0, "tick hz must be non-zero");
+
+    utils::logging::install_panic_hook();
+    let _guard = utils::logging::init_tracing(LogConfig {
+        log_root: workspace::workspace_root().join("logs"),
+        stdout_level: utils::constants::STDOUT_LOG_LEVEL,
+        ui: UiConfig::Stdout,
+    })?;
+
+    let file = std::fs::File::open(&input).with_context(|| format!("failed to open {}", input.display()))?;
+    let log = ReplayLog::parse(std::io::BufReader::new(file), tick_hz)
+        .with_context(|| format!("failed to read {}", input.display()))?;
+    eprintln!("replaying {:.1} s of {}", log.duration().as_millis() as f64 / 1000.0, input.display());
+
+    let filesystem = HostFileSystem::new(workspace::workspace_root().join("logs").join("replay")).await;
+    let comparisons = replay_flight_computer(&log, filesystem).await;
+
+    println!("{:<18} {:>12} {:>12} {:>10}", "state", "original", "replayed", "offset");
+    let seconds = |at: Option<embassy_time::Duration>| {
+        at.map_or_else(|| "-".to_owned(), |at| format!("{:.3} s", at.as_millis() as f64 / 1000.0))
+    };
+    for comparison in &comparisons {
+        println!(
+            "{:<18} {:>12} {:>12} {:>10} {}",
+            format!("{:?}", comparison.state),
+            seconds(comparison.original),
+            seconds(comparison.replayed),
+            comparison.offset_ms().map_or_else(|| "-".to_owned(), |offset| format!("{offset:+} ms")),
+            if comparison.matches() { "ok" } else { "MISMATCH" },
+        );
+    }
+
+    let mismatches = comparisons.iter().filter(|comparison| !comparison.matches()).count();
+    ensure!(mismatches == 0, "{mismatches} decision(s) differ from the original flight");
+    Ok(())
+}
@@ -1,13 +1,16 @@
 use flight_computer::tasks::postcard::{
-    embassy_time_tick_hz_handler, ping_handler, Context,
+    calibrate_imu_handler, embassy_time_tick_hz_handler, flight_summary_handler, ping_handler, preflight_override_handler,
+    tilt_inhibits_handler, trace_span_handler,
+    Context,
 };
 use flight_computer::tasks::simulation::{
-    sim_altimeter_update, sim_arming_activate, sim_gps_update, sim_imu_update,
+    sim_altimeter_update, sim_arming_activate, sim_gps_update, sim_high_g_accelerometer_update, sim_imu_update,
 };
 use postcard_rpc::define_dispatch;
 use proto::wire::{
-    ENDPOINT_LIST, GlobalTickHzEndpoint, PingEndpoint, SimAltimeterTopic,
-    SimArmTopic, SimGpsTopic, SimImuTopic, TOPICS_GS_IN_LIST,
+    ENDPOINT_LIST, CalibrateImuEndpoint, FlightSummaryEndpoint, GlobalTickHzEndpoint, PingEndpoint, PreflightOverrideEndpoint,
+    TiltInhibitsEndpoint, TraceSpanEndpoint,
+    SimAltimeterTopic, SimArmTopic, SimGpsTopic, SimHighGAccelerometerTopic, SimImuTopic, TOPICS_GS_IN_LIST,
     TOPICS_GS_OUT_LIST, TOPICS_SIM_IN_LIST,
     TOPICS_SIM_OUT_LIST,
 };
@@ -38,6 +41,7 @@ pub(crate) mod sim {
                | SimAltimeterTopic | blocking  | sim_altimeter_update     |
                | SimGpsTopic       | blocking  | sim_gps_update           |
                | SimImuTopic       | blocking  | sim_imu_update           |
+               | SimHighGAccelerometerTopic | blocking | sim_high_g_accelerometer_update |
                | SimArmTopic       | blocking  | sim_arming_activate      |
         };
         topics_out: {
@@ -46,11 +50,13 @@ pub(crate) mod sim {
             // | TopicTy                   | MessageTy         | Path                  |
             // | ------------------------- | ----------------- | --------------------- |
             // | SimDeploymentTopic        | ActuatorStatus    | "sim_deployment"      |
+            // | SimBuzzerTopic            | ActuatorStatus    | "sim_buzzer"          |
             // /* ------------------------------------------ LEDs ----------------------------------------------- */
             // | SimPostcardLedTopic       | LedStatus         | "sim_postcard_led"    |
             // | SimAltimeterLedTopic      | LedStatus         | "sim_altimeter_led"   |
             // | SimGpsLedTopic            | LedStatus         | "sim_gps_led"         |
             // | SimImuLedTopic            | LedStatus         | "sim_imu_led"         |
+            // | SimHighGAccelerometerLedTopic | LedStatus     | "sim_high_g_accelerometer_led" |
             // | SimArmLedTopic            | LedStatus         | "sim_arm_led"         |
             // | SimFileSystemLedTopic     | LedStatus         | "sim_file_system_led" |
             // | SimDeploymentLedTopic     | LedStatus         | "sim_deployment_led"  |
@@ -74,10 +80,15 @@ pub(crate) mod gs {
         endpoints: {
             list: ENDPOINT_LIST;
 
-               | EndpointTy           | kind       | handler                      |
-               | -                    | -          | -                            |
-               | PingEndpoint         | blocking   | ping_handler                 |
-               | GlobalTickHzEndpoint | blocking   | embassy_time_tick_hz_handler |
+               | EndpointTy                | kind       | handler                      |
+               | -                         | -          | -                            |
+               | PingEndpoint              | blocking   | ping_handler                 |
+               | GlobalTickHzEndpoint      | blocking   | embassy_time_tick_hz_handler |
+               | TraceSpanEndpoint         | blocking   | trace_span_handler           |
+               | CalibrateImuEndpoint      | blocking   | calibrate_imu_handler        |
+               | PreflightOverrideEndpoint | blocking   | preflight_override_handler   |
+               | FlightSummaryEndpoint     | blocking   | flight_summary_handler       |
+               | TiltInhibitsEndpoint      | blocking   | tilt_inhibits_handler        |
         };
         topics_in: {
             list: TOPICS_GS_IN_LIST;
@@ -91,6 +102,8 @@ pub(crate) mod gs {
             // | TopicTy                   | MessageTy         | Path                  |
             // | ------------------------- | ----------------- | --------------------- |
             // | RecordTopic               | Record            | "record"              |
+            // | ImuCalibrationTopic       | ImuCalibrationStatus | "imu_calibration"  |
+            // | PreflightReportTopic      | PreflightReport   | "preflight_report"    |
         };
     }
 }
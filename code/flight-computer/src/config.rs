@@ -1,6 +1,13 @@
 use embassy_time::Duration;
-use proto::sensor_data::{Altitude, Velocity};
-use proto::uom::si::{length::meter, velocity::meter_per_second};
+use proto::preflight::PreflightCheck;
+use proto::sensor_data::{Acceleration, Altitude, Angle, Pressure, Velocity};
+use proto::tilt::TiltAction;
+use proto::uom::si::{acceleration::meter_per_second_squared, angle::degree, length::meter, pressure::pascal, velocity::meter_per_second};
+
+use crate::core::state_machine::detectors::ApogeeStrategy;
+use crate::core::storage::LogFormat;
+use crate::core::watchdog::Deadlines;
+use crate::interfaces::SampleRates;
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
 
@@ -13,30 +20,72 @@ impl ApogeeDetectorConfig {
     pub const DETECTOR_TICK_INTERVAL: Duration = Duration::from_millis(Self::DETECTOR_TICK_INTERVAL_MS);
     pub const DATA_WAIT_TIMEOUT: Duration = Duration::from_millis(Self::DETECTOR_TICK_INTERVAL_MS / 2);
 
+    pub const STRATEGY: ApogeeStrategy = ApogeeStrategy::DescentVelocity;
+
+    pub const SIGN_CHANGE_VELOCITY_WINDOW: usize = 3;
+    pub const PRESSURE_RISE_SAMPLES: usize = 2;
+    pub const PREDICTED_APOGEE_LEAD_TIME: Duration = Duration::from_millis(500);
+    pub const ESTIMATOR_ALPHA: f32 = 0.5;
+    pub const ESTIMATOR_BETA: f32 = 0.3;
+    pub const VOTES_TO_DETECT: usize = 2;
+
     #[inline]
     pub fn max_descent_velocity() -> Velocity { Velocity::new::<meter_per_second>(-1.0) }
     #[inline]
     pub fn min_apogee_altitude_above_launchpad() -> Altitude { Altitude::new::<meter>(0.0) }
+    #[inline]
+    pub fn min_ascent_velocity() -> Velocity { Velocity::new::<meter_per_second>(10.0) }
+    #[inline]
+    pub fn ascent_detection_altitude() -> Altitude { Altitude::new::<meter>(20.0) }
+    #[inline]
+    pub fn pressure_rise_threshold() -> Pressure { Pressure::new::<pascal>(12.0) }
 }
 
 pub struct DataAcquisitionConfig;
 impl DataAcquisitionConfig {
-    pub const ALTIMETER_TICK_INTERVAL: Duration = Duration::from_hz(50);
-    pub const IMU_TICK_INTERVAL: Duration = Duration::from_hz(50);
-    pub const GPS_TICK_INTERVAL: Duration = Duration::from_hz(10);
+    pub const ALTIMETER_SAMPLE_RATES: SampleRates = SampleRates {
+        pad: Duration::from_hz(20),
+        ascent: Duration::from_hz(50),
+        descent: Duration::from_hz(20),
+        landed: Duration::from_hz(5),
+    };
+    pub const IMU_SAMPLE_RATES: SampleRates = SampleRates {
+        pad: Duration::from_hz(10),
+        ascent: Duration::from_hz(100),
+        descent: Duration::from_hz(50),
+        landed: Duration::from_hz(10),
+    };
+    /// Matches the IMU through boost, so a fresh high-g sample is at hand whenever the IMU saturates.
+    pub const HIGH_G_ACCELEROMETER_SAMPLE_RATES: SampleRates = SampleRates {
+        pad: Duration::from_hz(10),
+        ascent: Duration::from_hz(100),
+        descent: Duration::from_hz(20),
+        landed: Duration::from_hz(10),
+    };
+    pub const GPS_SAMPLE_RATES: SampleRates = SampleRates {
+        pad: Duration::from_hz(2),
+        ascent: Duration::from_hz(10),
+        descent: Duration::from_hz(5),
+        // only feeds the recovery beacon, which transmits less often still
+        landed: Duration::from_secs(5),
+    };
 }
 
 pub struct TasksConfig;
 impl TasksConfig {
-    pub const FLIGHT_STATE_WATCH_CONSUMERS: usize = 5;
+    pub const FLIGHT_STATE_WATCH_CONSUMERS: usize = 12;
+    pub const SENSOR_HEALTH_WATCH_CONSUMERS: usize = 6;
 
     pub const RECORD_TO_STORAGE_CHANNEL_DEPTH: usize = 30;
+    /// Reserved for flight state, event, error and calibration records, so sensor bursts cannot drop them.
+    pub const PRIORITY_RECORD_TO_STORAGE_CHANNEL_DEPTH: usize = 10;
 }
 
 pub struct TouchdownDetectorConfig;
 impl TouchdownDetectorConfig {
-    pub const ALTITUDE_BUFFER_SIZE: usize = 10;
-    pub const VELOCITY_BUFFER_SIZE: usize = 10;
+    /// Both buffers are reported whole in `FsmEvent::TouchdownDetected`.
+    pub const ALTITUDE_BUFFER_SIZE: usize = proto::event::TOUCHDOWN_WINDOW;
+    pub const VELOCITY_BUFFER_SIZE: usize = proto::event::TOUCHDOWN_WINDOW;
 
     const DETECTOR_TICK_INTERVAL_MS: u64 = 1000;
     pub const DETECTOR_TICK_INTERVAL: Duration = Duration::from_millis(Self::DETECTOR_TICK_INTERVAL_MS);
@@ -48,12 +97,72 @@ impl TouchdownDetectorConfig {
     pub fn touchdown_velocity_threshold() -> Velocity { Velocity::new::<meter_per_second>(0.5) }
 }
 
+pub struct SensorHealthConfig;
+impl SensorHealthConfig {
+    pub const CHECK_TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+    pub const ALTIMETER_STALE_TIMEOUT: Duration = Duration::from_millis(250);
+    pub const GPS_STALE_TIMEOUT: Duration = Duration::from_secs(1);
+    pub const IMU_STALE_TIMEOUT: Duration = Duration::from_millis(250);
+    /// Only checked once the high-g accelerometer reported, not every board carries one.
+    pub const HIGH_G_ACCELEROMETER_STALE_TIMEOUT: Duration = Duration::from_millis(250);
+
+    /// Consecutive bit-identical samples before a sensor is considered stuck.
+    pub const STUCK_SAMPLES: usize = 25;
+    /// Consecutive implausible rates of change before a sensor is considered failed.
+    pub const RATE_VIOLATIONS_TO_FAIL: usize = 5;
+    /// Maximum plausible jerk, in m/s³ (uom has no jerk quantity).
+    pub const MAX_ACCELERATION_RATE: f32 = 5_000.0;
+    /// Weight of each GPS sample in the learned barometric offset, smoothing out the skew between the two streams.
+    pub const GPS_ALTITUDE_OFFSET_WEIGHT: f32 = 0.1;
+
+    #[inline]
+    pub fn max_altitude_rate() -> Velocity { Velocity::new::<meter_per_second>(400.0) }
+    #[inline]
+    pub fn max_baro_gps_disagreement() -> Altitude { Altitude::new::<meter>(100.0) }
+    #[inline]
+    pub fn boost_acceleration_threshold() -> Acceleration { Acceleration::new::<meter_per_second_squared>(30.0) }
+    #[inline]
+    pub fn max_boost_descent_velocity() -> Velocity { Velocity::new::<meter_per_second>(-10.0) }
+}
+
+pub struct AccelerationBlendingConfig;
+impl AccelerationBlendingConfig {
+    /// Fraction of the IMU full scale above which an axis is considered clipped.
+    pub const SATURATION_RATIO: f32 = 0.95;
+    /// Oldest high-g sample still standing in for a saturated IMU sample.
+    pub const HIGH_G_MAX_AGE: Duration = Duration::from_millis(50);
+}
+
 pub struct StorageConfig;
 impl StorageConfig {
     pub const WRITE_BUFFER_SIZE: usize = 576;
-    pub const MAX_FILENAME_LENGTH: usize = 8;
+    /// Tail of the write buffer holding the serialized record before it is framed.
+    pub const RECORD_SCRATCH_SIZE: usize = 256;
+    pub const LOG_FORMAT: LogFormat = LogFormat::Json;
+    /// 8.3 names, e.g. `00042_03.BIN` for the fourth file of flight 42.
+    pub const MAX_FILENAME_LENGTH: usize = 12;
+    /// Two copies written alternately, so a torn write keeps the previous index.
+    pub const INDEX_FILENAMES: [&str; 2] = ["INDEX_A.DAT", "INDEX_B.DAT"];
+    pub const IMU_CALIBRATION_FILENAME: &str = "IMU_CAL.DAT";
+    /// Flight numbers skipped when the next one already has files, e.g. after losing the index.
+    pub const MAX_FLIGHT_PROBES: u16 = 64;
+    /// A flight's log is rotated to a new file past this size.
+    pub const MAX_SEGMENT_SIZE: u32 = 16 * 1024 * 1024;
+    /// The oldest flights are pruned while less is left on the card, room for a long flight's log.
+    pub const MIN_FREE_SPACE: u64 = 256 * 1024 * 1024;
     pub const SD_VOLUME_IDX: usize = 0;
 
+    /// Full-rate records kept in RAM on the pad, about 5 s at the pad data acquisition rates.
+    pub const PRE_LAUNCH_BUFFER_SIZE: usize = 256;
+    /// Ready records written per storage task iteration, so the pre-launch buffer drains over
+    /// several iterations once launch is detected.
+    pub const MAX_WRITES_PER_ITERATION: usize = 16;
+    /// On the pad, only one in this many samples of each sensor is written to disk.
+    pub const PRE_LAUNCH_DECIMATION: u16 = 10;
+    /// Height above the launchpad in meters past which storage considers the rocket launched.
+    pub const LAUNCH_ALTITUDE_THRESHOLD: f32 = 10.0;
+
     pub const FLUSH_FILES_TICK_INTERVAL: Duration = Duration::from_millis(500);
     pub const TOUCHDOWN_HOLD_DURATION: Duration = Duration::from_secs(30);
 
@@ -61,11 +170,75 @@ impl StorageConfig {
     pub const FLUSH_TIMEOUT: Duration = DEFAULT_TIMEOUT;
 }
 
+pub struct TraceConfig;
+impl TraceConfig {
+    /// Spans kept until the ground station pulls them.
+    pub const SPAN_BUFFER_SIZE: usize = 128;
+}
+
+pub struct CheckpointConfig;
+impl CheckpointConfig {
+    /// Flash slot holding one serialized checkpoint, header and checksum included.
+    pub const SLOT_SIZE: usize = 256;
+
+    pub const LOAD_TIMEOUT: Duration = DEFAULT_TIMEOUT;
+    pub const STORE_TIMEOUT: Duration = DEFAULT_TIMEOUT;
+    /// Waited at boot for the altitude a checkpoint is checked against.
+    pub const ALTITUDE_TIMEOUT: Duration = DEFAULT_TIMEOUT;
+
+    /// Resets tolerated within one flight, a boot loop past that falls back to `PreArmed`.
+    pub const MAX_RESUMES: u8 = 3;
+
+    /// Height above the checkpoint's launchpad proving the flight is still in progress.
+    /// A checkpoint left over on the bench reads about zero, give or take the weather.
+    #[inline]
+    pub fn min_resume_altitude() -> Altitude { Altitude::new::<meter>(30.0) }
+}
+
 pub struct GroundStationConfig;
 impl GroundStationConfig {
     pub const SEND_SENSOR_DATA_TICK_INTERVAL: Duration = Duration::from_hz(10);
+    /// Landed, little but the beacon is left to send.
+    pub const LANDED_SEND_SENSOR_DATA_TICK_INTERVAL: Duration = Duration::from_secs(1);
 
     pub const PUBLISH_TIMEOUT: Duration = DEFAULT_TIMEOUT;
+    /// Six records, then the IMU calibration status, the pre-flight report and the beacon.
+    pub const MAX_PUBLISHES_PER_TICK: u64 = 9;
+}
+
+pub struct WatchdogConfig;
+impl WatchdogConfig {
+    /// Tasks holding a watchdog token at once.
+    pub const MAX_TASKS: usize = 8;
+    pub const CHECK_INTERVAL: Duration = Duration::from_millis(100);
+    /// The MCU resets once the supervisor stops feeding it for this long, after a flight-critical stall.
+    pub const HARDWARE_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// The state machine waits at most a checkpoint load, store or altitude timeout between wakes.
+    pub const FSM_DEADLINES: Deadlines = Deadlines::fixed(Duration::from_secs(3));
+    /// Sample intervals a sensor task may go silent for, an iteration waits up to two.
+    pub const SENSOR_DEADLINE_TICKS: u64 = 5;
+    /// Keeps the fastest sample rates clear of scheduling jitter.
+    pub const MIN_SENSOR_DEADLINE: Duration = Duration::from_millis(500);
+
+    // Non-critical tasks: a missed deadline is only reported, never withholds the hardware feed
+
+    /// An iteration may time out storing the IMU calibration and flushing, back to back.
+    /// Tighter in flight, where a stalled log loses the records that matter most.
+    pub const STORAGE_DEADLINES: Deadlines = Deadlines {
+        pad: Duration::from_secs(10),
+        ascent: Duration::from_secs(5),
+        descent: Duration::from_secs(5),
+        landed: Duration::from_secs(10),
+    };
+    /// Every publish of an iteration may time out on a dead link, after waiting out the landed tick.
+    pub const GROUND_STATION_DEADLINES: Deadlines = Deadlines::fixed(Duration::from_ticks(
+        GroundStationConfig::PUBLISH_TIMEOUT.as_ticks() * GroundStationConfig::MAX_PUBLISHES_PER_TICK
+            + GroundStationConfig::LANDED_SEND_SENSOR_DATA_TICK_INTERVAL.as_ticks(),
+    ));
+    pub const SENSOR_HEALTH_DEADLINES: Deadlines = Deadlines::fixed(Duration::from_secs(1));
+    /// A transmission may wait for the whole interval, then time out reading the battery.
+    pub const BEACON_DEADLINES: Deadlines = Deadlines::fixed(Duration::from_secs(15));
 }
 
 pub struct PostcardConfig;
@@ -73,9 +246,23 @@ impl PostcardConfig {
     pub const RECONNECT_INTERVAL: Duration = DEFAULT_TIMEOUT;
 }
 
-pub struct FiniteStateMachineConfig;
-impl FiniteStateMachineConfig {
-    pub const WAITING_ARM_INTERVAL: Duration = Duration::from_hz(10);
+pub struct BuzzerConfig;
+impl BuzzerConfig {
+    pub const SHORT_BEEP: Duration = Duration::from_millis(100);
+    pub const LONG_BEEP: Duration = Duration::from_millis(500);
+    /// Silence between the beeps of a group.
+    pub const BEEP_GAP: Duration = Duration::from_millis(200);
+    /// Silence between groups, e.g. the digits of the apogee.
+    pub const GROUP_GAP: Duration = Duration::from_millis(800);
+    /// Silence before a code repeats.
+    pub const REPEAT_PAUSE: Duration = Duration::from_secs(3);
+}
+
+pub struct BeaconConfig;
+impl BeaconConfig {
+    /// Time between transmissions once landed, the link idles in between.
+    pub const INTERVAL: Duration = Duration::from_secs(10);
+    pub const BATTERY_TIMEOUT: Duration = Duration::from_millis(500);
 }
 
 pub struct ArmedConfig;
@@ -89,11 +276,110 @@ impl AltimeterConfig {
     pub const REFERENCE_PRESSURE: f32 = 101_325.0;
 }
 
+pub struct PreflightConfig;
+impl PreflightConfig {
+    pub const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+    pub const CONTINUITY_TIMEOUT: Duration = Duration::from_millis(500);
+    pub const MIN_FIX_SATELLITES: u8 = 6;
+    /// Checks refusing the arm command until they pass. The GPS fix only matters for finding
+    /// the rocket after landing, and may take minutes to settle.
+    pub const MANDATORY_CHECKS: [PreflightCheck; 6] = [
+        PreflightCheck::Altimeter,
+        PreflightCheck::Imu,
+        PreflightCheck::Gps,
+        PreflightCheck::Storage,
+        PreflightCheck::Deployment,
+        PreflightCheck::Tilt,
+    ];
+}
+
+pub struct TiltConfig;
+impl TiltConfig {
+    /// IMU axis pointing to the rocket's nose.
+    pub const BODY_AXIS: [f32; 3] = [0.0, 0.0, 1.0];
+    /// Actions inhibited while the rocket stands further off vertical than `pad_limit`, or its tilt is unknown.
+    pub const PAD_INHIBITS: &'static [TiltAction] = &[TiltAction::Arm, TiltAction::Launch];
+    /// Actions inhibited for the rest of the flight once boost turned past `boost_limit`.
+    pub const BOOST_INHIBITS: &'static [TiltAction] = &[TiltAction::AirStart];
+
+    #[inline]
+    pub fn pad_limit() -> Angle { Angle::new::<degree>(10.0) }
+    #[inline]
+    pub fn boost_limit() -> Angle { Angle::new::<degree>(30.0) }
+    /// Acceleration magnitude telling boost from standing on the pad, as for the sensor health cross-check.
+    #[inline]
+    pub fn boost_acceleration_threshold() -> Acceleration { SensorHealthConfig::boost_acceleration_threshold() }
+}
+
+pub struct AhrsConfig;
+impl AhrsConfig {
+    /// Weight of the accelerometer and magnetometer corrections against the gyro rates, in 1/s.
+    pub const PROPORTIONAL_GAIN: f32 = 1.0;
+    /// Rate at which the gyro bias is learned from the corrections, in 1/s².
+    pub const INTEGRAL_GAIN: f32 = 0.02;
+    /// Attitude records are published at most this often, the filter runs on every IMU sample.
+    pub const PUBLISH_INTERVAL: Duration = Duration::from_millis(100);
+
+    #[inline]
+    pub fn gravity() -> Acceleration { Acceleration::new::<meter_per_second_squared>(9.806_65) }
+    /// The accelerometer only corrects the attitude while it reads this close to gravity, thrust and free fall hide it.
+    #[inline]
+    pub fn gravity_tolerance() -> Acceleration { Acceleration::new::<meter_per_second_squared>(1.0) }
+}
+
+pub struct FlightSummaryConfig;
+impl FlightSummaryConfig {
+    /// Weight of the newest altimeter sample in the smoothed climb rate.
+    pub const VELOCITY_SMOOTHING: f32 = 0.3;
+    /// Height above the launchpad in meters marking launch when the IMU never measured boost.
+    pub const LAUNCH_ALTITUDE_THRESHOLD: f32 = StorageConfig::LAUNCH_ALTITUDE_THRESHOLD;
+
+    /// Time the acceleration has to stay under the threshold before it counts as burnout.
+    pub const BURNOUT_CONFIRMATION: Duration = Duration::from_millis(100);
+
+    /// Acceleration magnitude telling boost from coast, as for the sensor health cross-check.
+    #[inline]
+    pub fn burn_acceleration_threshold() -> Acceleration { SensorHealthConfig::boost_acceleration_threshold() }
+}
+
+pub struct ReplayConfig;
+impl ReplayConfig {
+    /// Time the replayed FC keeps running past the last recorded sample, to settle its last decision.
+    pub const END_GRACE: Duration = Duration::from_secs(10);
+    /// Largest gap between a recorded and a replayed flight state change still counted as the same decision.
+    /// Arming lags the recording by the pad calibration, as the log only tells when the FC was armed.
+    pub const TRANSITION_TOLERANCE: Duration = Duration::from_secs(2);
+    /// Accelerometer full scale of the BNO055 that recorded the logs, in g, fixed by its fusion modes.
+    /// The log does not tell which IMU wrote it, a log of another board needs this changed to replay.
+    pub const IMU_FULL_SCALE: f32 = 4.0;
+}
+
+pub struct PadCalibrationConfig;
+impl PadCalibrationConfig {
+    /// One second of altimeter samples at the pad rate.
+    pub const SAMPLES: u16 = 20;
+    pub const TIMEOUT: Duration = Duration::from_secs(3);
+}
+
 #[cfg(feature = "impl_embedded")]
 pub mod embedded {
     use bmp280_ehal::{Config, Control, Filter, Oversampling, PowerMode, Standby};
     use bno055::{BNO055OperationMode, BNO055PowerMode};
     use nmea::SentenceType;
+    use proto::flight_state::FlightState;
+
+    #[cfg(feature = "bmp3xx")]
+    use crate::interfaces::impls::embedded::sensor::bmp3xx::{self, Bmp3xxSettings, IirFilter, OutputDataRate};
+    #[cfg(feature = "ms5611")]
+    use crate::interfaces::impls::embedded::sensor::ms5611;
+    #[cfg(feature = "icm42688")]
+    use crate::interfaces::impls::embedded::sensor::icm42688;
+    #[cfg(feature = "mpu")]
+    use crate::interfaces::impls::embedded::sensor::mpu;
+    #[cfg(feature = "adxl375")]
+    use crate::interfaces::impls::embedded::sensor::adxl375;
+    #[cfg(feature = "ublox")]
+    use crate::interfaces::impls::embedded::sensor::ublox;
 
     pub struct Bmp280Config;
     impl Bmp280Config {
@@ -101,11 +387,68 @@ pub mod embedded {
             filter: Filter::c16,
             t_sb: Standby::ms0_5,
         };
-        pub const CONTROL: Control = Control {
-            osrs_t: Oversampling::x1,
-            osrs_p: Oversampling::x4,
-            mode: PowerMode::Normal,
-        };
+        pub const CONTROL: Control = Self::control(FlightState::PreArmed);
+
+        /// Pressure oversampling traded against the phase's sample rate, see `ALTIMETER_SAMPLE_RATES`.
+        #[must_use]
+        pub const fn control(state: FlightState) -> Control {
+            let osrs_p = match state {
+                FlightState::PreArmed | FlightState::Touchdown => Oversampling::x16,
+                FlightState::Armed => Oversampling::x4,
+                FlightState::RecoveryActivated => Oversampling::x8,
+            };
+            Control {
+                osrs_t: Oversampling::x1,
+                osrs_p,
+                mode: PowerMode::Normal,
+            }
+        }
+    }
+
+    #[cfg(feature = "bmp3xx")]
+    pub struct Bmp3xxConfig;
+    #[cfg(feature = "bmp3xx")]
+    impl Bmp3xxConfig {
+        /// With SDO pulled high, `0x76` with SDO to ground.
+        pub const ADDRESS: u8 = 0x77;
+        pub const RESET_DELAY: embassy_time::Duration = embassy_time::Duration::from_millis(2);
+        /// Light smoothing, the apogee detectors filter the altitude themselves.
+        pub const IIR_FILTER: IirFilter = IirFilter::Coefficient3;
+
+        /// Pressure oversampling traded against the phase's sample rate, as for the BMP280.
+        /// Each output data rate leaves time for the measurement, or the sensor rejects it.
+        #[must_use]
+        pub const fn settings(state: FlightState) -> Bmp3xxSettings {
+            let (pressure_oversampling, output_data_rate) = match state {
+                FlightState::PreArmed | FlightState::Touchdown => (bmp3xx::Oversampling::X16, OutputDataRate::Hz25),
+                FlightState::Armed => (bmp3xx::Oversampling::X4, OutputDataRate::Hz50),
+                FlightState::RecoveryActivated => (bmp3xx::Oversampling::X8, OutputDataRate::Hz25),
+            };
+            let temperature_oversampling = match pressure_oversampling {
+                bmp3xx::Oversampling::X16 | bmp3xx::Oversampling::X32 => bmp3xx::Oversampling::X2,
+                _ => bmp3xx::Oversampling::X1,
+            };
+            Bmp3xxSettings { pressure_oversampling, temperature_oversampling, output_data_rate }
+        }
+    }
+
+    #[cfg(feature = "ms5611")]
+    pub struct Ms5611Config;
+    #[cfg(feature = "ms5611")]
+    impl Ms5611Config {
+        pub const RESET_DELAY: embassy_time::Duration = embassy_time::Duration::from_millis(3);
+        /// Temperature drifts slowly, its conversion is kept short to leave time for the pressure's.
+        pub const TEMPERATURE_OVERSAMPLING: ms5611::Oversampling = ms5611::Oversampling::Osr1024;
+
+        /// Both conversions of a sample must fit in the phase's tick, see `ALTIMETER_SAMPLE_RATES`.
+        #[must_use]
+        pub const fn pressure_oversampling(state: FlightState) -> ms5611::Oversampling {
+            match state {
+                FlightState::PreArmed | FlightState::Touchdown => ms5611::Oversampling::Osr4096,
+                FlightState::Armed => ms5611::Oversampling::Osr1024,
+                FlightState::RecoveryActivated => ms5611::Oversampling::Osr2048,
+            }
+        }
     }
 
     pub struct Bno055Config;
@@ -114,6 +457,75 @@ pub mod embedded {
         pub const OPERATION_MODE: BNO055OperationMode = BNO055OperationMode::NDOF;
         pub const POWER_MODE: BNO055PowerMode = BNO055PowerMode::NORMAL;
         pub const USE_EXTERNAL_CRYSTAL: bool = true;
+        /// Longest operation mode switch, from any mode to config mode (datasheet table 3-6).
+        pub const MODE_SWITCH_DELAY: embassy_time::Duration = embassy_time::Duration::from_millis(19);
+    }
+
+    #[cfg(feature = "icm42688")]
+    pub struct Icm42688Config;
+    #[cfg(feature = "icm42688")]
+    impl Icm42688Config {
+        pub const RESET_DELAY: embassy_time::Duration = embassy_time::Duration::from_millis(1);
+        /// Gyroscope start-up, its first packets are flagged invalid and skipped anyway.
+        pub const STARTUP_DELAY: embassy_time::Duration = embassy_time::Duration::from_millis(30);
+        pub const ACCEL_RANGE: icm42688::AccelRange = icm42688::AccelRange::G16;
+        pub const GYRO_RANGE: icm42688::GyroRange = icm42688::GyroRange::Dps2000;
+        /// Packets read per sample, with headroom over the ten queued per tick.
+        pub const FIFO_BURST_PACKETS: usize = 16;
+
+        /// About ten packets per tick of `IMU_SAMPLE_RATES`, averaged into one sample.
+        #[must_use]
+        pub const fn output_data_rate(state: FlightState) -> icm42688::OutputDataRate {
+            match state {
+                FlightState::PreArmed | FlightState::Touchdown => icm42688::OutputDataRate::Hz100,
+                FlightState::Armed => icm42688::OutputDataRate::Hz1000,
+                FlightState::RecoveryActivated => icm42688::OutputDataRate::Hz500,
+            }
+        }
+    }
+
+    #[cfg(feature = "mpu")]
+    pub struct MpuConfig;
+    #[cfg(feature = "mpu")]
+    impl MpuConfig {
+        /// With AD0 to ground, `0x69` with AD0 pulled high.
+        pub const ADDRESS: u8 = 0x68;
+        pub const RESET_DELAY: embassy_time::Duration = embassy_time::Duration::from_millis(100);
+        /// Settling time of the MPU-9250's magnetometer between operating modes.
+        pub const MAGNETOMETER_MODE_DELAY: embassy_time::Duration = embassy_time::Duration::from_millis(1);
+        /// 44 Hz bandwidth (41 Hz on the MPU-9250), sampling internally at 1 kHz.
+        pub const DIGITAL_LOW_PASS_FILTER: u8 = 3;
+        pub const ACCEL_RANGE: mpu::AccelRange = mpu::AccelRange::G16;
+        pub const GYRO_RANGE: mpu::GyroRange = mpu::GyroRange::Dps2000;
+        /// Samples read per tick, with headroom over the ten queued per tick. Fits both FIFOs.
+        pub const FIFO_BURST_SAMPLES: usize = 16;
+
+        /// Divides the 1 kHz internal rate down to about ten samples per tick of `IMU_SAMPLE_RATES`.
+        #[must_use]
+        pub const fn sample_rate_divider(state: FlightState) -> u8 {
+            match state {
+                FlightState::PreArmed | FlightState::Touchdown => 9,
+                FlightState::Armed => 0,
+                FlightState::RecoveryActivated => 1,
+            }
+        }
+    }
+
+    #[cfg(feature = "adxl375")]
+    pub struct Adxl375Config;
+    #[cfg(feature = "adxl375")]
+    impl Adxl375Config {
+        /// With ALT ADDRESS to ground, `0x1D` with it pulled high.
+        pub const ADDRESS: u8 = 0x53;
+
+        /// At least four times the sample rate of `HIGH_G_ACCELEROMETER_SAMPLE_RATES`, so each read is fresh.
+        #[must_use]
+        pub const fn output_data_rate(state: FlightState) -> adxl375::OutputDataRate {
+            match state {
+                FlightState::PreArmed | FlightState::RecoveryActivated | FlightState::Touchdown => adxl375::OutputDataRate::Hz100,
+                FlightState::Armed => adxl375::OutputDataRate::Hz400,
+            }
+        }
     }
 
     pub struct GpsConfig;
@@ -122,6 +534,25 @@ pub mod embedded {
             SentenceType::GGA,
         ];
     }
+
+    /// Applied over UBX at every boot, only the port settings are taken from the receiver's flash.
+    ///
+    /// Takes the dynamic model and NMEA output of `gps_config/configured_gnss.txt` and adds NAV-PVT.
+    /// The measurement rate follows `GPS_SAMPLE_RATES` instead of the dump's 70 ms, so fixes never
+    /// queue up in the UART. The port is not reconfigured, the acknowledgement of a baud rate change
+    /// would already arrive at the new rate.
+    #[cfg(feature = "ublox")]
+    pub struct UbloxConfig;
+    #[cfg(feature = "ublox")]
+    impl UbloxConfig {
+        pub const DYNAMIC_MODEL: ublox::DynamicModel = ublox::DynamicModel::Airborne4g;
+        /// GLL, GSA, GSV, RMC and VTG, GGA stays enabled as in the dump and is skipped by the parser.
+        pub const DISABLED_NMEA_SENTENCES: &'static [u8] = &[0x01, 0x02, 0x03, 0x04, 0x05];
+        /// UART1 as stored in the receiver's flash, the board must open its UART at this rate.
+        pub const BAUD_RATE: u32 = 57_600;
+        /// The receiver answers configuration messages within a second.
+        pub const ACK_TIMEOUT: embassy_time::Duration = embassy_time::Duration::from_secs(1);
+    }
 }
 
 #[cfg(feature = "impl_host")]
@@ -132,5 +563,8 @@ pub mod host {
         /// Absolute path to the host storage directory (`<workspace>/code/host_storage/`).
         pub const STORAGE_PATH: &str =
             concat!(env!("CARGO_MANIFEST_DIR"), "/../host_storage");
+        /// Flight checkpoint file, kept next to the flight logs.
+        pub const CHECKPOINT_PATH: &str =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../host_storage/checkpoint.json");
     }
 }
@@ -0,0 +1,248 @@
+use core::f32::consts::PI;
+
+use embassy_time::{Duration, Instant};
+use proto::sensor_data::{Angle, AttitudeData, ImuData, UnitQuaternion, Vector3};
+#[allow(unused_imports)]
+use proto::uom::num_traits::Float;
+use proto::uom::si::{
+    acceleration::meter_per_second_squared, angle::radian, angular_velocity::radian_per_second,
+    magnetic_flux_density::microtesla,
+};
+
+use crate::config::AhrsConfig;
+
+/// Mahony filter: integrates the gyro rates, pulled towards gravity as read by the accelerometer
+/// and towards magnetic north as read by the magnetometer.
+///
+/// Needs no on-chip fusion, only the raw [`ImuData`].
+#[derive(Default)]
+pub struct Ahrs {
+    /// Rotates the IMU frame into the ground frame, `None` until gravity was measured.
+    attitude: Option<UnitQuaternion<f32>>,
+    /// Learned gyro bias, in rad/s.
+    integral: Vector3<f32>,
+    last_update: Option<Instant>,
+    heading_referenced: bool,
+}
+
+impl Ahrs {
+    pub fn update(&mut self, data: &ImuData, now: Instant) {
+        let up = gravity(data);
+        let north = magnetic_field(data);
+
+        let Some(attitude) = self.attitude else {
+            // level from the first clean gravity reading, rather than converging from an arbitrary guess
+            self.attitude = up.map(|up| initial_attitude(up, north));
+            self.last_update = self.attitude.map(|_| now);
+            self.heading_referenced = self.attitude.is_some() && north.is_some();
+            return;
+        };
+
+        let dt = self.last_update.filter(|last| now > *last).map_or(0.0, |last| seconds(now - last));
+        self.last_update = Some(now);
+
+        let mut error = Vector3::zeros();
+        if let Some(up) = up {
+            error += up.cross(&attitude.inverse_transform_vector(&Vector3::z()));
+        }
+        if let Some(field) = north {
+            // only the heading is taken from the field, its dip is whatever the estimate says
+            let ground = attitude.transform_vector(&field);
+            let reference = Vector3::new(ground.xy().norm(), 0.0, ground.z);
+            error += field.cross(&attitude.inverse_transform_vector(&reference));
+        }
+        self.heading_referenced = north.is_some();
+
+        self.integral += error * (AhrsConfig::INTEGRAL_GAIN * dt);
+        let corrected = rates(data) + error * AhrsConfig::PROPORTIONAL_GAIN + self.integral;
+        self.attitude = Some(attitude * UnitQuaternion::from_scaled_axis(corrected * dt));
+    }
+
+    /// Direction of up in the IMU frame, `None` until gravity was measured.
+    #[must_use]
+    pub fn up(&self) -> Option<Vector3<f32>> {
+        self.attitude.map(|attitude| attitude.inverse_transform_vector(&Vector3::z()))
+    }
+
+    /// Latest estimate, `None` until gravity was measured.
+    #[must_use]
+    pub fn attitude(&self) -> Option<AttitudeData> {
+        self.attitude.map(|attitude| {
+            let (roll, pitch, yaw) = attitude.euler_angles();
+            let quaternion = attitude.quaternion();
+            AttitudeData {
+                quaternion: [quaternion.w, quaternion.i, quaternion.j, quaternion.k],
+                roll: Angle::new::<radian>(roll),
+                pitch: Angle::new::<radian>(pitch),
+                yaw: Angle::new::<radian>(yaw),
+                heading_referenced: self.heading_referenced,
+            }
+        })
+    }
+}
+
+fn initial_attitude(up: Vector3<f32>, north: Option<Vector3<f32>>) -> UnitQuaternion<f32> {
+    let level = UnitQuaternion::rotation_between(&up, &Vector3::z())
+        .unwrap_or_else(|| UnitQuaternion::from_axis_angle(&Vector3::x_axis(), PI));
+    let Some(field) = north else {
+        return level;
+    };
+    let ground = level.transform_vector(&field);
+    UnitQuaternion::from_axis_angle(&Vector3::z_axis(), -ground.y.atan2(ground.x)) * level
+}
+
+/// Direction of up, if the accelerometer reads gravity alone.
+fn gravity(data: &ImuData) -> Option<Vector3<f32>> {
+    let acceleration = data.acceleration.map(|axis| axis.get::<meter_per_second_squared>());
+    let magnitude = acceleration.norm();
+    let off_gravity = (magnitude - AhrsConfig::gravity().get::<meter_per_second_squared>()).abs();
+    (off_gravity <= AhrsConfig::gravity_tolerance().get::<meter_per_second_squared>()).then(|| acceleration / magnitude)
+}
+
+/// Direction of the magnetic field, `None` for IMUs without a magnetometer.
+fn magnetic_field(data: &ImuData) -> Option<Vector3<f32>> {
+    data.mag.map(|axis| axis.get::<microtesla>()).try_normalize(f32::EPSILON)
+}
+
+fn rates(data: &ImuData) -> Vector3<f32> {
+    data.gyro.map(|axis| axis.get::<radian_per_second>())
+}
+
+fn seconds(duration: Duration) -> f32 {
+    duration.as_micros() as f32 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::sensor_data::MagneticFluxDensity;
+    use proto::uom::si::angle::degree;
+
+    use crate::test_utils::at;
+    use crate::test_utils::sensor_data::imu;
+
+    use super::*;
+
+    const G: f32 = 9.806_65;
+
+    fn magnetized(data: ImuData, mag: [f32; 3]) -> ImuData {
+        ImuData { mag: Vector3::from(mag).map(MagneticFluxDensity::new::<microtesla>), ..data }
+    }
+
+    fn level(rates: [f32; 3]) -> ImuData {
+        imu([0.0, 0.0, G], rates)
+    }
+
+    /// Feeds `data` at 100 Hz from `from_ms` up to `until_ms`.
+    fn run(ahrs: &mut Ahrs, data: &ImuData, from_ms: u64, until_ms: u64) {
+        for ms in (from_ms..=until_ms).step_by(10) {
+            ahrs.update(data, at(ms));
+        }
+    }
+
+    fn estimate(ahrs: &Ahrs) -> AttitudeData {
+        ahrs.attitude().expect("Attitude should be initialized")
+    }
+
+    fn assert_close(angle: Angle, expected_deg: f32, tolerance_deg: f32) {
+        let measured = angle.get::<degree>();
+        assert!((measured - expected_deg).abs() < tolerance_deg, "Expected {expected_deg} deg, measured {measured} deg");
+    }
+
+    #[rstest::rstest]
+    fn unknown_until_gravity_measured() {
+        let mut ahrs = Ahrs::default();
+        ahrs.update(&imu([0.0, 30.0, 60.0], [0.0; 3]), at(0));
+
+        assert_eq!(ahrs.attitude(), None);
+    }
+
+    #[rstest::rstest]
+    #[case::roll([0.0, 0.5, 0.866_025_4], 30.0, 0.0)]
+    #[case::pitch([-0.5, 0.0, 0.866_025_4], 0.0, 30.0)]
+    fn initializes_from_gravity(#[case] up: [f32; 3], #[case] roll: f32, #[case] pitch: f32) {
+        let mut ahrs = Ahrs::default();
+        ahrs.update(&imu(up.map(|axis| axis * G), [0.0; 3]), at(0));
+
+        let attitude = estimate(&ahrs);
+
+        assert_close(attitude.roll, roll, 0.1);
+        assert_close(attitude.pitch, pitch, 0.1);
+        assert!(!attitude.heading_referenced);
+    }
+
+    #[rstest::rstest]
+    #[case::yaw([0.0, 0.0, 0.5])]
+    #[case::pitch([0.0, 0.3, 0.0])]
+    fn integrates_gyro_rates(#[case] rates: [f32; 3]) {
+        let mut ahrs = Ahrs::default();
+        ahrs.update(&level([0.0; 3]), at(0));
+        // free fall hides gravity, the gyro alone turns the estimate
+        run(&mut ahrs, &imu([0.0; 3], rates), 10, 1_000);
+
+        let attitude = estimate(&ahrs);
+
+        assert_close(attitude.roll, rates[0].to_degrees(), 0.5);
+        assert_close(attitude.pitch, rates[1].to_degrees(), 0.5);
+        assert_close(attitude.yaw, rates[2].to_degrees(), 0.5);
+    }
+
+    #[rstest::rstest]
+    fn converges_to_measured_gravity() {
+        let mut ahrs = Ahrs::default();
+        ahrs.update(&level([0.0; 3]), at(0));
+        let (sin, cos) = 20.0_f32.to_radians().sin_cos();
+        run(&mut ahrs, &imu([0.0, G * sin, G * cos], [0.0; 3]), 10, 5_000);
+
+        assert_close(estimate(&ahrs).roll, 20.0, 1.0);
+    }
+
+    #[rstest::rstest]
+    fn ignores_accelerometer_under_thrust() {
+        let mut ahrs = Ahrs::default();
+        ahrs.update(&level([0.0; 3]), at(0));
+        run(&mut ahrs, &imu([0.0, 30.0, 60.0], [0.0; 3]), 10, 2_000);
+
+        assert_close(estimate(&ahrs).roll, 0.0, 0.01);
+    }
+
+    /// Specific force along a rail tilted by 3°, held through boost and coast without gyro rates.
+    #[rstest::rstest]
+    fn holds_simulated_rail_angle_through_flight() {
+        let (sin, cos) = 3.0_f32.to_radians().sin_cos();
+        let simulated = |force: f32| imu([0.0, force * sin, force * cos], [0.0; 3]);
+        let mut ahrs = Ahrs::default();
+
+        run(&mut ahrs, &simulated(G), 0, 2_000);
+        assert_close(estimate(&ahrs).roll, 3.0, 0.1);
+
+        run(&mut ahrs, &simulated(8.0 * G), 2_010, 4_000);
+        run(&mut ahrs, &simulated(0.5), 4_010, 10_000);
+        assert_close(estimate(&ahrs).roll, 3.0, 0.1);
+        assert_close(estimate(&ahrs).pitch, 0.0, 0.1);
+    }
+
+    #[rstest::rstest]
+    fn magnetometer_sets_heading() {
+        let yaw = 60.0_f32.to_radians();
+        let mut ahrs = Ahrs::default();
+        ahrs.update(&magnetized(imu([0.0, 0.0, G], [0.0; 3]), [20.0 * yaw.cos(), -20.0 * yaw.sin(), -40.0]), at(0));
+
+        let attitude = estimate(&ahrs);
+
+        assert_close(attitude.yaw, 60.0, 0.1);
+        assert_close(attitude.roll, 0.0, 0.1);
+        assert!(attitude.heading_referenced);
+    }
+
+    #[rstest::rstest]
+    fn magnetometer_holds_heading_against_gyro_bias() {
+        let bias = [0.0, 0.0, 0.05];
+        let mut drifting = Ahrs::default();
+        run(&mut drifting, &level(bias), 0, 10_000);
+        let mut referenced = Ahrs::default();
+        run(&mut referenced, &magnetized(imu([0.0, 0.0, G], bias), [20.0, 0.0, -10.0]), 0, 10_000);
+
+        assert_close(estimate(&drifting).yaw, 0.5_f32.to_degrees(), 0.5);
+        assert_close(estimate(&referenced).yaw, 0.0, 0.1_f32.to_degrees());
+    }
+}
@@ -0,0 +1,95 @@
+use embassy_time::Instant;
+use nmea::sentences::FixType;
+use proto::beacon::Beacon;
+use proto::flight_state::FlightSummary;
+use proto::sensor_data::{ElectricPotential, GpsData};
+
+/// Last known position of the landed rocket.
+#[derive(Default)]
+pub struct BeaconTracker {
+    last_fix: Option<(GpsData, Instant)>,
+}
+
+impl BeaconTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps the sample if it has a position, any fix beats none when searching for the rocket.
+    pub fn update_gps(&mut self, data: GpsData, now: Instant) {
+        if !matches!(*data.fix_type, FixType::Invalid) {
+            self.last_fix = Some((data, now));
+        }
+    }
+
+    #[must_use]
+    pub fn beacon(&self, now: Instant, battery: Option<ElectricPotential>, summary: Option<FlightSummary>) -> Beacon {
+        Beacon {
+            fix: self.last_fix.as_ref().map(|(fix, _)| fix.clone()),
+            fix_age_ms: self.last_fix.as_ref().map(|(_, received)| {
+                u32::try_from(now.saturating_duration_since(*received).as_millis()).unwrap_or(u32::MAX)
+            }),
+            battery,
+            summary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::at;
+    use crate::test_utils::sensor_data::random_gps_data;
+
+    use super::*;
+
+    fn gps_data(fix_type: FixType) -> GpsData {
+        GpsData { fix_type: fix_type.into(), ..random_gps_data() }
+    }
+
+    #[rstest::rstest]
+    fn reports_nothing_before_first_fix() {
+        let mut tracker = BeaconTracker::new();
+        tracker.update_gps(gps_data(FixType::Invalid), at(0));
+
+        let beacon = tracker.beacon(at(1_000), None, None);
+
+        assert_eq!(beacon.fix, None);
+        assert_eq!(beacon.fix_age_ms, None);
+    }
+
+    #[rstest::rstest]
+    fn keeps_last_fix_through_lost_signal() {
+        let mut tracker = BeaconTracker::new();
+        let fix = gps_data(FixType::Gps);
+        tracker.update_gps(fix.clone(), at(1_000));
+        tracker.update_gps(gps_data(FixType::Invalid), at(5_000));
+
+        let beacon = tracker.beacon(at(61_000), None, None);
+
+        assert_eq!(beacon.fix, Some(fix));
+        assert_eq!(beacon.fix_age_ms, Some(60_000));
+    }
+
+    #[rstest::rstest]
+    #[case(FixType::Gps)]
+    #[case(FixType::Simulation)]
+    #[case(FixType::Estimated)]
+    fn any_position_counts_as_fix(#[case] fix_type: FixType) {
+        let mut tracker = BeaconTracker::new();
+        tracker.update_gps(gps_data(fix_type), at(0));
+
+        assert_eq!(tracker.beacon(at(0), None, None).fix_age_ms, Some(0));
+    }
+
+    #[rstest::rstest]
+    fn carries_battery_and_summary() {
+        let summary = FlightSummary::default();
+        let battery = ElectricPotential::new::<proto::uom::si::electric_potential::volt>(7.4);
+
+        let beacon = BeaconTracker::new().beacon(at(0), Some(battery), Some(summary));
+
+        assert_eq!(beacon.battery, Some(battery));
+        assert_eq!(beacon.summary, Some(summary));
+    }
+}
@@ -0,0 +1,175 @@
+use embassy_time::{Duration, Timer};
+use proto::flight_state::FlightState;
+use proto::sensor_data::Altitude;
+use proto::sensor_health::{SensorHealth, SensorHealthReport, SensorId};
+use proto::uom::si::length::meter;
+
+use crate::config::BuzzerConfig;
+use crate::interfaces::Buzzer;
+
+/// Digits of the largest apogee, `u32::MAX` meters.
+const MAX_GROUPS: usize = 10;
+
+/// Groups of beeps, as number of beeps and length of each.
+pub type Groups = heapless::Vec<(u8, Duration), MAX_GROUPS>;
+
+/// Audible status, repeated after [`BuzzerConfig::REPEAT_PAUSE`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BeepCode {
+    /// Pre-flight checks passed: one long beep.
+    Ready,
+    /// Sensor failing its pre-flight checks: one, two or three long beeps for altimeter, GPS or IMU.
+    SensorFault(SensorId),
+    /// Three short beeps.
+    Armed,
+    /// One short chirp to home in on, while no apogee is known.
+    Locator,
+    /// Apogee in meters, one group of short beeps per digit, ten for a zero.
+    Apogee(u32),
+}
+
+impl BeepCode {
+    #[must_use]
+    pub fn groups(self) -> Groups {
+        let mut groups = Groups::new();
+        let group = match self {
+            Self::Ready => (1, BuzzerConfig::LONG_BEEP),
+            Self::SensorFault(sensor) => (sensor_number(sensor), BuzzerConfig::LONG_BEEP),
+            Self::Armed => (3, BuzzerConfig::SHORT_BEEP),
+            Self::Locator => (1, BuzzerConfig::SHORT_BEEP),
+            Self::Apogee(meters) => {
+                let mut divisor = 1;
+                while meters / divisor >= 10 {
+                    divisor *= 10;
+                }
+                while divisor > 0 {
+                    let beeps = match u8::try_from(meters / divisor % 10) {
+                        Ok(0) | Err(_) => 10,
+                        Ok(digit) => digit,
+                    };
+                    // at most MAX_GROUPS digits
+                    let _ = groups.push((beeps, BuzzerConfig::SHORT_BEEP));
+                    divisor /= 10;
+                }
+                return groups;
+            },
+        };
+        let _ = groups.push(group);
+        groups
+    }
+}
+
+const fn sensor_number(sensor: SensorId) -> u8 {
+    match sensor {
+        SensorId::Altimeter => 1,
+        SensorId::Gps => 2,
+        SensorId::Imu => 3,
+        SensorId::HighGAccelerometer => 4,
+    }
+}
+
+/// Code for the flight state, `None` to stay silent.
+///
+/// On the pad the sensors are checked: a fault is reported as soon as any sensor is degraded,
+/// while ready waits for every sensor to be healthy. The high-g accelerometer is optional,
+/// so only its faults are reported.
+#[must_use]
+pub fn beep_code(state: FlightState, health: &SensorHealthReport, apogee: Option<Altitude>) -> Option<BeepCode> {
+    const SENSORS: [SensorId; 4] = [SensorId::Altimeter, SensorId::Gps, SensorId::Imu, SensorId::HighGAccelerometer];
+    const REQUIRED_SENSORS: [SensorId; 3] = [SensorId::Altimeter, SensorId::Gps, SensorId::Imu];
+
+    match state {
+        FlightState::PreArmed => {
+            if let Some(sensor) = SENSORS.into_iter().find(|&sensor| matches!(health.get(sensor), SensorHealth::Degraded | SensorHealth::Failed)) {
+                Some(BeepCode::SensorFault(sensor))
+            } else {
+                REQUIRED_SENSORS.into_iter().all(|sensor| health.get(sensor) == SensorHealth::Healthy).then_some(BeepCode::Ready)
+            }
+        },
+        FlightState::Armed => Some(BeepCode::Armed),
+        FlightState::RecoveryActivated => Some(BeepCode::Locator),
+        FlightState::Touchdown => Some(apogee.map_or(BeepCode::Locator, |apogee| BeepCode::Apogee(whole_meters(apogee)))),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn whole_meters(altitude: Altitude) -> u32 {
+    // saturates, so a negative apogee reads out as zero
+    (altitude.get::<meter>() + 0.5) as u32
+}
+
+/// Beeps `code` once, including the pause before it repeats.
+pub async fn play<B: Buzzer>(buzzer: &mut B, code: BeepCode) -> Result<(), B::Error> {
+    for (beeps, length) in code.groups() {
+        for _ in 0..beeps {
+            buzzer.on().await?;
+            Timer::after(length).await;
+            buzzer.off().await?;
+            Timer::after(BuzzerConfig::BEEP_GAP).await;
+        }
+        Timer::after(BuzzerConfig::GROUP_GAP).await;
+    }
+    Timer::after(BuzzerConfig::REPEAT_PAUSE).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beeps(code: BeepCode) -> Vec<u8> {
+        code.groups().iter().map(|&(beeps, _)| beeps).collect()
+    }
+
+    fn report(altimeter: SensorHealth, gps: SensorHealth, imu: SensorHealth) -> SensorHealthReport {
+        SensorHealthReport { altimeter, gps, imu, ..Default::default() }
+    }
+
+    #[rstest::rstest]
+    #[case(0, &[10])]
+    #[case(7, &[7])]
+    #[case(1203, &[1, 2, 10, 3])]
+    #[case(u32::MAX, &[4, 2, 9, 4, 9, 6, 7, 2, 9, 5])]
+    fn apogee_is_read_out_digit_by_digit(#[case] meters: u32, #[case] expected: &[u8]) {
+        assert_eq!(beeps(BeepCode::Apogee(meters)), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(SensorId::Altimeter, 1)]
+    #[case(SensorId::Gps, 2)]
+    #[case(SensorId::Imu, 3)]
+    #[case(SensorId::HighGAccelerometer, 4)]
+    fn sensor_faults_count_out_sensor(#[case] sensor: SensorId, #[case] expected: u8) {
+        assert_eq!(BeepCode::SensorFault(sensor).groups().as_slice(), [(expected, BuzzerConfig::LONG_BEEP)]);
+    }
+
+    #[rstest::rstest]
+    #[case(report(SensorHealth::Healthy, SensorHealth::Healthy, SensorHealth::Healthy), Some(BeepCode::Ready))]
+    #[case(report(SensorHealth::Healthy, SensorHealth::Unknown, SensorHealth::Healthy), None)]
+    #[case(report(SensorHealth::Healthy, SensorHealth::Unknown, SensorHealth::Failed), Some(BeepCode::SensorFault(SensorId::Imu)))]
+    #[case(report(SensorHealth::Degraded, SensorHealth::Failed, SensorHealth::Healthy), Some(BeepCode::SensorFault(SensorId::Altimeter)))]
+    fn pad_reports_preflight_checks(#[case] health: SensorHealthReport, #[case] expected: Option<BeepCode>) {
+        assert_eq!(beep_code(FlightState::PreArmed, &health, None), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(SensorHealth::Unknown, Some(BeepCode::Ready))]
+    #[case(SensorHealth::Failed, Some(BeepCode::SensorFault(SensorId::HighGAccelerometer)))]
+    fn high_g_accelerometer_is_optional(#[case] high_g_accelerometer: SensorHealth, #[case] expected: Option<BeepCode>) {
+        let health = SensorHealthReport {
+            high_g_accelerometer,
+            ..report(SensorHealth::Healthy, SensorHealth::Healthy, SensorHealth::Healthy)
+        };
+
+        assert_eq!(beep_code(FlightState::PreArmed, &health, None), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(None, BeepCode::Locator)]
+    #[case(Some(Altitude::new::<meter>(1203.4)), BeepCode::Apogee(1203))]
+    #[case(Some(Altitude::new::<meter>(-3.0)), BeepCode::Apogee(0))]
+    fn touchdown_reads_out_apogee(#[case] apogee: Option<Altitude>, #[case] expected: BeepCode) {
+        assert_eq!(beep_code(FlightState::Touchdown, &SensorHealthReport::default(), apogee), Some(expected));
+    }
+}
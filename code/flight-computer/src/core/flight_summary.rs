@@ -0,0 +1,273 @@
+use embassy_time::{Duration, Instant};
+use proto::flight_state::FlightSummary;
+use proto::record::tick_hz::Timestamp;
+use proto::sensor_data::{Acceleration, AltimeterData, Altitude, ImuData, Time, Velocity};
+use proto::uom::si::{acceleration::meter_per_second_squared, length::meter, time::second, velocity::meter_per_second};
+
+use crate::config::FlightSummaryConfig;
+
+/// Running extrema and phase timings between arming and touchdown.
+#[derive(Default)]
+pub struct FlightSummaryTracker {
+    /// Altitude the apogee is measured from, the first sample after arming if the pad is not calibrated.
+    ground: Option<Altitude>,
+    armed_at: Option<Instant>,
+    launched_at: Option<Instant>,
+    burnout_at: Option<Instant>,
+    apogee: Option<(Altitude, Instant)>,
+    touchdown_at: Option<Instant>,
+    last_altitude: Option<(Altitude, Instant)>,
+    /// Smoothed climb rate, as single altimeter samples are too noisy to take a maximum of.
+    velocity: Option<f32>,
+    max_velocity: Option<f32>,
+    max_acceleration: Option<f32>,
+    /// Whether the IMU measured boost, which alone can tell burnout.
+    boost_seen: bool,
+    /// First sample of the current coast, a burnout once it lasted [`FlightSummaryConfig::BURNOUT_CONFIRMATION`].
+    coast_since: Option<Instant>,
+}
+
+impl FlightSummaryTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking, from the calibrated launchpad altitude if any.
+    pub fn arm(&mut self, now: Instant, ground: Option<Altitude>) {
+        *self = Self {
+            ground,
+            armed_at: Some(now),
+            ..Self::default()
+        };
+    }
+
+    /// Stops tracking, the summary is final from here on.
+    pub fn touchdown(&mut self, now: Instant) {
+        if self.is_tracking() {
+            self.touchdown_at = Some(now);
+        }
+    }
+
+    /// Whether liftoff was detected since arming.
+    #[must_use]
+    pub const fn launched(&self) -> bool {
+        self.launched_at.is_some()
+    }
+
+    const fn is_tracking(&self) -> bool {
+        self.armed_at.is_some() && self.touchdown_at.is_none()
+    }
+
+    pub fn update_altimeter(&mut self, data: &AltimeterData, now: Instant) {
+        if !self.is_tracking() {
+            return;
+        }
+        let ground = *self.ground.get_or_insert(data.altitude);
+        let height = data.altitude - ground;
+
+        if self.apogee.is_none_or(|(apogee, _)| height > apogee) {
+            self.apogee = Some((height, now));
+        }
+        // fallback for a missing or quiet IMU
+        if self.launched_at.is_none() && height.get::<meter>() > FlightSummaryConfig::LAUNCH_ALTITUDE_THRESHOLD {
+            self.launched_at = Some(now);
+        }
+
+        if let Some((last_altitude, last_time)) = self.last_altitude
+            && now > last_time
+        {
+            let rate = (data.altitude - last_altitude).get::<meter>() / seconds(now - last_time);
+            let velocity = self.velocity.map_or(rate, |velocity| {
+                FlightSummaryConfig::VELOCITY_SMOOTHING.mul_add(rate - velocity, velocity)
+            });
+            self.velocity = Some(velocity);
+            self.max_velocity = Some(self.max_velocity.map_or(velocity, |max| max.max(velocity)));
+        }
+        self.last_altitude = Some((data.altitude, now));
+    }
+
+    pub fn update_imu(&mut self, data: &ImuData, now: Instant) {
+        if !self.is_tracking() {
+            return;
+        }
+        let x = data.acceleration.x.get::<meter_per_second_squared>();
+        let y = data.acceleration.y.get::<meter_per_second_squared>();
+        let z = data.acceleration.z.get::<meter_per_second_squared>();
+        let magnitude = x.mul_add(x, y.mul_add(y, z * z)).sqrt();
+
+        self.max_acceleration = Some(self.max_acceleration.map_or(magnitude, |max| max.max(magnitude)));
+
+        let boosting = magnitude > FlightSummaryConfig::burn_acceleration_threshold().get::<meter_per_second_squared>();
+        if boosting {
+            if !self.boost_seen {
+                self.boost_seen = true;
+                self.launched_at = self.launched_at.or(Some(now));
+            }
+            self.coast_since = None;
+        } else if self.boost_seen && self.burnout_at.is_none() {
+            // a single quiet sample mid-burn, e.g. from vibration, is no burnout
+            let coast_since = *self.coast_since.get_or_insert(now);
+            if now - coast_since >= FlightSummaryConfig::BURNOUT_CONFIRMATION {
+                self.burnout_at = Some(coast_since);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> FlightSummary {
+        let burn_time = self.launched_at.zip(self.burnout_at).map(|(launch, burnout)| time(burnout - launch));
+        // up to the last sample, as touchdown is only detected once the altitude settled
+        let descent_rate = match (self.apogee, self.last_altitude, self.ground, self.touchdown_at) {
+            (Some((apogee, apogee_at)), Some((altitude, landed_at)), Some(ground), Some(_)) if landed_at > apogee_at => {
+                let descent = apogee - (altitude - ground);
+                Some(Velocity::new::<meter_per_second>(descent.get::<meter>() / seconds(landed_at - apogee_at)))
+            },
+            _ => None,
+        };
+
+        FlightSummary {
+            apogee: self.apogee.map(|(apogee, _)| apogee),
+            max_velocity: self.max_velocity.map(Velocity::new::<meter_per_second>),
+            max_acceleration: self.max_acceleration.map(Acceleration::new::<meter_per_second_squared>),
+            burn_time,
+            descent_rate,
+            armed_at: self.armed_at.map(timestamp),
+            launched_at: self.launched_at.map(timestamp),
+            burnout_at: self.burnout_at.map(timestamp),
+            apogee_at: self.apogee.map(|(_, at)| timestamp(at)),
+            touchdown_at: self.touchdown_at.map(timestamp),
+        }
+    }
+}
+
+fn timestamp(instant: Instant) -> Timestamp {
+    Timestamp::from(instant.as_ticks())
+}
+
+fn seconds(duration: Duration) -> f32 {
+    duration.as_micros() as f32 / 1_000_000.0
+}
+
+fn time(duration: Duration) -> Time {
+    Time::new::<second>(seconds(duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::sensor_data::Vector3;
+
+    use crate::test_utils::at;
+    use crate::test_utils::sensor_data::{random_altimeter_data, random_imu_data};
+
+    use super::*;
+
+    fn altimeter(meters: f32) -> AltimeterData {
+        AltimeterData { altitude: Altitude::new::<meter>(meters), ..random_altimeter_data() }
+    }
+
+    fn imu(vertical: f32) -> ImuData {
+        let zero = Acceleration::new::<meter_per_second_squared>(0.0);
+        ImuData {
+            acceleration: Vector3::new(zero, zero, Acceleration::new::<meter_per_second_squared>(vertical)),
+            ..random_imu_data()
+        }
+    }
+
+    /// Armed at 0 on a pad at 100 m, boost from 1 s to 3 s, apogee of 50 m at 5 s, touchdown at 15 s.
+    fn flight() -> FlightSummaryTracker {
+        let mut tracker = FlightSummaryTracker::new();
+        tracker.arm(at(0), Some(Altitude::new::<meter>(100.0)));
+
+        let profile = [(0, 100.0), (1_000, 100.0), (2_000, 110.0), (3_000, 130.0), (4_000, 145.0), (5_000, 150.0), (10_000, 125.0), (15_000, 100.0)];
+        for (ms, meters) in profile {
+            tracker.update_imu(&imu(if (1_000..3_000).contains(&ms) { 60.0 } else { 9.8 }), at(ms));
+            tracker.update_altimeter(&altimeter(meters), at(ms));
+        }
+        tracker.touchdown(at(15_000));
+        tracker
+    }
+
+    #[rstest::rstest]
+    fn tracks_extrema_and_timings() {
+        let summary = flight().summary();
+
+        assert_eq!(summary.apogee, Some(Altitude::new::<meter>(50.0)));
+        assert_eq!(summary.max_acceleration, Some(Acceleration::new::<meter_per_second_squared>(60.0)));
+        assert_eq!(summary.burn_time, Some(Time::new::<second>(2.0)));
+        assert_eq!(summary.descent_rate, Some(Velocity::new::<meter_per_second>(5.0)));
+        assert_eq!(summary.armed_at, Some(timestamp(at(0))));
+        assert_eq!(summary.launched_at, Some(timestamp(at(1_000))));
+        assert_eq!(summary.burnout_at, Some(timestamp(at(3_000))));
+        assert_eq!(summary.apogee_at, Some(timestamp(at(5_000))));
+        assert_eq!(summary.touchdown_at, Some(timestamp(at(15_000))));
+    }
+
+    #[rstest::rstest]
+    fn max_velocity_is_smoothed_climb_rate() {
+        let max_velocity = flight().summary().max_velocity.expect("Climb rate should be known").get::<meter_per_second>();
+
+        assert!(max_velocity > 0.0);
+        assert!(max_velocity < 20.0, "a single sample climbed at 20 m/s, got {max_velocity}");
+    }
+
+    #[rstest::rstest]
+    fn ignores_samples_outside_flight() {
+        let mut tracker = FlightSummaryTracker::new();
+        tracker.update_altimeter(&altimeter(1_000.0), at(0));
+        tracker.update_imu(&imu(100.0), at(0));
+        assert_eq!(tracker.summary(), FlightSummary::default());
+
+        let mut tracker = flight();
+        let summary = tracker.summary();
+        tracker.update_altimeter(&altimeter(1_000.0), at(20_000));
+        tracker.update_imu(&imu(100.0), at(20_000));
+        assert_eq!(tracker.summary(), summary);
+    }
+
+    #[rstest::rstest]
+    fn burnout_needs_a_sustained_coast() {
+        let mut tracker = FlightSummaryTracker::new();
+        tracker.arm(at(0), Some(Altitude::new::<meter>(100.0)));
+
+        // boost sampled every 10 ms from 1 s, with a quiet sample at 1.5 s, until 2 s
+        for ms in (1_000..3_000).step_by(10) {
+            let boosting = ms < 2_000 && ms != 1_500;
+            tracker.update_imu(&imu(if boosting { 60.0 } else { 9.8 }), at(ms));
+        }
+
+        let summary = tracker.summary();
+        assert_eq!(summary.burnout_at, Some(timestamp(at(2_000))));
+        assert_eq!(summary.burn_time, Some(Time::new::<second>(1.0)));
+    }
+
+    #[rstest::rstest]
+    fn burnout_waits_for_confirmation() {
+        let mut tracker = FlightSummaryTracker::new();
+        tracker.arm(at(0), Some(Altitude::new::<meter>(100.0)));
+
+        tracker.update_imu(&imu(60.0), at(1_000));
+        tracker.update_imu(&imu(9.8), at(2_000));
+        assert_eq!(tracker.summary().burnout_at, None);
+
+        tracker.update_imu(&imu(9.8), at(2_000 + FlightSummaryConfig::BURNOUT_CONFIRMATION.as_millis()));
+        assert_eq!(tracker.summary().burnout_at, Some(timestamp(at(2_000))));
+    }
+
+    #[rstest::rstest]
+    fn launch_falls_back_to_altitude_without_imu() {
+        let mut tracker = FlightSummaryTracker::new();
+        tracker.arm(at(0), None);
+
+        tracker.update_altimeter(&altimeter(200.0), at(0));
+        tracker.update_altimeter(&altimeter(200.0 + FlightSummaryConfig::LAUNCH_ALTITUDE_THRESHOLD / 2.0), at(1_000));
+        assert!(!tracker.launched());
+        tracker.update_altimeter(&altimeter(200.0 + FlightSummaryConfig::LAUNCH_ALTITUDE_THRESHOLD * 2.0), at(2_000));
+        assert!(tracker.launched());
+
+        let summary = tracker.summary();
+        assert_eq!(summary.launched_at, Some(timestamp(at(2_000))));
+        assert_eq!(summary.burnout_at, None);
+        assert_eq!(summary.burn_time, None);
+    }
+}
@@ -0,0 +1,224 @@
+use core::pin::pin;
+
+use embassy_futures::select::{Either, select};
+use embassy_time::{Duration, Instant, Timer};
+use proto::actuator_data::LedPattern;
+use proto::flight_state::FlightState;
+use proto::sensor_health::SensorHealth;
+
+use crate::interfaces::Led;
+use crate::log::warn;
+
+/* --------------------------------- Mappings --------------------------------- */
+
+// | LED             | Slow blink     | Fast blink     | Solid         | Heartbeat   | Pulses           |
+// |-----------------|----------------|----------------|---------------|-------------|------------------|
+// | Sensors         | no sample yet  | degraded       | healthy       |             | 3: failed        |
+// | File system     |                |                | final flush   | logging     | 2: drops, 3: I/O |
+// | Postcard, GS    | disconnected   |                |               | connected   |                  |
+// | Arm button      | under canopy   | waiting to arm | armed, landed |             |                  |
+
+/// Sensor LEDs: blinking slowly until the first sample, solid while healthy.
+#[must_use]
+pub const fn sensor_health_pattern(health: SensorHealth) -> LedPattern {
+    match health {
+        SensorHealth::Unknown => LedPattern::SlowBlink,
+        SensorHealth::Healthy => LedPattern::Solid,
+        SensorHealth::Degraded => LedPattern::FastBlink,
+        SensorHealth::Failed => LedPattern::Pulses(3),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageStatus {
+    Logging,
+    /// Records were dropped since the last flush.
+    DroppingRecords,
+    /// Writes since the last flush failed or timed out.
+    WriteFailed,
+    /// Final flush done, safe to power off.
+    Closed,
+}
+
+#[must_use]
+pub const fn storage_pattern(status: StorageStatus) -> LedPattern {
+    match status {
+        StorageStatus::Logging => LedPattern::Heartbeat,
+        StorageStatus::DroppingRecords => LedPattern::Pulses(2),
+        StorageStatus::WriteFailed => LedPattern::Pulses(3),
+        StorageStatus::Closed => LedPattern::Solid,
+    }
+}
+
+/// Postcard and ground station LEDs.
+#[must_use]
+pub const fn link_pattern(connected: bool) -> LedPattern {
+    if connected { LedPattern::Heartbeat } else { LedPattern::SlowBlink }
+}
+
+/// Arm button LED: blinking until armed.
+#[must_use]
+pub const fn flight_state_pattern(state: FlightState) -> LedPattern {
+    match state {
+        FlightState::PreArmed => LedPattern::FastBlink,
+        FlightState::Armed | FlightState::Touchdown => LedPattern::Solid,
+        FlightState::RecoveryActivated => LedPattern::SlowBlink,
+    }
+}
+
+/* -------------------------------- Pattern Led ------------------------------- */
+
+/// Plays a [`LedPattern`] on any [`Led`], only switching it on the pattern's edges.
+pub struct PatternLed<L: Led> {
+    led: L,
+    pattern: LedPattern,
+    started: Instant,
+    /// `None` until the LED was switched once, as its initial state is unknown.
+    lit: Option<bool>,
+}
+
+impl<L: Led> PatternLed<L> {
+    pub fn new(led: L) -> Self {
+        Self {
+            led,
+            pattern: LedPattern::Off,
+            started: Instant::now(),
+            lit: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn pattern(&self) -> LedPattern {
+        self.pattern
+    }
+
+    /// Switches to `pattern`, restarting its period unless it is already playing.
+    pub fn set(&mut self, pattern: LedPattern) {
+        if pattern != self.pattern {
+            self.pattern = pattern;
+            self.started = Instant::now();
+        }
+    }
+
+    /// Shows the current phase of the pattern once, e.g. before handing the LED over or returning.
+    pub async fn refresh(&mut self) {
+        let (lit, _) = self.pattern.phase((Instant::now() - self.started).into());
+        self.switch(lit).await;
+    }
+
+    /// Plays the pattern until `future` completes.
+    pub async fn play_until<F: Future>(&mut self, future: F) -> F::Output {
+        let mut future = pin!(future);
+        loop {
+            let (lit, next_edge) = self.pattern.phase((Instant::now() - self.started).into());
+            self.switch(lit).await;
+
+            // a segment lasts a second at most, well within a `Duration`
+            let Some(next_edge) = next_edge.and_then(|edge| Duration::try_from(edge).ok()) else {
+                return future.await;
+            };
+            if let Either::First(output) = select(&mut future, Timer::after(next_edge)).await {
+                return output;
+            }
+        }
+    }
+
+    async fn switch(&mut self, lit: bool) {
+        if self.lit == Some(lit) {
+            return;
+        }
+        let result = if lit { self.led.on().await } else { self.led.off().await };
+        match result {
+            Ok(()) => self.lit = Some(lit),
+            Err(e) => warn!("Led: Failed to switch {}: {:?}", if lit { "on" } else { "off" }, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_utils::ms;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct RecordingLed(Arc<Mutex<Vec<bool>>>);
+
+    impl RecordingLed {
+        fn switches(&self) -> Vec<bool> {
+            self.0.lock().expect("Led mutex poisoned").clone()
+        }
+    }
+
+    impl Led for RecordingLed {
+        type Error = Infallible;
+
+        async fn on(&mut self) -> Result<(), Self::Error> {
+            self.0.lock().expect("Led mutex poisoned").push(true);
+            Ok(())
+        }
+
+        async fn off(&mut self) -> Result<(), Self::Error> {
+            self.0.lock().expect("Led mutex poisoned").push(false);
+            Ok(())
+        }
+
+        async fn toggle(&mut self) -> Result<(), Self::Error> {
+            unreachable!("patterns only switch on and off")
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(SensorHealth::Unknown)]
+    #[case(SensorHealth::Healthy)]
+    #[case(SensorHealth::Degraded)]
+    #[case(SensorHealth::Failed)]
+    fn sensor_health_patterns_are_distinct(#[case] health: SensorHealth) {
+        let others = [SensorHealth::Unknown, SensorHealth::Healthy, SensorHealth::Degraded, SensorHealth::Failed]
+            .into_iter()
+            .filter(|&other| other != health);
+
+        for other in others {
+            assert_ne!(sensor_health_pattern(health), sensor_health_pattern(other));
+        }
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn refresh_only_switches_on_change() {
+        let led = RecordingLed::default();
+        let mut pattern_led = PatternLed::new(led.clone());
+
+        pattern_led.refresh().await;
+        pattern_led.refresh().await;
+        pattern_led.set(LedPattern::Solid);
+        pattern_led.refresh().await;
+        pattern_led.set(LedPattern::Solid);
+        pattern_led.refresh().await;
+
+        assert_eq!(led.switches(), [false, true]);
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(1_000))]
+    async fn plays_pattern_until_future_completes() {
+        let led = RecordingLed::default();
+        let mut pattern_led = PatternLed::new(led.clone());
+        pattern_led.set(LedPattern::FastBlink);
+
+        // edges at 0, 1 and 2 blinks, finishing half a blink before the third
+        let blink = Duration::try_from(LedPattern::FAST_BLINK).expect("Blink should fit a Duration");
+        let output = pattern_led.play_until(async {
+            Timer::after(blink * 2 + blink / 2).await;
+            42
+        }).await;
+
+        assert_eq!(output, 42);
+        assert_eq!(led.switches(), [true, false, true]);
+    }
+}
@@ -1,4 +1,14 @@
+pub mod ahrs;
+pub mod beacon;
+pub mod beep_code;
+pub mod flight_summary;
+pub mod led_pattern;
+pub mod preflight;
+#[cfg(feature = "impl_replay")]
+pub mod replay;
 pub mod sensors;
 pub mod storage;
 pub mod state_machine;
+pub mod tilt;
 pub mod trace;
+pub mod watchdog;
@@ -0,0 +1,222 @@
+use nmea::sentences::FixType;
+use proto::preflight::{CheckStatus, PreflightCheck, PreflightReport};
+use proto::sensor_data::GpsData;
+use proto::sensor_health::{SensorHealth, SensorHealthReport};
+use proto::tilt::TiltAction;
+
+use crate::config::PreflightConfig;
+use crate::core::tilt::TiltStatus;
+
+/// Self-test run while pre-armed, gating the arm command.
+pub struct PreflightChecks {
+    report: PreflightReport,
+    /// Holds arming back even when overridden, as `TiltInhibitsEndpoint` reports it to the ground station.
+    tilt_inhibits_arming: bool,
+}
+
+impl Default for PreflightChecks {
+    fn default() -> Self {
+        Self {
+            report: PreflightReport::default(),
+            tilt_inhibits_arming: TiltStatus::UNKNOWN.inhibits(TiltAction::Arm),
+        }
+    }
+}
+
+impl PreflightChecks {
+    pub const fn update_health(&mut self, health: &SensorHealthReport) {
+        self.report.altimeter = sensor_status(health.altimeter);
+        self.report.imu = sensor_status(health.imu);
+        self.report.gps = sensor_status(health.gps);
+    }
+
+    pub fn update_gps(&mut self, data: &GpsData) {
+        // the simulator's fixes stand in for real ones
+        let valid = matches!(
+            *data.fix_type,
+            FixType::Gps | FixType::DGps | FixType::Pps | FixType::Rtk | FixType::FloatRtk | FixType::Simulation
+        );
+        self.report.gps_fix = if valid && data.num_of_fix_satellites >= PreflightConfig::MIN_FIX_SATELLITES {
+            CheckStatus::Passed
+        } else {
+            CheckStatus::Failed
+        };
+    }
+
+    /// `None` until storage flushed once.
+    pub const fn update_storage(&mut self, writes_ok: Option<bool>) {
+        self.report.storage = match writes_ok {
+            None => CheckStatus::Pending,
+            Some(true) => CheckStatus::Passed,
+            Some(false) => CheckStatus::Failed,
+        };
+    }
+
+    pub const fn update_deployment(&mut self, status: CheckStatus) {
+        self.report.deployment = status;
+    }
+
+    pub fn update_tilt(&mut self, status: &TiltStatus) {
+        self.report.tilt = status.pad_check();
+        self.tilt_inhibits_arming = status.inhibits(TiltAction::Arm);
+    }
+
+    pub const fn set_override(&mut self, overridden: bool) {
+        self.report.overridden = overridden;
+    }
+
+    #[must_use]
+    pub fn report(&self) -> PreflightReport {
+        PreflightReport {
+            arming_allowed: !self.tilt_inhibits_arming && (self.report.overridden || failing(&self.report).next().is_none()),
+            ..self.report
+        }
+    }
+}
+
+/// Mandatory checks that did not pass yet, pending ones included.
+pub fn failing(report: &PreflightReport) -> impl Iterator<Item = (PreflightCheck, CheckStatus)> + '_ {
+    PreflightConfig::MANDATORY_CHECKS
+        .into_iter()
+        .map(|check| (check, report.get(check)))
+        .filter(|(_, status)| !status.allows_arming())
+}
+
+const fn sensor_status(health: SensorHealth) -> CheckStatus {
+    match health {
+        SensorHealth::Unknown => CheckStatus::Pending,
+        SensorHealth::Healthy => CheckStatus::Passed,
+        SensorHealth::Degraded | SensorHealth::Failed => CheckStatus::Failed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::sensor_data::Angle;
+    use proto::uom::si::angle::degree;
+
+    use crate::core::tilt::TiltLockout;
+    use crate::test_utils::sensor_data::random_gps_data;
+
+    use super::*;
+
+    fn tilt(degrees: f32, lockout: TiltLockout) -> TiltStatus {
+        TiltStatus { tilt: Some(Angle::new::<degree>(degrees)), lockout }
+    }
+
+    fn passing() -> PreflightChecks {
+        let mut checks = PreflightChecks::default();
+        checks.update_health(&SensorHealthReport {
+            altimeter: SensorHealth::Healthy,
+            gps: SensorHealth::Healthy,
+            imu: SensorHealth::Healthy,
+            ..Default::default()
+        });
+        checks.update_storage(Some(true));
+        checks.update_deployment(CheckStatus::Unsupported);
+        checks.update_tilt(&tilt(2.0, TiltLockout::Clear));
+        checks
+    }
+
+    fn gps_data(fix_type: FixType, satellites: u8) -> GpsData {
+        GpsData {
+            fix_type: fix_type.into(),
+            num_of_fix_satellites: satellites,
+            ..random_gps_data()
+        }
+    }
+
+    #[rstest::rstest]
+    fn refuses_arming_until_checks_ran() {
+        let report = PreflightChecks::default().report();
+
+        assert!(!report.arming_allowed);
+        assert_eq!(failing(&report).count(), PreflightConfig::MANDATORY_CHECKS.len());
+    }
+
+    #[rstest::rstest]
+    fn allows_arming_once_mandatory_checks_pass() {
+        let report = passing().report();
+
+        assert!(report.arming_allowed);
+        assert_eq!(report.gps_fix, CheckStatus::Pending);
+    }
+
+    #[rstest::rstest]
+    #[case(SensorHealth::Unknown, CheckStatus::Pending)]
+    #[case(SensorHealth::Degraded, CheckStatus::Failed)]
+    #[case(SensorHealth::Failed, CheckStatus::Failed)]
+    fn unhealthy_sensor_blocks_arming(#[case] health: SensorHealth, #[case] expected: CheckStatus) {
+        let mut checks = passing();
+        checks.update_health(&SensorHealthReport {
+            altimeter: SensorHealth::Healthy,
+            gps: SensorHealth::Healthy,
+            imu: health,
+            ..Default::default()
+        });
+
+        let report = checks.report();
+
+        assert!(!report.arming_allowed);
+        assert_eq!(failing(&report).collect::<Vec<_>>(), [(PreflightCheck::Imu, expected)]);
+    }
+
+    #[rstest::rstest]
+    fn failed_storage_writes_block_arming() {
+        let mut checks = passing();
+        checks.update_storage(Some(false));
+
+        assert_eq!(failing(&checks.report()).collect::<Vec<_>>(), [(PreflightCheck::Storage, CheckStatus::Failed)]);
+    }
+
+    #[rstest::rstest]
+    fn rocket_off_vertical_blocks_arming() {
+        let mut checks = passing();
+        checks.update_tilt(&tilt(20.0, TiltLockout::Pad));
+
+        assert_eq!(failing(&checks.report()).collect::<Vec<_>>(), [(PreflightCheck::Tilt, CheckStatus::Failed)]);
+    }
+
+    #[rstest::rstest]
+    #[case(None)]
+    #[case(Some(tilt(20.0, TiltLockout::Pad)))]
+    fn override_never_bypasses_the_tilt_lockout(#[case] status: Option<TiltStatus>) {
+        let mut checks = PreflightChecks::default();
+        if let Some(status) = status {
+            checks.update_tilt(&status);
+        }
+        checks.set_override(true);
+
+        let report = checks.report();
+
+        assert!(!report.arming_allowed);
+        assert!(report.overridden);
+    }
+
+    #[rstest::rstest]
+    fn override_allows_arming_despite_failures() {
+        let mut checks = passing();
+        checks.update_deployment(CheckStatus::Failed);
+        checks.set_override(true);
+
+        let report = checks.report();
+
+        assert!(report.arming_allowed);
+        assert!(report.overridden);
+        assert_eq!(failing(&report).count(), 1);
+    }
+
+    #[rstest::rstest]
+    #[case(FixType::Gps, PreflightConfig::MIN_FIX_SATELLITES, CheckStatus::Passed)]
+    #[case(FixType::Simulation, PreflightConfig::MIN_FIX_SATELLITES, CheckStatus::Passed)]
+    #[case(FixType::Gps, PreflightConfig::MIN_FIX_SATELLITES - 1, CheckStatus::Failed)]
+    #[case(FixType::Invalid, 20, CheckStatus::Failed)]
+    #[case(FixType::Estimated, 20, CheckStatus::Failed)]
+    fn grades_gps_fix_quality(#[case] fix_type: FixType, #[case] satellites: u8, #[case] expected: CheckStatus) {
+        let mut checks = PreflightChecks::default();
+
+        checks.update_gps(&gps_data(fix_type, satellites));
+
+        assert_eq!(checks.report().gps_fix, expected);
+    }
+}
@@ -0,0 +1,117 @@
+use embassy_time::Duration;
+use proto::flight_state::FlightState;
+
+use crate::config::ReplayConfig;
+
+/// Flight state change, as an offset from the start of the flight's log.
+pub type Transition = (Duration, FlightState);
+
+/// States the FSM decides on, `PreArmed` is where every boot starts.
+const DECISIONS: [FlightState; 3] = [FlightState::Armed, FlightState::RecoveryActivated, FlightState::Touchdown];
+
+/// When the original flight and its replay entered a state, if ever.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransitionComparison {
+    pub state: FlightState,
+    pub original: Option<Duration>,
+    pub replayed: Option<Duration>,
+}
+
+impl TransitionComparison {
+    /// Replayed minus original time in milliseconds, negative if the replay decided earlier.
+    #[must_use]
+    pub fn offset_ms(&self) -> Option<i64> {
+        let (original, replayed) = (self.original?, self.replayed?);
+        let millis = |duration: Duration| i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+        Some(millis(replayed) - millis(original))
+    }
+
+    /// Whether both runs made the decision close enough to each other, or neither made it.
+    #[must_use]
+    pub fn matches(&self) -> bool {
+        match (self.original, self.replayed) {
+            (Some(original), Some(replayed)) => {
+                let gap = if replayed > original { replayed - original } else { original - replayed };
+                gap <= ReplayConfig::TRANSITION_TOLERANCE
+            },
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Pairs the first entry into each decided state of both runs.
+#[must_use]
+pub fn compare_transitions(original: &[Transition], replayed: &[Transition]) -> [TransitionComparison; 3] {
+    let first_entry = |transitions: &[Transition], state| {
+        transitions.iter().find(|(_, entered)| *entered == state).map(|(at, _)| *at)
+    };
+
+    DECISIONS.map(|state| TransitionComparison {
+        state,
+        original: first_entry(original, state),
+        replayed: first_entry(replayed, state),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::offset;
+
+    use super::*;
+
+    fn flight(armed: u64, apogee: u64, touchdown: u64) -> [Transition; 4] {
+        [
+            (offset(0), FlightState::PreArmed),
+            (offset(armed), FlightState::Armed),
+            (offset(apogee), FlightState::RecoveryActivated),
+            (offset(touchdown), FlightState::Touchdown),
+        ]
+    }
+
+    #[rstest::rstest]
+    fn pairs_decisions_in_order() {
+        let comparisons = compare_transitions(&flight(1_000, 10_000, 60_000), &flight(2_000, 9_500, 61_000));
+
+        assert_eq!(comparisons.map(|comparison| comparison.state), DECISIONS);
+        assert_eq!(comparisons.map(|comparison| comparison.offset_ms()), [Some(1_000), Some(-500), Some(1_000)]);
+    }
+
+    #[rstest::rstest]
+    fn tolerates_gaps_up_to_config() {
+        let tolerance = ReplayConfig::TRANSITION_TOLERANCE.as_millis();
+        let original = flight(1_000, 10_000, 60_000);
+
+        let late = compare_transitions(&original, &flight(1_000 + tolerance, 10_000 - tolerance, 60_000));
+        assert!(late.iter().all(TransitionComparison::matches));
+
+        let too_late = compare_transitions(&original, &flight(1_001 + tolerance, 10_000, 60_000));
+        assert!(!too_late[0].matches());
+    }
+
+    #[rstest::rstest]
+    fn missed_decision_does_not_match() {
+        let original = flight(1_000, 10_000, 60_000);
+        let comparisons = compare_transitions(&original, &original[..2]);
+
+        assert!(comparisons[0].matches());
+        assert_eq!(comparisons[1].replayed, None);
+        assert_eq!(comparisons[1].offset_ms(), None);
+        assert!(!comparisons[1].matches());
+    }
+
+    #[rstest::rstest]
+    fn decision_missing_from_both_matches() {
+        let comparisons = compare_transitions(&[(offset(0), FlightState::PreArmed)], &[]);
+
+        assert!(comparisons.iter().all(TransitionComparison::matches));
+    }
+
+    #[rstest::rstest]
+    fn first_entry_counts() {
+        let original = [(offset(1_000), FlightState::Armed), (offset(5_000), FlightState::Armed)];
+        let comparisons = compare_transitions(&original, &original[1..]);
+
+        assert_eq!(comparisons[0].offset_ms(), Some(4_000));
+    }
+}
@@ -1,12 +1,12 @@
 use crate::config::AltimeterConfig;
-use proto::uom::si::{length::meter, pressure::pascal};
-use proto::sensor_data::{Altitude, Pressure};
+use crate::sync::pad_calibration;
+use proto::uom::si::{length::meter, pressure::pascal, thermodynamic_temperature::kelvin};
+use proto::sensor_data::{AltimeterData, Altitude, PadCalibration, Pressure, ThermodynamicTemperature};
+#[allow(unused_imports)]
+use proto::uom::num_traits::Float;
 
-#[allow(dead_code)]
+/// Altitude above sea level, assuming the ISA standard atmosphere.
 pub fn altitude_from_pressure(pressure: Pressure) -> Altitude {
-    #[allow(unused_imports)]
-    use proto::uom::num_traits::Float;
-
     let pressure = pressure.get::<pascal>();
     let p0 = AltimeterConfig::REFERENCE_PRESSURE; // ISA sea level standard pressure in pascal
     let exponent = 0.190_284;
@@ -18,9 +18,67 @@ pub fn altitude_from_pressure(pressure: Pressure) -> Altitude {
     Altitude::new::<meter>(altitude_m)
 }
 
+/// Altitude above the launchpad, from the hypsometric equation anchored on the pressure and temperature measured on the pad.
+pub fn hypsometric_altitude(pressure: Pressure, calibration: &PadCalibration) -> Altitude {
+    let pressure = pressure.get::<pascal>();
+    let p0 = calibration.ground_pressure.get::<pascal>();
+    let t0 = calibration.ground_temperature.get::<kelvin>();
+    let lapse_rate = 0.0065; // ISA temperature lapse rate in K/m
+    let exponent = 0.190_263; // R * lapse_rate / (g * M)
+
+    let pressure_ratio = pressure / p0;
+    let altitude_m = t0 / lapse_rate * (1.0 - pressure_ratio.powf(exponent));
+
+    Altitude::new::<meter>(altitude_m)
+}
+
+/// Altitude reported by the altimeter drivers: pad-referenced once the launchpad is calibrated, ISA before.
+pub fn pressure_altitude(pressure: Pressure) -> Altitude {
+    pad_calibration().map_or_else(
+        || altitude_from_pressure(pressure),
+        |calibration| calibration.ground_altitude + hypsometric_altitude(pressure, &calibration),
+    )
+}
+
+/// Averages altimeter samples taken on the launchpad.
+#[derive(Default)]
+pub struct PadCalibrator {
+    pressure: f32,
+    temperature: f32,
+    altitude: f32,
+    samples: u16,
+}
+
+impl PadCalibrator {
+    #[must_use]
+    pub const fn samples(&self) -> u16 {
+        self.samples
+    }
+
+    pub fn add_sample(&mut self, data: &AltimeterData) {
+        self.samples = self.samples.saturating_add(1);
+
+        // running mean, a plain sum of pressures loses precision in f32
+        let n = f32::from(self.samples);
+        self.pressure += (data.pressure.get::<pascal>() - self.pressure) / n;
+        self.temperature += (data.temperature.get::<kelvin>() - self.temperature) / n;
+        self.altitude += (data.altitude.get::<meter>() - self.altitude) / n;
+    }
+
+    #[must_use]
+    pub fn finish(&self) -> Option<PadCalibration> {
+        (self.samples > 0).then(|| PadCalibration {
+            ground_pressure: Pressure::new::<pascal>(self.pressure),
+            ground_temperature: ThermodynamicTemperature::new::<kelvin>(self.temperature),
+            ground_altitude: Altitude::new::<meter>(self.altitude),
+            samples: self.samples,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use proto::uom::si::pressure::millibar;
+    use proto::uom::si::{pressure::millibar, thermodynamic_temperature::degree_celsius};
 
     use super::*;
 
@@ -49,4 +107,77 @@ mod tests {
 
         assert!((altitude - expected_altitude).abs() < error_margin, "Expected altitude: {expected_altitude}, Calculated altitude: {altitude}");
     }
+
+    fn calibration(ground_pressure: f32, ground_temperature: f32) -> PadCalibration {
+        PadCalibration {
+            ground_pressure: Pressure::new::<millibar>(ground_pressure),
+            ground_temperature: ThermodynamicTemperature::new::<degree_celsius>(ground_temperature),
+            ground_altitude: Altitude::new::<meter>(0.0),
+            samples: 1,
+        }
+    }
+
+    /// On an ISA day the hypsometric altitude matches the same table
+    #[rstest::rstest]
+    #[case(1013.25, 0.0)]
+    #[case(1001.2, 100.0)]
+    #[case(989.45,  200.0)]
+    #[case(977.72,  300.0)]
+    #[case(966.11,  400.0)]
+    #[case(954.61,  500.0)]
+    #[trace]
+    fn hypsometric_matches_isa_on_standard_day(
+        #[case] pressure: f32,
+        #[case] expected_altitude: f32,
+        #[values(1.0)] error_margin: f32
+    ) {
+        let altitude = hypsometric_altitude(Pressure::new::<millibar>(pressure), &calibration(1013.25, 15.0));
+        let altitude = altitude.get::<meter>();
+
+        assert!((altitude - expected_altitude).abs() < error_margin, "Expected altitude: {expected_altitude}, Calculated altitude: {altitude}");
+    }
+
+    #[rstest::rstest]
+    #[case(990.0, -10.0)]
+    #[case(1013.25, 15.0)]
+    #[case(1030.0, 35.0)]
+    fn hypsometric_is_zero_on_the_pad(#[case] ground_pressure: f32, #[case] ground_temperature: f32) {
+        let altitude = hypsometric_altitude(Pressure::new::<millibar>(ground_pressure), &calibration(ground_pressure, ground_temperature));
+
+        assert!(altitude.get::<meter>().abs() < 0.01, "Calculated altitude: {}", altitude.get::<meter>());
+    }
+
+    #[rstest::rstest]
+    fn warmer_air_is_less_dense() {
+        let pressure = Pressure::new::<millibar>(1000.0);
+        let cold = hypsometric_altitude(pressure, &calibration(1013.25, -10.0));
+        let warm = hypsometric_altitude(pressure, &calibration(1013.25, 35.0));
+
+        assert!(warm > cold, "Same pressure drop spans more altitude in warmer air");
+    }
+
+    #[rstest::rstest]
+    #[case(&[1000.0], 1000.0)]
+    #[case(&[999.0, 1001.0], 1000.0)]
+    #[case(&[1013.0, 1013.5, 1012.5, 1013.0], 1013.0)]
+    fn pad_calibrator_averages_samples(#[case] pressures: &[f32], #[case] expected_pressure: f32) {
+        let mut calibrator = PadCalibrator::default();
+        for &pressure in pressures {
+            calibrator.add_sample(&AltimeterData {
+                pressure: Pressure::new::<millibar>(pressure),
+                altitude: altitude_from_pressure(Pressure::new::<millibar>(pressure)),
+                temperature: ThermodynamicTemperature::new::<degree_celsius>(20.0),
+            });
+        }
+
+        let calibration = calibrator.finish().expect("At least one sample was added");
+        assert_eq!(usize::from(calibration.samples), pressures.len());
+        assert!((calibration.ground_pressure.get::<millibar>() - expected_pressure).abs() < 0.01);
+        assert!((calibration.ground_temperature.get::<degree_celsius>() - 20.0).abs() < 0.01);
+    }
+
+    #[rstest::rstest]
+    fn pad_calibrator_without_samples() {
+        assert!(PadCalibrator::default().finish().is_none());
+    }
 }
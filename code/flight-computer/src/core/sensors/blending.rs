@@ -0,0 +1,95 @@
+use embassy_time::Instant;
+use proto::sensor_data::{Acceleration, HighGAccelerometerData, ImuData, Vector3};
+use proto::uom::si::acceleration::meter_per_second_squared;
+#[allow(unused_imports)]
+use proto::uom::num_traits::Float;
+
+use crate::config::AccelerationBlendingConfig;
+
+/// Whether any axis reads close enough to `full_scale`, the IMU's `ImuSensor::FULL_SCALE`, to be clipped.
+#[must_use]
+pub fn is_saturated(acceleration: &Vector3<Acceleration>, full_scale: Acceleration) -> bool {
+    let limit = full_scale.get::<meter_per_second_squared>() * AccelerationBlendingConfig::SATURATION_RATIO;
+
+    acceleration.iter().any(|axis| axis.get::<meter_per_second_squared>().abs() >= limit)
+}
+
+/// IMU sample with its acceleration taken from the high-g accelerometer while the IMU saturates.
+///
+/// The IMU reading is kept when no high-g sample arrived within `HIGH_G_MAX_AGE`, a clipped reading
+/// still beats a stale one.
+#[must_use]
+pub fn blend(imu: &ImuData, full_scale: Acceleration, high_g: Option<(HighGAccelerometerData, Instant)>, now: Instant) -> ImuData {
+    match high_g {
+        Some((high_g, received)) if is_saturated(&imu.acceleration, full_scale)
+            && now.saturating_duration_since(received) <= AccelerationBlendingConfig::HIGH_G_MAX_AGE =>
+            ImuData { acceleration: high_g.acceleration, ..imu.clone() },
+        _ => imu.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::uom::si::acceleration::standard_gravity;
+
+    use crate::test_utils::sensor_data::random_imu_data;
+
+    use super::*;
+
+    fn sixteen_g() -> Acceleration {
+        Acceleration::new::<standard_gravity>(16.0)
+    }
+
+    fn acceleration(z: f32) -> Vector3<Acceleration> {
+        Vector3::new(
+            Acceleration::new::<meter_per_second_squared>(0.5),
+            Acceleration::new::<meter_per_second_squared>(-0.5),
+            Acceleration::new::<meter_per_second_squared>(z),
+        )
+    }
+
+    fn imu(z: f32) -> ImuData {
+        ImuData { acceleration: acceleration(z), ..random_imu_data() }
+    }
+
+    fn high_g(z: f32, received_ms: u64) -> (HighGAccelerometerData, Instant) {
+        (HighGAccelerometerData { acceleration: acceleration(z) }, Instant::from_millis(received_ms))
+    }
+
+    #[rstest::rstest]
+    #[case(16.0, 9.81, false)]
+    #[case(16.0, 149.0, false)]
+    #[case(16.0, 150.0, true)]
+    #[case(16.0, -156.9, true)]
+    #[case(4.0, 36.0, false)]
+    #[case(4.0, 37.5, true)]
+    fn detects_saturation(#[case] full_scale: f32, #[case] z: f32, #[case] expected: bool) {
+        assert_eq!(is_saturated(&acceleration(z), Acceleration::new::<standard_gravity>(full_scale)), expected);
+    }
+
+    #[rstest::rstest]
+    fn keeps_unsaturated_imu() {
+        let imu = imu(40.0);
+
+        assert_eq!(blend(&imu, sixteen_g(), Some(high_g(41.0, 100)), Instant::from_millis(100)), imu);
+    }
+
+    #[rstest::rstest]
+    fn switches_to_high_g_when_saturated() {
+        let imu = imu(156.9);
+
+        let blended = blend(&imu, sixteen_g(), Some(high_g(300.0, 90)), Instant::from_millis(100));
+
+        assert_eq!(blended.acceleration, acceleration(300.0));
+        assert_eq!(blended.gyro, imu.gyro, "Only the acceleration is replaced");
+    }
+
+    #[rstest::rstest]
+    #[case(None)]
+    #[case(Some(high_g(300.0, 0)))]
+    fn keeps_saturated_imu_without_fresh_high_g(#[case] high_g: Option<(HighGAccelerometerData, Instant)>) {
+        let imu = imu(156.9);
+
+        assert_eq!(blend(&imu, sixteen_g(), high_g, Instant::from_millis(100)), imu);
+    }
+}
@@ -0,0 +1,480 @@
+use embassy_time::{Duration, Instant};
+use heapless::Vec;
+use proto::event::SensorHealthEvent;
+use proto::sensor_data::{Acceleration, AltimeterData, Altitude, GpsData, HighGAccelerometerData, ImuData, Vector3};
+use proto::sensor_health::{AltitudeSource, SensorFault, SensorHealth, SensorHealthReport, SensorId};
+use proto::uom::si::{acceleration::meter_per_second_squared, length::meter, velocity::meter_per_second};
+#[allow(unused_imports)]
+use proto::uom::num_traits::Float;
+
+use crate::config::SensorHealthConfig;
+
+/// Health bookkeeping for one sensor, fed with one representative scalar (SI units) per sample.
+struct HealthTracker {
+    stale_timeout: Duration,
+    /// Maximum plausible rate of change, in SI units per second.
+    max_rate: f32,
+
+    received_any: bool,
+    last_update: Instant,
+    last_value: f32,
+    rate: f32,
+
+    stuck_samples: usize,
+    rate_violations: usize,
+    disagreement: bool,
+    stale: bool,
+}
+
+impl HealthTracker {
+    const fn new(stale_timeout: Duration, max_rate: f32, now: Instant) -> Self {
+        Self {
+            stale_timeout,
+            max_rate,
+
+            received_any: false,
+            last_update: now,
+            last_value: 0.0,
+            rate: 0.0,
+
+            stuck_samples: 0,
+            rate_violations: 0,
+            disagreement: false,
+            stale: false,
+        }
+    }
+
+    fn update(&mut self, value: f32, now: Instant) {
+        if self.received_any {
+            // a stuck sensor repeats bit-identical readings, noise never does
+            if value.to_bits() == self.last_value.to_bits() {
+                self.stuck_samples = self.stuck_samples.saturating_add(1);
+            } else {
+                self.stuck_samples = 0;
+            }
+
+            let delta_t = now.saturating_duration_since(self.last_update).as_micros() as f32 / 1_000_000.0;
+            if delta_t > 0.0 {
+                self.rate = (value - self.last_value) / delta_t;
+
+                if self.rate.abs() > self.max_rate {
+                    self.rate_violations = self.rate_violations.saturating_add(1);
+                } else {
+                    self.rate_violations = 0;
+                }
+            }
+        }
+
+        self.received_any = true;
+        self.last_update = now;
+        self.last_value = value;
+        self.stale = false;
+    }
+
+    fn check_staleness(&mut self, now: Instant) {
+        self.stale = now.saturating_duration_since(self.last_update) > self.stale_timeout;
+    }
+
+    const fn fault(&self) -> Option<SensorFault> {
+        if self.stale {
+            Some(SensorFault::Stale)
+        } else if self.rate_violations > 0 {
+            Some(SensorFault::RateOfChange)
+        } else if self.stuck_samples >= SensorHealthConfig::STUCK_SAMPLES {
+            Some(SensorFault::Stuck)
+        } else if self.disagreement {
+            Some(SensorFault::Disagreement)
+        } else {
+            None
+        }
+    }
+
+    const fn health(&self) -> SensorHealth {
+        if self.stale || self.rate_violations >= SensorHealthConfig::RATE_VIOLATIONS_TO_FAIL {
+            SensorHealth::Failed
+        } else if !self.received_any {
+            SensorHealth::Unknown
+        } else if self.fault().is_some() {
+            SensorHealth::Degraded
+        } else {
+            SensorHealth::Healthy
+        }
+    }
+}
+
+/// Runs plausibility checks on the raw sensor streams and picks the altitude source for the detectors.
+///
+/// Time is passed in explicitly, so the monitor can be driven from recorded or synthetic data.
+pub struct SensorHealthMonitor {
+    altimeter: HealthTracker,
+    gps: HealthTracker,
+    imu: HealthTracker,
+    high_g_accelerometer: HealthTracker,
+
+    baro_altitude: Option<f32>,
+    gps_altitude: Option<f32>,
+    /// Barometric minus GPS altitude, learned while both are healthy.
+    gps_altitude_offset: Option<f32>,
+    imu_acceleration: Option<f32>,
+
+    published: SensorHealthReport,
+}
+
+impl SensorHealthMonitor {
+    #[must_use]
+    pub fn new(now: Instant) -> Self {
+        Self {
+            altimeter: HealthTracker::new(
+                SensorHealthConfig::ALTIMETER_STALE_TIMEOUT,
+                SensorHealthConfig::max_altitude_rate().get::<meter_per_second>(),
+                now,
+            ),
+            gps: HealthTracker::new(
+                SensorHealthConfig::GPS_STALE_TIMEOUT,
+                SensorHealthConfig::max_altitude_rate().get::<meter_per_second>(),
+                now,
+            ),
+            imu: HealthTracker::new(
+                SensorHealthConfig::IMU_STALE_TIMEOUT,
+                SensorHealthConfig::MAX_ACCELERATION_RATE,
+                now,
+            ),
+            high_g_accelerometer: HealthTracker::new(
+                SensorHealthConfig::HIGH_G_ACCELEROMETER_STALE_TIMEOUT,
+                SensorHealthConfig::MAX_ACCELERATION_RATE,
+                now,
+            ),
+
+            baro_altitude: None,
+            gps_altitude: None,
+            gps_altitude_offset: None,
+            imu_acceleration: None,
+
+            published: SensorHealthReport::default(),
+        }
+    }
+
+    pub fn update_altimeter(&mut self, data: &AltimeterData, now: Instant) {
+        let altitude = data.altitude.get::<meter>();
+        self.altimeter.update(altitude, now);
+        self.baro_altitude = Some(altitude);
+        self.cross_check();
+    }
+
+    pub fn update_gps(&mut self, data: &GpsData, now: Instant) {
+        let altitude = data.altitude.get::<meter>();
+        self.gps.update(altitude, now);
+        self.gps_altitude = Some(altitude);
+        self.cross_check();
+        self.learn_gps_altitude_offset(altitude, now);
+    }
+
+    pub fn update_imu(&mut self, data: &ImuData, now: Instant) {
+        let magnitude = magnitude(&data.acceleration);
+
+        self.imu.update(magnitude, now);
+        self.imu_acceleration = Some(magnitude);
+        self.cross_check();
+    }
+
+    pub fn update_high_g_accelerometer(&mut self, data: &HighGAccelerometerData, now: Instant) {
+        self.high_g_accelerometer.update(magnitude(&data.acceleration), now);
+    }
+
+    pub fn check_staleness(&mut self, now: Instant) {
+        self.altimeter.check_staleness(now);
+        self.gps.check_staleness(now);
+        self.imu.check_staleness(now);
+        // optional on the board, a missing one is not a failure
+        if self.high_g_accelerometer.received_any {
+            self.high_g_accelerometer.check_staleness(now);
+        }
+    }
+
+    fn learn_gps_altitude_offset(&mut self, gps_altitude: f32, now: Instant) {
+        let (Some(baro_altitude), SensorHealth::Healthy, SensorHealth::Healthy) = (self.baro_altitude, self.altimeter.health(), self.gps.health()) else {
+            return;
+        };
+        // staleness is only flagged on the next check, a late barometer must not skew the offset meanwhile
+        if now.saturating_duration_since(self.altimeter.last_update) > self.altimeter.stale_timeout {
+            return;
+        }
+
+        let offset = baro_altitude - gps_altitude;
+        self.gps_altitude_offset = Some(self.gps_altitude_offset.map_or(offset, |previous| {
+            (offset - previous).mul_add(SensorHealthConfig::GPS_ALTITUDE_OFFSET_WEIGHT, previous)
+        }));
+    }
+
+    /// Brings GPS altitudes, above mean sea level, onto the barometric scale of the launchpad reference.
+    ///
+    /// Frozen once the barometer stops being healthy, zero if it never was.
+    #[must_use]
+    pub fn gps_altitude_offset(&self) -> Altitude {
+        Altitude::new::<meter>(self.gps_altitude_offset.unwrap_or(0.0))
+    }
+
+    /// The barometer is the only sensor cross-checked: GPS and IMU are the references.
+    fn cross_check(&mut self) {
+        let disagrees_with_gps = match (self.baro_altitude, self.gps_altitude) {
+            (Some(baro), Some(gps)) if matches!(self.gps.health(), SensorHealth::Healthy) =>
+                (baro - gps).abs() > SensorHealthConfig::max_baro_gps_disagreement().get::<meter>(),
+            _ => false,
+        };
+
+        // IMU reports boost while the barometer reports a fast descent
+        let disagrees_with_imu = match self.imu_acceleration {
+            Some(acceleration) if matches!(self.imu.health(), SensorHealth::Healthy) && self.altimeter.received_any =>
+                acceleration > SensorHealthConfig::boost_acceleration_threshold().get::<meter_per_second_squared>() &&
+                self.altimeter.rate < SensorHealthConfig::max_boost_descent_velocity().get::<meter_per_second>(),
+            _ => false,
+        };
+
+        self.altimeter.disagreement = disagrees_with_gps || disagrees_with_imu;
+    }
+
+    #[must_use]
+    pub fn report(&self) -> SensorHealthReport {
+        let altimeter = self.altimeter.health();
+        let gps = self.gps.health();
+
+        let altitude_source = if matches!(altimeter, SensorHealth::Failed) && matches!(gps, SensorHealth::Healthy | SensorHealth::Degraded) {
+            AltitudeSource::Gps
+        } else {
+            AltitudeSource::Barometer
+        };
+
+        SensorHealthReport {
+            altimeter,
+            gps,
+            imu: self.imu.health(),
+            high_g_accelerometer: self.high_g_accelerometer.health(),
+            altitude_source,
+        }
+    }
+
+    /// Returns one event per change since the last call.
+    pub fn poll_events(&mut self) -> Vec<SensorHealthEvent, 5> {
+        let report = self.report();
+        let mut events = Vec::new();
+
+        for (sensor, tracker) in [
+            (SensorId::Altimeter, &self.altimeter),
+            (SensorId::Gps, &self.gps),
+            (SensorId::Imu, &self.imu),
+            (SensorId::HighGAccelerometer, &self.high_g_accelerometer),
+        ] {
+            if self.published.get(sensor) != report.get(sensor) {
+                events.push(SensorHealthEvent::HealthChanged {
+                    sensor,
+                    health: report.get(sensor),
+                    fault: tracker.fault(),
+                }).ok();
+            }
+        }
+
+        if self.published.altitude_source != report.altitude_source {
+            events.push(SensorHealthEvent::AltitudeSourceChanged(report.altitude_source)).ok();
+        }
+
+        self.published = report;
+        events
+    }
+}
+
+fn magnitude(acceleration: &Vector3<Acceleration>) -> f32 {
+    let x = acceleration.x.get::<meter_per_second_squared>();
+    let y = acceleration.y.get::<meter_per_second_squared>();
+    let z = acceleration.z.get::<meter_per_second_squared>();
+    x.mul_add(x, y.mul_add(y, z * z)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::sensor_data::{random_altimeter_data, random_gps_data, random_imu_data};
+
+    use super::*;
+
+    fn altimeter(altitude: f32) -> AltimeterData {
+        AltimeterData { altitude: Altitude::new::<meter>(altitude), ..random_altimeter_data() }
+    }
+
+    fn gps(altitude: f32) -> GpsData {
+        GpsData { altitude: Altitude::new::<meter>(altitude), ..random_gps_data() }
+    }
+
+    fn imu(acceleration_z: f32) -> ImuData {
+        ImuData {
+            acceleration: Vector3::new(
+                Acceleration::new::<meter_per_second_squared>(0.0),
+                Acceleration::new::<meter_per_second_squared>(0.0),
+                Acceleration::new::<meter_per_second_squared>(acceleration_z),
+            ),
+            ..random_imu_data()
+        }
+    }
+
+    fn high_g(acceleration_z: f32) -> HighGAccelerometerData {
+        HighGAccelerometerData {
+            acceleration: Vector3::new(
+                Acceleration::new::<meter_per_second_squared>(0.0),
+                Acceleration::new::<meter_per_second_squared>(0.0),
+                Acceleration::new::<meter_per_second_squared>(acceleration_z),
+            ),
+        }
+    }
+
+    /// Slightly noisy samples, so they are never flagged as stuck.
+    fn noise(i: u64) -> f32 {
+        (i % 3) as f32 * 0.01
+    }
+
+    #[rstest::rstest]
+    fn healthy_sensors() {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        for i in 0..10 {
+            let now = Instant::from_millis(i * 20);
+            monitor.update_altimeter(&altimeter(100.0 + noise(i)), now);
+            monitor.update_gps(&gps(100.0 + noise(i)), now);
+            monitor.update_imu(&imu(9.81 + noise(i)), now);
+        }
+        monitor.check_staleness(Instant::from_millis(200));
+
+        let report = monitor.report();
+        assert_eq!(report.altimeter, SensorHealth::Healthy);
+        assert_eq!(report.gps, SensorHealth::Healthy);
+        assert_eq!(report.imu, SensorHealth::Healthy);
+        assert_eq!(report.altitude_source, AltitudeSource::Barometer);
+    }
+
+    #[rstest::rstest]
+    fn stale_altimeter_fails_over_to_gps() {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        for i in 0..3 {
+            monitor.update_altimeter(&altimeter(100.0 + noise(i)), Instant::from_millis(i * 20));
+        }
+        for i in 0..6 {
+            monitor.update_gps(&gps(100.0 + noise(i)), Instant::from_millis(i * 100));
+        }
+        monitor.check_staleness(Instant::from_millis(500));
+
+        let events = monitor.poll_events();
+        assert!(events.contains(&SensorHealthEvent::HealthChanged {
+            sensor: SensorId::Altimeter,
+            health: SensorHealth::Failed,
+            fault: Some(SensorFault::Stale),
+        }));
+        assert!(events.contains(&SensorHealthEvent::AltitudeSourceChanged(AltitudeSource::Gps)));
+        assert_eq!(monitor.report().imu, SensorHealth::Failed, "IMU never reported");
+
+        assert!(monitor.poll_events().is_empty(), "Events are only reported once");
+    }
+
+    #[rstest::rstest]
+    fn learns_gps_altitude_offset_while_barometer_is_healthy() {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+        assert!(monitor.gps_altitude_offset().get::<meter>().abs() < f32::EPSILON, "No offset before both reported");
+
+        for i in 0..10 {
+            let now = Instant::from_millis(i * 100);
+            monitor.update_altimeter(&altimeter(120.0 + noise(i)), now);
+            monitor.update_gps(&gps(80.0 + noise(i)), now);
+        }
+        let learned = monitor.gps_altitude_offset().get::<meter>();
+        assert!((learned - 40.0).abs() < 0.1, "Expected about 40 m, got {learned} m");
+
+        // the barometer goes stale while GPS keeps climbing
+        for i in 13..23 {
+            monitor.update_gps(&gps(80.0 + i as f32), Instant::from_millis(i * 100));
+        }
+        monitor.check_staleness(Instant::from_millis(2_300));
+
+        assert_eq!(monitor.report().altitude_source, AltitudeSource::Gps);
+        assert!((monitor.gps_altitude_offset().get::<meter>() - learned).abs() < f32::EPSILON, "Offset is frozen once the barometer fails");
+    }
+
+    #[rstest::rstest]
+    #[case(SensorHealthConfig::STUCK_SAMPLES - 1, SensorHealth::Healthy)]
+    #[case(SensorHealthConfig::STUCK_SAMPLES, SensorHealth::Degraded)]
+    fn stuck_altimeter_is_degraded(#[case] repeated_samples: usize, #[case] expected: SensorHealth) {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        for i in 0..=repeated_samples as u64 {
+            monitor.update_altimeter(&altimeter(100.0), Instant::from_millis(i * 20));
+        }
+
+        assert_eq!(monitor.report().altimeter, expected);
+    }
+
+    #[rstest::rstest]
+    #[case(1, SensorHealth::Degraded)]
+    #[case(SensorHealthConfig::RATE_VIOLATIONS_TO_FAIL, SensorHealth::Failed)]
+    fn implausible_rate_of_change(#[case] violations: usize, #[case] expected: SensorHealth) {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        // +1 km every 20 ms
+        for i in 0..=violations as u64 {
+            monitor.update_altimeter(&altimeter(i as f32 * 1_000.0), Instant::from_millis(i * 20));
+        }
+
+        assert_eq!(monitor.report().altimeter, expected);
+    }
+
+    #[rstest::rstest]
+    fn baro_gps_disagreement() {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        for i in 0..3 {
+            let now = Instant::from_millis(i * 100);
+            monitor.update_gps(&gps(100.0 + noise(i)), now);
+            monitor.update_altimeter(&altimeter(500.0 + noise(i)), now);
+        }
+
+        let report = monitor.report();
+        assert_eq!(report.altimeter, SensorHealth::Degraded);
+        assert_eq!(report.altitude_source, AltitudeSource::Barometer, "Degraded barometer is still used");
+    }
+
+    #[rstest::rstest]
+    fn baro_descending_during_boost() {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        for i in 0..5 {
+            let now = Instant::from_millis(i * 20);
+            monitor.update_imu(&imu(50.0 + noise(i)), now);
+            // -20 m/s
+            monitor.update_altimeter(&altimeter(100.0 - i as f32 * 0.4), now);
+        }
+
+        assert_eq!(monitor.report().altimeter, SensorHealth::Degraded);
+    }
+
+    #[rstest::rstest]
+    fn missing_high_g_accelerometer_is_not_a_failure() {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        monitor.check_staleness(Instant::from_millis(1_000));
+
+        assert_eq!(monitor.report().high_g_accelerometer, SensorHealth::Unknown);
+    }
+
+    #[rstest::rstest]
+    fn stale_high_g_accelerometer_fails() {
+        let mut monitor = SensorHealthMonitor::new(Instant::from_millis(0));
+
+        for i in 0..3 {
+            monitor.update_high_g_accelerometer(&high_g(9.81 + noise(i)), Instant::from_millis(i * 10));
+        }
+        assert_eq!(monitor.report().high_g_accelerometer, SensorHealth::Healthy);
+
+        monitor.check_staleness(Instant::from_millis(500));
+
+        assert!(monitor.poll_events().contains(&SensorHealthEvent::HealthChanged {
+            sensor: SensorId::HighGAccelerometer,
+            health: SensorHealth::Failed,
+            fault: Some(SensorFault::Stale),
+        }));
+    }
+}
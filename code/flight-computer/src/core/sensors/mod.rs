@@ -1 +1,3 @@
-pub mod altimeter;
\ No newline at end of file
+pub mod altimeter;
+pub mod blending;
+pub mod health;
@@ -1,47 +1,120 @@
 use core::marker::PhantomData;
 
+use proto::event::{Event, FsmEvent, TransitionReason};
+use proto::flight_state::FlightCheckpoint;
+use proto::record::tick_hz::Timestamp;
 use proto::sensor_data::Altitude;
 
-use crate::{core::state_machine::states::FlightState, interfaces::{ArmingSystem, DeploymentSystem, Led}};
+use crate::core::led_pattern::{PatternLed, flight_state_pattern};
+use crate::{core::state_machine::{detectors::ApogeeDetection, states::{Armed, FlightState, PreArmed, RecoveryActivated}}, interfaces::{ArmingSystem, DeploymentSystem, Led}, sync::{broadcast_record, pad_calibration}};
 
 mod states;
-mod detectors;
+pub mod detectors;
 
-pub struct FlightStateMachine<A, LedA, D, LedD, S>
+pub struct FlightStateMachine<A, LedA, D, LedD, AD, S>
 where
     A: ArmingSystem,
     LedA: Led,
     D: DeploymentSystem,
     LedD: Led,
+    AD: ApogeeDetection,
     S: FlightState,
 {
     arm_button: A,
-    arm_button_led: LedA,
+    arm_button_led: PatternLed<LedA>,
     deployment_system: D,
     deployment_system_led: LedD,
+    apogee_detection: AD,
     _state: PhantomData<S>,
 
     /// The following attributes describe 
     launchpad_altitude: Option<Altitude>,
+    armed_at: Option<Timestamp>,
+    recovery_activated_at: Option<Timestamp>,
+    resumes: u8,
 }
 
-impl<A, LedA, D, LedD, S> FlightStateMachine<A, LedA, D, LedD, S>
+/// Typestate the FSM boots into, depending on the checkpoint left by the previous boot.
+pub enum ResumedFlightStateMachine<A, LedA, D, LedD, AD>
 where
     A: ArmingSystem,
     LedA: Led,
     D: DeploymentSystem,
     LedD: Led,
+    AD: ApogeeDetection,
+{
+    PreArmed(FlightStateMachine<A, LedA, D, LedD, AD, PreArmed>),
+    Armed(FlightStateMachine<A, LedA, D, LedD, AD, Armed>),
+    RecoveryActivated(FlightStateMachine<A, LedA, D, LedD, AD, RecoveryActivated>),
+}
+
+impl<A, LedA, D, LedD, AD, S> FlightStateMachine<A, LedA, D, LedD, AD, S>
+where
+    A: ArmingSystem,
+    LedA: Led,
+    D: DeploymentSystem,
+    LedD: Led,
+    AD: ApogeeDetection,
     S: FlightState,
 {
     // Common transition helper
-    fn transition<T: FlightState>(self) -> FlightStateMachine<A, LedA, D, LedD, T> {
+    fn transition<T: FlightState>(self, reason: TransitionReason) -> FlightStateMachine<A, LedA, D, LedD, AD, T> {
+        broadcast_record(Event::Fsm(FsmEvent::Transition { from: S::STATE, to: T::STATE, reason }).into());
+
+        let mut arm_button_led = self.arm_button_led;
+        arm_button_led.set(flight_state_pattern(T::STATE));
+
         FlightStateMachine {
             arm_button: self.arm_button,
-            arm_button_led: self.arm_button_led,
+            arm_button_led,
             deployment_system: self.deployment_system,
             deployment_system_led: self.deployment_system_led,
+            apogee_detection: self.apogee_detection,
             launchpad_altitude: self.launchpad_altitude,
+            armed_at: self.armed_at,
+            recovery_activated_at: self.recovery_activated_at,
+            resumes: self.resumes,
             _state: PhantomData,
         }
     }
+
+    pub fn checkpoint(&self) -> FlightCheckpoint {
+        FlightCheckpoint {
+            state: S::STATE,
+            launchpad_altitude: self.launchpad_altitude,
+            pad_calibration: pad_calibration(),
+            armed_at: self.armed_at,
+            recovery_activated_at: self.recovery_activated_at,
+            resumes: self.resumes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::flight_state::FlightState as State;
+
+    use crate::core::state_machine::detectors::apogee_detection::PressureMinimum;
+    use crate::sync::{PRIORITY_RECORD_TO_STORAGE_CHANNEL, set_storage_open};
+    use crate::test_utils::interfaces::{FlakyDeployment, TestArming, TestLed};
+    use crate::test_utils::{ms, stored_fsm_events};
+
+    use super::*;
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(100))]
+    async fn transition_reports_both_states_and_the_reason() {
+        set_storage_open(true);
+        PRIORITY_RECORD_TO_STORAGE_CHANNEL.clear();
+        let fsm = FlightStateMachine::new(TestArming, TestLed, FlakyDeployment::default(), TestLed, PressureMinimum::default()).await;
+
+        let _: FlightStateMachine<_, _, _, _, _, Armed> = fsm.transition(TransitionReason::ArmCommand);
+
+        assert_eq!(
+            stored_fsm_events(),
+            [FsmEvent::Transition { from: State::PreArmed, to: State::Armed, reason: TransitionReason::ArmCommand }],
+        );
+    }
 }
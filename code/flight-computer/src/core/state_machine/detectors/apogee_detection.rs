@@ -0,0 +1,162 @@
+use embassy_time::Instant;
+use proto::sensor_data::{Altitude, Pressure, Time, Velocity};
+use proto::uom::si::time::microsecond;
+
+use crate::config::ApogeeDetectorConfig;
+
+mod descent_velocity;
+pub use descent_velocity::DescentVelocity;
+mod velocity_sign_change;
+pub use velocity_sign_change::VelocitySignChange;
+mod pressure_minimum;
+pub use pressure_minimum::PressureMinimum;
+mod predicted_apogee;
+pub use predicted_apogee::PredictedApogee;
+mod voting;
+pub use voting::Voting;
+
+/// Altitude sample fed to the apogee detection strategies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ApogeeSample {
+    /// Altitude above the launchpad.
+    pub altitude: Altitude,
+    /// Raw barometric pressure, absent while the altitude comes from the GPS.
+    pub pressure: Option<Pressure>,
+    pub time: Instant,
+}
+
+impl ApogeeSample {
+    /// Velocity between two samples, `None` if they share a timestamp.
+    fn velocity_since(&self, prev: &Self) -> Option<Velocity> {
+        let delta_t = self.time.saturating_duration_since(prev.time).as_micros();
+        (delta_t > 0).then(|| (self.altitude - prev.altitude) / Time::new::<microsecond>(delta_t as f32))
+    }
+}
+
+pub trait ApogeeDetection {
+    fn name(&self) -> &'static str;
+
+    /// Feeds a new sample. Returns `true` once apogee has been detected.
+    fn update(&mut self, sample: &ApogeeSample) -> bool;
+}
+
+/// Apogee detection strategies selectable through [`ApogeeDetectorConfig::STRATEGY`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApogeeStrategy {
+    DescentVelocity,
+    VelocitySignChange,
+    PressureMinimum,
+    PredictedApogee,
+    /// Majority vote of velocity sign change, pressure minimum and predicted apogee.
+    Voting,
+}
+
+pub enum AnyApogeeDetection {
+    DescentVelocity(DescentVelocity),
+    VelocitySignChange(VelocitySignChange),
+    PressureMinimum(PressureMinimum),
+    PredictedApogee(PredictedApogee),
+    Voting(Voting<VelocitySignChange, PressureMinimum, PredictedApogee>),
+}
+
+impl From<ApogeeStrategy> for AnyApogeeDetection {
+    fn from(strategy: ApogeeStrategy) -> Self {
+        match strategy {
+            ApogeeStrategy::DescentVelocity => Self::DescentVelocity(DescentVelocity::default()),
+            ApogeeStrategy::VelocitySignChange => Self::VelocitySignChange(VelocitySignChange::default()),
+            ApogeeStrategy::PressureMinimum => Self::PressureMinimum(PressureMinimum::default()),
+            ApogeeStrategy::PredictedApogee => Self::PredictedApogee(PredictedApogee::default()),
+            ApogeeStrategy::Voting => Self::Voting(Voting::new(
+                VelocitySignChange::default(),
+                PressureMinimum::default(),
+                PredictedApogee::default(),
+            )),
+        }
+    }
+}
+
+impl Default for AnyApogeeDetection {
+    fn default() -> Self {
+        ApogeeDetectorConfig::STRATEGY.into()
+    }
+}
+
+impl ApogeeDetection for AnyApogeeDetection {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::DescentVelocity(detection) => detection.name(),
+            Self::VelocitySignChange(detection) => detection.name(),
+            Self::PressureMinimum(detection) => detection.name(),
+            Self::PredictedApogee(detection) => detection.name(),
+            Self::Voting(detection) => detection.name(),
+        }
+    }
+
+    fn update(&mut self, sample: &ApogeeSample) -> bool {
+        match self {
+            Self::DescentVelocity(detection) => detection.update(sample),
+            Self::VelocitySignChange(detection) => detection.update(sample),
+            Self::PressureMinimum(detection) => detection.update(sample),
+            Self::PredictedApogee(detection) => detection.update(sample),
+            Self::Voting(detection) => detection.update(sample),
+        }
+    }
+}
+
+/// Every strategy runs against the same flight profiles.
+#[cfg(test)]
+mod tests {
+    use embassy_time::Duration;
+
+    use crate::test_utils::flight_profiles::{FlightProfile, gps_only_flight, high_flight, low_flight, noisy_flight, nominal_flight, pad_only};
+
+    use super::*;
+
+    /// Predicted apogee may fire up to its lead time, plus one sample, before the real apogee.
+    const MAX_EARLY: Duration = Duration::from_millis(ApogeeDetectorConfig::PREDICTED_APOGEE_LEAD_TIME.as_millis() + ApogeeDetectorConfig::DETECTOR_TICK_INTERVAL.as_millis());
+    const MAX_LATE: Duration = Duration::from_secs(3);
+
+    fn detect(strategy: ApogeeStrategy, profile: &FlightProfile) -> Option<Instant> {
+        let mut detection = AnyApogeeDetection::from(strategy);
+        profile.samples.iter()
+            .find(|sample| detection.update(sample))
+            .map(|sample| sample.time)
+    }
+
+    #[rstest::rstest]
+    fn detects_apogee(
+        #[values(
+            ApogeeStrategy::DescentVelocity,
+            ApogeeStrategy::VelocitySignChange,
+            ApogeeStrategy::PressureMinimum,
+            ApogeeStrategy::PredictedApogee,
+            ApogeeStrategy::Voting,
+        )] strategy: ApogeeStrategy,
+        #[values(nominal_flight(), noisy_flight(), low_flight(), high_flight(), gps_only_flight())] profile: FlightProfile,
+    ) {
+        let apogee = profile.apogee_time.expect("Flight profile should reach apogee");
+        let detected = detect(strategy, &profile);
+
+        if strategy == ApogeeStrategy::PressureMinimum && !profile.has_pressure() {
+            assert_eq!(detected, None, "Pressure minimum can't detect apogee without pressure");
+            return;
+        }
+
+        let detected = detected.unwrap_or_else(|| panic!("{strategy:?} never detected apogee"));
+        assert!(detected + MAX_EARLY >= apogee, "{strategy:?} detected apogee {} ms early", (apogee - detected).as_millis());
+        assert!(detected <= apogee + MAX_LATE, "{strategy:?} detected apogee {} ms late", (detected - apogee).as_millis());
+    }
+
+    #[rstest::rstest]
+    fn never_detects_apogee_on_the_pad(
+        #[values(
+            ApogeeStrategy::DescentVelocity,
+            ApogeeStrategy::VelocitySignChange,
+            ApogeeStrategy::PressureMinimum,
+            ApogeeStrategy::PredictedApogee,
+            ApogeeStrategy::Voting,
+        )] strategy: ApogeeStrategy,
+    ) {
+        assert_eq!(detect(strategy, &pad_only()), None);
+    }
+}
@@ -0,0 +1,54 @@
+use heapless::HistoryBuf;
+use proto::sensor_data::{Altitude, Velocity};
+
+use crate::config::ApogeeDetectorConfig;
+
+use super::{ApogeeDetection, ApogeeSample};
+
+/// Apogee once every buffered velocity is below [`ApogeeDetectorConfig::max_descent_velocity`].
+#[derive(Default)]
+pub struct DescentVelocity {
+    altitude_buffer: HistoryBuf<Altitude, { ApogeeDetectorConfig::ALTITUDE_BUFFER_SIZE }>,
+    velocity_buffer: HistoryBuf<Velocity, { ApogeeDetectorConfig::VELOCITY_BUFFER_SIZE }>,
+    prev_sample: Option<ApogeeSample>,
+}
+
+impl DescentVelocity {
+    fn are_buffers_full(&self) -> bool {
+        self.altitude_buffer.is_full() &&
+        self.velocity_buffer.is_full()
+    }
+}
+
+impl ApogeeDetection for DescentVelocity {
+    fn name(&self) -> &'static str {
+        "DescentVelocity"
+    }
+
+    fn update(&mut self, sample: &ApogeeSample) -> bool {
+        let velocity = self.prev_sample.and_then(|prev| sample.velocity_since(&prev));
+        self.prev_sample = Some(*sample);
+
+        let Some(velocity) = velocity else {
+            return false;
+        };
+
+        self.altitude_buffer.write(sample.altitude);
+        self.velocity_buffer.write(velocity);
+
+        // Check if buffers are full before evaluating conditions
+        if !self.are_buffers_full() {
+            return false;
+        }
+
+        let descent_vel_check = self.velocity_buffer.iter().all(
+            |&v| v <= ApogeeDetectorConfig::max_descent_velocity()
+        );
+
+        let minimum_altitude_check = self.altitude_buffer.iter().all(
+            |&h| h >= ApogeeDetectorConfig::min_apogee_altitude_above_launchpad()
+        );
+
+        descent_vel_check && minimum_altitude_check
+    }
+}
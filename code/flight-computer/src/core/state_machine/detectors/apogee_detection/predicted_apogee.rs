@@ -0,0 +1,63 @@
+use embassy_time::Instant;
+use proto::uom::si::{length::meter, velocity::meter_per_second};
+#[allow(unused_imports)]
+use proto::uom::num_traits::Float;
+
+use crate::config::ApogeeDetectorConfig;
+
+use super::{ApogeeDetection, ApogeeSample};
+
+const GRAVITY: f32 = 9.806_65;
+
+/// Alpha-beta estimate of altitude and vertical velocity.
+struct Estimate {
+    altitude: f32,
+    velocity: f32,
+    time: Instant,
+}
+
+/// Fires ahead of apogee, once the ballistic time to apogee drops below the deployment lead time.
+///
+/// Drag is ignored, so the predicted time to apogee is an upper bound and the lead is never larger than configured.
+#[derive(Default)]
+pub struct PredictedApogee {
+    estimate: Option<Estimate>,
+    ascending: bool,
+}
+
+impl ApogeeDetection for PredictedApogee {
+    fn name(&self) -> &'static str {
+        "PredictedApogee"
+    }
+
+    fn update(&mut self, sample: &ApogeeSample) -> bool {
+        let altitude = sample.altitude.get::<meter>();
+
+        let Some(estimate) = self.estimate.as_mut() else {
+            self.estimate = Some(Estimate { altitude, velocity: 0.0, time: sample.time });
+            return false;
+        };
+
+        let delta_t = sample.time.saturating_duration_since(estimate.time).as_micros() as f32 / 1_000_000.0;
+        if delta_t <= 0.0 {
+            return false;
+        }
+        estimate.time = sample.time;
+
+        // gravity is only modelled once airborne, it would bias the estimate on the pad
+        let gravity = if self.ascending { GRAVITY } else { 0.0 };
+        let predicted_altitude = estimate.velocity.mul_add(delta_t, -0.5 * gravity * delta_t * delta_t) + estimate.altitude;
+        let predicted_velocity = gravity.mul_add(-delta_t, estimate.velocity);
+
+        let residual = altitude - predicted_altitude;
+        estimate.altitude = ApogeeDetectorConfig::ESTIMATOR_ALPHA.mul_add(residual, predicted_altitude);
+        estimate.velocity = (ApogeeDetectorConfig::ESTIMATOR_BETA / delta_t).mul_add(residual, predicted_velocity);
+
+        if estimate.velocity > ApogeeDetectorConfig::min_ascent_velocity().get::<meter_per_second>() {
+            self.ascending = true;
+        }
+
+        let time_to_apogee = estimate.velocity / GRAVITY;
+        self.ascending && time_to_apogee <= ApogeeDetectorConfig::PREDICTED_APOGEE_LEAD_TIME.as_micros() as f32 / 1_000_000.0
+    }
+}
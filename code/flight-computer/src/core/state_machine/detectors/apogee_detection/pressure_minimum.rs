@@ -0,0 +1,48 @@
+use proto::sensor_data::Pressure;
+
+use crate::config::ApogeeDetectorConfig;
+
+use super::{ApogeeDetection, ApogeeSample};
+
+/// Apogee when the pressure rises consistently above the minimum seen during ascent.
+///
+/// Samples without pressure (GPS altitude source) are ignored.
+#[derive(Default)]
+pub struct PressureMinimum {
+    min_pressure: Option<Pressure>,
+    rising_samples: usize,
+    ascending: bool,
+}
+
+impl ApogeeDetection for PressureMinimum {
+    fn name(&self) -> &'static str {
+        "PressureMinimum"
+    }
+
+    fn update(&mut self, sample: &ApogeeSample) -> bool {
+        let Some(pressure) = sample.pressure else {
+            return false;
+        };
+
+        if sample.altitude > ApogeeDetectorConfig::ascent_detection_altitude() {
+            self.ascending = true;
+        }
+        if !self.ascending {
+            return false;
+        }
+
+        let min_pressure = match self.min_pressure {
+            Some(min_pressure) if min_pressure <= pressure => min_pressure,
+            _ => pressure,
+        };
+        self.min_pressure = Some(min_pressure);
+
+        if pressure - min_pressure > ApogeeDetectorConfig::pressure_rise_threshold() {
+            self.rising_samples += 1;
+        } else {
+            self.rising_samples = 0;
+        }
+
+        self.rising_samples >= ApogeeDetectorConfig::PRESSURE_RISE_SAMPLES
+    }
+}
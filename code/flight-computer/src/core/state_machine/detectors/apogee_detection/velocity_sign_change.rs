@@ -0,0 +1,45 @@
+use heapless::HistoryBuf;
+use proto::sensor_data::Velocity;
+use proto::uom::si::velocity::meter_per_second;
+
+use crate::config::ApogeeDetectorConfig;
+
+use super::{ApogeeDetection, ApogeeSample};
+
+/// Apogee when the averaged vertical velocity turns negative after a confirmed ascent.
+#[derive(Default)]
+pub struct VelocitySignChange {
+    velocity_buffer: HistoryBuf<Velocity, { ApogeeDetectorConfig::SIGN_CHANGE_VELOCITY_WINDOW }>,
+    prev_sample: Option<ApogeeSample>,
+    ascending: bool,
+}
+
+impl ApogeeDetection for VelocitySignChange {
+    fn name(&self) -> &'static str {
+        "VelocitySignChange"
+    }
+
+    fn update(&mut self, sample: &ApogeeSample) -> bool {
+        let velocity = self.prev_sample.and_then(|prev| sample.velocity_since(&prev));
+        self.prev_sample = Some(*sample);
+
+        let Some(velocity) = velocity else {
+            return false;
+        };
+        self.velocity_buffer.write(velocity);
+
+        if !self.velocity_buffer.is_full() {
+            return false;
+        }
+
+        let mean_velocity = self.velocity_buffer.iter()
+            .fold(Velocity::new::<meter_per_second>(0.0), |sum, &v| sum + v)
+            / self.velocity_buffer.len() as f32;
+
+        if mean_velocity > ApogeeDetectorConfig::min_ascent_velocity() {
+            self.ascending = true;
+        }
+
+        self.ascending && mean_velocity < Velocity::new::<meter_per_second>(0.0)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::config::ApogeeDetectorConfig;
+
+use super::{ApogeeDetection, ApogeeSample};
+
+/// Apogee once [`ApogeeDetectorConfig::VOTES_TO_DETECT`] of the three strategies agree.
+///
+/// A vote is latched: strategies that already detected apogee keep counting.
+pub struct Voting<A, B, C> {
+    strategies: (A, B, C),
+    votes: [bool; 3],
+}
+
+impl<A, B, C> Voting<A, B, C>
+where
+    A: ApogeeDetection,
+    B: ApogeeDetection,
+    C: ApogeeDetection,
+{
+    pub const fn new(a: A, b: B, c: C) -> Self {
+        Self {
+            strategies: (a, b, c),
+            votes: [false; 3],
+        }
+    }
+}
+
+impl<A, B, C> ApogeeDetection for Voting<A, B, C>
+where
+    A: ApogeeDetection,
+    B: ApogeeDetection,
+    C: ApogeeDetection,
+{
+    fn name(&self) -> &'static str {
+        "Voting"
+    }
+
+    fn update(&mut self, sample: &ApogeeSample) -> bool {
+        // every strategy sees every sample, so their internal state stays coherent
+        let results = [
+            self.strategies.0.update(sample),
+            self.strategies.1.update(sample),
+            self.strategies.2.update(sample),
+        ];
+
+        for (vote, result) in self.votes.iter_mut().zip(results) {
+            *vote |= result;
+        }
+
+        self.votes.iter().filter(|&&vote| vote).count() >= ApogeeDetectorConfig::VOTES_TO_DETECT
+    }
+}
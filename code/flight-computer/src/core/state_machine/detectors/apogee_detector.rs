@@ -1,68 +1,50 @@
 use embassy_time::{Instant, Ticker, with_timeout};
 use heapless::HistoryBuf;
-use proto::sensor_data::{Altitude, Time, Velocity};
-use proto::uom::si::time::microsecond;
+use proto::event::{APOGEE_WINDOW, Event, FsmEvent};
+use proto::sensor_data::Altitude;
 
 use crate::config::ApogeeDetectorConfig;
-use crate::log::warn;
-use crate::sync::LATEST_ALTITUDE_SIGNAL;
+use crate::core::state_machine::detectors::{ApogeeDetection, ApogeeSample};
+use crate::log::{info, warn};
+use crate::sync::{LATEST_ALTITUDE_SIGNAL, LATEST_PRESSURE_SIGNAL, broadcast_record};
 
-pub struct ApogeeDetector {
+/// Samples the latest altitude at a fixed rate and feeds it to an [`ApogeeDetection`] strategy.
+pub struct ApogeeDetector<'a, AD: ApogeeDetection> {
     launchpad_altitude: Altitude,
-
-    altitude_buffer: HistoryBuf<Altitude, { ApogeeDetectorConfig::ALTITUDE_BUFFER_SIZE }>,
-    velocity_buffer: HistoryBuf<Velocity, { ApogeeDetectorConfig::VELOCITY_BUFFER_SIZE }>,
-    prev_data: (Altitude, Instant),
+    detection: &'a mut AD,
+    /// Reported along with the detection.
+    recent_altitudes: HistoryBuf<Altitude, APOGEE_WINDOW>,
 }
 
-impl ApogeeDetector {
-    pub async fn new(
+impl<'a, AD: ApogeeDetection> ApogeeDetector<'a, AD> {
+    pub const fn new(
         launchpad_altitude: Altitude,
+        detection: &'a mut AD,
     ) -> Self {
-        let altitude = LATEST_ALTITUDE_SIGNAL.wait().await;
-        let altitude_above_launchpad = altitude - launchpad_altitude;
-
         Self {
             launchpad_altitude,
-
-            altitude_buffer: HistoryBuf::new(),
-            velocity_buffer: HistoryBuf::new(),
-            prev_data: (altitude_above_launchpad, Instant::now()),
+            detection,
+            recent_altitudes: HistoryBuf::new(),
         }
     }
 
-    fn are_buffers_full(&self) -> bool {
-        self.altitude_buffer.is_full() &&
-        self.velocity_buffer.is_full()
-    }
-
-    async fn get_altitude_above_launchpad(&self) -> Altitude {
+    async fn wait_new_sample(&self) -> ApogeeSample {
         let raw_altitude = LATEST_ALTITUDE_SIGNAL.wait().await;
-        raw_altitude - self.launchpad_altitude
-    }
 
-    fn get_latest_altitude_above_launchpad(&self) -> Altitude {
-        self.prev_data.0
+        ApogeeSample {
+            altitude: raw_altitude - self.launchpad_altitude,
+            pressure: LATEST_PRESSURE_SIGNAL.try_take(),
+            time: Instant::now(),
+        }
     }
 
-    async fn wait_new_data_and_update_buffers(&mut self) {
-        // delta_h
-        let prev_altitude = self.get_latest_altitude_above_launchpad();
-        let altitude = self.get_altitude_above_launchpad().await;
-        let delta_h = altitude - prev_altitude;
-
-        // delta_t
-        let now = Instant::now();
-        let delta_t = now - self.prev_data.1;
-        let delta_t = Time::new::<microsecond>(delta_t.as_micros() as f32);
-
-        // calculate velocity
-        let velocity = delta_h / delta_t;
-
-        // update buffers
-        self.altitude_buffer.write(altitude);
-        self.velocity_buffer.write(velocity);
-        self.prev_data = (altitude, now);
+    fn recent_altitudes_oldest_first(&self) -> [Option<Altitude>; APOGEE_WINDOW] {
+        let mut recent_altitudes = [None; APOGEE_WINDOW];
+        let missing = APOGEE_WINDOW - self.recent_altitudes.len();
+        for (slot, altitude) in recent_altitudes[missing..].iter_mut().zip(self.recent_altitudes.oldest_ordered()) {
+            *slot = Some(*altitude);
+        }
+        recent_altitudes
     }
 
     pub async fn await_apogee(&mut self) -> Altitude {
@@ -71,24 +53,20 @@ impl ApogeeDetector {
 
         loop {
             ticker.next().await;
-            if with_timeout(timeout, self.wait_new_data_and_update_buffers()).await.is_err() {
+            let Ok(sample) = with_timeout(timeout, self.wait_new_sample()).await else {
                 warn!("ApogeeDetector: Timed out waiting for new altitude data");
                 continue;
-            }
-
-            // Check if buffers are full before evaluating conditions
-            if self.are_buffers_full() {
-                let descent_vel_check = self.velocity_buffer.iter().all(
-                    |&v| v <= ApogeeDetectorConfig::max_descent_velocity()
-                );
+            };
 
-                let minimum_altitude_check = self.altitude_buffer.iter().all(
-                    |&h| h >= ApogeeDetectorConfig::min_apogee_altitude_above_launchpad()
-                );
+            self.recent_altitudes.write(sample.altitude);
 
-                if descent_vel_check && minimum_altitude_check {
-                    return self.get_latest_altitude_above_launchpad();
-                }
+            if self.detection.update(&sample) {
+                info!("ApogeeDetector: Apogee detected by {}", self.detection.name());
+                broadcast_record(Event::Fsm(FsmEvent::ApogeeDetected {
+                    altitude: sample.altitude,
+                    recent_altitudes: self.recent_altitudes_oldest_first(),
+                }).into());
+                return sample.altitude;
             }
         }
     }
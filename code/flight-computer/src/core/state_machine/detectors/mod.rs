@@ -1,5 +1,8 @@
 mod apogee_detector;
 pub use apogee_detector::ApogeeDetector;
 
+pub mod apogee_detection;
+pub use apogee_detection::{AnyApogeeDetection, ApogeeDetection, ApogeeSample, ApogeeStrategy};
+
 mod touchdown_detector;
-pub use touchdown_detector::TouchdownDetector;
\ No newline at end of file
+pub use touchdown_detector::TouchdownDetector;
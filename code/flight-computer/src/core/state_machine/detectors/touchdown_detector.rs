@@ -2,12 +2,13 @@ use core::cmp::Ordering;
 
 use embassy_time::{Instant, Ticker, with_timeout};
 use heapless::HistoryBuf;
+use proto::event::{Event, FsmEvent};
 use proto::sensor_data::{Altitude, Time, Velocity};
 use proto::uom::si::time::microsecond;
 
 use crate::config::TouchdownDetectorConfig;
 use crate::log::warn;
-use crate::sync::LATEST_ALTITUDE_SIGNAL;
+use crate::sync::{LATEST_ALTITUDE_SIGNAL, broadcast_record};
 
 pub struct TouchdownDetector {
     altitude_buffer: HistoryBuf<Altitude, { TouchdownDetectorConfig::ALTITUDE_BUFFER_SIZE }>,
@@ -79,6 +80,14 @@ impl TouchdownDetector {
                     .all(|&vel| vel.abs() <= TouchdownDetectorConfig::touchdown_velocity_threshold());
 
                 if touchdown_stability_check && touchdown_velocity_check {
+                    let mut altitudes = self.altitude_buffer.oldest_ordered().copied();
+                    let mut velocities = self.velocity_buffer.oldest_ordered().copied();
+                    broadcast_record(Event::Fsm(FsmEvent::TouchdownDetected {
+                        altitude: self.get_latest_altitude(),
+                        altitudes: core::array::from_fn(|_| altitudes.next().expect("Buffer is full")),
+                        velocities: core::array::from_fn(|_| velocities.next().expect("Buffer is full")),
+                    }).into());
+
                     return self.get_latest_altitude();
                 }
             }
@@ -8,8 +8,18 @@ pub struct Armed;
 pub struct RecoveryActivated;
 pub struct Touchdown;
 
-pub trait FlightState {}
-impl FlightState for PreArmed {}
-impl FlightState for Armed {}
-impl FlightState for RecoveryActivated {}
-impl FlightState for Touchdown {}
+pub trait FlightState {
+    const STATE: proto::flight_state::FlightState;
+}
+impl FlightState for PreArmed {
+    const STATE: proto::flight_state::FlightState = proto::flight_state::FlightState::PreArmed;
+}
+impl FlightState for Armed {
+    const STATE: proto::flight_state::FlightState = proto::flight_state::FlightState::Armed;
+}
+impl FlightState for RecoveryActivated {
+    const STATE: proto::flight_state::FlightState = proto::flight_state::FlightState::RecoveryActivated;
+}
+impl FlightState for Touchdown {
+    const STATE: proto::flight_state::FlightState = proto::flight_state::FlightState::Touchdown;
+}
@@ -1,64 +1,119 @@
 use embassy_time::with_timeout;
+use proto::event::{DeployOutcome, Detector, Event, FsmEvent, TransitionReason};
+use proto::record::tick_hz::Timestamp;
 use proto::uom::si::length::meter;
 use defmt_or_log::Debug2Format;
 
 use crate::config::ArmedConfig;
 use crate::log::{error, info};
-use crate::{core::state_machine::{FlightStateMachine, detectors::ApogeeDetector, states::{Armed, RecoveryActivated}}, interfaces::{ArmingSystem, DeploymentSystem, Led}};
+use crate::{core::state_machine::{FlightStateMachine, detectors::{ApogeeDetection, ApogeeDetector}, states::{Armed, RecoveryActivated}}, interfaces::{ArmingSystem, DeploymentSystem, Led}, sync::{APOGEE_SIGNAL, broadcast_record}};
 
-impl<A, LedA, D, LedD> FlightStateMachine<A, LedA, D, LedD, Armed>
+fn report_deploy_attempt(attempt: u32, outcome: DeployOutcome) {
+    broadcast_record(Event::Fsm(FsmEvent::DeployAttempt { attempt, outcome }).into());
+}
+
+impl<A, LedA, D, LedD, AD> FlightStateMachine<A, LedA, D, LedD, AD, Armed>
 where
     A: ArmingSystem,
     LedA: Led,
     D: DeploymentSystem,
     LedD: Led,
+    AD: ApogeeDetection,
 {
     async fn await_deployment_system(&mut self) {
         let mut deploy_attempt = 0u32;
         loop {
             deploy_attempt += 1;
             info!("Deploy attempt #{deploy_attempt}: calling deploy()");
-            match with_timeout(ArmedConfig::DEPLOY_TIMEOUT, self.deployment_system.deploy()).await {
+            report_deploy_attempt(deploy_attempt, DeployOutcome::Started);
+            let outcome = match with_timeout(ArmedConfig::DEPLOY_TIMEOUT, self.deployment_system.deploy()).await {
                 Err(_) => {
                     error!("Deploy attempt #{deploy_attempt} deploy() timed out, retrying");
+                    DeployOutcome::DeployTimedOut
                 },
                 Ok(Err(e)) => {
                     error!("Deploy attempt #{deploy_attempt} deploy() failed: {:?}", Debug2Format(&e));
+                    DeployOutcome::DeployFailed
                 },
                 Ok(Ok(())) => {
                     info!("Deploy attempt #{deploy_attempt} deploy() Ok, calling verify()");
                     match with_timeout(ArmedConfig::VERIFY_TIMEOUT, self.deployment_system.verify_deployment()).await {
                         Err(_) => {
                             error!("Deploy attempt #{deploy_attempt} verify() timed out, retrying");
+                            DeployOutcome::VerifyTimedOut
                         },
                         Ok(Err(e)) => {
                             error!("Deploy attempt #{deploy_attempt} verify() error: {:?}", Debug2Format(&e));
+                            DeployOutcome::VerifyFailed
                         },
                         Ok(Ok(false)) => {
                             error!("Deploy attempt #{deploy_attempt} verify() false, retrying");
+                            DeployOutcome::NotDeployed
                         },
                         Ok(Ok(true)) => {
                             info!("Deploy attempt #{deploy_attempt} verify Ok — deployment confirmed");
-                            return;
+                            DeployOutcome::Verified
                         },
                     }
                 },
+            };
+
+            report_deploy_attempt(deploy_attempt, outcome);
+            if outcome == DeployOutcome::Verified {
+                return;
             }
         }
     }
 
-    pub async fn wait_activate_recovery(mut self) -> FlightStateMachine<A, LedA, D, LedD, RecoveryActivated> {
-        let altitude_above_launchpad = ApogeeDetector::new(
+    pub async fn wait_activate_recovery(mut self) -> FlightStateMachine<A, LedA, D, LedD, AD, RecoveryActivated> {
+        broadcast_record(Event::Fsm(FsmEvent::DetectorArmed(Detector::Apogee)).into());
+
+        let mut apogee_detector = ApogeeDetector::new(
             self.launchpad_altitude.expect("Launchpad altitude should have been set in Armed state"),
-        ).await
-        .await_apogee()
-        .await;
+            &mut self.apogee_detection,
+        );
+        let altitude_above_launchpad = self.arm_button_led.play_until(apogee_detector.await_apogee()).await;
 
         info!("Apogee of {} m Reached!", altitude_above_launchpad.get::<meter>());
+        APOGEE_SIGNAL.signal(altitude_above_launchpad);
 
         self.await_deployment_system().await;
         self.deployment_system_led.on().await.ok();
+        self.recovery_activated_at = Some(Timestamp::now());
+
+        self.transition(TransitionReason::DeploymentVerified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::state_machine::detectors::apogee_detection::PressureMinimum;
+    use crate::core::state_machine::states::PreArmed;
+    use crate::sync::{PRIORITY_RECORD_TO_STORAGE_CHANNEL, set_storage_open};
+    use crate::test_utils::interfaces::{FlakyDeployment, TestArming, TestLed};
+    use crate::test_utils::{ms, stored_fsm_events};
+
+    use super::*;
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(100))]
+    async fn reports_each_deploy_attempt_until_verified() {
+        set_storage_open(true);
+        let fsm = FlightStateMachine::<_, _, _, _, _, PreArmed>::new(
+            TestArming, TestLed, FlakyDeployment { failures: 1 }, TestLed, PressureMinimum::default(),
+        ).await;
+        let mut fsm: FlightStateMachine<_, _, _, _, _, Armed> = fsm.transition(TransitionReason::ArmCommand);
+        PRIORITY_RECORD_TO_STORAGE_CHANNEL.clear();
+
+        fsm.await_deployment_system().await;
 
-        self.transition()
+        assert_eq!(stored_fsm_events(), [
+            FsmEvent::DeployAttempt { attempt: 1, outcome: DeployOutcome::Started },
+            FsmEvent::DeployAttempt { attempt: 1, outcome: DeployOutcome::DeployFailed },
+            FsmEvent::DeployAttempt { attempt: 2, outcome: DeployOutcome::Started },
+            FsmEvent::DeployAttempt { attempt: 2, outcome: DeployOutcome::Verified },
+        ]);
     }
 }
@@ -1,26 +1,56 @@
 use core::marker::PhantomData;
 
 use embassy_futures::select::{Either, select};
-use embassy_time::Ticker;
-use proto::uom::si::length::meter;
-use crate::log::{error, info, warn};
+use embassy_time::{Ticker, with_timeout};
+use proto::event::{Event, TransitionReason};
+use proto::flight_state::{FlightCheckpoint, FlightState};
+use proto::preflight::{CheckStatus, PreflightReport};
+use proto::record::tick_hz::Timestamp;
+use proto::sensor_data::{Altitude, PadCalibration};
+use proto::uom::si::{length::meter, pressure::pascal, thermodynamic_temperature::degree_celsius};
+use crate::log::{debug, error, info, warn};
 
-use crate::{config::FiniteStateMachineConfig, core::state_machine::{FlightStateMachine, states::{Armed, PreArmed}}, interfaces::{ArmingSystem, DeploymentSystem, Led}, sync::LATEST_ALTITUDE_SIGNAL};
+use crate::config::{CheckpointConfig, PadCalibrationConfig, PreflightConfig};
+use crate::core::led_pattern::{PatternLed, flight_state_pattern};
+use crate::core::preflight::{PreflightChecks, failing};
+use crate::core::sensors::altimeter::PadCalibrator;
+use crate::sync::{ALTIMETER_DATA_TO_CALIBRATION_SIGNAL, GPS_DATA_TO_PREFLIGHT_SIGNAL, LATEST_ALTITUDE_SIGNAL, SENSOR_HEALTH_WATCH, broadcast_record, finish_imu_calibration, preflight_override, set_pad_calibration, set_preflight_override, storage_writes_ok, tilt_status};
+use crate::{core::state_machine::{FlightStateMachine, ResumedFlightStateMachine, detectors::ApogeeDetection, states::{Armed, PreArmed}}, interfaces::{ArmingSystem, DeploymentSystem, Led}};
 
-impl<A, LedA, D, LedD> FlightStateMachine<A, LedA, D, LedD, PreArmed>
+async fn calibrate_pad() -> Option<PadCalibration> {
+    let mut calibrator = PadCalibrator::default();
+
+    // drop the sample taken before arming
+    ALTIMETER_DATA_TO_CALIBRATION_SIGNAL.reset();
+
+    if with_timeout(PadCalibrationConfig::TIMEOUT, async {
+        while calibrator.samples() < PadCalibrationConfig::SAMPLES {
+            calibrator.add_sample(&ALTIMETER_DATA_TO_CALIBRATION_SIGNAL.wait().await);
+        }
+    }).await.is_err() {
+        warn!("FSM: Pad calibration timed out after {} samples", calibrator.samples());
+    }
+
+    calibrator.finish()
+}
+
+impl<A, LedA, D, LedD, AD> FlightStateMachine<A, LedA, D, LedD, AD, PreArmed>
 where
     A: ArmingSystem,
     LedA: Led,
     D: DeploymentSystem,
     LedD: Led,
+    AD: ApogeeDetection,
 {
     pub async fn new(
         arm_button: A,
-        mut arm_button_led: LedA,
+        arm_button_led: LedA,
         deployment_system: D,
         mut deployment_system_led: LedD,
+        apogee_detection: AD,
     ) -> Self {
-        if arm_button_led.off().await.is_err() { warn!("FSM: Arm Button Led error"); }
+        let mut arm_button_led = PatternLed::new(arm_button_led);
+        arm_button_led.refresh().await;
         if deployment_system_led.off().await.is_err() { warn!("FSM: Deployment System Led error"); }
 
         Self {
@@ -28,38 +58,213 @@ where
             arm_button_led,
             deployment_system,
             deployment_system_led,
+            apogee_detection,
             _state: PhantomData,
 
             launchpad_altitude: None,
+            armed_at: None,
+            recovery_activated_at: None,
+            resumes: 0,
         }
     }
 
-    pub async fn wait_arm(mut self) -> FlightStateMachine<A, LedA, D, LedD, Armed> {
-        let mut waiting_arm_ticker = Ticker::every(FiniteStateMachineConfig::WAITING_ARM_INTERVAL);
+    /// Skips arming when the previous boot was reset mid-flight.
+    ///
+    /// A checkpoint outliving its flight, e.g. power cut while armed on the bench, must not arm
+    /// the rocket: the current altitude is checked against the checkpoint's launchpad first.
+    pub async fn resume(mut self, checkpoint: Option<FlightCheckpoint>) -> ResumedFlightStateMachine<A, LedA, D, LedD, AD> {
+        let Some(checkpoint) = checkpoint.filter(|checkpoint| matches!(checkpoint.state, FlightState::Armed | FlightState::RecoveryActivated)) else {
+            return ResumedFlightStateMachine::PreArmed(self);
+        };
+
+        // altitudes are still on the ISA scale the launchpad altitude was averaged on
+        let altitude = with_timeout(CheckpointConfig::ALTITUDE_TIMEOUT, LATEST_ALTITUDE_SIGNAL.wait()).await.ok();
+        if let Err(reason) = check_resumable(&checkpoint, altitude) {
+            warn!("FSM: Not resuming the {} checkpoint, {}", checkpoint.state, reason);
+            return ResumedFlightStateMachine::PreArmed(self);
+        }
+
+        error!("FSM: Reset mid-flight, resuming in {}", checkpoint.state);
+        broadcast_record(Event::FlightResumed(checkpoint).into());
+
+        if let Some(calibration) = checkpoint.pad_calibration {
+            set_pad_calibration(calibration);
+        }
+        self.launchpad_altitude = checkpoint.launchpad_altitude;
+        self.armed_at = checkpoint.armed_at;
+        self.recovery_activated_at = checkpoint.recovery_activated_at;
+        self.resumes = checkpoint.resumes + 1;
+
+        if checkpoint.state == FlightState::RecoveryActivated {
+            ResumedFlightStateMachine::RecoveryActivated(self.transition(TransitionReason::Resumed))
+        } else {
+            ResumedFlightStateMachine::Armed(self.transition(TransitionReason::Resumed))
+        }
+    }
+
+    /// Runs the pre-flight checks until an arm command arrives while they allow arming.
+    pub async fn wait_arm(mut self) -> FlightStateMachine<A, LedA, D, LedD, AD, Armed> {
+        self.arm_button_led.set(flight_state_pattern(FlightState::PreArmed));
+
+        let mut health_receiver = SENSOR_HEALTH_WATCH.receiver()
+            .expect("FSM: Not enough sensor health consumers");
+        let mut checks = PreflightChecks::default();
+        let mut published = None;
+        let mut ticker = Ticker::every(PreflightConfig::CHECK_INTERVAL);
 
         loop {
-            match select(
-                self.arm_button.wait_arm(),
-                waiting_arm_ticker.next(),
-            ).await {
+            match self.arm_button_led.play_until(select(self.arm_button.wait_arm(), ticker.next())).await {
                 Either::First(Ok(())) => {
-                    info!("Arm button pressed");
-                    self.arm_button_led.on().await.ok();
-                    break;
-                },
-                Either::First(Err(_)) => {
-                    error!("Failed to wait for button press");
+                    // the lockout as of the press, as the ground station would be told
+                    checks.update_tilt(&tilt_status());
+                    let report = checks.report();
+                    if report.arming_allowed {
+                        break;
+                    }
+                    for (check, status) in failing(&report) {
+                        warn!("FSM: Arming refused, {} check is {}", check, status);
+                    }
                 },
+                Either::First(Err(_)) => error!("Failed to wait for button press"),
                 Either::Second(()) => {
-                    self.arm_button_led.toggle().await.ok();
+                    if let Some(health) = health_receiver.try_changed() {
+                        checks.update_health(&health);
+                    }
+                    if let Some(data) = GPS_DATA_TO_PREFLIGHT_SIGNAL.try_take() {
+                        checks.update_gps(&data);
+                    }
+                    checks.update_storage(storage_writes_ok());
+                    checks.update_deployment(self.check_continuity().await);
+                    checks.update_tilt(&tilt_status());
+                    checks.set_override(preflight_override());
+
+                    let report = checks.report();
+                    if published != Some(report) {
+                        log_report(&report);
+                        broadcast_record(report.into());
+                        published = Some(report);
+                    }
                 },
             }
         }
+        info!("Arm button pressed");
+        // an override covers the arming it was sent for, never a later one
+        if preflight_override() {
+            info!("FSM: Armed with the pre-flight override, clearing it");
+            set_preflight_override(false);
+        }
+        // its profile would only be stored by interrupting the flight log
+        finish_imu_calibration();
+        self.arm_button_led.set(flight_state_pattern(FlightState::Armed));
+        self.arm_button_led.refresh().await;
+
+        let launchpad_altitude = if let Some(calibration) = calibrate_pad().await {
+            info!(
+                "Pad calibrated over {} samples: {} Pa, {} C",
+                calibration.samples,
+                calibration.ground_pressure.get::<pascal>(),
+                calibration.ground_temperature.get::<degree_celsius>(),
+            );
+            set_pad_calibration(calibration);
+            broadcast_record(calibration.into());
+            calibration.ground_altitude
+        } else {
+            error!("FSM: Pad calibration failed, falling back to a single altitude sample");
+            LATEST_ALTITUDE_SIGNAL.wait().await
+        };
 
-        let launchpad_altitude = LATEST_ALTITUDE_SIGNAL.wait().await;
         info!("Launchpad Altitude: {} m", launchpad_altitude.get::<meter>());
         self.launchpad_altitude = Some(launchpad_altitude);
+        self.armed_at = Some(Timestamp::now());
+
+        self.transition(TransitionReason::ArmCommand)
+    }
+
+    async fn check_continuity(&mut self) -> CheckStatus {
+        match with_timeout(PreflightConfig::CONTINUITY_TIMEOUT, self.deployment_system.continuity()).await {
+            Ok(Ok(None)) => CheckStatus::Unsupported,
+            Ok(Ok(Some(true))) => CheckStatus::Passed,
+            Ok(Ok(Some(false))) => CheckStatus::Failed,
+            Ok(Err(e)) => { debug!("FSM: Failed to check deployment continuity: {:?}", e); CheckStatus::Failed },
+            Err(_) => { debug!("FSM: Timed out checking deployment continuity"); CheckStatus::Failed },
+        }
+    }
+}
+
+/// Why a checkpoint does not belong to a flight still in progress.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+enum NotResumable {
+    #[error("it has no launchpad altitude")]
+    NoLaunchpad,
+    #[error("no altitude to check it against")]
+    NoAltitude,
+    #[error("the rocket is not above its launchpad")]
+    OnTheGround,
+    #[error("it was already resumed {0} times")]
+    ResetLoop(u8),
+}
+
+fn check_resumable(checkpoint: &FlightCheckpoint, altitude: Option<Altitude>) -> Result<(), NotResumable> {
+    if checkpoint.resumes >= CheckpointConfig::MAX_RESUMES {
+        return Err(NotResumable::ResetLoop(checkpoint.resumes));
+    }
+    let launchpad_altitude = checkpoint.launchpad_altitude.ok_or(NotResumable::NoLaunchpad)?;
+    let altitude = altitude.ok_or(NotResumable::NoAltitude)?;
+
+    if altitude - launchpad_altitude < CheckpointConfig::min_resume_altitude() {
+        return Err(NotResumable::OnTheGround);
+    }
+    Ok(())
+}
+
+fn log_report(report: &PreflightReport) {
+    if report.arming_allowed {
+        info!("FSM: Pre-flight checks allow arming{}", if report.overridden { " (overridden)" } else { "" });
+    } else {
+        for (check, status) in failing(report) {
+            info!("FSM: Pre-flight {} check is {}", check, status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::uom::si::length::meter;
+
+    use super::*;
+
+    fn checkpoint(state: FlightState, resumes: u8) -> FlightCheckpoint {
+        FlightCheckpoint {
+            state,
+            launchpad_altitude: Some(Altitude::new::<meter>(120.0)),
+            pad_calibration: None,
+            armed_at: None,
+            recovery_activated_at: None,
+            resumes,
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(FlightState::Armed, 0, Some(400.0), Ok(()))]
+    #[case(FlightState::RecoveryActivated, 2, Some(300.0), Ok(()))]
+    #[case(FlightState::Armed, 0, Some(125.0), Err(NotResumable::OnTheGround))]
+    #[case(FlightState::Armed, 0, Some(60.0), Err(NotResumable::OnTheGround))]
+    #[case(FlightState::Armed, 0, None, Err(NotResumable::NoAltitude))]
+    #[case(FlightState::Armed, CheckpointConfig::MAX_RESUMES, Some(400.0), Err(NotResumable::ResetLoop(CheckpointConfig::MAX_RESUMES)))]
+    fn resumes_only_flights_in_progress(
+        #[case] state: FlightState,
+        #[case] resumes: u8,
+        #[case] altitude: Option<f32>,
+        #[case] expected: Result<(), NotResumable>,
+    ) {
+        let altitude = altitude.map(Altitude::new::<meter>);
+        assert_eq!(check_resumable(&checkpoint(state, resumes), altitude), expected);
+    }
 
-        self.transition()
+    #[rstest::rstest]
+    fn needs_a_launchpad_altitude() {
+        let checkpoint = FlightCheckpoint { launchpad_altitude: None, ..checkpoint(FlightState::Armed, 0) };
+        assert_eq!(check_resumable(&checkpoint, Some(Altitude::new::<meter>(400.0))), Err(NotResumable::NoLaunchpad));
     }
 }
@@ -1,23 +1,29 @@
+use proto::event::{Detector, Event, FsmEvent, TransitionReason};
 use proto::uom::si::length::meter;
 use crate::log::info;
 
-use crate::{core::state_machine::{FlightStateMachine, detectors::TouchdownDetector, states::{RecoveryActivated, Touchdown}}, interfaces::{ArmingSystem, DeploymentSystem, Led}};
+use crate::{core::state_machine::{FlightStateMachine, detectors::{ApogeeDetection, TouchdownDetector}, states::{RecoveryActivated, Touchdown}}, interfaces::{ArmingSystem, DeploymentSystem, Led}, sync::broadcast_record};
 
-impl<A, LedA, D, LedD> FlightStateMachine<A, LedA, D, LedD, RecoveryActivated>
+impl<A, LedA, D, LedD, AD> FlightStateMachine<A, LedA, D, LedD, AD, RecoveryActivated>
 where
     A: ArmingSystem,
     LedA: Led,
     D: DeploymentSystem,
     LedD: Led,
+    AD: ApogeeDetection,
 {
-    pub async fn wait_touchdown(self) -> FlightStateMachine<A, LedA, D, LedD, Touchdown> {
-        let altitude = TouchdownDetector::new()
-        .await
-        .await_touchdown()
-        .await;
+    pub async fn wait_touchdown(mut self) -> FlightStateMachine<A, LedA, D, LedD, AD, Touchdown> {
+        broadcast_record(Event::Fsm(FsmEvent::DetectorArmed(Detector::Touchdown)).into());
+
+        let altitude = self.arm_button_led.play_until(async {
+            TouchdownDetector::new()
+            .await
+            .await_touchdown()
+            .await
+        }).await;
 
         info!("Touchdown of {} m!", altitude.get::<meter>());
 
-        self.transition()
+        self.transition(TransitionReason::TouchdownDetected)
     }
 }
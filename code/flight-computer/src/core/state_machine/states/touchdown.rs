@@ -1,15 +1,17 @@
 use crate::log::info;
 
-use crate::{core::state_machine::{FlightStateMachine, states::Touchdown}, interfaces::{ArmingSystem, DeploymentSystem, Led}};
+use crate::{core::state_machine::{FlightStateMachine, detectors::ApogeeDetection, states::Touchdown}, interfaces::{ArmingSystem, DeploymentSystem, Led}};
 
-impl<A, LedA, D, LedD> FlightStateMachine<A, LedA, D, LedD, Touchdown>
+impl<A, LedA, D, LedD, AD> FlightStateMachine<A, LedA, D, LedD, AD, Touchdown>
 where
     A: ArmingSystem,
     LedA: Led,
     D: DeploymentSystem,
     LedD: Led,
+    AD: ApogeeDetection,
 {
-    pub async fn shutdown(self) {
+    pub async fn shutdown(mut self) {
+        self.arm_button_led.refresh().await;
         info!("Shutting down flight computer.");
     }
 }
@@ -1,71 +1,326 @@
 #[allow(unused_imports)]
-use core::fmt::Write as _;
-#[allow(unused_imports)]
 use embedded_io::Write as _;
+use proto::Serialize;
 use proto::wire::Record;
 use proto::error::FileSystemError;
+use proto::sensor_data::ImuCalibrationProfile;
+use proto::log_format::{FRAME_DELIMITER, FlightLog, LogHeader, LogIndex, decode_frame, encode_frame, max_frame_len};
+use proto::trace::TraceFunction;
 
 use crate::{config::StorageConfig, interfaces::FileSystem, core::trace::TraceSync};
-use crate::log::error;
+use crate::log::{error, info, warn};
 
 use defmt_or_log::Debug2Format;
-use static_cell::ConstStaticCell;
-type FileUniqueId = u16;
 
+mod index;
+pub mod pre_launch;
+
+/// On-board log encoding, see [`StorageConfig::LOG_FORMAT`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One `serde_json_core` record per CRLF-terminated line.
+    Json,
+    /// [`proto::log_format`] frames: a header, then one CRC-checked record per frame.
+    Binary,
+}
+
+impl LogFormat {
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "JSN",
+            Self::Binary => "BIN",
+        }
+    }
+}
+
+/// Flight logs on a FAT card, tracked by a [`LogIndex`].
+///
+/// Each boot logs a new flight, rotated across segment files of at most
+/// [`StorageConfig::MAX_SEGMENT_SIZE`] (give or take a record). At most one file is open at a
+/// time, as the SD card driver only allows one.
 pub struct Storage<FS, FH>
 where
     FS: FileSystem<File = FH>,
 {
     filesystem: FS,
-    file: FH,
+    /// `None` between closing a segment and opening the next one.
+    file: Option<FH>,
     write_buffer: &'static mut [u8],
+    index: LogIndex,
+    flight: u16,
+    segment: u8,
+    segment_bytes: u32,
 }
 
 impl<FS, FH> Storage<FS, FH>
 where
     FS: FileSystem<File = FH>,
 {
-    pub async fn new(mut filesystem: FS) -> Result<Self, FileSystemError> {
-        static WRITE_BUFFER: ConstStaticCell<[u8; StorageConfig::WRITE_BUFFER_SIZE]> = ConstStaticCell::new([0_u8; StorageConfig::WRITE_BUFFER_SIZE]);
-        let mut filename: heapless::String<{ StorageConfig::MAX_FILENAME_LENGTH }> = heapless::String::new();
-        let trace = TraceSync::start("Storage::new");
-
-        for uid in 0..=FileUniqueId::MAX {
-            filename.clear();
-            write!(&mut filename, "{uid}").map_err(|_| FileSystemError::FilenameTooLong)?;
-            match filesystem.exist_file(&filename).await {
+    pub async fn new(filesystem: FS, write_buffer: &'static mut [u8; StorageConfig::WRITE_BUFFER_SIZE]) -> Result<Self, FileSystemError> {
+        let trace = TraceSync::start(TraceFunction::StorageNew);
+
+        let mut storage = Self {
+            filesystem,
+            file: None,
+            write_buffer,
+            index: LogIndex::default(),
+            flight: 0,
+            segment: 0,
+            segment_bytes: 0,
+        };
+
+        storage.index = storage.load_index().await.unwrap_or_else(|| {
+            warn!("No valid log index, starting a new one");
+            LogIndex::default()
+        });
+        storage.skip_existing_flights().await?;
+        storage.measure_flights().await;
+
+        // nothing of the new flight can be pruned yet
+        storage.flight = storage.index.next_flight;
+        storage.prune().await;
+
+        let (flight, evicted) = index::begin_flight(&mut storage.index);
+        storage.flight = flight;
+        if let Some(log) = evicted {
+            warn!("Log index full, deleting flight {} ({} bytes)", log.flight, log.bytes);
+            storage.delete_flight(&log).await;
+        }
+        storage.store_index().await?;
+        storage.open_segment().await?;
+
+        drop(trace);
+        Ok(storage)
+    }
+
+    #[must_use]
+    pub const fn flight(&self) -> u16 {
+        self.flight
+    }
+
+    /* -------------------------------- Index ------------------------------- */
+
+    /// The newest valid index copy.
+    async fn load_index(&mut self) -> Option<LogIndex> {
+        let mut newest: Option<LogIndex> = None;
+        for filename in StorageConfig::INDEX_FILENAMES {
+            if let Some(index) = self.read_index(filename).await
+                && newest.is_none_or(|newest| index.sequence > newest.sequence)
+            {
+                newest = Some(index);
+            }
+        }
+        newest
+    }
+
+    async fn read_index(&mut self, filename: &str) -> Option<LogIndex> {
+        match self.filesystem.exist_file(filename).await {
+            Ok(true) => (),
+            Ok(false) => return None,
+            Err(e) => {
+                warn!("Failed to check existence of file {}: {:?}", filename, Debug2Format(&e));
+                return None;
+            },
+        }
+
+        let len = match self.filesystem.read_file(filename, self.write_buffer).await {
+            Ok(len) => len,
+            Err(e) => {
+                warn!("Failed to read file {}: {:?}", filename, Debug2Format(&e));
+                return None;
+            },
+        };
+
+        let end = self.write_buffer[..len].iter().position(|&byte| byte == FRAME_DELIMITER).unwrap_or(len);
+        decode_frame(&mut self.write_buffer[..end])
+            .inspect_err(|e| warn!("Discarding corrupted index {}: {:?}", filename, e))
+            .ok()
+    }
+
+    async fn store_index(&mut self) -> Result<(), FileSystemError> {
+        self.index.sequence = self.index.sequence.wrapping_add(1);
+        let index = self.index;
+        let len = self.serialize_frame(&index).map_err(|_| FileSystemError::FailedToWriteIndex)?;
+
+        self.replace_file(index::index_filename(self.index.sequence), len).await
+            .map_err(|()| FileSystemError::FailedToWriteIndex)
+    }
+
+    /// Writes the start of the write buffer as the whole content of `filename`.
+    /// No other file may be open.
+    async fn replace_file(&mut self, filename: &str, len: usize) -> Result<(), ()> {
+        let result = async {
+            if self.filesystem.exist_file(filename).await? {
+                self.filesystem.delete_file(filename).await?;
+            }
+            let mut file = self.filesystem.create_file(filename).await?;
+            let written = self.filesystem.write_file(&mut file, &self.write_buffer[..len]).await;
+            let flushed = self.filesystem.flush_file(&mut file).await;
+            let closed = self.filesystem.close_file(file).await;
+            written.and(flushed).and(closed)
+        }.await;
+
+        result.map_err(|e| error!("Failed to write file {}: {:?}", filename, Debug2Format(&e)))
+    }
+
+    /// Moves past flight numbers that already have files, e.g. left over from a lost index.
+    async fn skip_existing_flights(&mut self) -> Result<(), FileSystemError> {
+        for _ in 0..StorageConfig::MAX_FLIGHT_PROBES {
+            let filename = index::segment_filename(self.index.next_flight, 0, StorageConfig::LOG_FORMAT);
+            match self.filesystem.exist_file(&filename).await {
                 Err(e) => {
                     error!("Failed to check existence of file {}: {:?}", filename, Debug2Format(&e));
                     return Err(FileSystemError::GetUniqueIdFailed);
                 },
-                Ok(true) if uid == FileUniqueId::MAX => {
-                    error!("No unique ID available");
-                    return Err(FileSystemError::UniqueIdUnavailable);
+                Ok(true) => self.index.next_flight = self.index.next_flight.wrapping_add(1),
+                Ok(false) => return Ok(()),
+            }
+        }
+
+        error!("No unique flight number available");
+        Err(FileSystemError::UniqueIdUnavailable)
+    }
+
+    /// Sums the segments of every indexed flight as found on the card, since a boot that lost
+    /// power never accounted for the segment it had open.
+    async fn measure_flights(&mut self) {
+        for slot in 0..self.index.flights.len() {
+            let Some(log) = self.index.flights[slot] else { continue };
+
+            let mut bytes = 0_u32;
+            for segment in 0..log.segments {
+                let filename = index::segment_filename(log.flight, segment, StorageConfig::LOG_FORMAT);
+                match self.filesystem.file_size(&filename).await {
+                    Ok(size) => bytes = bytes.saturating_add(size),
+                    Err(e) => warn!("Failed to measure file {}: {:?}", filename, Debug2Format(&e)),
+                }
+            }
+
+            if let Some(log) = self.index.flights[slot].as_mut() {
+                log.bytes = bytes;
+            }
+        }
+    }
+
+    /// Deletes the oldest flights while less than [`StorageConfig::MIN_FREE_SPACE`] is left on the card.
+    async fn prune(&mut self) {
+        loop {
+            let free = match self.filesystem.free_space().await {
+                Ok(free) => free,
+                Err(e) => {
+                    warn!("Failed to measure free space: {:?}", Debug2Format(&e));
+                    return;
                 },
-                Ok(true) => (),
-                Ok(false) => break,
+            };
+            if free >= StorageConfig::MIN_FREE_SPACE {
+                return;
             }
+
+            let Some(log) = index::pop_prunable(&mut self.index, self.flight) else {
+                warn!("Only {} bytes left on the card, with no flight left to prune", free);
+                return;
+            };
+            info!("Pruning flight {} ({} bytes), {} bytes left on the card", log.flight, log.bytes, free);
+            self.delete_flight(&log).await;
         }
+    }
+
+    /// Best effort: files that cannot be deleted are left behind, untracked.
+    async fn delete_flight(&mut self, log: &FlightLog) {
+        for segment in 0..log.segments {
+            let filename = index::segment_filename(log.flight, segment, StorageConfig::LOG_FORMAT);
+            if let Err(e) = self.filesystem.delete_file(&filename).await {
+                warn!("Failed to delete file {}: {:?}", filename, Debug2Format(&e));
+            }
+        }
+    }
+
+    /* --------------------------- IMU Calibration -------------------------- */
+
+    /// Persists the IMU calibration for [`load_imu_calibration`], pausing the current segment meanwhile.
+    pub async fn store_imu_calibration(&mut self, profile: &ImuCalibrationProfile) -> Result<(), FileSystemError> {
+        let len = self.serialize_frame(profile)?;
+
+        if let Some(mut file) = self.file.take() {
+            let flushed = self.filesystem.flush_file(&mut file).await;
+            flushed.and(self.filesystem.close_file(file).await).map_err(|e| {
+                error!("Failed to close file: {:?}", Debug2Format(&e));
+                FileSystemError::FailedToCloseFile
+            })?;
+        }
+
+        let stored = self.replace_file(StorageConfig::IMU_CALIBRATION_FILENAME, len).await
+            .map_err(|()| FileSystemError::FailedToWriteRecord);
+
+        let filename = index::segment_filename(self.flight, self.segment, StorageConfig::LOG_FORMAT);
+        match self.filesystem.open_file_append(&filename).await {
+            Ok(file) => self.file = Some(file),
+            // the next record rotates to a new segment
+            Err(e) => warn!("Failed to reopen file {}: {:?}", filename, Debug2Format(&e)),
+        }
+
+        stored
+    }
+
+    /* ------------------------------- Segments ----------------------------- */
 
-        let file = match filesystem.create_file(&filename).await {
-            Ok(file) => file,
+    async fn open_segment(&mut self) -> Result<(), FileSystemError> {
+        let filename = index::segment_filename(self.flight, self.segment, StorageConfig::LOG_FORMAT);
+        match self.filesystem.create_file(&filename).await {
+            Ok(file) => self.file = Some(file),
             Err(e) => {
                 error!("Failed to create file {}: {:?}", filename, Debug2Format(&e));
                 return Err(FileSystemError::FileCreationFailed);
             },
-        };
+        }
+        self.segment_bytes = 0;
 
-        drop(trace);
-        Ok(Self {
-            filesystem,
-            file,
-            write_buffer: WRITE_BUFFER.take(),
-        })
+        if StorageConfig::LOG_FORMAT == LogFormat::Binary {
+            // every segment can be decoded on its own
+            let header = LogHeader::new(u32::from(self.flight), embassy_time::TICK_HZ);
+            let len = self.serialize_frame(&header).map_err(|_| FileSystemError::FailedToWriteHeader)?;
+            self.write(len).await.map_err(|_| FileSystemError::FailedToWriteHeader)?;
+        }
+
+        Ok(())
     }
 
-    pub async fn append_record(&mut self, data: &Record) -> Result<(), FileSystemError> {
-        let trace = TraceSync::start("Storage::append_record");
+    async fn rotate(&mut self) -> Result<(), FileSystemError> {
+        if let Some(mut file) = self.file.take() {
+            let flushed = self.filesystem.flush_file(&mut file).await;
+            flushed.and(self.filesystem.close_file(file).await).map_err(|e| {
+                error!("Failed to close file: {:?}", Debug2Format(&e));
+                FileSystemError::FailedToCloseFile
+            })?;
+        }
+
+        self.segment = index::close_segment(&mut self.index, self.flight, self.segment_bytes).ok_or_else(|| {
+            error!("Flight {} reached the segment limit", self.flight);
+            FileSystemError::SegmentLimitReached
+        })?;
+        self.segment_bytes = 0;
+        info!("Rotating to segment {} of flight {}", self.segment, self.flight);
+
+        self.prune().await;
+        self.store_index().await?;
+        self.open_segment().await
+    }
+
+    /* -------------------------------- Records ----------------------------- */
+
+    /// Encodes a frame at the start of the write buffer, returning its length.
+    fn serialize_frame<T: Serialize>(&mut self, value: &T) -> Result<usize, FileSystemError> {
+        let (frame, scratch) = self.write_buffer.split_at_mut(StorageConfig::WRITE_BUFFER_SIZE - StorageConfig::RECORD_SCRATCH_SIZE);
 
+        encode_frame(value, scratch, frame).map_err(|e| {
+            error!("Failed to encode frame: {:?}", e);
+            FileSystemError::FailedToSerializeRecord
+        })
+    }
+
+    /// Serializes a CRLF-terminated line at the start of the write buffer, returning its length.
+    fn serialize_json_line(&mut self, data: &Record) -> Result<usize, FileSystemError> {
         let len = serde_json_core::to_slice(&data, self.write_buffer).map_err(|_| {
             error!("Failed to serialize record");
             FileSystemError::FailedToSerializeRecord
@@ -78,12 +333,34 @@ where
         }
         self.write_buffer[len] = b'\r';
         self.write_buffer[len + 1] = b'\n';
-        let len = len + 2;
+        Ok(len + 2)
+    }
 
-        self.filesystem.write_file(&mut self.file, &self.write_buffer[..len]).await.map_err(|e| {
+    /// Appends the start of the write buffer to the current segment.
+    async fn write(&mut self, len: usize) -> Result<(), FileSystemError> {
+        let file = self.file.as_mut().ok_or(FileSystemError::FailedToWriteRecord)?;
+
+        self.filesystem.write_file(file, &self.write_buffer[..len]).await.map_err(|e| {
             error!("Failed to append data: {:?}", Debug2Format(&e));
             FileSystemError::FailedToWriteRecord
         })?;
+        self.segment_bytes = self.segment_bytes.saturating_add(u32::try_from(len).unwrap_or(u32::MAX));
+
+        Ok(())
+    }
+
+    pub async fn append_record(&mut self, data: &Record) -> Result<(), FileSystemError> {
+        let trace = TraceSync::start(TraceFunction::StorageAppendRecord);
+
+        if self.file.is_none() || self.segment_bytes >= StorageConfig::MAX_SEGMENT_SIZE {
+            self.rotate().await?;
+        }
+
+        let len = match StorageConfig::LOG_FORMAT {
+            LogFormat::Json => self.serialize_json_line(data)?,
+            LogFormat::Binary => self.serialize_frame(data)?,
+        };
+        self.write(len).await?;
 
         drop(trace);
         Ok(())
@@ -91,14 +368,127 @@ where
 
     /// Flush all open files.
     pub async fn flush(&mut self) -> Result<(), FileSystemError> {
-        let trace = TraceSync::start("Storage::flush");
+        let trace = TraceSync::start(TraceFunction::StorageFlush);
 
-        self.filesystem.flush_file(&mut self.file).await.map_err(|e| {
-            error!("Failed to flush file:{:?}", Debug2Format(&e));
-            FileSystemError::FailedToFlushFile
-        })?;
+        if let Some(file) = self.file.as_mut() {
+            self.filesystem.flush_file(file).await.map_err(|e| {
+                error!("Failed to flush file:{:?}", Debug2Format(&e));
+                FileSystemError::FailedToFlushFile
+            })?;
+        }
 
         drop(trace);
         Ok(())
     }
 }
+
+/// Reads the IMU calibration persisted by [`Storage::store_imu_calibration`], before the
+/// filesystem is handed over to the storage task.
+pub async fn load_imu_calibration<FS: FileSystem>(filesystem: &mut FS) -> Option<ImuCalibrationProfile> {
+    let mut buffer = [0_u8; max_frame_len(size_of::<ImuCalibrationProfile>())];
+    let filename = StorageConfig::IMU_CALIBRATION_FILENAME;
+
+    match filesystem.exist_file(filename).await {
+        Ok(true) => (),
+        Ok(false) => return None,
+        Err(e) => {
+            warn!("Failed to check existence of file {}: {:?}", filename, Debug2Format(&e));
+            return None;
+        },
+    }
+
+    let len = filesystem.read_file(filename, &mut buffer).await
+        .inspect_err(|e| warn!("Failed to read file {}: {:?}", filename, Debug2Format(e)))
+        .ok()?;
+
+    let end = buffer[..len].iter().position(|&byte| byte == FRAME_DELIMITER).unwrap_or(len);
+    decode_frame(&mut buffer[..end])
+        .inspect_err(|e| warn!("Discarding corrupted IMU calibration: {:?}", e))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::flight_state::FlightState;
+    use proto::log_format::INDEX_MAX_FLIGHTS;
+    use proto::sensor_data::IMU_CALIBRATION_PROFILE_SIZE;
+
+    use crate::test_utils::filesystem::RamFileSystem;
+
+    use super::*;
+
+    fn profile() -> ImuCalibrationProfile {
+        let mut offsets = [0_u8; IMU_CALIBRATION_PROFILE_SIZE];
+        for (offset, value) in offsets.iter_mut().zip(1..) {
+            *offset = value;
+        }
+        ImuCalibrationProfile { offsets }
+    }
+
+    fn write_buffer() -> &'static mut [u8; StorageConfig::WRITE_BUFFER_SIZE] {
+        Box::leak(Box::new([0; StorageConfig::WRITE_BUFFER_SIZE]))
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    async fn imu_calibration_is_loaded_at_next_boot() {
+        let mut storage = Storage::new(RamFileSystem::default(), write_buffer()).await.expect("RAM never fails");
+        let record = Record::from(FlightState::PreArmed);
+
+        storage.append_record(&record).await.expect("RAM never fails");
+        storage.store_imu_calibration(&profile()).await.expect("RAM never fails");
+        storage.append_record(&record).await.expect("RAM never fails");
+
+        let flight = storage.flight();
+        let mut filesystem = storage.filesystem;
+        assert_eq!(load_imu_calibration(&mut filesystem).await, Some(profile()));
+
+        // the log carried on in the segment it paused
+        let segments = (0..2).map(|segment| index::segment_filename(flight, segment, StorageConfig::LOG_FORMAT));
+        let sizes: Vec<_> = segments.map(|filename| filesystem.files.get(filename.as_str()).map(Vec::len)).collect();
+        assert!(sizes[0].is_some_and(|size| size > 0));
+        assert_eq!(sizes[1], None);
+    }
+
+    /// Boots on `filesystem` and logs a record, as every flight does.
+    async fn fly(filesystem: RamFileSystem) -> RamFileSystem {
+        let mut storage = Storage::new(filesystem, write_buffer()).await.expect("RAM never fails");
+        storage.append_record(&Record::from(FlightState::PreArmed)).await.expect("RAM never fails");
+        storage.filesystem
+    }
+
+    fn logged_flights(filesystem: &RamFileSystem) -> Vec<u16> {
+        filesystem.files.keys().filter_map(|filename| filename.split_once('_')?.0.parse().ok()).collect()
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    async fn deletes_flights_evicted_from_the_index() {
+        let mut filesystem = RamFileSystem::default();
+        for _ in 0..INDEX_MAX_FLIGHTS + 4 {
+            filesystem = fly(filesystem).await;
+        }
+
+        assert_eq!(logged_flights(&filesystem), (4..20).collect::<Vec<_>>());
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    async fn prunes_oldest_flights_while_the_card_is_short_of_space() {
+        let mut filesystem = RamFileSystem::default();
+        for _ in 0..3 {
+            filesystem = fly(filesystem).await;
+        }
+        filesystem.capacity = filesystem.used() + StorageConfig::MIN_FREE_SPACE - 1;
+
+        let filesystem = fly(filesystem).await;
+
+        assert_eq!(logged_flights(&filesystem), [1, 2, 3]);
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    async fn no_imu_calibration_on_a_blank_card() {
+        assert_eq!(load_imu_calibration(&mut RamFileSystem::default()).await, None);
+    }
+}
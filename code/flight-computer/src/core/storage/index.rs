@@ -0,0 +1,170 @@
+use core::fmt::Write as _;
+
+use proto::log_format::{FlightLog, INDEX_MAX_FLIGHTS, LogIndex};
+
+use crate::config::StorageConfig;
+use crate::core::storage::LogFormat;
+
+/// Segment numbers are two decimal digits.
+pub const MAX_SEGMENTS: u8 = 100;
+
+pub type Filename = heapless::String<{ StorageConfig::MAX_FILENAME_LENGTH }>;
+
+/// 8.3 name of one segment of a flight's log, e.g. `00042_03.BIN`.
+#[must_use]
+pub fn segment_filename(flight: u16, segment: u8, format: LogFormat) -> Filename {
+    let mut filename = Filename::new();
+    // at most 5 + 1 + 2 + 1 + 3 characters
+    let _ = write!(filename, "{flight:05}_{segment:02}.{}", format.extension());
+    filename
+}
+
+/// Index copy the write of `sequence` goes to, leaving the other one intact.
+#[must_use]
+pub const fn index_filename(sequence: u32) -> &'static str {
+    StorageConfig::INDEX_FILENAMES[if sequence % 2 == 0 { 0 } else { 1 }]
+}
+
+/// Registers the next flight, returning its number and the oldest flight if a full index had to
+/// evict it. Nothing accounts for the evicted flight's files anymore, the caller deletes them.
+pub fn begin_flight(index: &mut LogIndex) -> (u16, Option<FlightLog>) {
+    let evicted = if index.flights().count() == INDEX_MAX_FLIGHTS { remove_oldest(index) } else { None };
+
+    let flight = index.next_flight;
+    index.next_flight = flight.wrapping_add(1);
+    if let Some(slot) = index.flights.iter_mut().find(|slot| slot.is_none()) {
+        *slot = Some(FlightLog { flight, segments: 1, bytes: 0 });
+    }
+
+    (flight, evicted)
+}
+
+/// Accounts for a closed segment of `flight` and returns the number of the next one,
+/// or `None` once the flight has [`MAX_SEGMENTS`].
+pub fn close_segment(index: &mut LogIndex, flight: u16, bytes: u32) -> Option<u8> {
+    let log = index.flights.iter_mut().flatten().find(|log| log.flight == flight)?;
+    log.bytes = log.bytes.saturating_add(bytes);
+
+    if log.segments >= MAX_SEGMENTS {
+        return None;
+    }
+    log.segments += 1;
+    Some(log.segments - 1)
+}
+
+/// Removes the oldest flight, never the `current` one.
+pub fn pop_prunable(index: &mut LogIndex, current: u16) -> Option<FlightLog> {
+    if index.flights[0]?.flight == current {
+        return None;
+    }
+    remove_oldest(index)
+}
+
+fn remove_oldest(index: &mut LogIndex) -> Option<FlightLog> {
+    let oldest = index.flights[0].take();
+    index.flights.rotate_left(1);
+    oldest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_with(flights: &[(u16, u32)]) -> LogIndex {
+        let mut index = LogIndex::default();
+        for &(flight, bytes) in flights {
+            index.next_flight = flight;
+            begin_flight(&mut index);
+            close_segment(&mut index, flight, bytes);
+        }
+        index
+    }
+
+    fn flight_numbers(index: &LogIndex) -> Vec<u16> {
+        index.flights().map(|log| log.flight).collect()
+    }
+
+    #[rstest::rstest]
+    #[case(0, 0, LogFormat::Json, "00000_00.JSN")]
+    #[case(42, 3, LogFormat::Binary, "00042_03.BIN")]
+    #[case(u16::MAX, MAX_SEGMENTS - 1, LogFormat::Binary, "65535_99.BIN")]
+    fn segment_filenames_are_8_3(
+        #[case] flight: u16,
+        #[case] segment: u8,
+        #[case] format: LogFormat,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(segment_filename(flight, segment, format), expected);
+    }
+
+    #[rstest::rstest]
+    fn index_copies_alternate() {
+        assert_ne!(index_filename(1), index_filename(2));
+        assert_eq!(index_filename(1), index_filename(3));
+    }
+
+    #[rstest::rstest]
+    fn begin_flight_allocates_numbers_in_order() {
+        let mut index = LogIndex::default();
+
+        assert_eq!(begin_flight(&mut index), (0, None));
+        assert_eq!(begin_flight(&mut index), (1, None));
+        assert_eq!(index.next_flight, 2);
+        assert_eq!(flight_numbers(&index), [0, 1]);
+    }
+
+    #[rstest::rstest]
+    fn begin_flight_evicts_oldest_when_full() {
+        let mut index = LogIndex::default();
+        for _ in 0..INDEX_MAX_FLIGHTS {
+            begin_flight(&mut index);
+        }
+
+        let (flight, evicted) = begin_flight(&mut index);
+
+        assert_eq!(flight, 16);
+        assert_eq!(evicted.map(|log| log.flight), Some(0));
+        assert_eq!(flight_numbers(&index), (1..=16).collect::<Vec<_>>());
+    }
+
+    #[rstest::rstest]
+    fn close_segment_accounts_bytes() {
+        let mut index = LogIndex::default();
+        begin_flight(&mut index);
+
+        assert_eq!(close_segment(&mut index, 0, 100), Some(1));
+        assert_eq!(close_segment(&mut index, 0, 50), Some(2));
+        assert_eq!(index.flights[0], Some(FlightLog { flight: 0, segments: 3, bytes: 150 }));
+        assert_eq!(close_segment(&mut index, 7, 50), None);
+    }
+
+    #[rstest::rstest]
+    fn close_segment_stops_at_limit() {
+        let mut index = LogIndex::default();
+        begin_flight(&mut index);
+        for segment in 1..MAX_SEGMENTS {
+            assert_eq!(close_segment(&mut index, 0, 1), Some(segment));
+        }
+
+        assert_eq!(close_segment(&mut index, 0, 1), None);
+        assert_eq!(index.total_bytes(), u64::from(MAX_SEGMENTS));
+    }
+
+    #[rstest::rstest]
+    fn prunes_oldest_flights_first() {
+        let mut index = index_with(&[(3, 400), (4, 300), (5, 200)]);
+
+        assert_eq!(pop_prunable(&mut index, 5).map(|log| log.flight), Some(3));
+        assert_eq!(pop_prunable(&mut index, 5).map(|log| log.flight), Some(4));
+        assert_eq!(pop_prunable(&mut index, 5), None);
+        assert_eq!(flight_numbers(&index), [5]);
+    }
+
+    #[rstest::rstest]
+    fn never_prunes_current_flight() {
+        let mut index = index_with(&[(9, 1000)]);
+
+        assert_eq!(pop_prunable(&mut index, 9), None);
+        assert_eq!(pop_prunable(&mut LogIndex::default(), 0), None);
+    }
+}
@@ -0,0 +1,211 @@
+use heapless::Deque;
+use proto::flight_state::FlightState;
+use proto::sensor_data::Altitude;
+use proto::uom::si::length::meter;
+use proto::wire::{Record, RecordData};
+
+use crate::config::StorageConfig;
+use crate::sync::is_priority;
+
+/// Holds the latest full-rate records in RAM until launch.
+///
+/// On the pad, records only leave the buffer once it is full, and sensor samples are then
+/// decimated by [`StorageConfig::PRE_LAUNCH_DECIMATION`]. From launch on every record leaves,
+/// so the seconds before liftoff reach the disk at full rate, still in order.
+pub struct PreLaunchBuffer<const N: usize> {
+    records: Deque<Record, N>,
+    /// Samples seen per sensor: altimeter, GPS, IMU, the attitude derived from the IMU, and the high-g accelerometer.
+    samples: [u16; 5],
+    launched: bool,
+}
+
+impl<const N: usize> PreLaunchBuffer<N> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            records: Deque::new(),
+            samples: [0; 5],
+            launched: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_launched(&self) -> bool {
+        self.launched
+    }
+
+    /// Ends the pad phase, e.g. before the final flush.
+    pub const fn launch(&mut self) {
+        self.launched = true;
+    }
+
+    /// Buffers a record, returning whether it is the one launch was detected on.
+    ///
+    /// Launch is a flight state past apogee, or an altitude [`StorageConfig::LAUNCH_ALTITUDE_THRESHOLD`]
+    /// above `ground_altitude`. Drain [`Self::pop_ready`] before pushing the next record.
+    pub fn push(&mut self, record: Record, ground_altitude: Option<Altitude>) -> bool {
+        let launch = !self.launched && is_launch(&record, ground_altitude);
+        self.launched |= launch;
+
+        if let Err(record) = self.records.push_back(record) {
+            // not drained, the newest records matter most
+            self.records.pop_front();
+            let _ = self.records.push_back(record);
+        }
+
+        launch
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Next record to write to disk, if any.
+    pub fn pop_ready(&mut self) -> Option<Record> {
+        while self.launched || self.records.is_full() {
+            let record = self.records.pop_front()?;
+            if self.launched || self.keep_on_pad(&record) {
+                return Some(record);
+            }
+        }
+        None
+    }
+
+    fn keep_on_pad(&mut self, record: &Record) -> bool {
+        if is_priority(record.payload()) {
+            return true;
+        }
+
+        let sensor = match record.payload() {
+            RecordData::Altimeter(_) => 0,
+            RecordData::Gps(_) => 1,
+            RecordData::Imu(_) => 2,
+            RecordData::Attitude(_) => 3,
+            RecordData::HighGAccelerometer(_) => 4,
+            _ => return true,
+        };
+
+        let sample = self.samples[sensor];
+        self.samples[sensor] = sample.wrapping_add(1);
+        sample % StorageConfig::PRE_LAUNCH_DECIMATION == 0
+    }
+}
+
+impl<const N: usize> Default for PreLaunchBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_launch(record: &Record, ground_altitude: Option<Altitude>) -> bool {
+    match record.payload() {
+        RecordData::FlightState(state) => matches!(state, FlightState::RecoveryActivated | FlightState::Touchdown),
+        RecordData::Altimeter(data) => ground_altitude
+            .is_some_and(|ground| (data.altitude - ground).get::<meter>() > StorageConfig::LAUNCH_ALTITUDE_THRESHOLD),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::sensor_data::AltimeterData;
+
+    use crate::test_utils::sensor_data::{random_altimeter_data, random_imu_data};
+
+    use super::*;
+
+    const SIZE: usize = 4;
+
+    fn altimeter_record(altitude: f32) -> Record {
+        Record::from(AltimeterData {
+            altitude: Altitude::new::<meter>(altitude),
+            ..random_altimeter_data()
+        })
+    }
+
+    fn drain(buffer: &mut PreLaunchBuffer<SIZE>) -> Vec<Record> {
+        core::iter::from_fn(|| buffer.pop_ready()).collect()
+    }
+
+    #[rstest::rstest]
+    fn keeps_records_until_full() {
+        let mut buffer = PreLaunchBuffer::<SIZE>::new();
+        let records: Vec<Record> = (0..SIZE).map(|_| random_imu_data().into()).collect();
+
+        for record in &records[..SIZE - 1] {
+            buffer.push(record.clone(), None);
+            assert_eq!(buffer.pop_ready(), None);
+        }
+        buffer.push(records[SIZE - 1].clone(), None);
+
+        assert_eq!(buffer.pop_ready().as_ref(), Some(&records[0]));
+        assert_eq!(buffer.pop_ready(), None);
+    }
+
+    #[rstest::rstest]
+    fn decimates_sensor_samples_on_pad() {
+        let mut buffer = PreLaunchBuffer::<SIZE>::new();
+        let decimation = usize::from(StorageConfig::PRE_LAUNCH_DECIMATION);
+        let records: Vec<Record> = (0..SIZE + 2 * decimation).map(|_| random_imu_data().into()).collect();
+
+        let mut written = Vec::new();
+        for record in &records {
+            buffer.push(record.clone(), None);
+            written.extend(drain(&mut buffer));
+        }
+
+        assert_eq!(written, [records[0].clone(), records[decimation].clone()]);
+    }
+
+    #[rstest::rstest]
+    fn keeps_every_non_sensor_record_on_pad() {
+        let mut buffer = PreLaunchBuffer::<SIZE>::new();
+        let records: Vec<Record> = (0..2 * SIZE).map(|_| FlightState::Armed.into()).collect();
+
+        let mut written = Vec::new();
+        for record in &records {
+            buffer.push(record.clone(), None);
+            written.extend(drain(&mut buffer));
+        }
+
+        assert_eq!(written, records[..SIZE]);
+    }
+
+    #[rstest::rstest]
+    fn flushes_full_rate_records_on_launch() {
+        let mut buffer = PreLaunchBuffer::<SIZE>::new();
+        let ground = Some(Altitude::new::<meter>(100.0));
+        let records = [altimeter_record(100.0), altimeter_record(101.0), altimeter_record(100.0 + StorageConfig::LAUNCH_ALTITUDE_THRESHOLD + 1.0)];
+
+        assert!(!buffer.push(records[0].clone(), ground));
+        assert!(!buffer.push(records[1].clone(), ground));
+        assert_eq!(buffer.pop_ready(), None);
+        assert!(buffer.push(records[2].clone(), ground));
+
+        assert!(buffer.is_launched());
+        assert_eq!(drain(&mut buffer), records);
+
+        let record = Record::from(random_imu_data());
+        assert!(!buffer.push(record.clone(), ground));
+        assert_eq!(drain(&mut buffer), [record]);
+    }
+
+    #[rstest::rstest]
+    #[case(FlightState::Armed, false)]
+    #[case(FlightState::RecoveryActivated, true)]
+    #[case(FlightState::Touchdown, true)]
+    fn launches_on_flight_state_past_apogee(#[case] state: FlightState, #[case] launch: bool) {
+        let mut buffer = PreLaunchBuffer::<SIZE>::new();
+
+        assert_eq!(buffer.push(state.into(), None), launch);
+        assert_eq!(buffer.is_launched(), launch);
+    }
+
+    #[rstest::rstest]
+    fn ignores_altitude_without_pad_calibration() {
+        let mut buffer = PreLaunchBuffer::<SIZE>::new();
+
+        assert!(!buffer.push(altimeter_record(10_000.0), None));
+    }
+}
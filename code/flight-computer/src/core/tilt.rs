@@ -0,0 +1,251 @@
+use proto::event::TiltEvent;
+use proto::flight_state::FlightState;
+use proto::preflight::CheckStatus;
+use proto::sensor_data::{Angle, ImuData, Vector3};
+use proto::tilt::TiltAction;
+use proto::uom::si::{acceleration::meter_per_second_squared, angle::radian};
+
+use crate::config::TiltConfig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TiltLockout {
+    Clear,
+    /// Standing off vertical, or not measured yet.
+    Pad,
+    /// Boost turned past its limit, held for the rest of the flight.
+    Boost,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TiltStatus {
+    pub tilt: Option<Angle>,
+    pub lockout: TiltLockout,
+}
+
+impl TiltStatus {
+    /// Before the first IMU sample.
+    pub const UNKNOWN: Self = Self { tilt: None, lockout: TiltLockout::Pad };
+
+    /// Whether the lockout holds `action` back, per [`TiltConfig::PAD_INHIBITS`] and [`TiltConfig::BOOST_INHIBITS`].
+    #[must_use]
+    pub fn inhibits(&self, action: TiltAction) -> bool {
+        match self.lockout {
+            TiltLockout::Clear => false,
+            TiltLockout::Pad => TiltConfig::PAD_INHIBITS.contains(&action),
+            TiltLockout::Boost => TiltConfig::BOOST_INHIBITS.contains(&action),
+        }
+    }
+
+    #[must_use]
+    pub fn pad_check(&self) -> CheckStatus {
+        match self.tilt {
+            None => CheckStatus::Pending,
+            Some(tilt) if tilt > TiltConfig::pad_limit() => CheckStatus::Failed,
+            Some(_) => CheckStatus::Passed,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Phase {
+    #[default]
+    Pad,
+    Boost,
+    Coast,
+}
+
+/// Tilt through the flight phases, from the pad to the end of the ascent, read off the attitude
+/// estimated by [`Ahrs`](crate::core::ahrs::Ahrs).
+#[derive(Default)]
+pub struct TiltMonitor {
+    tilt: Option<Angle>,
+    phase: Phase,
+    boost_limit_exceeded: bool,
+}
+
+impl TiltMonitor {
+    /// Takes the direction of `up` in the IMU frame estimated from `data`, `None` until gravity was
+    /// measured. Returns an event the first time boost turns past [`TiltConfig::boost_limit`].
+    pub fn update(&mut self, data: &ImuData, up: Option<Vector3<f32>>, state: FlightState) -> Option<TiltEvent> {
+        let boosting = acceleration(data).norm()
+            > TiltConfig::boost_acceleration_threshold().get::<meter_per_second_squared>();
+
+        match (state, self.phase) {
+            (FlightState::Armed, Phase::Pad) if boosting => self.phase = Phase::Boost,
+            (FlightState::Armed, Phase::Boost) if !boosting => self.phase = Phase::Coast,
+            (FlightState::PreArmed | FlightState::Armed, _) => {},
+            (FlightState::RecoveryActivated | FlightState::Touchdown, _) => return None,
+        }
+
+        let axis = Vector3::from(TiltConfig::BODY_AXIS);
+        self.tilt = up.map(|up| Angle::new::<radian>(up.dot(&axis).clamp(-1.0, 1.0).acos()));
+
+        if self.phase == Phase::Boost
+            && !self.boost_limit_exceeded
+            && let Some(tilt) = self.tilt
+            && tilt > TiltConfig::boost_limit()
+        {
+            self.boost_limit_exceeded = true;
+            return Some(TiltEvent::BoostLimitExceeded { tilt });
+        }
+        None
+    }
+
+    #[must_use]
+    pub fn status(&self) -> TiltStatus {
+        let lockout = if self.boost_limit_exceeded {
+            TiltLockout::Boost
+        } else if self.phase == Phase::Pad && self.tilt.is_none_or(|tilt| tilt > TiltConfig::pad_limit()) {
+            TiltLockout::Pad
+        } else {
+            TiltLockout::Clear
+        };
+        TiltStatus { tilt: self.tilt, lockout }
+    }
+}
+
+fn acceleration(data: &ImuData) -> Vector3<f32> {
+    data.acceleration.map(|axis| axis.get::<meter_per_second_squared>())
+}
+
+#[cfg(test)]
+mod tests {
+    use embassy_time::Instant;
+    use proto::uom::si::angle::degree;
+
+    use crate::core::ahrs::Ahrs;
+    use crate::test_utils::at;
+    use crate::test_utils::flight_profiles::simulated_gravity_turn;
+    use crate::test_utils::sensor_data::imu;
+
+    use super::*;
+
+    /// Gravity as measured standing `degrees` off vertical.
+    fn standing(degrees: f32) -> ImuData {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        imu([0.0, 9.81 * sin, 9.81 * cos], [0.0; 3])
+    }
+
+    fn degrees(status: TiltStatus) -> f32 {
+        status.tilt.expect("Tilt should be measured").get::<degree>()
+    }
+
+    /// The attitude task's estimate feeding the monitor, as the tasks chain them.
+    #[derive(Default)]
+    struct Chain {
+        ahrs: Ahrs,
+        monitor: TiltMonitor,
+    }
+
+    impl Chain {
+        fn update(&mut self, data: &ImuData, state: FlightState, now: Instant) -> Option<TiltEvent> {
+            self.ahrs.update(data, now);
+            self.monitor.update(data, self.ahrs.up(), state)
+        }
+
+        fn status(&self) -> TiltStatus {
+            self.monitor.status()
+        }
+    }
+
+    #[rstest::rstest]
+    fn unknown_tilt_locks_pad_actions() {
+        let status = TiltMonitor::default().status();
+
+        assert_eq!(status, TiltStatus::UNKNOWN);
+        assert_eq!(status.pad_check(), CheckStatus::Pending);
+        assert!(status.inhibits(TiltAction::Arm));
+        assert!(status.inhibits(TiltAction::Launch));
+    }
+
+    #[rstest::rstest]
+    #[case(2.0, TiltLockout::Clear, CheckStatus::Passed)]
+    #[case(20.0, TiltLockout::Pad, CheckStatus::Failed)]
+    fn measures_tilt_on_pad(#[case] tilt: f32, #[case] lockout: TiltLockout, #[case] check: CheckStatus) {
+        let mut chain = Chain::default();
+        chain.update(&standing(tilt), FlightState::PreArmed, at(0));
+
+        let status = chain.status();
+
+        assert!((degrees(status) - tilt).abs() < 0.1, "measured {} deg", degrees(status));
+        assert_eq!(status.lockout, lockout);
+        assert_eq!(status.pad_check(), check);
+        assert_eq!(status.inhibits(TiltAction::Launch), lockout == TiltLockout::Pad);
+        assert!(!status.inhibits(TiltAction::AirStart));
+    }
+
+    #[rstest::rstest]
+    fn smooths_gravity_while_standing() {
+        let mut chain = Chain::default();
+        chain.update(&standing(0.0), FlightState::PreArmed, at(0));
+        chain.update(&standing(45.0), FlightState::PreArmed, at(10));
+
+        let tilt = degrees(chain.status());
+
+        assert!(tilt > 0.0 && tilt < 10.0, "a single bump tilted to {tilt} deg");
+    }
+
+    /// Armed upright, then boosting while pitching over at 1 rad/s.
+    fn pitching_boost(chain: &mut Chain, until_ms: u64) -> Vec<TiltEvent> {
+        chain.update(&standing(0.0), FlightState::Armed, at(0));
+        (10..=until_ms).step_by(10)
+            .filter_map(|ms| chain.update(&imu([0.0, 0.0, 60.0], [1.0, 0.0, 0.0]), FlightState::Armed, at(ms)))
+            .collect()
+    }
+
+    #[rstest::rstest]
+    fn follows_attitude_through_boost() {
+        let mut chain = Chain::default();
+        pitching_boost(&mut chain, 250);
+
+        let tilt = degrees(chain.status());
+
+        assert!((tilt - 0.25_f32.to_degrees()).abs() < 1.0, "measured {tilt} deg");
+        assert_eq!(chain.status().lockout, TiltLockout::Clear);
+    }
+
+    #[rstest::rstest]
+    fn boost_past_limit_raises_event_once_and_latches() {
+        let mut chain = Chain::default();
+        let events = pitching_boost(&mut chain, 1_000);
+
+        let [TiltEvent::BoostLimitExceeded { tilt }] = events.as_slice() else {
+            panic!("Expected a single event, got {events:?}");
+        };
+        assert!(*tilt > TiltConfig::boost_limit());
+
+        chain.update(&standing(0.0), FlightState::Armed, at(1_010));
+        let status = chain.status();
+        assert_eq!(status.lockout, TiltLockout::Boost);
+        assert!(status.inhibits(TiltAction::AirStart));
+        assert!(!status.inhibits(TiltAction::Launch));
+    }
+
+    #[rstest::rstest]
+    fn tilt_after_boost_raises_no_event() {
+        let mut chain = Chain::default();
+        chain.update(&standing(0.0), FlightState::Armed, at(0));
+        chain.update(&imu([0.0, 0.0, 60.0], [0.0; 3]), FlightState::Armed, at(10));
+
+        let events: Vec<_> = (20..=2_000).step_by(10)
+            .filter_map(|ms| chain.update(&imu([0.0, 0.0, -5.0], [1.0, 0.0, 0.0]), FlightState::Armed, at(ms)))
+            .collect();
+
+        assert!(events.is_empty());
+        assert!(degrees(chain.status()) > TiltConfig::boost_limit().get::<degree>());
+    }
+
+    #[rstest::rstest]
+    fn tracks_simulated_gravity_turn() {
+        let mut chain = Chain::default();
+
+        for sample in simulated_gravity_turn() {
+            let event = chain.update(&sample.data, FlightState::Armed, sample.time);
+
+            assert_eq!(event, None);
+            let (measured, simulated) = (degrees(chain.status()), sample.tilt.get::<degree>());
+            assert!((measured - simulated).abs() < 0.5, "measured {measured} deg, simulated {simulated} deg at {:?}", sample.time);
+        }
+        assert!(degrees(chain.status()) > 20.0, "the turn should have been recorded");
+    }
+}
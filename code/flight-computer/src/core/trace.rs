@@ -1,9 +1,33 @@
 #![allow(dead_code)]
 
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{Mutex, raw::CriticalSectionRawMutex};
+use heapless::Deque;
+use proto::trace::{TraceFunction, TraceSpan};
+
+use crate::config::TraceConfig;
+
 type Timestamp = u64;
 
-fn log_record(fn_name: &'static str, stage: Option<u64>, start: Timestamp, end: Timestamp) {
-    crate::log::trace!("trace: {} {:?} {} {}", fn_name, stage, start, end);
+/// Latest spans, the oldest ones are overwritten once full.
+static SPANS: Mutex<CriticalSectionRawMutex, RefCell<Deque<TraceSpan, { TraceConfig::SPAN_BUFFER_SIZE }>>> = Mutex::new(RefCell::new(Deque::new()));
+
+/// Removes the oldest recorded span.
+pub fn pop_span() -> Option<TraceSpan> {
+    SPANS.lock(|spans| spans.borrow_mut().pop_front())
+}
+
+fn log_record(function: TraceFunction, stage: Option<u64>, start: Timestamp, end: Timestamp) {
+    crate::log::trace!("trace: {} {:?} {} {}", function, stage, start, end);
+
+    SPANS.lock(|spans| {
+        let mut spans = spans.borrow_mut();
+        if spans.is_full() {
+            spans.pop_front();
+        }
+        spans.push_back(TraceSpan { function, stage, start_ticks: start, end_ticks: end }).ok();
+    });
 }
 
 fn now() -> Timestamp {
@@ -11,12 +35,12 @@ fn now() -> Timestamp {
 }
 
 pub struct TraceSync {
-    function: &'static str,
+    function: TraceFunction,
     start: Timestamp,
 }
 
 impl TraceSync {
-    pub fn start(function: &'static str) -> Self {
+    pub fn start(function: TraceFunction) -> Self {
         Self {
             function,
             start: now(),
@@ -32,7 +56,7 @@ impl Drop for TraceSync {
 }
 
 pub struct TraceAsync {
-    function: &'static str,
+    function: TraceFunction,
     start: Timestamp,
     // even numbers represent syncronous execution time, odd numbers represent await time
     stage: u64,
@@ -40,7 +64,7 @@ pub struct TraceAsync {
 
 impl TraceAsync {
     /// Starts a new trace
-    pub fn start(function: &'static str) -> Self {
+    pub fn start(function: TraceFunction) -> Self {
         Self {
             function,
             start: now(),
@@ -90,22 +114,22 @@ mod tests {
 
     #[rstest::rstest]
     #[serial_test::serial]
-    #[case("my_fn", Some(3), 10, 20)]
-    #[case("other_fn", None, 0, 42)]
-    #[case("compute", Some(0), 5, 5)]
+    #[case(TraceFunction::StorageAppendRecord, Some(3), 10, 20)]
+    #[case(TraceFunction::StorageFlush, None, 0, 42)]
+    #[case(TraceFunction::StorageNew, Some(0), 5, 5)]
     fn log_record_emits_trace(
-        #[case] name: &'static str,
+        #[case] function: TraceFunction,
         #[case] stage: Option<u64>,
         #[case] start: u64,
         #[case] end: u64,
         mut mock_logger: Logger,
     ) {
-        log_record(name, stage, start, end);
+        log_record(function, stage, start, end);
 
         let record = mock_logger.pop().expect("expected log record");
         let msg = record.args();
         assert_eq!(record.level(), log::Level::Trace);
-        assert!(msg.contains(name));
+        assert!(msg.contains(&function.to_string()));
         assert!(msg.contains(&format!("{stage:?}")));
         assert!(msg.contains(&start.to_string()));
         assert!(msg.contains(&end.to_string()));
@@ -113,21 +137,21 @@ mod tests {
 
     #[rstest::rstest]
     #[serial_test::serial]
-    #[case("compute_heavy")]
-    #[case("fast")]
-    #[case::panic("time_travel")]
+    #[case(TraceFunction::StorageNew)]
+    #[case(TraceFunction::StorageAppendRecord)]
+    #[case(TraceFunction::StorageFlush)]
     fn trace_sync(
-        #[case] fn_name: &'static str,
+        #[case] function: TraceFunction,
         mut mock_logger: Logger,
     ) {
         {
-            let _trace = TraceSync::start(fn_name);
+            let _trace = TraceSync::start(function);
         } // <- drop happens here
 
         let record = mock_logger.pop().expect("expected log record");
         let msg = record.args();
         assert_eq!(record.level(), log::Level::Trace);
-        assert!(msg.contains(fn_name));
+        assert!(msg.contains(&function.to_string()));
         assert!(msg.contains("None"));
         assert!(mock_logger.pop().is_none());
     }
@@ -135,16 +159,16 @@ mod tests {
     #[rstest::rstest]
     #[async_std::test]
     #[serial_test::serial]
-    #[case("three_stages", 3)]
-    #[case("five_stages", 5)]
-    #[case("only_drop", 1)]
+    #[case(TraceFunction::StorageAppendRecord, 3)]
+    #[case(TraceFunction::StorageFlush, 5)]
+    #[case(TraceFunction::StorageNew, 1)]
     async fn trace_async(
-        #[case] fn_name: &'static str,
+        #[case] function: TraceFunction,
         #[case] expected_records: usize,
         mut mock_logger: Logger,
     ) {
         {
-            let mut trace = TraceAsync::start(fn_name);
+            let mut trace = TraceAsync::start(function);
             for i in 0..expected_records.saturating_sub(1) {
                 if i % 2 == 0 {
                     trace.before_await();
@@ -159,7 +183,7 @@ mod tests {
                 .unwrap_or_else(|| panic!("expected log record {idx}"));
             let msg = r.args();
             assert_eq!(r.level(), log::Level::Trace);
-            assert!(msg.contains(fn_name));
+            assert!(msg.contains(&function.to_string()));
             assert!(
                 msg.contains(&format!("Some({idx})")),
                 "stage Some({idx}) not found in '{msg}'"
@@ -170,4 +194,42 @@ mod tests {
             "expected exactly {expected_records} log records"
         );
     }
+
+    #[rstest::rstest]
+    #[async_std::test]
+    #[serial_test::serial]
+    async fn trace_async_records_spans() {
+        while pop_span().is_some() {}
+
+        {
+            let mut trace = TraceAsync::start(TraceFunction::StorageFlush);
+            trace.before_await();
+            trace.after_await();
+        } // <- drop happens here
+
+        for stage in 0..3 {
+            let span = pop_span().unwrap_or_else(|| panic!("expected span {stage}"));
+            assert_eq!(span.function, TraceFunction::StorageFlush);
+            assert_eq!(span.stage, Some(stage));
+            assert!(span.start_ticks <= span.end_ticks);
+            assert_eq!(span.is_await(), stage == 1);
+        }
+        assert_eq!(pop_span(), None);
+    }
+
+    #[rstest::rstest]
+    #[serial_test::serial]
+    fn full_buffer_overwrites_oldest_span() {
+        while pop_span().is_some() {}
+
+        let spans = TraceConfig::SPAN_BUFFER_SIZE as u64 + 3;
+        for start in 0..spans {
+            log_record(TraceFunction::StorageAppendRecord, None, start, start + 1);
+        }
+
+        for start in 3..spans {
+            assert_eq!(pop_span().map(|span| span.start_ticks), Some(start));
+        }
+        assert_eq!(pop_span(), None);
+    }
 }
@@ -0,0 +1,332 @@
+use core::cell::RefCell;
+use core::future::{Future, poll_fn};
+use core::pin::pin;
+
+use embassy_sync::blocking_mutex::{Mutex, raw::CriticalSectionRawMutex};
+use embassy_time::{Duration, Instant};
+use proto::event::{TaskId, WatchdogEvent};
+use proto::flight_state::FlightState;
+
+use crate::config::WatchdogConfig;
+use crate::interfaces::SampleRates;
+
+/// Longest a task may go without feeding its token, in each flight phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Deadlines {
+    pub pad: Duration,
+    pub ascent: Duration,
+    pub descent: Duration,
+    pub landed: Duration,
+}
+
+impl Deadlines {
+    #[must_use]
+    pub const fn fixed(deadline: Duration) -> Self {
+        Self {
+            pad: deadline,
+            ascent: deadline,
+            descent: deadline,
+            landed: deadline,
+        }
+    }
+
+    /// [`WatchdogConfig::SENSOR_DEADLINE_TICKS`] sample intervals, at least [`WatchdogConfig::MIN_SENSOR_DEADLINE`].
+    #[must_use]
+    pub const fn sensor(rates: SampleRates) -> Self {
+        const fn deadline(interval: Duration) -> Duration {
+            let ticks = interval.as_ticks() * WatchdogConfig::SENSOR_DEADLINE_TICKS;
+            let min = WatchdogConfig::MIN_SENSOR_DEADLINE.as_ticks();
+            Duration::from_ticks(if ticks > min { ticks } else { min })
+        }
+
+        Self {
+            pad: deadline(rates.pad),
+            ascent: deadline(rates.ascent),
+            descent: deadline(rates.descent),
+            landed: deadline(rates.landed),
+        }
+    }
+
+    #[must_use]
+    pub const fn get(&self, state: FlightState) -> Duration {
+        match state {
+            FlightState::PreArmed => self.pad,
+            FlightState::Armed => self.ascent,
+            FlightState::RecoveryActivated => self.descent,
+            FlightState::Touchdown => self.landed,
+        }
+    }
+}
+
+/// What a missed deadline does besides being reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Criticality {
+    /// Withholds the hardware feed, resetting the MCU unless the task recovers in time.
+    FlightCritical,
+    /// Only reported, e.g. a slow SD card must not reset the MCU during boost.
+    NonCritical,
+}
+
+/* --------------------------------- Registry --------------------------------- */
+
+/// Deadline misses found by a single check.
+pub type Missed = heapless::Vec<WatchdogEvent, { WatchdogConfig::MAX_TASKS }>;
+
+#[derive(Clone, Copy)]
+struct Entry {
+    task: TaskId,
+    deadlines: Deadlines,
+    criticality: Criticality,
+    last_fed: Instant,
+    due: Instant,
+    /// Set once the miss was reported, until the task feeds again.
+    reported: bool,
+}
+
+/// Liveness of the tasks holding a token.
+pub struct Registry {
+    entries: [Option<Entry>; WatchdogConfig::MAX_TASKS],
+    state: FlightState,
+}
+
+impl Registry {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: [None; WatchdogConfig::MAX_TASKS],
+            state: FlightState::PreArmed,
+        }
+    }
+
+    /// Returns the task's slot, `None` once [`WatchdogConfig::MAX_TASKS`] are registered.
+    pub fn register(&mut self, task: TaskId, deadlines: Deadlines, criticality: Criticality, now: Instant) -> Option<usize> {
+        let slot = self.entries.iter().position(Option::is_none)?;
+        self.entries[slot] = Some(Entry {
+            task,
+            deadlines,
+            criticality,
+            last_fed: now,
+            due: now + deadlines.get(self.state),
+            reported: false,
+        });
+        Some(slot)
+    }
+
+    pub fn unregister(&mut self, slot: usize) {
+        if let Some(entry) = self.entries.get_mut(slot) {
+            *entry = None;
+        }
+    }
+
+    pub fn feed(&mut self, slot: usize, now: Instant) {
+        let state = self.state;
+        if let Some(Some(entry)) = self.entries.get_mut(slot) {
+            entry.last_fed = now;
+            entry.due = now + entry.deadlines.get(state);
+            entry.reported = false;
+        }
+    }
+
+    /// Switches to the deadlines of `state`, each task's from its next feed on.
+    ///
+    /// Tasks only pick up a new phase at their next iteration, so the one in progress keeps the
+    /// previous phase's deadline, e.g. a GPS sample awaited at the pad rate right after launch.
+    pub const fn set_state(&mut self, state: FlightState) {
+        self.state = state;
+    }
+
+    /// Returns the tasks past their deadline, each reported once until it feeds again.
+    pub fn check(&mut self, now: Instant) -> Missed {
+        let mut missed = Missed::new();
+        for entry in self.entries.iter_mut().flatten() {
+            if now <= entry.due || entry.reported {
+                continue;
+            }
+            entry.reported = true;
+
+            let silent_ms = u32::try_from((now - entry.last_fed).as_millis()).unwrap_or(u32::MAX);
+            // at most one miss per entry
+            let _ = missed.push(WatchdogEvent::DeadlineMissed { task: entry.task, silent_ms });
+        }
+        missed
+    }
+
+    /// Whether every [`Criticality::FlightCritical`] task fed its token within its deadline.
+    #[must_use]
+    pub fn critical_alive(&self, now: Instant) -> bool {
+        self.entries.iter().flatten()
+            .filter(|entry| entry.criticality == Criticality::FlightCritical)
+            .all(|entry| now <= entry.due)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* ---------------------------------- Tokens ---------------------------------- */
+
+static REGISTRY: Mutex<CriticalSectionRawMutex, RefCell<Registry>> = Mutex::new(RefCell::new(Registry::new()));
+
+/// Proof of life of a task, fed once per iteration of its loop.
+///
+/// Dropping the token unregisters the task, e.g. once the storage task closed its log.
+pub struct WatchdogToken {
+    slot: usize,
+}
+
+impl WatchdogToken {
+    /// Returns `None` once [`WatchdogConfig::MAX_TASKS`] tokens are registered.
+    pub fn register(task: TaskId, deadlines: Deadlines, criticality: Criticality) -> Option<Self> {
+        REGISTRY
+            .lock(|registry| registry.borrow_mut().register(task, deadlines, criticality, Instant::now()))
+            .map(|slot| Self { slot })
+    }
+
+    pub fn feed(&self) {
+        REGISTRY.lock(|registry| registry.borrow_mut().feed(self.slot, Instant::now()));
+    }
+
+    /// Runs `future`, feeding the token each time it is woken and polled.
+    ///
+    /// For tasks that wait deep inside nested loops, e.g. the state machine's detectors: their
+    /// tickers and timeouts wake them well within the deadline, unless they hang.
+    pub async fn supervise<F: Future>(&self, future: F) -> F::Output {
+        let mut future = pin!(future);
+        poll_fn(|cx| {
+            self.feed();
+            future.as_mut().poll(cx)
+        }).await
+    }
+}
+
+impl Drop for WatchdogToken {
+    fn drop(&mut self) {
+        REGISTRY.lock(|registry| registry.borrow_mut().unregister(self.slot));
+    }
+}
+
+/// See [`Registry::set_state`].
+pub fn set_flight_state(state: FlightState) {
+    REGISTRY.lock(|registry| registry.borrow_mut().set_state(state));
+}
+
+/// Tasks newly past their deadline, and whether every flight-critical task is within it.
+pub fn check_deadlines(now: Instant) -> (Missed, bool) {
+    REGISTRY.lock(|registry| {
+        let mut registry = registry.borrow_mut();
+        (registry.check(now), registry.critical_alive(now))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::sensor_health::SensorId;
+
+    use crate::test_utils::at;
+
+    use super::*;
+
+    const DEADLINE: Duration = Duration::from_millis(100);
+
+    fn missed_tasks(missed: &Missed) -> Vec<TaskId> {
+        missed.iter().map(|WatchdogEvent::DeadlineMissed { task, .. }| *task).collect()
+    }
+
+    #[rstest::rstest]
+    fn fed_tasks_stay_alive() {
+        let mut registry = Registry::new();
+        let slot = registry.register(TaskId::FlightStateMachine, Deadlines::fixed(DEADLINE), Criticality::FlightCritical, at(0)).expect("Registry full");
+
+        for ms in (50..=500).step_by(50) {
+            registry.feed(slot, at(ms));
+            assert!(registry.check(at(ms + 100)).is_empty());
+            assert!(registry.critical_alive(at(ms + 100)));
+        }
+    }
+
+    #[rstest::rstest]
+    fn reports_missed_deadline_once_until_fed() {
+        let mut registry = Registry::new();
+        let slot = registry.register(TaskId::FlightStateMachine, Deadlines::fixed(DEADLINE), Criticality::FlightCritical, at(0)).expect("Registry full");
+        registry.register(TaskId::Sensor(SensorId::Altimeter), Deadlines::fixed(DEADLINE * 10), Criticality::FlightCritical, at(0)).expect("Registry full");
+
+        assert_eq!(
+            registry.check(at(150)).as_slice(),
+            [WatchdogEvent::DeadlineMissed { task: TaskId::FlightStateMachine, silent_ms: 150 }]
+        );
+        assert!(registry.check(at(200)).is_empty());
+        assert!(!registry.critical_alive(at(200)));
+
+        registry.feed(slot, at(250));
+        assert!(registry.critical_alive(at(300)));
+        assert_eq!(missed_tasks(&registry.check(at(400))), [TaskId::FlightStateMachine]);
+    }
+
+    #[rstest::rstest]
+    fn non_critical_stalls_are_only_reported() {
+        let mut registry = Registry::new();
+        registry.register(TaskId::Storage, Deadlines::fixed(DEADLINE), Criticality::NonCritical, at(0)).expect("Registry full");
+        let slot = registry.register(TaskId::FlightStateMachine, Deadlines::fixed(DEADLINE), Criticality::FlightCritical, at(0)).expect("Registry full");
+
+        registry.feed(slot, at(150));
+
+        assert_eq!(missed_tasks(&registry.check(at(200))), [TaskId::Storage]);
+        assert!(registry.critical_alive(at(200)));
+        assert_eq!(missed_tasks(&registry.check(at(300))), [TaskId::FlightStateMachine]);
+        assert!(!registry.critical_alive(at(300)));
+    }
+
+    #[rstest::rstest]
+    fn unregistered_tasks_are_not_supervised() {
+        let mut registry = Registry::new();
+        let slot = registry.register(TaskId::FlightStateMachine, Deadlines::fixed(DEADLINE), Criticality::FlightCritical, at(0)).expect("Registry full");
+
+        registry.unregister(slot);
+
+        assert!(registry.check(at(1_000)).is_empty());
+        assert!(registry.critical_alive(at(1_000)));
+    }
+
+    #[rstest::rstest]
+    fn registry_holds_max_tasks() {
+        let mut registry = Registry::new();
+        for _ in 0..WatchdogConfig::MAX_TASKS {
+            assert!(registry.register(TaskId::GroundStation, Deadlines::fixed(DEADLINE), Criticality::NonCritical, at(0)).is_some());
+        }
+
+        assert_eq!(registry.register(TaskId::GroundStation, Deadlines::fixed(DEADLINE), Criticality::NonCritical, at(0)), None);
+        registry.unregister(3);
+        assert_eq!(registry.register(TaskId::GroundStation, Deadlines::fixed(DEADLINE), Criticality::NonCritical, at(0)), Some(3));
+    }
+
+    #[rstest::rstest]
+    fn phase_deadline_applies_from_next_feed() {
+        let mut registry = Registry::new();
+        let deadlines = Deadlines { ascent: DEADLINE, ..Deadlines::fixed(DEADLINE * 5) };
+        let slot = registry.register(TaskId::Sensor(SensorId::Gps), deadlines, Criticality::FlightCritical, at(0)).expect("Registry full");
+
+        registry.set_state(FlightState::Armed);
+        assert!(registry.check(at(400)).is_empty());
+
+        registry.feed(slot, at(400));
+        assert_eq!(missed_tasks(&registry.check(at(550))), [TaskId::Sensor(SensorId::Gps)]);
+    }
+
+    #[rstest::rstest]
+    fn sensor_deadlines_span_several_samples() {
+        let rates = SampleRates {
+            pad: Duration::from_hz(1),
+            ascent: Duration::from_hz(1_000),
+            descent: Duration::from_hz(1),
+            landed: Duration::from_hz(1),
+        };
+
+        let deadlines = Deadlines::sensor(rates);
+
+        assert_eq!(deadlines.pad, Duration::from_ticks(rates.pad.as_ticks() * WatchdogConfig::SENSOR_DEADLINE_TICKS));
+        assert_eq!(deadlines.ascent, WatchdogConfig::MIN_SENSOR_DEADLINE);
+    }
+}
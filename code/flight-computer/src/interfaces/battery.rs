@@ -0,0 +1,8 @@
+use proto::sensor_data::ElectricPotential;
+
+/// Supply voltage of the flight computer, reported by the recovery beacon.
+pub trait Battery {
+    type Error: core::fmt::Debug;
+
+    async fn voltage(&mut self) -> Result<ElectricPotential, Self::Error>;
+}
@@ -0,0 +1,6 @@
+pub trait Buzzer {
+    type Error: core::fmt::Debug;
+
+    async fn on(&mut self) -> Result<(), Self::Error>;
+    async fn off(&mut self) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,13 @@
+use proto::flight_state::FlightCheckpoint;
+
+/// Non-volatile storage for the latest [`FlightCheckpoint`], read back at boot.
+pub trait CheckpointStore {
+    type Error: core::fmt::Debug;
+
+    /// Returns `Ok(None)` if no checkpoint was ever stored.
+    async fn load(&mut self) -> Result<Option<FlightCheckpoint>, Self::Error>;
+    /// Replaces the stored checkpoint. A reset while storing must leave the previous checkpoint readable.
+    async fn store(&mut self, checkpoint: &FlightCheckpoint) -> Result<(), Self::Error>;
+    /// Forgets the stored checkpoint, once the flight it tracked is over.
+    async fn clear(&mut self) -> Result<(), Self::Error>;
+}
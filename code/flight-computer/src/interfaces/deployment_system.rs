@@ -6,4 +6,10 @@ pub trait DeploymentSystem {
     /// Confirm that deployment was successful.
     /// Returns `Ok(true)` if deployed, `Ok(false)` if not yet confirmed.
     async fn verify_deployment(&mut self) -> Result<bool, Self::Error>;
+
+    /// Whether the deployment circuit is intact, checked before arming.
+    /// Returns `Ok(None)` where the hardware cannot sense it.
+    async fn continuity(&mut self) -> Result<Option<bool>, Self::Error> {
+        Ok(None)
+    }
 }
@@ -6,6 +6,14 @@ pub trait FileSystem {
 
     async fn exist_file(&mut self, filename: &str) -> Result<bool, Self::Error>;
     async fn create_file(&mut self, filename: &str) -> Result<Self::File, Self::Error>;
+    async fn delete_file(&mut self, filename: &str) -> Result<(), Self::Error>;
+
+    /// Reads the start of a file that is not open, returning the number of bytes read.
+    async fn read_file(&mut self, filename: &str, buffer: &mut [u8]) -> Result<usize, Self::Error>;
+    /// Size in bytes of a file that is not open.
+    async fn file_size(&mut self, filename: &str) -> Result<u32, Self::Error>;
+    /// Bytes left on the volume.
+    async fn free_space(&mut self) -> Result<u64, Self::Error>;
 
     async fn open_file_append(&mut self, filename: &str) -> Result<Self::File, Self::Error>;
     async fn close_file(&mut self, file: Self::File) -> Result<(), Self::Error>;
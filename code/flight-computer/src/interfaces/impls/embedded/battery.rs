@@ -0,0 +1,40 @@
+use proto::sensor_data::ElectricPotential;
+use proto::uom::si::electric_potential::millivolt;
+
+use crate::interfaces::Battery;
+
+/// Battery behind a resistor divider on an ADC pin, read through its HAL's function as
+/// embedded-hal has no ADC trait.
+///
+/// e.g. `AdcBattery::new(adc, |adc| adc.read_millivolts(&mut pin), 3.0)` with 20 kΩ over 10 kΩ.
+pub struct AdcBattery<A, R, E>
+where
+    R: FnMut(&mut A) -> Result<u16, E>,
+{
+    adc: A,
+    read_millivolts: R,
+    /// Battery voltage over the voltage at the pin.
+    divider_ratio: f32,
+}
+
+impl<A, R, E> AdcBattery<A, R, E>
+where
+    R: FnMut(&mut A) -> Result<u16, E>,
+{
+    pub const fn new(adc: A, read_millivolts: R, divider_ratio: f32) -> Self {
+        Self { adc, read_millivolts, divider_ratio }
+    }
+}
+
+impl<A, R, E> Battery for AdcBattery<A, R, E>
+where
+    R: FnMut(&mut A) -> Result<u16, E>,
+    E: core::fmt::Debug,
+{
+    type Error = E;
+
+    async fn voltage(&mut self) -> Result<ElectricPotential, Self::Error> {
+        let pin = (self.read_millivolts)(&mut self.adc)?;
+        Ok(ElectricPotential::new::<millivolt>(f32::from(pin) * self.divider_ratio))
+    }
+}
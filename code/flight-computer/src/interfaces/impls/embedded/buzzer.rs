@@ -0,0 +1,70 @@
+use embedded_hal::pwm::SetDutyCycle;
+use switch_hal::OutputSwitch;
+
+use crate::interfaces::Buzzer;
+
+/// Active buzzer, sounding its own tone while the pin is driven.
+pub struct GpioBuzzer<O>
+where
+    O: OutputSwitch,
+{
+    pin: O,
+}
+
+impl<O> GpioBuzzer<O>
+where
+    O: OutputSwitch,
+{
+    pub const fn new(pin: O) -> Self {
+        Self { pin }
+    }
+}
+
+impl<O> Buzzer for GpioBuzzer<O>
+where
+    O: OutputSwitch,
+    <O as OutputSwitch>::Error: core::fmt::Debug,
+{
+    type Error = O::Error;
+
+    async fn on(&mut self) -> Result<(), Self::Error> {
+        self.pin.on()
+    }
+
+    async fn off(&mut self) -> Result<(), Self::Error> {
+        self.pin.off()
+    }
+}
+
+/// Passive buzzer on a PWM channel, whose timer sets the tone's frequency.
+pub struct PwmBuzzer<P>
+where
+    P: SetDutyCycle,
+{
+    channel: P,
+}
+
+impl<P> PwmBuzzer<P>
+where
+    P: SetDutyCycle,
+{
+    pub const fn new(channel: P) -> Self {
+        Self { channel }
+    }
+}
+
+impl<P> Buzzer for PwmBuzzer<P>
+where
+    P: SetDutyCycle,
+{
+    type Error = P::Error;
+
+    async fn on(&mut self) -> Result<(), Self::Error> {
+        // a square wave is the loudest a piezo gets
+        self.channel.set_duty_cycle_percent(50)
+    }
+
+    async fn off(&mut self) -> Result<(), Self::Error> {
+        self.channel.set_duty_cycle_fully_off()
+    }
+}
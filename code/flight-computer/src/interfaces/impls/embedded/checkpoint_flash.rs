@@ -0,0 +1,320 @@
+use core::fmt::Debug;
+
+use embedded_storage::nor_flash::NorFlash;
+use proto::flight_state::FlightCheckpoint;
+
+use crate::config::CheckpointConfig;
+use crate::interfaces::CheckpointStore;
+
+const MAGIC: u16 = 0xFC01;
+/// magic (2) + sequence (4) + payload length (2)
+const HEADER_SIZE: usize = 8;
+const CHECKSUM_SIZE: usize = 2;
+const MAX_PAYLOAD_SIZE: usize = CheckpointConfig::SLOT_SIZE - HEADER_SIZE - CHECKSUM_SIZE;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum FlashCheckpointError<E: Debug> {
+    #[error("Flash error")]
+    Flash(E),
+    #[error("Serialize error")]
+    Serialize(#[from] serde_json_core::ser::Error),
+    #[error("Deserialize error")]
+    Deserialize(#[from] serde_json_core::de::Error),
+}
+
+/// Checkpoints appended to fixed-size slots across two flash sectors.
+///
+/// Every slot carries a sequence number and a checksum, and a sector is only erased when the
+/// other one holds the latest checkpoint, so a torn write or erase never loses the previous one.
+///
+/// `offset` must be aligned to `F::ERASE_SIZE`, and `CheckpointConfig::SLOT_SIZE` must be a
+/// multiple of `F::WRITE_SIZE` dividing `F::ERASE_SIZE`.
+pub struct NorFlashCheckpointStore<F: NorFlash> {
+    flash: F,
+    offset: u32,
+    /// Next slot and sequence number, known once both sectors were scanned.
+    next: Option<(u32, u32)>,
+}
+
+impl<F: NorFlash> NorFlashCheckpointStore<F> {
+    pub const fn new(flash: F, offset: u32) -> Self {
+        Self {
+            flash,
+            offset,
+            next: None,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn slots_per_sector() -> u32 {
+        (F::ERASE_SIZE / CheckpointConfig::SLOT_SIZE) as u32
+    }
+
+    const fn total_slots() -> u32 {
+        2 * Self::slots_per_sector()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn slot_offset(&self, slot: u32) -> u32 {
+        self.offset + slot * CheckpointConfig::SLOT_SIZE as u32
+    }
+
+    /// Reads a slot, returning its sequence number and payload length if it holds a complete checkpoint.
+    fn read_slot(&mut self, slot: u32, buf: &mut [u8; CheckpointConfig::SLOT_SIZE]) -> Result<Option<(u32, usize)>, F::Error> {
+        self.flash.read(self.slot_offset(slot), buf)?;
+
+        let magic = u16::from_le_bytes([buf[0], buf[1]]);
+        let seq = u32::from_le_bytes([buf[2], buf[3], buf[4], buf[5]]);
+        let len = usize::from(u16::from_le_bytes([buf[6], buf[7]]));
+
+        if magic != MAGIC || len > MAX_PAYLOAD_SIZE {
+            return Ok(None);
+        }
+
+        let end = HEADER_SIZE + len;
+        let checksum = u16::from_le_bytes([buf[end], buf[end + 1]]);
+        Ok((checksum == fletcher16(&buf[..end])).then_some((seq, len)))
+    }
+
+    /// Finds the slot with the highest sequence number.
+    fn scan(&mut self, buf: &mut [u8; CheckpointConfig::SLOT_SIZE]) -> Result<Option<(u32, u32, usize)>, F::Error> {
+        let mut latest: Option<(u32, u32, usize)> = None;
+
+        for slot in 0..Self::total_slots() {
+            if let Some((seq, len)) = self.read_slot(slot, buf)?
+                && latest.is_none_or(|(_, latest_seq, _)| seq > latest_seq)
+            {
+                latest = Some((slot, seq, len));
+            }
+        }
+
+        self.next = Some(latest.map_or((0, 0), |(slot, seq, _)| (
+            (slot + 1) % Self::total_slots(),
+            seq.wrapping_add(1),
+        )));
+
+        Ok(latest)
+    }
+}
+
+impl<F: NorFlash> CheckpointStore for NorFlashCheckpointStore<F> {
+    type Error = FlashCheckpointError<F::Error>;
+
+    async fn load(&mut self) -> Result<Option<FlightCheckpoint>, Self::Error> {
+        let mut buf = [0; CheckpointConfig::SLOT_SIZE];
+
+        let Some((slot, _, len)) = self.scan(&mut buf).map_err(FlashCheckpointError::Flash)? else {
+            return Ok(None);
+        };
+
+        self.read_slot(slot, &mut buf).map_err(FlashCheckpointError::Flash)?;
+        let (checkpoint, _) = serde_json_core::from_slice(&buf[HEADER_SIZE..HEADER_SIZE + len])?;
+
+        Ok(Some(checkpoint))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn store(&mut self, checkpoint: &FlightCheckpoint) -> Result<(), Self::Error> {
+        let mut buf = [0xFF; CheckpointConfig::SLOT_SIZE];
+
+        let (slot, seq) = match self.next {
+            Some(next) => next,
+            None => {
+                self.scan(&mut buf).map_err(FlashCheckpointError::Flash)?;
+                buf.fill(0xFF);
+                self.next.unwrap_or((0, 0))
+            },
+        };
+
+        let len = serde_json_core::to_slice(checkpoint, &mut buf[HEADER_SIZE..HEADER_SIZE + MAX_PAYLOAD_SIZE])?;
+        buf[0..2].copy_from_slice(&MAGIC.to_le_bytes());
+        buf[2..6].copy_from_slice(&seq.to_le_bytes());
+        buf[6..8].copy_from_slice(&(len as u16).to_le_bytes());
+        let end = HEADER_SIZE + len;
+        let checksum = fletcher16(&buf[..end]);
+        buf[end..end + CHECKSUM_SIZE].copy_from_slice(&checksum.to_le_bytes());
+
+        // entering a sector: the other one still holds the latest checkpoint
+        if slot % Self::slots_per_sector() == 0 {
+            let sector_start = self.slot_offset(slot);
+            self.flash.erase(sector_start, sector_start + F::ERASE_SIZE as u32).map_err(FlashCheckpointError::Flash)?;
+        }
+        self.flash.write(self.slot_offset(slot), &buf).map_err(FlashCheckpointError::Flash)?;
+
+        self.next = Some(((slot + 1) % Self::total_slots(), seq.wrapping_add(1)));
+        Ok(())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn clear(&mut self) -> Result<(), Self::Error> {
+        let mut buf = [0; CheckpointConfig::SLOT_SIZE];
+        let latest_sector = self.scan(&mut buf).map_err(FlashCheckpointError::Flash)?
+            .map_or(0, |(slot, _, _)| slot / Self::slots_per_sector());
+
+        // the latest checkpoint goes last: a reset in between must not bring back an older one
+        for sector in [1 - latest_sector, latest_sector] {
+            let sector_start = self.slot_offset(sector * Self::slots_per_sector());
+            self.flash.erase(sector_start, sector_start + F::ERASE_SIZE as u32).map_err(FlashCheckpointError::Flash)?;
+        }
+
+        self.next = Some((0, 0));
+        Ok(())
+    }
+}
+
+fn fletcher16(data: &[u8]) -> u16 {
+    let (mut sum1, mut sum2) = (0_u16, 0_u16);
+    for &byte in data {
+        sum1 = (sum1 + u16::from(byte)) % 255;
+        sum2 = (sum2 + sum1) % 255;
+    }
+    (sum2 << 8) | sum1
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_storage::nor_flash::{ErrorType, NorFlashErrorKind, ReadNorFlash};
+    use proto::flight_state::FlightState;
+    use proto::record::tick_hz::Timestamp;
+    use proto::sensor_data::Altitude;
+    use proto::uom::si::length::meter;
+
+    use super::*;
+
+    const SECTOR_SIZE: usize = 4 * CheckpointConfig::SLOT_SIZE;
+
+    /// Two sectors of NOR flash: writes can only clear bits.
+    struct RamFlash(Vec<u8>);
+
+    impl RamFlash {
+        fn new() -> Self {
+            Self(vec![0xFF; 2 * SECTOR_SIZE])
+        }
+    }
+
+    impl ErrorType for RamFlash {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl ReadNorFlash for RamFlash {
+        const READ_SIZE: usize = 1;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            let offset = offset as usize;
+            bytes.copy_from_slice(self.0.get(offset..offset + bytes.len()).ok_or(NorFlashErrorKind::OutOfBounds)?);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl NorFlash for RamFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = SECTOR_SIZE;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+            self.0.get_mut(from as usize..to as usize).ok_or(NorFlashErrorKind::OutOfBounds)?.fill(0xFF);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            let offset = offset as usize;
+            let target = self.0.get_mut(offset..offset + bytes.len()).ok_or(NorFlashErrorKind::OutOfBounds)?;
+            for (byte, new) in target.iter_mut().zip(bytes) {
+                *byte &= new;
+            }
+            Ok(())
+        }
+    }
+
+    fn checkpoint(state: FlightState, ticks: u64) -> FlightCheckpoint {
+        FlightCheckpoint {
+            state,
+            launchpad_altitude: Some(Altitude::new::<meter>(90.0)),
+            pad_calibration: None,
+            armed_at: Some(Timestamp::from(ticks)),
+            recovery_activated_at: None,
+            resumes: 0,
+        }
+    }
+
+    /// Simulates a reboot: a fresh store over the same flash.
+    fn reboot(store: NorFlashCheckpointStore<RamFlash>) -> NorFlashCheckpointStore<RamFlash> {
+        NorFlashCheckpointStore::new(store.flash, 0)
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    async fn empty_flash_has_no_checkpoint() {
+        let mut store = NorFlashCheckpointStore::new(RamFlash::new(), 0);
+        assert_eq!(store.load().await.expect("Flash never fails"), None);
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[case(1)]
+    #[case(4)]
+    #[case(5)]
+    #[case(21)]
+    async fn latest_checkpoint_survives_reboot(#[case] stores: u64) {
+        let mut store = NorFlashCheckpointStore::new(RamFlash::new(), 0);
+        for ticks in 0..stores {
+            store.store(&checkpoint(FlightState::Armed, ticks)).await.expect("Flash never fails");
+        }
+
+        let mut store = reboot(store);
+        assert_eq!(store.load().await.expect("Flash never fails"), Some(checkpoint(FlightState::Armed, stores - 1)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    async fn store_without_load_keeps_sequence() {
+        let mut store = NorFlashCheckpointStore::new(RamFlash::new(), 0);
+        for ticks in 0..6 {
+            store.store(&checkpoint(FlightState::Armed, ticks)).await.expect("Flash never fails");
+        }
+
+        let mut store = reboot(store);
+        store.store(&checkpoint(FlightState::RecoveryActivated, 6)).await.expect("Flash never fails");
+
+        let mut store = reboot(store);
+        assert_eq!(store.load().await.expect("Flash never fails"), Some(checkpoint(FlightState::RecoveryActivated, 6)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    async fn torn_write_keeps_previous_checkpoint() {
+        let mut store = NorFlashCheckpointStore::new(RamFlash::new(), 0);
+        store.store(&checkpoint(FlightState::Armed, 1)).await.expect("Flash never fails");
+        store.store(&checkpoint(FlightState::RecoveryActivated, 2)).await.expect("Flash never fails");
+
+        // reset while writing the second slot: its tail never reached the flash
+        let slot = CheckpointConfig::SLOT_SIZE;
+        store.flash.0[slot + HEADER_SIZE + 10..2 * slot].fill(0xFF);
+
+        let mut store = reboot(store);
+        assert_eq!(store.load().await.expect("Flash never fails"), Some(checkpoint(FlightState::Armed, 1)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[case(2)]
+    #[case(6)]
+    async fn cleared_checkpoint_stays_cleared(#[case] stores: u64) {
+        let mut store = NorFlashCheckpointStore::new(RamFlash::new(), 0);
+        for ticks in 0..stores {
+            store.store(&checkpoint(FlightState::Armed, ticks)).await.expect("Flash never fails");
+        }
+        store.clear().await.expect("Flash never fails");
+
+        let mut store = reboot(store);
+        assert_eq!(store.load().await.expect("Flash never fails"), None);
+
+        store.store(&checkpoint(FlightState::Armed, stores)).await.expect("Flash never fails");
+        let mut store = reboot(store);
+        assert_eq!(store.load().await.expect("Flash never fails"), Some(checkpoint(FlightState::Armed, stores)));
+    }
+}
@@ -1,5 +1,9 @@
 pub mod sensor;
 pub mod sd_card;
+pub mod checkpoint_flash;
 pub mod deployment_switch;
 pub mod arming_button;
-pub mod led_device;
\ No newline at end of file
+pub mod buzzer;
+pub mod led_device;
+pub mod watchdog;
+pub mod battery;
@@ -70,6 +70,41 @@ impl<D: embedded_sdmmc::BlockDevice> FileSystem for SdCardFatFS<D> {
         ).map_err(SdCardError::FileSystem)
     }
 
+    async fn delete_file(&mut self, filename: &str) -> Result<(), Self::Error> {
+        self.volume_manager.delete_file_in_dir(
+            self.raw_root_dir,
+            filename,
+        ).map_err(SdCardError::FileSystem)
+    }
+
+    async fn read_file(&mut self, filename: &str, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+        let file = self.volume_manager.open_file_in_dir(
+            self.raw_root_dir,
+            filename,
+            Mode::ReadOnly,
+        ).map_err(SdCardError::FileSystem)?;
+
+        let read = self.volume_manager.read(file, buffer);
+        self.volume_manager.close_file(file).map_err(SdCardError::FileSystem)?;
+        read.map_err(SdCardError::FileSystem)
+    }
+
+    async fn file_size(&mut self, filename: &str) -> Result<u32, Self::Error> {
+        self.volume_manager.find_directory_entry(
+            self.raw_root_dir,
+            filename,
+        ).map(|entry| entry.size).map_err(SdCardError::FileSystem)
+    }
+
+    /// Card capacity less the files in the root directory, the only one written to.
+    /// The FAT and the slack of partly used clusters are not accounted for.
+    async fn free_space(&mut self) -> Result<u64, Self::Error> {
+        let blocks = self.volume_manager.device(|card| card.num_blocks()).map_err(embedded_sdmmc::Error::DeviceError)?;
+        let mut used = 0_u64;
+        self.volume_manager.iterate_dir(self.raw_root_dir, |entry| used += u64::from(entry.size))?;
+        Ok((u64::from(blocks.0) * u64::from(embedded_sdmmc::Block::LEN_U32)).saturating_sub(used))
+    }
+
     async fn open_file_append(&mut self, filename: &str) -> Result<Self::File, Self::Error> {
         self.volume_manager.open_file_in_dir(
             self.raw_root_dir,
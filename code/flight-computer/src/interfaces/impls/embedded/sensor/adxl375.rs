@@ -0,0 +1,209 @@
+use core::fmt::Debug;
+
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+use proto::flight_state::FlightState;
+use proto::sensor_data::{Acceleration, HighGAccelerometerData, Vector3};
+use proto::uom::si::acceleration::meter_per_second_squared;
+use proto::sensor_health::SensorId;
+
+use crate::config::DataAcquisitionConfig;
+use crate::config::embedded::Adxl375Config;
+use crate::interfaces::{SampleRates, Sensor};
+
+pub const DEVICE_ID: u8 = 0xE5;
+
+const DEVICE_ID_REGISTER: u8 = 0x00;
+const BANDWIDTH_RATE_REGISTER: u8 = 0x2C;
+const POWER_CONTROL_REGISTER: u8 = 0x2D;
+const DATA_FORMAT_REGISTER: u8 = 0x31;
+const DATA_REGISTER: u8 = 0x32;
+
+const MEASURE: u8 = 0b0000_1000;
+/// Right-justified samples, with the bits the datasheet requires set.
+const DATA_FORMAT: u8 = 0b0000_1011;
+
+const DATA_SIZE: usize = 6;
+/// Nominal sensitivity, over the fixed ±200 g range.
+const G_PER_LSB: f32 = 0.049;
+const STANDARD_GRAVITY: f32 = 9.806_65;
+
+/// Output data rate, the bandwidth being half of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OutputDataRate {
+    Hz100 = 0x0A,
+    Hz200 = 0x0B,
+    Hz400 = 0x0C,
+    Hz800 = 0x0D,
+    Hz1600 = 0x0E,
+    Hz3200 = 0x0F,
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Adxl375Error<E: Debug> {
+    #[error("I2C error")]
+    Bus(E),
+    #[error("Unknown device ID: {0:#04x}")]
+    UnknownChip(u8),
+}
+
+/// Analog Devices ADXL375 over I2C, a ±200 g accelerometer taking over from the IMU once it saturates.
+///
+/// Its axes are expected to be mounted parallel to the IMU's.
+pub struct Adxl375Device<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    i2c: I,
+    address: u8,
+}
+
+impl<I, E> Adxl375Device<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    pub fn init(mut i2c: I, address: u8) -> Result<Self, Adxl375Error<E>> {
+        let [device_id] = read(&mut i2c, address, DEVICE_ID_REGISTER)?;
+        if device_id != DEVICE_ID {
+            return Err(Adxl375Error::UnknownChip(device_id));
+        }
+
+        let mut adxl375 = Self { i2c, address };
+        adxl375.write(DATA_FORMAT_REGISTER, DATA_FORMAT)?;
+        adxl375.write(BANDWIDTH_RATE_REGISTER, Adxl375Config::output_data_rate(FlightState::PreArmed) as u8)?;
+        adxl375.write(POWER_CONTROL_REGISTER, MEASURE)?;
+        Ok(adxl375)
+    }
+
+    fn write(&mut self, register: u8, value: u8) -> Result<(), Adxl375Error<E>> {
+        self.i2c.write(self.address, &[register, value]).map_err(Adxl375Error::Bus)
+    }
+}
+
+fn read<I, E, const N: usize>(i2c: &mut I, address: u8, register: u8) -> Result<[u8; N], Adxl375Error<E>>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    let mut buffer = [0; N];
+    i2c.write_read(address, &[register], &mut buffer).map_err(Adxl375Error::Bus)?;
+    Ok(buffer)
+}
+
+fn acceleration(low: u8, high: u8) -> Acceleration {
+    let g = f32::from(i16::from_le_bytes([low, high])) * G_PER_LSB;
+    Acceleration::new::<meter_per_second_squared>(g * STANDARD_GRAVITY)
+}
+
+impl<I, E> Sensor for Adxl375Device<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    type Data = HighGAccelerometerData;
+    type Error = Adxl375Error<E>;
+
+    const NAME: &'static str = "ADXL375 High-g Accelerometer";
+    const ID: SensorId = SensorId::HighGAccelerometer;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::HIGH_G_ACCELEROMETER_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        // a single burst, so all three axes come from the same sample
+        let data: [u8; DATA_SIZE] = read(&mut self.i2c, self.address, DATA_REGISTER)?;
+
+        Ok(HighGAccelerometerData {
+            acceleration: Vector3::new(
+                acceleration(data[0], data[1]),
+                acceleration(data[2], data[3]),
+                acceleration(data[4], data[5]),
+            ),
+        })
+    }
+
+    async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+        self.write(BANDWIDTH_RATE_REGISTER, Adxl375Config::output_data_rate(state) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    use crate::test_utils::ms;
+
+    use super::*;
+
+    const ADDRESS: u8 = 0x53;
+
+    fn initialization() -> Vec<I2cTransaction> {
+        vec![
+            I2cTransaction::write_read(ADDRESS, vec![DEVICE_ID_REGISTER], vec![DEVICE_ID]),
+            I2cTransaction::write(ADDRESS, vec![DATA_FORMAT_REGISTER, DATA_FORMAT]),
+            I2cTransaction::write(ADDRESS, vec![BANDWIDTH_RATE_REGISTER, Adxl375Config::output_data_rate(FlightState::PreArmed) as u8]),
+            I2cTransaction::write(ADDRESS, vec![POWER_CONTROL_REGISTER, MEASURE]),
+        ]
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn scales_samples_to_acceleration() {
+        let mut transactions = initialization();
+        // 20 LSB, -1000 LSB and 4000 LSB
+        transactions.push(I2cTransaction::write_read(
+            ADDRESS,
+            vec![DATA_REGISTER],
+            vec![0x14, 0x00, 0x18, 0xFC, 0xA0, 0x0F],
+        ));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut adxl375 = Adxl375Device::init(i2c.clone(), ADDRESS).expect("Initialization should succeed");
+        let data = adxl375.parse_new_data().await.expect("Reading should succeed");
+        i2c.done();
+
+        for (axis, expected_g) in data.acceleration.iter().zip([0.98, -49.0, 196.0]) {
+            let expected = expected_g * STANDARD_GRAVITY;
+            assert!((axis.get::<meter_per_second_squared>() - expected).abs() < 0.01, "Expected {expected} m/s², got {axis:?}");
+        }
+    }
+
+    #[rstest::rstest]
+    fn rejects_unknown_chip() {
+        let mut i2c = I2cMock::new(&[I2cTransaction::write_read(ADDRESS, vec![DEVICE_ID_REGISTER], vec![0xE6])]);
+
+        let result = Adxl375Device::init(i2c.clone(), ADDRESS);
+        i2c.done();
+
+        assert_eq!(result.err(), Some(Adxl375Error::UnknownChip(0xE6)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn reconfigures_per_flight_state() {
+        let mut transactions = initialization();
+        transactions.push(I2cTransaction::write(ADDRESS, vec![BANDWIDTH_RATE_REGISTER, Adxl375Config::output_data_rate(FlightState::Armed) as u8]));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut adxl375 = Adxl375Device::init(i2c.clone(), ADDRESS).expect("Initialization should succeed");
+        assert_eq!(adxl375.configure(FlightState::Armed).await, Ok(()));
+        i2c.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn forwards_bus_errors() {
+        let mut transactions = initialization();
+        transactions.push(I2cTransaction::write_read(ADDRESS, vec![DATA_REGISTER], vec![0; DATA_SIZE]).with_error(ErrorKind::Other));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut adxl375 = Adxl375Device::init(i2c.clone(), ADDRESS).expect("Initialization should succeed");
+        assert_eq!(adxl375.parse_new_data().await, Err(Adxl375Error::Bus(ErrorKind::Other)));
+        i2c.done();
+    }
+}
@@ -2,12 +2,14 @@ use core::fmt::Debug;
 
 use bmp280_ehal::BMP280;
 use embedded_hal::i2c::{I2c, SevenBitAddress};
+use proto::flight_state::FlightState;
 use proto::sensor_data::{AltimeterData, Pressure, ThermodynamicTemperature};
 use proto::uom::si::{pressure::pascal, thermodynamic_temperature::degree_celsius};
+use proto::sensor_health::SensorId;
 
 use crate::config::DataAcquisitionConfig;
 use crate::config::embedded::Bmp280Config;
-use crate::{interfaces::Sensor, core::sensors::altimeter::altitude_from_pressure};
+use crate::{interfaces::{SampleRates, Sensor}, core::sensors::altimeter::pressure_altitude};
 
 pub struct Bmp280Device<I, E>
 where
@@ -43,7 +45,8 @@ where
     type Error = E;
 
     const NAME: &'static str = "BMP280 Altimeter";
-    const TICK_INTERVAL: embassy_time::Duration = DataAcquisitionConfig::ALTIMETER_TICK_INTERVAL;
+    const ID: SensorId = SensorId::Altimeter;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::ALTIMETER_SAMPLE_RATES;
 
     #[allow(clippy::cast_possible_truncation)]
     async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
@@ -54,7 +57,7 @@ where
             .map(|t| t as f32)
             .map(ThermodynamicTemperature::new::<degree_celsius>)?;
 
-        let altitude = altitude_from_pressure(pressure);
+        let altitude = pressure_altitude(pressure);
 
         Ok(AltimeterData {
             altitude,
@@ -62,4 +65,8 @@ where
             temperature, 
         })
     }
+
+    async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+        self.bmp280.set_control(Bmp280Config::control(state))
+    }
 }
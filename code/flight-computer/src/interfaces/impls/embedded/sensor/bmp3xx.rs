@@ -0,0 +1,369 @@
+use core::fmt::Debug;
+
+use embassy_time::Timer;
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+use proto::flight_state::FlightState;
+use proto::sensor_data::{AltimeterData, Pressure, ThermodynamicTemperature};
+#[allow(unused_imports)]
+use proto::uom::num_traits::Float;
+use proto::uom::si::{pressure::pascal, thermodynamic_temperature::degree_celsius};
+use proto::sensor_health::SensorId;
+
+use crate::config::DataAcquisitionConfig;
+use crate::config::embedded::Bmp3xxConfig;
+use crate::{interfaces::{SampleRates, Sensor}, core::sensors::altimeter::pressure_altitude};
+
+pub const BMP388_CHIP_ID: u8 = 0x50;
+pub const BMP390_CHIP_ID: u8 = 0x60;
+
+const CHIP_ID_REGISTER: u8 = 0x00;
+const ERROR_REGISTER: u8 = 0x02;
+const DATA_REGISTER: u8 = 0x04;
+const POWER_CONTROL_REGISTER: u8 = 0x1B;
+const OVERSAMPLING_REGISTER: u8 = 0x1C;
+const OUTPUT_DATA_RATE_REGISTER: u8 = 0x1D;
+const CONFIG_REGISTER: u8 = 0x1F;
+const CALIBRATION_REGISTER: u8 = 0x31;
+const COMMAND_REGISTER: u8 = 0x7E;
+
+const SOFT_RESET: u8 = 0xB6;
+/// Pressure and temperature enabled, sensor asleep.
+const POWER_SLEEP: u8 = 0b0000_0011;
+/// Pressure and temperature enabled, measuring at the output data rate.
+const POWER_NORMAL: u8 = 0b0011_0011;
+
+const CALIBRATION_SIZE: usize = 21;
+const DATA_SIZE: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Oversampling {
+    X1 = 0,
+    X2 = 1,
+    X4 = 2,
+    X8 = 3,
+    X16 = 4,
+    X32 = 5,
+}
+
+/// Coefficient of the on-chip IIR filter smoothing the pressure, higher is smoother but lags more.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum IirFilter {
+    Bypass = 0,
+    Coefficient1 = 1,
+    Coefficient3 = 2,
+    Coefficient7 = 3,
+    Coefficient15 = 4,
+    Coefficient31 = 5,
+    Coefficient63 = 6,
+    Coefficient127 = 7,
+}
+
+/// Measurement rate in normal mode, it must leave time for the configured oversampling.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OutputDataRate {
+    Hz200 = 0,
+    Hz100 = 1,
+    Hz50 = 2,
+    Hz25 = 3,
+    Hz12_5 = 4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bmp3xxSettings {
+    pub pressure_oversampling: Oversampling,
+    pub temperature_oversampling: Oversampling,
+    pub output_data_rate: OutputDataRate,
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Bmp3xxError<E: Debug> {
+    #[error("I2C error")]
+    Bus(E),
+    #[error("Unknown chip ID: {0:#04x}")]
+    UnknownChip(u8),
+    /// The sensor rejected its settings, usually an output data rate too fast for the oversampling.
+    #[error("Configuration rejected, error register: {0:#04x}")]
+    Configuration(u8),
+}
+
+/// Trimming coefficients, scaled as in Bosch's floating point compensation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Calibration {
+    t1: f64,
+    t2: f64,
+    t3: f64,
+    p1: f64,
+    p2: f64,
+    p3: f64,
+    p4: f64,
+    p5: f64,
+    p6: f64,
+    p7: f64,
+    p8: f64,
+    p9: f64,
+    p10: f64,
+    p11: f64,
+}
+
+/// `2^exponent`, the scales of the coefficients are all powers of two.
+const fn pow2(exponent: u32) -> f64 {
+    let mut value = 1.0;
+    let mut remaining = exponent;
+    while remaining > 0 {
+        value *= 2.0;
+        remaining -= 1;
+    }
+    value
+}
+
+impl Calibration {
+    fn parse(nvm: &[u8; CALIBRATION_SIZE]) -> Self {
+        let u16_at = |index: usize| f64::from(u16::from_le_bytes([nvm[index], nvm[index + 1]]));
+        let i16_at = |index: usize| f64::from(i16::from_le_bytes([nvm[index], nvm[index + 1]]));
+        let i8_at = |index: usize| f64::from(i8::from_le_bytes([nvm[index]]));
+
+        Self {
+            t1: u16_at(0) * pow2(8),
+            t2: u16_at(2) / pow2(30),
+            t3: i8_at(4) / pow2(48),
+            p1: (i16_at(5) - pow2(14)) / pow2(20),
+            p2: (i16_at(7) - pow2(14)) / pow2(29),
+            p3: i8_at(9) / pow2(32),
+            p4: i8_at(10) / pow2(37),
+            p5: u16_at(11) * pow2(3),
+            p6: u16_at(13) / pow2(6),
+            p7: i8_at(15) / pow2(8),
+            p8: i8_at(16) / pow2(15),
+            p9: i16_at(17) / pow2(48),
+            p10: i8_at(19) / pow2(48),
+            p11: i8_at(20) / pow2(65),
+        }
+    }
+
+    /// Temperature in Celsius degrees.
+    fn temperature(&self, raw: u32) -> f64 {
+        let offset = f64::from(raw) - self.t1;
+        offset.mul_add(self.t2, offset * offset * self.t3)
+    }
+
+    /// Pressure in Pascal, at `temperature` as returned by [`Self::temperature`].
+    fn pressure(&self, raw: u32, temperature: f64) -> f64 {
+        let raw = f64::from(raw);
+        let cubic = |c0: f64, c1: f64, c2: f64, c3: f64| c3.mul_add(temperature, c2).mul_add(temperature, c1).mul_add(temperature, c0);
+
+        let offset = cubic(self.p5, self.p6, self.p7, self.p8);
+        let sensitivity = raw * cubic(self.p1, self.p2, self.p3, self.p4);
+        let nonlinearity = (raw * raw).mul_add(self.p10.mul_add(temperature, self.p9), raw * raw * raw * self.p11);
+        offset + sensitivity + nonlinearity
+    }
+}
+
+/// Bosch BMP388 or BMP390 over I2C, both share their registers and compensation.
+pub struct Bmp3xxDevice<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    i2c: I,
+    address: u8,
+    calibration: Calibration,
+}
+
+impl<I, E> Bmp3xxDevice<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    pub async fn init(mut i2c: I, address: u8) -> Result<Self, Bmp3xxError<E>> {
+        let [chip_id] = read(&mut i2c, address, CHIP_ID_REGISTER)?;
+        if chip_id != BMP388_CHIP_ID && chip_id != BMP390_CHIP_ID {
+            return Err(Bmp3xxError::UnknownChip(chip_id));
+        }
+
+        write(&mut i2c, address, COMMAND_REGISTER, SOFT_RESET)?;
+        Timer::after(Bmp3xxConfig::RESET_DELAY).await;
+
+        let calibration = Calibration::parse(&read(&mut i2c, address, CALIBRATION_REGISTER)?);
+        write(&mut i2c, address, CONFIG_REGISTER, (Bmp3xxConfig::IIR_FILTER as u8) << 1)?;
+
+        let mut bmp3xx = Self { i2c, address, calibration };
+        bmp3xx.apply(Bmp3xxConfig::settings(FlightState::PreArmed))?;
+        Ok(bmp3xx)
+    }
+
+    /// Settings only change while asleep, the sensor then resumes measuring.
+    fn apply(&mut self, settings: Bmp3xxSettings) -> Result<(), Bmp3xxError<E>> {
+        let oversampling = ((settings.temperature_oversampling as u8) << 3) | settings.pressure_oversampling as u8;
+
+        write(&mut self.i2c, self.address, POWER_CONTROL_REGISTER, POWER_SLEEP)?;
+        write(&mut self.i2c, self.address, OVERSAMPLING_REGISTER, oversampling)?;
+        write(&mut self.i2c, self.address, OUTPUT_DATA_RATE_REGISTER, settings.output_data_rate as u8)?;
+        write(&mut self.i2c, self.address, POWER_CONTROL_REGISTER, POWER_NORMAL)?;
+
+        match read(&mut self.i2c, self.address, ERROR_REGISTER)? {
+            [0] => Ok(()),
+            [error] => Err(Bmp3xxError::Configuration(error)),
+        }
+    }
+}
+
+fn read<I, E, const N: usize>(i2c: &mut I, address: u8, register: u8) -> Result<[u8; N], Bmp3xxError<E>>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    let mut buffer = [0; N];
+    i2c.write_read(address, &[register], &mut buffer).map_err(Bmp3xxError::Bus)?;
+    Ok(buffer)
+}
+
+fn write<I, E>(i2c: &mut I, address: u8, register: u8, value: u8) -> Result<(), Bmp3xxError<E>>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    i2c.write(address, &[register, value]).map_err(Bmp3xxError::Bus)
+}
+
+impl<I, E> Sensor for Bmp3xxDevice<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    type Data = AltimeterData;
+    type Error = Bmp3xxError<E>;
+
+    const NAME: &'static str = "BMP3xx Altimeter";
+    const ID: SensorId = SensorId::Altimeter;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::ALTIMETER_SAMPLE_RATES;
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        let data: [u8; DATA_SIZE] = read(&mut self.i2c, self.address, DATA_REGISTER)?;
+        let raw_pressure = u32::from_le_bytes([data[0], data[1], data[2], 0]);
+        let raw_temperature = u32::from_le_bytes([data[3], data[4], data[5], 0]);
+
+        let temperature = self.calibration.temperature(raw_temperature);
+        let pressure = Pressure::new::<pascal>(self.calibration.pressure(raw_pressure, temperature) as f32);
+
+        Ok(AltimeterData {
+            altitude: pressure_altitude(pressure),
+            pressure,
+            temperature: ThermodynamicTemperature::new::<degree_celsius>(temperature as f32),
+        })
+    }
+
+    async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+        self.apply(Bmp3xxConfig::settings(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use proto::uom::si::thermodynamic_temperature::kelvin;
+
+    use crate::test_utils::ms;
+
+    use super::*;
+
+    const ADDRESS: u8 = 0x77;
+
+    /// Trimming scaled to 25 °C for a raw `0x99_0000`, and to `100_000 Pa + raw / 2^16 - 25² / 2^8` of pressure.
+    const CALIBRATION: [u8; CALIBRATION_SIZE] = [
+        0x00, 0x80, 0x00, 0x40, 0x00, // T1, T2, T3
+        0x10, 0x40, 0x00, 0x40, 0x00, 0x00, // P1, P2, P3, P4
+        0xD4, 0x30, 0x00, 0x00, 0xFF, 0x00, // P5, P6, P7, P8
+        0x00, 0x00, 0x00, 0x00, // P9, P10, P11
+    ];
+
+    fn configuration(settings: Bmp3xxSettings, error: u8) -> Vec<I2cTransaction> {
+        let oversampling = ((settings.temperature_oversampling as u8) << 3) | settings.pressure_oversampling as u8;
+        vec![
+            I2cTransaction::write(ADDRESS, vec![POWER_CONTROL_REGISTER, POWER_SLEEP]),
+            I2cTransaction::write(ADDRESS, vec![OVERSAMPLING_REGISTER, oversampling]),
+            I2cTransaction::write(ADDRESS, vec![OUTPUT_DATA_RATE_REGISTER, settings.output_data_rate as u8]),
+            I2cTransaction::write(ADDRESS, vec![POWER_CONTROL_REGISTER, POWER_NORMAL]),
+            I2cTransaction::write_read(ADDRESS, vec![ERROR_REGISTER], vec![error]),
+        ]
+    }
+
+    fn initialization(chip_id: u8) -> Vec<I2cTransaction> {
+        let mut transactions = vec![
+            I2cTransaction::write_read(ADDRESS, vec![CHIP_ID_REGISTER], vec![chip_id]),
+            I2cTransaction::write(ADDRESS, vec![COMMAND_REGISTER, SOFT_RESET]),
+            I2cTransaction::write_read(ADDRESS, vec![CALIBRATION_REGISTER], CALIBRATION.to_vec()),
+            I2cTransaction::write(ADDRESS, vec![CONFIG_REGISTER, (Bmp3xxConfig::IIR_FILTER as u8) << 1]),
+        ];
+        transactions.extend(configuration(Bmp3xxConfig::settings(FlightState::PreArmed), 0));
+        transactions
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[case(BMP388_CHIP_ID)]
+    #[case(BMP390_CHIP_ID)]
+    #[timeout(ms(100))]
+    async fn compensates_pressure_and_temperature(#[case] chip_id: u8) {
+        let mut transactions = initialization(chip_id);
+        transactions.push(I2cTransaction::write_read(
+            ADDRESS,
+            vec![DATA_REGISTER],
+            vec![0x00, 0x00, 0x64, 0x00, 0x00, 0x99],
+        ));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut bmp3xx = Bmp3xxDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        let data = bmp3xx.parse_new_data().await.expect("Reading should succeed");
+        i2c.done();
+
+        let expected_pressure = 100_000.0 + 100.0 - 625.0 / 256.0;
+        assert!((data.pressure.get::<pascal>() - expected_pressure).abs() < 0.01, "Pressure: {:?}", data.pressure);
+        assert!((data.temperature.get::<kelvin>() - 298.15).abs() < 0.001, "Temperature: {:?}", data.temperature);
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn rejects_unknown_chip() {
+        let mut i2c = I2cMock::new(&[I2cTransaction::write_read(ADDRESS, vec![CHIP_ID_REGISTER], vec![0x58])]);
+
+        let result = Bmp3xxDevice::init(i2c.clone(), ADDRESS).await;
+        i2c.done();
+
+        assert_eq!(result.err(), Some(Bmp3xxError::UnknownChip(0x58)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn reconfigures_per_flight_state() {
+        let mut transactions = initialization(BMP390_CHIP_ID);
+        transactions.extend(configuration(Bmp3xxConfig::settings(FlightState::Armed), 0));
+        transactions.extend(configuration(Bmp3xxConfig::settings(FlightState::RecoveryActivated), 0x04));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut bmp3xx = Bmp3xxDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        assert_eq!(bmp3xx.configure(FlightState::Armed).await, Ok(()));
+        assert_eq!(bmp3xx.configure(FlightState::RecoveryActivated).await, Err(Bmp3xxError::Configuration(0x04)));
+        i2c.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn forwards_bus_errors() {
+        let mut transactions = initialization(BMP388_CHIP_ID);
+        transactions.push(I2cTransaction::write_read(ADDRESS, vec![DATA_REGISTER], vec![0; DATA_SIZE]).with_error(ErrorKind::Other));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut bmp3xx = Bmp3xxDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        assert_eq!(bmp3xx.parse_new_data().await, Err(Bmp3xxError::Bus(ErrorKind::Other)));
+        i2c.done();
+    }
+}
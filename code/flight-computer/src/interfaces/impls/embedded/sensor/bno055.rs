@@ -1,15 +1,28 @@
 use core::fmt::Debug;
 
-use bno055::Bno055;
+use bno055::{BNO055Calibration, BNO055OperationMode, Bno055};
 use embassy_time::{Delay, Timer};
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::{I2c, SevenBitAddress};
-use proto::sensor_data::{Vector3, ImuData};
+use proto::sensor_data::{Vector3, ImuData, ImuCalibrationProfile, ImuCalibrationStatus, IMU_CALIBRATION_PROFILE_SIZE};
 use proto::uom::si::{acceleration::meter_per_second_squared, angular_velocity::degree_per_second, magnetic_flux_density::microtesla, thermodynamic_temperature::degree_celsius};
 use proto::sensor_data::{Acceleration, AngularVelocity, MagneticFluxDensity, ThermodynamicTemperature};
+use proto::sensor_health::SensorId;
 
 use crate::config::DataAcquisitionConfig;
 use crate::config::embedded::Bno055Config;
-use crate::interfaces::Sensor;
+use crate::core::storage::load_imu_calibration;
+use crate::interfaces::{FileSystem, ImuSensor, SampleRates, Sensor};
+use crate::log::info;
+use crate::sync::{IMU_CALIBRATION_PROFILE_SIGNAL, IMU_CALIBRATION_STATUS_SIGNAL, finish_imu_calibration, imu_calibration_requested};
+
+/// Leaves the mode switch delays to the caller, who awaits them with a [`Timer`] instead of
+/// blocking the executor.
+struct AwaitedDelay;
+
+impl DelayNs for AwaitedDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
 
 pub struct Bno055Device<I, E>
 where
@@ -25,7 +38,8 @@ where
     I: I2c<SevenBitAddress, Error = E>,
     E: Debug,
 {
-    pub async fn init(mut bno055: Bno055<I>) -> Result<Self, bno055::Error<E>> {
+    /// `profile` is a calibration persisted by a previous guided calibration, see `load_imu_calibration`.
+    pub async fn init(mut bno055: Bno055<I>, profile: Option<ImuCalibrationProfile>) -> Result<Self, bno055::Error<E>> {
         // The sensor has an initial startup time of 400ms - 650ms during which interaction with it will fail
         Timer::at(Bno055Config::STARTUP_DELAY).await;
         let mut delay = Delay;
@@ -41,11 +55,56 @@ where
         // Enable usage of external crystal
         bno055.set_external_crystal(Bno055Config::USE_EXTERNAL_CRYSTAL, &mut delay)?;
 
+        // Restore offsets so the pad calibration only has to refine them
+        if let Some(profile) = profile {
+            bno055.set_calibration_profile(BNO055Calibration::from_buf(&profile.offsets), &mut delay)?;
+            info!("BNO055 IMU: Restored calibration profile");
+        }
+
         Ok(Self {
             bno055,
             _error: core::marker::PhantomData,
         })
     }
+
+    /// Boots the IMU with the calibration persisted on `filesystem`, before the filesystem is
+    /// handed over to the storage task.
+    pub async fn init_with_stored_calibration<FS: FileSystem>(bno055: Bno055<I>, filesystem: &mut FS) -> Result<Self, bno055::Error<E>> {
+        let profile = load_imu_calibration(filesystem).await;
+        if profile.is_none() {
+            info!("BNO055 IMU: No stored calibration profile");
+        }
+        Self::init(bno055, profile).await
+    }
+
+    /// Streams the calibration levels, and reads the profile out once fully calibrated.
+    async fn poll_calibration(&mut self) -> Result<(), bno055::Error<E>> {
+        let status = self.bno055.calibration_status()?;
+        let status = ImuCalibrationStatus {
+            system: status.sys,
+            gyroscope: status.gyr,
+            accelerometer: status.acc,
+            magnetometer: status.mag,
+        };
+        IMU_CALIBRATION_STATUS_SIGNAL.signal(status);
+
+        if status.is_complete() {
+            // the profile is only readable in config mode, entered here so the read does not switch itself
+            self.bno055.set_mode(BNO055OperationMode::CONFIG_MODE, &mut AwaitedDelay)?;
+            Timer::after(Bno055Config::MODE_SWITCH_DELAY).await;
+            let profile = self.bno055.calibration_profile(&mut AwaitedDelay)?;
+            self.bno055.set_mode(Bno055Config::OPERATION_MODE, &mut AwaitedDelay)?;
+            Timer::after(Bno055Config::MODE_SWITCH_DELAY).await;
+            let mut offsets = [0_u8; IMU_CALIBRATION_PROFILE_SIZE];
+            offsets.copy_from_slice(profile.as_bytes());
+
+            IMU_CALIBRATION_PROFILE_SIGNAL.signal(ImuCalibrationProfile { offsets });
+            finish_imu_calibration();
+            info!("BNO055 IMU: Fully calibrated");
+        }
+
+        Ok(())
+    }
 }
 
 impl<I, E> Sensor for Bno055Device<I, E>
@@ -57,9 +116,14 @@ where
     type Error = bno055::Error<E>;
 
     const NAME: &'static str = "BNO055 IMU";
-    const TICK_INTERVAL: embassy_time::Duration = DataAcquisitionConfig::IMU_TICK_INTERVAL;
+    const ID: SensorId = SensorId::Imu;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::IMU_SAMPLE_RATES;
 
     async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        if imu_calibration_requested() {
+            self.poll_calibration().await?;
+        }
+
         let acceleration = self.bno055.accel_data()?;
         let gyro = self.bno055.gyro_data()?;
         let mag = self.bno055.mag_data()?;
@@ -91,3 +155,12 @@ where
         })
     }
 }
+
+impl<I, E> ImuSensor for Bno055Device<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    /// Fixed by the fusion modes, [`Bno055Config::OPERATION_MODE`] among them.
+    const FULL_SCALE: f32 = 4.0;
+}
@@ -2,10 +2,11 @@ use nmea::{Nmea, SentenceType, SENTENCE_MAX_LEN};
 use static_cell::ConstStaticCell;
 use proto::sensor_data::{Altitude, GpsCoordinates, GpsData};
 use proto::uom::si::length::meter;
+use proto::sensor_health::SensorId;
 
 use crate::config::DataAcquisitionConfig;
 use crate::config::embedded::GpsConfig;
-use crate::interfaces::Sensor;
+use crate::interfaces::{SampleRates, Sensor};
 
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(thiserror::Error, Debug, PartialEq, Eq, Clone)]
@@ -62,7 +63,8 @@ where
     type Error = GpsError;
 
     const NAME: &'static str = "GPS";
-    const TICK_INTERVAL: embassy_time::Duration = DataAcquisitionConfig::GPS_TICK_INTERVAL;
+    const ID: SensorId = SensorId::Gps;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::GPS_SAMPLE_RATES;
 
     #[allow(clippy::cast_possible_truncation)]
     async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
@@ -0,0 +1,458 @@
+use core::fmt::Debug;
+
+use embassy_time::Timer;
+use embedded_hal::spi::{Operation, SpiDevice};
+use proto::flight_state::FlightState;
+use proto::sensor_data::{Acceleration, AngularVelocity, ImuData, MagneticFluxDensity, ThermodynamicTemperature, Vector3};
+use proto::uom::si::{
+    acceleration::standard_gravity, angular_velocity::degree_per_second, magnetic_flux_density::microtesla,
+    thermodynamic_temperature::degree_celsius,
+};
+use proto::sensor_health::SensorId;
+
+use crate::config::DataAcquisitionConfig;
+use crate::config::embedded::Icm42688Config;
+use crate::interfaces::{ImuSensor, SampleRates, Sensor};
+
+pub const ICM42688_WHO_AM_I: u8 = 0x47;
+
+const DEVICE_CONFIG_REGISTER: u8 = 0x11;
+const FIFO_CONFIG_REGISTER: u8 = 0x16;
+const FIFO_COUNT_REGISTER: u8 = 0x2E;
+const FIFO_DATA_REGISTER: u8 = 0x30;
+const SIGNAL_PATH_RESET_REGISTER: u8 = 0x4B;
+const POWER_MANAGEMENT_REGISTER: u8 = 0x4E;
+const GYRO_CONFIG_REGISTER: u8 = 0x4F;
+const ACCEL_CONFIG_REGISTER: u8 = 0x50;
+const FIFO_CONFIG1_REGISTER: u8 = 0x5F;
+const WHO_AM_I_REGISTER: u8 = 0x75;
+
+const READ: u8 = 0x80;
+const SOFT_RESET: u8 = 0x01;
+const FIFO_FLUSH: u8 = 0x02;
+const FIFO_STREAM: u8 = 0b0100_0000;
+/// Accelerometer, gyroscope and temperature in each FIFO packet.
+const FIFO_PACKET_CONTENT: u8 = 0b0000_0111;
+/// Accelerometer and gyroscope in low noise mode.
+const POWER_LOW_NOISE: u8 = 0b0000_1111;
+
+/// Header, accelerometer, gyroscope, temperature and timestamp.
+pub const FIFO_PACKET_SIZE: usize = 16;
+const HEADER_CONTENT: u8 = 0b1110_0000;
+/// Not empty, holding both the accelerometer and the gyroscope.
+const HEADER_ACCEL_GYRO: u8 = 0b0110_0000;
+/// Reported by axes that have no sample yet, right after power up.
+const INVALID_SAMPLE: i16 = i16::MIN;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AccelRange {
+    G16 = 0,
+    G8 = 1,
+    G4 = 2,
+    G2 = 3,
+}
+
+impl AccelRange {
+    /// Full scale in g.
+    #[must_use]
+    pub const fn full_scale(self) -> f32 {
+        match self {
+            Self::G16 => 16.0,
+            Self::G8 => 8.0,
+            Self::G4 => 4.0,
+            Self::G2 => 2.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GyroRange {
+    Dps2000 = 0,
+    Dps1000 = 1,
+    Dps500 = 2,
+    Dps250 = 3,
+    Dps125 = 4,
+    Dps62_5 = 5,
+    Dps31_25 = 6,
+    Dps15_625 = 7,
+}
+
+impl GyroRange {
+    /// Full scale in deg/s.
+    #[must_use]
+    pub const fn full_scale(self) -> f32 {
+        match self {
+            Self::Dps2000 => 2000.0,
+            Self::Dps1000 => 1000.0,
+            Self::Dps500 => 500.0,
+            Self::Dps250 => 250.0,
+            Self::Dps125 => 125.0,
+            Self::Dps62_5 => 62.5,
+            Self::Dps31_25 => 31.25,
+            Self::Dps15_625 => 15.625,
+        }
+    }
+}
+
+/// Rate at which both the accelerometer and the gyroscope push packets into the FIFO.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OutputDataRate {
+    Hz8000 = 0x03,
+    Hz4000 = 0x04,
+    Hz2000 = 0x05,
+    Hz1000 = 0x06,
+    Hz500 = 0x0F,
+    Hz200 = 0x07,
+    Hz100 = 0x08,
+    Hz50 = 0x09,
+    Hz25 = 0x0A,
+    Hz12_5 = 0x0B,
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Icm42688Error<E: Debug> {
+    #[error("SPI error")]
+    Bus(E),
+    #[error("Unknown chip ID: {0:#04x}")]
+    UnknownChip(u8),
+    /// No complete packet was queued since the last read, the output data rate is slower than the sample rate.
+    #[error("FIFO empty")]
+    EmptyFifo,
+}
+
+/// TDK ICM-42688-P over SPI, read in FIFO bursts.
+pub struct Icm42688Device<S, E>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    spi: S,
+}
+
+impl<S, E> Icm42688Device<S, E>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    pub async fn init(mut spi: S) -> Result<Self, Icm42688Error<E>> {
+        let [who_am_i] = read(&mut spi, WHO_AM_I_REGISTER)?;
+        if who_am_i != ICM42688_WHO_AM_I {
+            return Err(Icm42688Error::UnknownChip(who_am_i));
+        }
+
+        write(&mut spi, DEVICE_CONFIG_REGISTER, SOFT_RESET)?;
+        Timer::after(Icm42688Config::RESET_DELAY).await;
+
+        let mut icm42688 = Self { spi };
+        icm42688.apply(Icm42688Config::output_data_rate(FlightState::PreArmed))?;
+        write(&mut icm42688.spi, FIFO_CONFIG1_REGISTER, FIFO_PACKET_CONTENT)?;
+        write(&mut icm42688.spi, FIFO_CONFIG_REGISTER, FIFO_STREAM)?;
+        write(&mut icm42688.spi, POWER_MANAGEMENT_REGISTER, POWER_LOW_NOISE)?;
+        Timer::after(Icm42688Config::STARTUP_DELAY).await;
+
+        Ok(icm42688)
+    }
+
+    /// Ranges are fixed, only the output data rate follows the flight state.
+    fn apply(&mut self, output_data_rate: OutputDataRate) -> Result<(), Icm42688Error<E>> {
+        let gyro_config = ((Icm42688Config::GYRO_RANGE as u8) << 5) | output_data_rate as u8;
+        let accel_config = ((Icm42688Config::ACCEL_RANGE as u8) << 5) | output_data_rate as u8;
+
+        write(&mut self.spi, GYRO_CONFIG_REGISTER, gyro_config)?;
+        write(&mut self.spi, ACCEL_CONFIG_REGISTER, accel_config)
+    }
+
+    fn flush(&mut self) -> Result<(), Icm42688Error<E>> {
+        write(&mut self.spi, SIGNAL_PATH_RESET_REGISTER, FIFO_FLUSH)
+    }
+}
+
+fn read<S, E, const N: usize>(spi: &mut S, register: u8) -> Result<[u8; N], Icm42688Error<E>>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    let mut buffer = [0; N];
+    read_into(spi, register, &mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_into<S, E>(spi: &mut S, register: u8, buffer: &mut [u8]) -> Result<(), Icm42688Error<E>>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    spi.transaction(&mut [Operation::Write(&[register | READ]), Operation::Read(buffer)])
+        .map_err(Icm42688Error::Bus)
+}
+
+fn write<S, E>(spi: &mut S, register: u8, value: u8) -> Result<(), Icm42688Error<E>>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    spi.write(&[register, value]).map_err(Icm42688Error::Bus)
+}
+
+/// Sums of the valid packets of a burst, in raw counts.
+#[derive(Default)]
+struct Burst {
+    acceleration: [i32; 3],
+    gyro: [i32; 3],
+    temperature: i32,
+    packets: i32,
+}
+
+impl Burst {
+    fn parse(bytes: &[u8]) -> Self {
+        let mut burst = Self::default();
+        for packet in bytes.chunks_exact(FIFO_PACKET_SIZE) {
+            let axis = |index: usize| i16::from_be_bytes([packet[index], packet[index + 1]]);
+            let acceleration = [axis(1), axis(3), axis(5)];
+            let gyro = [axis(7), axis(9), axis(11)];
+
+            if packet[0] & HEADER_CONTENT != HEADER_ACCEL_GYRO || acceleration.contains(&INVALID_SAMPLE) || gyro.contains(&INVALID_SAMPLE) {
+                continue;
+            }
+            for (sum, value) in burst.acceleration.iter_mut().zip(acceleration) {
+                *sum += i32::from(value);
+            }
+            for (sum, value) in burst.gyro.iter_mut().zip(gyro) {
+                *sum += i32::from(value);
+            }
+            burst.temperature += i32::from(i8::from_be_bytes([packet[13]]));
+            burst.packets += 1;
+        }
+        burst
+    }
+
+    /// Mean of the burst, the FIFO's samples boxcar-filtered down to the sample rate.
+    fn mean(&self) -> ImuData {
+        let mean = |sum: i32| sum as f32 / self.packets as f32;
+        let acceleration = self.acceleration.map(|sum| {
+            Acceleration::new::<standard_gravity>(mean(sum) * Icm42688Config::ACCEL_RANGE.full_scale() / 32_768.0)
+        });
+        let gyro = self.gyro.map(|sum| {
+            AngularVelocity::new::<degree_per_second>(mean(sum) * Icm42688Config::GYRO_RANGE.full_scale() / 32_768.0)
+        });
+
+        ImuData {
+            acceleration: Vector3::from(acceleration),
+            gyro: Vector3::from(gyro),
+            // no magnetometer on this part
+            mag: Vector3::from([MagneticFluxDensity::new::<microtesla>(0.0); 3]),
+            temperature: ThermodynamicTemperature::new::<degree_celsius>(mean(self.temperature) / 2.07 + 25.0),
+        }
+    }
+}
+
+impl<S, E> Sensor for Icm42688Device<S, E>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    type Data = ImuData;
+    type Error = Icm42688Error<E>;
+
+    const NAME: &'static str = "ICM-42688 IMU";
+    const ID: SensorId = SensorId::Imu;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::IMU_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        let queued = usize::from(u16::from_be_bytes(read(&mut self.spi, FIFO_COUNT_REGISTER)?)) / FIFO_PACKET_SIZE;
+        let packets = queued.min(Icm42688Config::FIFO_BURST_PACKETS);
+
+        let mut buffer = [0; FIFO_PACKET_SIZE * Icm42688Config::FIFO_BURST_PACKETS];
+        let bytes = &mut buffer[..packets * FIFO_PACKET_SIZE];
+        if !bytes.is_empty() {
+            read_into(&mut self.spi, FIFO_DATA_REGISTER, bytes)?;
+        }
+        // a backlog only builds up when the task stalled, the next burst starts fresh rather than lagging behind
+        if queued > packets {
+            self.flush()?;
+        }
+
+        let burst = Burst::parse(bytes);
+        if burst.packets == 0 {
+            return Err(Icm42688Error::EmptyFifo);
+        }
+        Ok(burst.mean())
+    }
+
+    async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+        self.apply(Icm42688Config::output_data_rate(state))?;
+        // packets queued at the previous rate would skew the next mean
+        self.flush()
+    }
+}
+
+impl<S, E> ImuSensor for Icm42688Device<S, E>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    const FULL_SCALE: f32 = Icm42688Config::ACCEL_RANGE.full_scale();
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    use proto::uom::si::acceleration::meter_per_second_squared;
+
+    use crate::test_utils::ms;
+    use crate::test_utils::sensor_data::{assert_close, counts};
+
+    use super::*;
+
+    fn read(register: u8, response: Vec<u8>) -> Vec<SpiTransaction<u8>> {
+        vec![
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![register | READ]),
+            SpiTransaction::read_vec(response),
+            SpiTransaction::transaction_end(),
+        ]
+    }
+
+    fn write(register: u8, value: u8) -> Vec<SpiTransaction<u8>> {
+        vec![
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![register, value]),
+            SpiTransaction::transaction_end(),
+        ]
+    }
+
+    fn configuration(output_data_rate: OutputDataRate) -> Vec<SpiTransaction<u8>> {
+        let mut transactions = write(GYRO_CONFIG_REGISTER, ((Icm42688Config::GYRO_RANGE as u8) << 5) | output_data_rate as u8);
+        transactions.extend(write(ACCEL_CONFIG_REGISTER, ((Icm42688Config::ACCEL_RANGE as u8) << 5) | output_data_rate as u8));
+        transactions
+    }
+
+    fn initialization() -> Vec<SpiTransaction<u8>> {
+        let mut transactions = read(WHO_AM_I_REGISTER, vec![ICM42688_WHO_AM_I]);
+        transactions.extend(write(DEVICE_CONFIG_REGISTER, SOFT_RESET));
+        transactions.extend(configuration(Icm42688Config::output_data_rate(FlightState::PreArmed)));
+        transactions.extend(write(FIFO_CONFIG1_REGISTER, FIFO_PACKET_CONTENT));
+        transactions.extend(write(FIFO_CONFIG_REGISTER, FIFO_STREAM));
+        transactions.extend(write(POWER_MANAGEMENT_REGISTER, POWER_LOW_NOISE));
+        transactions
+    }
+
+    fn packet(header: u8, acceleration: [i16; 3], gyro: [i16; 3], temperature: i8) -> Vec<u8> {
+        let mut packet = vec![header];
+        for axis in acceleration.into_iter().chain(gyro) {
+            packet.extend(axis.to_be_bytes());
+        }
+        packet.extend(temperature.to_be_bytes());
+        packet.extend([0x12, 0x34]);
+        packet
+    }
+
+    fn burst(packets: &[Vec<u8>], queued: usize) -> Vec<SpiTransaction<u8>> {
+        let bytes = packets.concat();
+        let mut transactions = read(FIFO_COUNT_REGISTER, u16::try_from(queued * FIFO_PACKET_SIZE).expect("Count should fit").to_be_bytes().to_vec());
+        if !bytes.is_empty() {
+            transactions.extend(read(FIFO_DATA_REGISTER, bytes));
+        }
+        transactions
+    }
+
+    #[rstest::rstest]
+    fn averages_valid_packets() {
+        let g = counts(1.0, Icm42688Config::ACCEL_RANGE.full_scale());
+        let dps = counts(125.0, Icm42688Config::GYRO_RANGE.full_scale());
+        let bytes = [
+            packet(0x68, [0, 0, g], [dps, 0, 0], 0),
+            packet(0x68, [0, g, g], [dps, 0, -dps], 41),
+            // gyro still starting up
+            packet(0x68, [0, 0, g], [INVALID_SAMPLE, 0, 0], 0),
+            // accelerometer only
+            packet(0x48, [g, g, g], [0, 0, 0], 0),
+        ]
+        .concat();
+
+        let data = Burst::parse(&bytes).mean();
+
+        assert_close(data.acceleration.x.get::<standard_gravity>(), 0.0);
+        assert_close(data.acceleration.y.get::<standard_gravity>(), 0.5);
+        assert_close(data.acceleration.z.get::<standard_gravity>(), 1.0);
+        assert_close(data.gyro.x.get::<degree_per_second>(), 125.0);
+        assert_close(data.gyro.z.get::<degree_per_second>(), -62.5);
+        assert_close(data.temperature.get::<degree_celsius>(), 20.5 / 2.07 + 25.0);
+        assert_eq!(data.mag, Vector3::from([MagneticFluxDensity::new::<microtesla>(0.0); 3]));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(200))]
+    async fn reads_fifo_burst() {
+        let g = counts(1.0, Icm42688Config::ACCEL_RANGE.full_scale());
+        let packets = [packet(0x68, [0, 0, g], [0; 3], 0), packet(0x68, [0, 0, g], [0; 3], 0)];
+        let mut transactions = initialization();
+        transactions.extend(burst(&packets, packets.len()));
+        let mut spi = SpiMock::new(&transactions);
+
+        let mut icm42688 = Icm42688Device::init(spi.clone()).await.expect("Initialization should succeed");
+        let data = icm42688.parse_new_data().await.expect("Reading should succeed");
+        spi.done();
+
+        assert_close(data.acceleration.z.get::<meter_per_second_squared>(), 9.806_65);
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(200))]
+    async fn flushes_backlog_beyond_burst() {
+        let packets = vec![packet(0x68, [0; 3], [0; 3], 0); Icm42688Config::FIFO_BURST_PACKETS];
+        let mut transactions = initialization();
+        transactions.extend(burst(&packets, Icm42688Config::FIFO_BURST_PACKETS + 5));
+        transactions.extend(write(SIGNAL_PATH_RESET_REGISTER, FIFO_FLUSH));
+        let mut spi = SpiMock::new(&transactions);
+
+        let mut icm42688 = Icm42688Device::init(spi.clone()).await.expect("Initialization should succeed");
+        assert!(icm42688.parse_new_data().await.is_ok());
+        spi.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(200))]
+    async fn reports_empty_fifo() {
+        let mut transactions = initialization();
+        transactions.extend(burst(&[], 0));
+        let mut spi = SpiMock::new(&transactions);
+
+        let mut icm42688 = Icm42688Device::init(spi.clone()).await.expect("Initialization should succeed");
+        assert_eq!(icm42688.parse_new_data().await, Err(Icm42688Error::EmptyFifo));
+        spi.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(200))]
+    async fn reconfigures_per_flight_state() {
+        let mut transactions = initialization();
+        transactions.extend(configuration(Icm42688Config::output_data_rate(FlightState::Armed)));
+        transactions.extend(write(SIGNAL_PATH_RESET_REGISTER, FIFO_FLUSH));
+        let mut spi = SpiMock::new(&transactions);
+
+        let mut icm42688 = Icm42688Device::init(spi.clone()).await.expect("Initialization should succeed");
+        assert_eq!(icm42688.configure(FlightState::Armed).await, Ok(()));
+        spi.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn rejects_unknown_chip() {
+        let mut spi = SpiMock::new(&read(WHO_AM_I_REGISTER, vec![0x6A]));
+
+        let result = Icm42688Device::init(spi.clone()).await;
+        spi.done();
+
+        assert_eq!(result.err(), Some(Icm42688Error::UnknownChip(0x6A)));
+    }
+}
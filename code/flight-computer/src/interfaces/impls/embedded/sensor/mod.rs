@@ -1,3 +1,15 @@
 pub mod gps;
 pub mod bno055;
-pub mod bmp280;
\ No newline at end of file
+pub mod bmp280;
+#[cfg(feature = "bmp3xx")]
+pub mod bmp3xx;
+#[cfg(feature = "ms5611")]
+pub mod ms5611;
+#[cfg(feature = "icm42688")]
+pub mod icm42688;
+#[cfg(feature = "mpu")]
+pub mod mpu;
+#[cfg(feature = "adxl375")]
+pub mod adxl375;
+#[cfg(feature = "ublox")]
+pub mod ublox;
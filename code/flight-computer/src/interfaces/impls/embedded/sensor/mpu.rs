@@ -0,0 +1,550 @@
+use core::fmt::Debug;
+
+use embassy_time::Timer;
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+use proto::flight_state::FlightState;
+use proto::sensor_data::{Acceleration, AngularVelocity, ImuData, MagneticFluxDensity, ThermodynamicTemperature, Vector3};
+use proto::uom::si::{
+    acceleration::standard_gravity, angular_velocity::degree_per_second, magnetic_flux_density::microtesla,
+    thermodynamic_temperature::degree_celsius,
+};
+use proto::sensor_health::SensorId;
+
+use crate::config::DataAcquisitionConfig;
+use crate::config::embedded::MpuConfig;
+use crate::interfaces::{ImuSensor, SampleRates, Sensor};
+
+pub const MPU6050_WHO_AM_I: u8 = 0x68;
+pub const MPU9250_WHO_AM_I: u8 = 0x71;
+
+const SAMPLE_RATE_DIVIDER_REGISTER: u8 = 0x19;
+const CONFIG_REGISTER: u8 = 0x1A;
+const GYRO_CONFIG_REGISTER: u8 = 0x1B;
+const ACCEL_CONFIG_REGISTER: u8 = 0x1C;
+/// MPU-9250 only, the MPU-6050 filters both sensors from `CONFIG_REGISTER`.
+const ACCEL_CONFIG2_REGISTER: u8 = 0x1D;
+const FIFO_ENABLE_REGISTER: u8 = 0x23;
+const INTERRUPT_PIN_CONFIG_REGISTER: u8 = 0x37;
+const USER_CONTROL_REGISTER: u8 = 0x6A;
+const POWER_MANAGEMENT_REGISTER: u8 = 0x6B;
+const FIFO_COUNT_REGISTER: u8 = 0x72;
+const FIFO_DATA_REGISTER: u8 = 0x74;
+const WHO_AM_I_REGISTER: u8 = 0x75;
+
+const DEVICE_RESET: u8 = 0b1000_0000;
+/// Awake, clocked from the X gyroscope's PLL.
+const CLOCK_PLL: u8 = 0b0000_0001;
+/// Temperature, the three gyroscope axes and the accelerometer into the FIFO.
+const FIFO_CONTENT: u8 = 0b1111_1000;
+/// FIFO enabled, and emptied.
+const FIFO_RESTART: u8 = 0b0100_0100;
+/// Exposes the MPU-9250's magnetometer on the host's I2C bus.
+const BYPASS_ENABLE: u8 = 0b0000_0010;
+
+/// Accelerometer, temperature and gyroscope, in register order.
+pub const FIFO_SAMPLE_SIZE: usize = 14;
+
+/// AKM AK8963 inside the MPU-9250.
+const MAGNETOMETER_ADDRESS: u8 = 0x0C;
+const MAGNETOMETER_ID: u8 = 0x48;
+const MAGNETOMETER_ID_REGISTER: u8 = 0x00;
+const MAGNETOMETER_STATUS_REGISTER: u8 = 0x02;
+const MAGNETOMETER_CONTROL_REGISTER: u8 = 0x0A;
+const MAGNETOMETER_ADJUSTMENT_REGISTER: u8 = 0x10;
+const MAGNETOMETER_POWER_DOWN: u8 = 0x00;
+const MAGNETOMETER_FUSE_ROM: u8 = 0x0F;
+/// 16-bit output, measuring continuously at 100 Hz.
+const MAGNETOMETER_CONTINUOUS: u8 = 0x16;
+const MAGNETOMETER_DATA_READY: u8 = 0b0000_0001;
+const MAGNETOMETER_OVERFLOW: u8 = 0b0000_1000;
+/// Status, the three axes and the second status, which must be read to release the next measurement.
+const MAGNETOMETER_DATA_SIZE: usize = 8;
+/// Resolution of the 16-bit output.
+const MAGNETOMETER_MICROTESLA_PER_COUNT: f32 = 0.15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MpuModel {
+    Mpu6050,
+    /// MPU-6500 die with an AK8963 magnetometer.
+    Mpu9250,
+}
+
+impl MpuModel {
+    const fn from_who_am_i(who_am_i: u8) -> Option<Self> {
+        match who_am_i {
+            MPU6050_WHO_AM_I => Some(Self::Mpu6050),
+            MPU9250_WHO_AM_I => Some(Self::Mpu9250),
+            _ => None,
+        }
+    }
+
+    /// Bytes the FIFO holds before it overwrites, tearing samples apart.
+    const fn fifo_capacity(self) -> usize {
+        match self {
+            Self::Mpu6050 => 1024,
+            Self::Mpu9250 => 512,
+        }
+    }
+
+    fn temperature(self, raw: f32) -> f32 {
+        match self {
+            Self::Mpu6050 => raw / 340.0 + 36.53,
+            Self::Mpu9250 => raw / 333.87 + 21.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AccelRange {
+    G2 = 0,
+    G4 = 1,
+    G8 = 2,
+    G16 = 3,
+}
+
+impl AccelRange {
+    /// Full scale in g.
+    #[must_use]
+    pub const fn full_scale(self) -> f32 {
+        match self {
+            Self::G2 => 2.0,
+            Self::G4 => 4.0,
+            Self::G8 => 8.0,
+            Self::G16 => 16.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GyroRange {
+    Dps250 = 0,
+    Dps500 = 1,
+    Dps1000 = 2,
+    Dps2000 = 3,
+}
+
+impl GyroRange {
+    /// Full scale in deg/s.
+    #[must_use]
+    pub const fn full_scale(self) -> f32 {
+        match self {
+            Self::Dps250 => 250.0,
+            Self::Dps500 => 500.0,
+            Self::Dps1000 => 1000.0,
+            Self::Dps2000 => 2000.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum MpuError<E: Debug> {
+    #[error("I2C error")]
+    Bus(E),
+    #[error("Unknown chip ID: {0:#04x}")]
+    UnknownChip(u8),
+    #[error("Unknown magnetometer ID: {0:#04x}")]
+    UnknownMagnetometer(u8),
+    /// No complete sample was queued since the last read, the sample rate divider is too high.
+    #[error("FIFO empty")]
+    EmptyFifo,
+    /// The FIFO filled up and overwrote samples, it was restarted.
+    #[error("FIFO overflow")]
+    FifoOverflow,
+}
+
+/// Last field measured by the MPU-9250's magnetometer, which runs slower than the FIFO.
+struct Magnetometer {
+    /// Factory sensitivity adjustment of each axis.
+    adjustment: [f32; 3],
+    field: Vector3<MagneticFluxDensity>,
+}
+
+/// TDK MPU-6050 or MPU-9250 over I2C, read in FIFO bursts.
+pub struct MpuDevice<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    i2c: I,
+    address: u8,
+    model: MpuModel,
+    magnetometer: Option<Magnetometer>,
+}
+
+impl<I, E> MpuDevice<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    pub async fn init(mut i2c: I, address: u8) -> Result<Self, MpuError<E>> {
+        let [who_am_i] = read(&mut i2c, address, WHO_AM_I_REGISTER)?;
+        let model = MpuModel::from_who_am_i(who_am_i).ok_or(MpuError::UnknownChip(who_am_i))?;
+
+        write(&mut i2c, address, POWER_MANAGEMENT_REGISTER, DEVICE_RESET)?;
+        Timer::after(MpuConfig::RESET_DELAY).await;
+        write(&mut i2c, address, POWER_MANAGEMENT_REGISTER, CLOCK_PLL)?;
+
+        write(&mut i2c, address, CONFIG_REGISTER, MpuConfig::DIGITAL_LOW_PASS_FILTER)?;
+        if model == MpuModel::Mpu9250 {
+            write(&mut i2c, address, ACCEL_CONFIG2_REGISTER, MpuConfig::DIGITAL_LOW_PASS_FILTER)?;
+        }
+        write(&mut i2c, address, GYRO_CONFIG_REGISTER, (MpuConfig::GYRO_RANGE as u8) << 3)?;
+        write(&mut i2c, address, ACCEL_CONFIG_REGISTER, (MpuConfig::ACCEL_RANGE as u8) << 3)?;
+        write(&mut i2c, address, SAMPLE_RATE_DIVIDER_REGISTER, MpuConfig::sample_rate_divider(FlightState::PreArmed))?;
+
+        let magnetometer = match model {
+            MpuModel::Mpu6050 => None,
+            MpuModel::Mpu9250 => Some(init_magnetometer(&mut i2c, address).await?),
+        };
+
+        write(&mut i2c, address, FIFO_ENABLE_REGISTER, FIFO_CONTENT)?;
+        write(&mut i2c, address, USER_CONTROL_REGISTER, FIFO_RESTART)?;
+
+        Ok(Self { i2c, address, model, magnetometer })
+    }
+
+    fn restart_fifo(&mut self) -> Result<(), MpuError<E>> {
+        write(&mut self.i2c, self.address, USER_CONTROL_REGISTER, FIFO_RESTART)
+    }
+
+    /// Keeps the last field until the magnetometer has a new, unsaturated measurement.
+    fn read_magnetometer(&mut self) -> Result<Vector3<MagneticFluxDensity>, MpuError<E>> {
+        let Some(magnetometer) = &mut self.magnetometer else {
+            return Ok(Vector3::from([MagneticFluxDensity::new::<microtesla>(0.0); 3]));
+        };
+
+        let data: [u8; MAGNETOMETER_DATA_SIZE] = read(&mut self.i2c, MAGNETOMETER_ADDRESS, MAGNETOMETER_STATUS_REGISTER)?;
+        if data[0] & MAGNETOMETER_DATA_READY != 0 && data[7] & MAGNETOMETER_OVERFLOW == 0 {
+            let axis = |index: usize| {
+                let raw = f32::from(i16::from_le_bytes([data[1 + 2 * index], data[2 + 2 * index]]));
+                MagneticFluxDensity::new::<microtesla>(raw * magnetometer.adjustment[index] * MAGNETOMETER_MICROTESLA_PER_COUNT)
+            };
+            // the magnetometer's x and y are swapped and its z is flipped relative to the accelerometer
+            magnetometer.field = Vector3::new(axis(1), axis(0), -axis(2));
+        }
+        Ok(magnetometer.field)
+    }
+}
+
+async fn init_magnetometer<I, E>(i2c: &mut I, address: u8) -> Result<Magnetometer, MpuError<E>>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    write(i2c, address, INTERRUPT_PIN_CONFIG_REGISTER, BYPASS_ENABLE)?;
+
+    let [id] = read(i2c, MAGNETOMETER_ADDRESS, MAGNETOMETER_ID_REGISTER)?;
+    if id != MAGNETOMETER_ID {
+        return Err(MpuError::UnknownMagnetometer(id));
+    }
+
+    // the sensitivity adjustment is only readable in fuse ROM mode, entered from power down
+    for mode in [MAGNETOMETER_POWER_DOWN, MAGNETOMETER_FUSE_ROM] {
+        write(i2c, MAGNETOMETER_ADDRESS, MAGNETOMETER_CONTROL_REGISTER, mode)?;
+        Timer::after(MpuConfig::MAGNETOMETER_MODE_DELAY).await;
+    }
+    let adjustment: [u8; 3] = read(i2c, MAGNETOMETER_ADDRESS, MAGNETOMETER_ADJUSTMENT_REGISTER)?;
+    for mode in [MAGNETOMETER_POWER_DOWN, MAGNETOMETER_CONTINUOUS] {
+        write(i2c, MAGNETOMETER_ADDRESS, MAGNETOMETER_CONTROL_REGISTER, mode)?;
+        Timer::after(MpuConfig::MAGNETOMETER_MODE_DELAY).await;
+    }
+
+    Ok(Magnetometer {
+        adjustment: adjustment.map(|value| (f32::from(value) - 128.0) / 256.0 + 1.0),
+        field: Vector3::from([MagneticFluxDensity::new::<microtesla>(0.0); 3]),
+    })
+}
+
+fn read<I, E, const N: usize>(i2c: &mut I, address: u8, register: u8) -> Result<[u8; N], MpuError<E>>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    let mut buffer = [0; N];
+    i2c.write_read(address, &[register], &mut buffer).map_err(MpuError::Bus)?;
+    Ok(buffer)
+}
+
+fn write<I, E>(i2c: &mut I, address: u8, register: u8, value: u8) -> Result<(), MpuError<E>>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    i2c.write(address, &[register, value]).map_err(MpuError::Bus)
+}
+
+/// Mean of a burst of FIFO samples, the FIFO boxcar-filtered down to the sample rate.
+fn mean(bytes: &[u8]) -> ([f32; 3], f32, [f32; 3]) {
+    let mut sums = [0_i32; 7];
+    let samples = bytes.chunks_exact(FIFO_SAMPLE_SIZE);
+    let count = samples.len() as f32;
+    for sample in samples {
+        for (sum, value) in sums.iter_mut().zip(sample.chunks_exact(2)) {
+            *sum += i32::from(i16::from_be_bytes([value[0], value[1]]));
+        }
+    }
+
+    let means = sums.map(|sum| sum as f32 / count);
+    ([means[0], means[1], means[2]], means[3], [means[4], means[5], means[6]])
+}
+
+impl<I, E> Sensor for MpuDevice<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    type Data = ImuData;
+    type Error = MpuError<E>;
+
+    const NAME: &'static str = "MPU IMU";
+    const ID: SensorId = SensorId::Imu;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::IMU_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        let queued = usize::from(u16::from_be_bytes(read(&mut self.i2c, self.address, FIFO_COUNT_REGISTER)?));
+        if queued >= self.model.fifo_capacity() {
+            self.restart_fifo()?;
+            return Err(MpuError::FifoOverflow);
+        }
+
+        let samples = (queued / FIFO_SAMPLE_SIZE).min(MpuConfig::FIFO_BURST_SAMPLES);
+        if samples == 0 {
+            return Err(MpuError::EmptyFifo);
+        }
+        let mut buffer = [0; FIFO_SAMPLE_SIZE * MpuConfig::FIFO_BURST_SAMPLES];
+        let bytes = &mut buffer[..samples * FIFO_SAMPLE_SIZE];
+        self.i2c.write_read(self.address, &[FIFO_DATA_REGISTER], bytes).map_err(MpuError::Bus)?;
+        // a backlog only builds up when the task stalled, the next burst starts fresh rather than lagging behind
+        if queued / FIFO_SAMPLE_SIZE > samples {
+            self.restart_fifo()?;
+        }
+
+        let (acceleration, temperature, gyro) = mean(bytes);
+        let acceleration = acceleration
+            .map(|raw| Acceleration::new::<standard_gravity>(raw * MpuConfig::ACCEL_RANGE.full_scale() / 32_768.0));
+        let gyro = gyro.map(|raw| AngularVelocity::new::<degree_per_second>(raw * MpuConfig::GYRO_RANGE.full_scale() / 32_768.0));
+
+        Ok(ImuData {
+            acceleration: Vector3::from(acceleration),
+            gyro: Vector3::from(gyro),
+            mag: self.read_magnetometer()?,
+            temperature: ThermodynamicTemperature::new::<degree_celsius>(self.model.temperature(temperature)),
+        })
+    }
+
+    async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+        write(&mut self.i2c, self.address, SAMPLE_RATE_DIVIDER_REGISTER, MpuConfig::sample_rate_divider(state))?;
+        // samples queued at the previous rate would skew the next mean
+        self.restart_fifo()
+    }
+}
+
+impl<I, E> ImuSensor for MpuDevice<I, E>
+where
+    I: I2c<SevenBitAddress, Error = E>,
+    E: Debug,
+{
+    const FULL_SCALE: f32 = MpuConfig::ACCEL_RANGE.full_scale();
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    use crate::test_utils::ms;
+    use crate::test_utils::sensor_data::{assert_close, counts};
+
+    use super::*;
+
+    const ADDRESS: u8 = 0x68;
+    /// Scales the x axis up by almost half, the y axis down by half and leaves the z axis.
+    const ADJUSTMENT: [u8; 3] = [0xFF, 0x00, 0x80];
+
+    fn write(register: u8, value: u8) -> I2cTransaction {
+        I2cTransaction::write(ADDRESS, vec![register, value])
+    }
+
+    fn write_magnetometer(value: u8) -> I2cTransaction {
+        I2cTransaction::write(MAGNETOMETER_ADDRESS, vec![MAGNETOMETER_CONTROL_REGISTER, value])
+    }
+
+    fn initialization(who_am_i: u8) -> Vec<I2cTransaction> {
+        let mut transactions = vec![
+            I2cTransaction::write_read(ADDRESS, vec![WHO_AM_I_REGISTER], vec![who_am_i]),
+            write(POWER_MANAGEMENT_REGISTER, DEVICE_RESET),
+            write(POWER_MANAGEMENT_REGISTER, CLOCK_PLL),
+            write(CONFIG_REGISTER, MpuConfig::DIGITAL_LOW_PASS_FILTER),
+        ];
+        if who_am_i == MPU9250_WHO_AM_I {
+            transactions.push(write(ACCEL_CONFIG2_REGISTER, MpuConfig::DIGITAL_LOW_PASS_FILTER));
+        }
+        transactions.extend([
+            write(GYRO_CONFIG_REGISTER, (MpuConfig::GYRO_RANGE as u8) << 3),
+            write(ACCEL_CONFIG_REGISTER, (MpuConfig::ACCEL_RANGE as u8) << 3),
+            write(SAMPLE_RATE_DIVIDER_REGISTER, MpuConfig::sample_rate_divider(FlightState::PreArmed)),
+        ]);
+        if who_am_i == MPU9250_WHO_AM_I {
+            transactions.extend([
+                write(INTERRUPT_PIN_CONFIG_REGISTER, BYPASS_ENABLE),
+                I2cTransaction::write_read(MAGNETOMETER_ADDRESS, vec![MAGNETOMETER_ID_REGISTER], vec![MAGNETOMETER_ID]),
+                write_magnetometer(MAGNETOMETER_POWER_DOWN),
+                write_magnetometer(MAGNETOMETER_FUSE_ROM),
+                I2cTransaction::write_read(MAGNETOMETER_ADDRESS, vec![MAGNETOMETER_ADJUSTMENT_REGISTER], ADJUSTMENT.to_vec()),
+                write_magnetometer(MAGNETOMETER_POWER_DOWN),
+                write_magnetometer(MAGNETOMETER_CONTINUOUS),
+            ]);
+        }
+        transactions.extend([write(FIFO_ENABLE_REGISTER, FIFO_CONTENT), write(USER_CONTROL_REGISTER, FIFO_RESTART)]);
+        transactions
+    }
+
+    fn sample(acceleration: [i16; 3], temperature: i16, gyro: [i16; 3]) -> Vec<u8> {
+        acceleration.into_iter().chain([temperature]).chain(gyro).flat_map(i16::to_be_bytes).collect()
+    }
+
+    fn fifo_count(bytes: usize) -> I2cTransaction {
+        let count = u16::try_from(bytes).expect("Count should fit");
+        I2cTransaction::write_read(ADDRESS, vec![FIFO_COUNT_REGISTER], count.to_be_bytes().to_vec())
+    }
+
+    fn burst(samples: &[Vec<u8>]) -> Vec<I2cTransaction> {
+        let bytes = samples.concat();
+        vec![fifo_count(bytes.len()), I2cTransaction::write_read(ADDRESS, vec![FIFO_DATA_REGISTER], bytes)]
+    }
+
+    fn magnetometer(status: u8, field: [i16; 3], overflow: bool) -> I2cTransaction {
+        let mut data = vec![status];
+        data.extend(field.into_iter().flat_map(i16::to_le_bytes));
+        data.push(if overflow { MAGNETOMETER_OVERFLOW } else { 0 });
+        I2cTransaction::write_read(MAGNETOMETER_ADDRESS, vec![MAGNETOMETER_STATUS_REGISTER], data)
+    }
+
+    #[rstest::rstest]
+    fn averages_burst() {
+        let g = counts(1.0, MpuConfig::ACCEL_RANGE.full_scale());
+        let dps = counts(125.0, MpuConfig::GYRO_RANGE.full_scale());
+        let bytes = [sample([0, 0, g], 340, [dps, 0, 0]), sample([0, g, g], -340, [dps, 0, -dps])].concat();
+
+        let (acceleration, temperature, gyro) = mean(&bytes);
+
+        for (measured, expected) in acceleration.into_iter().zip([0.0, f32::from(g) / 2.0, f32::from(g)]) {
+            assert_close(measured, expected);
+        }
+        assert_close(temperature, 0.0);
+        for (measured, expected) in gyro.into_iter().zip([f32::from(dps), 0.0, -f32::from(dps) / 2.0]) {
+            assert_close(measured, expected);
+        }
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(500))]
+    async fn reads_mpu6050_fifo_burst() {
+        let g = counts(1.0, MpuConfig::ACCEL_RANGE.full_scale());
+        let dps = counts(125.0, MpuConfig::GYRO_RANGE.full_scale());
+        let mut transactions = initialization(MPU6050_WHO_AM_I);
+        transactions.extend(burst(&vec![sample([0, 0, g], 340, [0, dps, 0]); 4]));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut mpu = MpuDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        let data = mpu.parse_new_data().await.expect("Reading should succeed");
+        i2c.done();
+
+        assert_close(data.acceleration.z.get::<standard_gravity>(), 1.0);
+        assert_close(data.gyro.y.get::<degree_per_second>(), 125.0);
+        assert_close(data.temperature.get::<degree_celsius>(), 37.53);
+        assert_eq!(data.mag, Vector3::from([MagneticFluxDensity::new::<microtesla>(0.0); 3]));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(500))]
+    async fn reads_mpu9250_magnetometer() {
+        let mut transactions = initialization(MPU9250_WHO_AM_I);
+        transactions.extend(burst(&[sample([0; 3], 0, [0; 3])]));
+        transactions.push(magnetometer(MAGNETOMETER_DATA_READY, [100, 200, 300], false));
+        // saturated, the previous field is kept
+        transactions.extend(burst(&[sample([0; 3], 0, [0; 3])]));
+        transactions.push(magnetometer(MAGNETOMETER_DATA_READY, [i16::MAX; 3], true));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut mpu = MpuDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        let first = mpu.parse_new_data().await.expect("Reading should succeed");
+        let second = mpu.parse_new_data().await.expect("Reading should succeed");
+        i2c.done();
+
+        assert_close(first.mag.x.get::<microtesla>(), 200.0 * 0.5 * 0.15);
+        assert_close(first.mag.y.get::<microtesla>(), 100.0 * 1.496_09 * 0.15);
+        assert_close(first.mag.z.get::<microtesla>(), -300.0 * 0.15);
+        assert_close(first.temperature.get::<degree_celsius>(), 21.0);
+        assert_eq!(second.mag, first.mag);
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(500))]
+    async fn restarts_fifo_on_backlog() {
+        let samples = vec![sample([0; 3], 0, [0; 3]); MpuConfig::FIFO_BURST_SAMPLES];
+        let mut transactions = initialization(MPU6050_WHO_AM_I);
+        transactions.extend([
+            fifo_count((MpuConfig::FIFO_BURST_SAMPLES + 2) * FIFO_SAMPLE_SIZE),
+            I2cTransaction::write_read(ADDRESS, vec![FIFO_DATA_REGISTER], samples.concat()),
+            write(USER_CONTROL_REGISTER, FIFO_RESTART),
+            fifo_count(MpuModel::Mpu6050.fifo_capacity()),
+            write(USER_CONTROL_REGISTER, FIFO_RESTART),
+            fifo_count(FIFO_SAMPLE_SIZE - 1),
+        ]);
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut mpu = MpuDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        assert!(mpu.parse_new_data().await.is_ok());
+        assert_eq!(mpu.parse_new_data().await, Err(MpuError::FifoOverflow));
+        assert_eq!(mpu.parse_new_data().await, Err(MpuError::EmptyFifo));
+        i2c.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(500))]
+    async fn reconfigures_per_flight_state() {
+        let mut transactions = initialization(MPU6050_WHO_AM_I);
+        transactions.extend([
+            write(SAMPLE_RATE_DIVIDER_REGISTER, MpuConfig::sample_rate_divider(FlightState::Armed)),
+            write(USER_CONTROL_REGISTER, FIFO_RESTART),
+        ]);
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut mpu = MpuDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        assert_eq!(mpu.configure(FlightState::Armed).await, Ok(()));
+        i2c.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn rejects_unknown_chip() {
+        let mut i2c = I2cMock::new(&[I2cTransaction::write_read(ADDRESS, vec![WHO_AM_I_REGISTER], vec![0x70])]);
+
+        let result = MpuDevice::init(i2c.clone(), ADDRESS).await;
+        i2c.done();
+
+        assert_eq!(result.err(), Some(MpuError::UnknownChip(0x70)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(500))]
+    async fn forwards_bus_errors() {
+        let mut transactions = initialization(MPU6050_WHO_AM_I);
+        transactions.push(fifo_count(0).with_error(ErrorKind::Other));
+        let mut i2c = I2cMock::new(&transactions);
+
+        let mut mpu = MpuDevice::init(i2c.clone(), ADDRESS).await.expect("Initialization should succeed");
+        assert_eq!(mpu.parse_new_data().await, Err(MpuError::Bus(ErrorKind::Other)));
+        i2c.done();
+    }
+}
@@ -0,0 +1,338 @@
+use core::fmt::Debug;
+
+use embassy_time::{Duration, Timer};
+use embedded_hal::spi::{Operation, SpiDevice};
+use proto::flight_state::FlightState;
+use proto::sensor_data::{AltimeterData, Pressure, ThermodynamicTemperature};
+use proto::uom::si::{pressure::pascal, thermodynamic_temperature::degree_celsius};
+use proto::sensor_health::SensorId;
+
+use crate::config::DataAcquisitionConfig;
+use crate::config::embedded::Ms5611Config;
+use crate::{interfaces::{SampleRates, Sensor}, core::sensors::altimeter::pressure_altitude};
+
+const RESET: u8 = 0x1E;
+const CONVERT_PRESSURE: u8 = 0x40;
+const CONVERT_TEMPERATURE: u8 = 0x50;
+const READ_ADC: u8 = 0x00;
+const READ_PROM: u8 = 0xA0;
+
+const PROM_WORDS: usize = 8;
+
+/// Conversion resolution, every step doubles the conversion time and halves the noise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Oversampling {
+    Osr256 = 0,
+    Osr512 = 1,
+    Osr1024 = 2,
+    Osr2048 = 3,
+    Osr4096 = 4,
+}
+
+impl Oversampling {
+    /// Longest conversion time from the datasheet.
+    #[must_use]
+    pub const fn conversion_time(self) -> Duration {
+        Duration::from_micros(match self {
+            Self::Osr256 => 600,
+            Self::Osr512 => 1_170,
+            Self::Osr1024 => 2_280,
+            Self::Osr2048 => 4_540,
+            Self::Osr4096 => 9_040,
+        })
+    }
+
+    const fn command_offset(self) -> u8 {
+        (self as u8) << 1
+    }
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Ms5611Error<E: Debug> {
+    #[error("SPI error")]
+    Bus(E),
+    /// All zeros or all ones, nothing answers on the bus.
+    #[error("PROM is blank")]
+    BlankProm,
+    #[error("PROM CRC mismatch: stored {stored:#x}, computed {computed:#x}")]
+    Crc { stored: u8, computed: u8 },
+}
+
+/// CRC-4 over the PROM, its last word's lowest nibble excluded, as in application note AN520.
+fn crc4(prom: &[u16; PROM_WORDS]) -> u8 {
+    let mut remainder: u16 = 0;
+    for index in 0..2 * PROM_WORDS {
+        let word = if index / 2 == PROM_WORDS - 1 { prom[index / 2] & 0xFF00 } else { prom[index / 2] };
+        remainder ^= if index % 2 == 1 { word & 0x00FF } else { word >> 8 };
+        for _ in 0..8 {
+            remainder = if remainder & 0x8000 == 0 { remainder << 1 } else { (remainder << 1) ^ 0x3000 };
+        }
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let crc = (remainder >> 12) as u8;
+    crc
+}
+
+/// Factory calibration coefficients C1 to C6.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Calibration([i64; 6]);
+
+impl Calibration {
+    fn parse<E: Debug>(prom: &[u16; PROM_WORDS]) -> Result<Self, Ms5611Error<E>> {
+        if prom.iter().all(|word| *word == 0) || prom.iter().all(|word| *word == 0xFFFF) {
+            return Err(Ms5611Error::BlankProm);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let stored = (prom[PROM_WORDS - 1] & 0x000F) as u8;
+        let computed = crc4(prom);
+        if stored != computed {
+            return Err(Ms5611Error::Crc { stored, computed });
+        }
+
+        let mut coefficients = [0; 6];
+        for (coefficient, word) in coefficients.iter_mut().zip(&prom[1..7]) {
+            *coefficient = i64::from(*word);
+        }
+        Ok(Self(coefficients))
+    }
+
+    /// Temperature in hundredths of Celsius degrees and pressure in Pascal, second order
+    /// compensated below 20 °C.
+    fn compensate(&self, raw_pressure: u32, raw_temperature: u32) -> (i64, i64) {
+        let [c1, c2, c3, c4, c5, c6] = self.0;
+        let (d1, d2) = (i64::from(raw_pressure), i64::from(raw_temperature));
+
+        let dt = d2 - (c5 << 8);
+        let mut temperature = 2000 + ((dt * c6) >> 23);
+        let mut offset = (c2 << 16) + ((c4 * dt) >> 7);
+        let mut sensitivity = (c1 << 15) + ((c3 * dt) >> 8);
+
+        if temperature < 2000 {
+            let low = (temperature - 2000) * (temperature - 2000);
+            let mut offset2 = 5 * low / 2;
+            let mut sensitivity2 = 5 * low / 4;
+            if temperature < -1500 {
+                let very_low = (temperature + 1500) * (temperature + 1500);
+                offset2 += 7 * very_low;
+                sensitivity2 += 11 * very_low / 2;
+            }
+            temperature -= (dt * dt) >> 31;
+            offset -= offset2;
+            sensitivity -= sensitivity2;
+        }
+
+        (temperature, (((d1 * sensitivity) >> 21) - offset) >> 15)
+    }
+}
+
+/// TE Connectivity MS5611 over SPI.
+pub struct Ms5611Device<S, E>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    spi: S,
+    calibration: Calibration,
+    pressure_oversampling: Oversampling,
+}
+
+impl<S, E> Ms5611Device<S, E>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    pub async fn init(mut spi: S) -> Result<Self, Ms5611Error<E>> {
+        spi.write(&[RESET]).map_err(Ms5611Error::Bus)?;
+        Timer::after(Ms5611Config::RESET_DELAY).await;
+
+        let mut prom = [0; PROM_WORDS];
+        for (address, word) in (0..).step_by(2).zip(prom.iter_mut()) {
+            let mut buffer = [0; 2];
+            spi.transaction(&mut [Operation::Write(&[READ_PROM + address]), Operation::Read(&mut buffer)])
+                .map_err(Ms5611Error::Bus)?;
+            *word = u16::from_be_bytes(buffer);
+        }
+
+        Ok(Self {
+            spi,
+            calibration: Calibration::parse(&prom)?,
+            pressure_oversampling: Ms5611Config::pressure_oversampling(FlightState::PreArmed),
+        })
+    }
+
+    async fn convert(&mut self, command: u8, oversampling: Oversampling) -> Result<u32, Ms5611Error<E>> {
+        self.spi.write(&[command + oversampling.command_offset()]).map_err(Ms5611Error::Bus)?;
+        Timer::after(oversampling.conversion_time()).await;
+
+        let mut buffer = [0; 3];
+        self.spi.transaction(&mut [Operation::Write(&[READ_ADC]), Operation::Read(&mut buffer)])
+            .map_err(Ms5611Error::Bus)?;
+        Ok(u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]))
+    }
+}
+
+impl<S, E> Sensor for Ms5611Device<S, E>
+where
+    S: SpiDevice<Error = E>,
+    E: Debug,
+{
+    type Data = AltimeterData;
+    type Error = Ms5611Error<E>;
+
+    const NAME: &'static str = "MS5611 Altimeter";
+    const ID: SensorId = SensorId::Altimeter;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::ALTIMETER_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        let raw_pressure = self.convert(CONVERT_PRESSURE, self.pressure_oversampling).await?;
+        let raw_temperature = self.convert(CONVERT_TEMPERATURE, Ms5611Config::TEMPERATURE_OVERSAMPLING).await?;
+
+        let (temperature, pressure) = self.calibration.compensate(raw_pressure, raw_temperature);
+        let pressure = Pressure::new::<pascal>(pressure as f32);
+
+        Ok(AltimeterData {
+            altitude: pressure_altitude(pressure),
+            pressure,
+            temperature: ThermodynamicTemperature::new::<degree_celsius>(temperature as f32 / 100.0),
+        })
+    }
+
+    async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+        self.pressure_oversampling = Ms5611Config::pressure_oversampling(state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::spi::ErrorKind;
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    use proto::uom::si::thermodynamic_temperature::kelvin;
+
+    use crate::test_utils::ms;
+
+    use super::*;
+
+    /// Coefficients of the datasheet's example, behind a factory word, with their CRC.
+    const PROM: [u16; PROM_WORDS] = [0x0001, 40_127, 36_924, 23_317, 23_282, 33_464, 28_312, 0x0008];
+
+    fn read(command: u8, response: Vec<u8>) -> Vec<SpiTransaction<u8>> {
+        vec![
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![command]),
+            SpiTransaction::read_vec(response),
+            SpiTransaction::transaction_end(),
+        ]
+    }
+
+    fn write(command: u8) -> Vec<SpiTransaction<u8>> {
+        vec![
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![command]),
+            SpiTransaction::transaction_end(),
+        ]
+    }
+
+    fn initialization(prom: [u16; PROM_WORDS]) -> Vec<SpiTransaction<u8>> {
+        let mut transactions = write(RESET);
+        for (address, word) in (0..).step_by(2).zip(prom) {
+            transactions.extend(read(READ_PROM + address, word.to_be_bytes().to_vec()));
+        }
+        transactions
+    }
+
+    fn conversion(command: u8, oversampling: Oversampling, raw: u32) -> Vec<SpiTransaction<u8>> {
+        let mut transactions = write(command + oversampling.command_offset());
+        transactions.extend(read(READ_ADC, raw.to_be_bytes()[1..].to_vec()));
+        transactions
+    }
+
+    #[rstest::rstest]
+    #[case::datasheet(PROM, 0x8)]
+    #[case::factory_word(
+        [0x0000, 40_127, 36_924, 23_317, 23_282, 33_464, 28_312, 0x0000], 0x0
+    )]
+    fn crc_matches_application_note(#[case] prom: [u16; PROM_WORDS], #[case] crc: u8) {
+        assert_eq!(crc4(&prom), crc);
+    }
+
+    #[rstest::rstest]
+    fn compensates_datasheet_example() {
+        let calibration = Calibration::parse::<ErrorKind>(&PROM).expect("PROM should be valid");
+
+        assert_eq!(calibration.compensate(9_085_466, 8_569_150), (2007, 100_009));
+    }
+
+    #[rstest::rstest]
+    fn compensates_second_order_below_20_degrees() {
+        let calibration = Calibration::parse::<ErrorKind>(&PROM).expect("PROM should be valid");
+        let (first_order_temperature, _) = calibration.compensate(9_085_466, 8_569_150);
+
+        // 8 569 150 - 300 000 reads about 9 °C colder
+        let (temperature, pressure) = calibration.compensate(9_085_466, 8_269_150);
+
+        assert!(temperature < 2000 && temperature < first_order_temperature - 800, "Temperature: {temperature}");
+        assert!((90_000..110_000).contains(&pressure), "Pressure: {pressure}");
+    }
+
+    #[rstest::rstest]
+    #[case::crc(
+        [0x0001, 40_127, 36_924, 23_317, 23_282, 33_464, 28_313, 0x0008],
+        Ms5611Error::Crc { stored: 0x8, computed: 0x7 }
+    )]
+    #[case::zeros([0x0000; PROM_WORDS], Ms5611Error::BlankProm)]
+    #[case::ones([0xFFFF; PROM_WORDS], Ms5611Error::BlankProm)]
+    fn rejects_invalid_prom(#[case] prom: [u16; PROM_WORDS], #[case] error: Ms5611Error<ErrorKind>) {
+        assert_eq!(Calibration::parse::<ErrorKind>(&prom), Err(error));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn reads_prom_and_converts() {
+        let pressure_oversampling = Ms5611Config::pressure_oversampling(FlightState::PreArmed);
+        let mut transactions = initialization(PROM);
+        transactions.extend(conversion(CONVERT_PRESSURE, pressure_oversampling, 9_085_466));
+        transactions.extend(conversion(CONVERT_TEMPERATURE, Ms5611Config::TEMPERATURE_OVERSAMPLING, 8_569_150));
+        let mut spi = SpiMock::new(&transactions);
+
+        let mut ms5611 = Ms5611Device::init(spi.clone()).await.expect("Initialization should succeed");
+        let data = ms5611.parse_new_data().await.expect("Reading should succeed");
+        spi.done();
+
+        assert!((data.pressure.get::<pascal>() - 100_009.0).abs() < f32::EPSILON * 100_009.0, "Pressure: {:?}", data.pressure);
+        assert!((data.temperature.get::<kelvin>() - 293.22).abs() < 0.001, "Temperature: {:?}", data.temperature);
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn oversamples_per_flight_state() {
+        let pressure_oversampling = Ms5611Config::pressure_oversampling(FlightState::Armed);
+        let mut transactions = initialization(PROM);
+        transactions.extend(conversion(CONVERT_PRESSURE, pressure_oversampling, 9_085_466));
+        transactions.extend(conversion(CONVERT_TEMPERATURE, Ms5611Config::TEMPERATURE_OVERSAMPLING, 8_569_150));
+        let mut spi = SpiMock::new(&transactions);
+
+        let mut ms5611 = Ms5611Device::init(spi.clone()).await.expect("Initialization should succeed");
+        assert_eq!(ms5611.configure(FlightState::Armed).await, Ok(()));
+        ms5611.parse_new_data().await.expect("Reading should succeed");
+        spi.done();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn init_fails_on_corrupt_prom() {
+        let mut prom = PROM;
+        prom[3] ^= 0x0100;
+        let mut spi = SpiMock::new(&initialization(prom));
+
+        let result = Ms5611Device::init(spi.clone()).await;
+        spi.done();
+
+        assert!(matches!(result, Err(Ms5611Error::Crc { stored: 0x8, .. })));
+    }
+}
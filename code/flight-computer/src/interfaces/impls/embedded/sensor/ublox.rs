@@ -0,0 +1,731 @@
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use embassy_time::{Duration, with_timeout};
+use nmea::sentences::FixType;
+use proto::flight_state::FlightState;
+use proto::sensor_data::{Altitude, GpsCoordinates, GpsData, Length, Vector3, Velocity};
+use proto::uom::si::length::millimeter;
+use proto::uom::si::velocity::millimeter_per_second;
+use proto::sensor_health::SensorId;
+
+use crate::config::DataAcquisitionConfig;
+use crate::config::embedded::UbloxConfig;
+use crate::interfaces::{SampleRates, Sensor};
+
+const SYNC: [u8; 2] = [0xB5, 0x62];
+/// Sync characters, class, id and little-endian payload length.
+const HEADER_SIZE: usize = 6;
+const CHECKSUM_SIZE: usize = 2;
+/// u-blox 8 and later append the vehicle heading and magnetic declination.
+const NAV_PVT_SIZE: usize = 92;
+/// u-blox 7 (protocol 14) ends NAV-PVT after the position DOP, every field read lies before.
+const NAV_PVT_MIN_SIZE: usize = 84;
+/// NAV-PVT is the longest message read, anything longer is skipped.
+pub const MAX_PAYLOAD_SIZE: usize = NAV_PVT_SIZE;
+pub const MAX_FRAME_SIZE: usize = HEADER_SIZE + MAX_PAYLOAD_SIZE + CHECKSUM_SIZE;
+
+const READ_CHUNK_SIZE: usize = 64;
+const NAV5_SIZE: usize = 36;
+/// Only the dynamic model is applied, every other NAV5 setting keeps its default.
+const NAV5_DYNAMIC_MODEL_MASK: u16 = 0x0001;
+const NMEA_CLASS: u8 = 0xF0;
+/// Aligns measurements to GPS time, as in the stored configuration.
+const GPS_TIME_REFERENCE: u16 = 1;
+
+const VALID_DATE: u8 = 0b0000_0001;
+const VALID_TIME: u8 = 0b0000_0010;
+const GNSS_FIX_OK: u8 = 0b0000_0001;
+const DIFFERENTIAL_SOLUTION: u8 = 0b0000_0010;
+const CARRIER_SOLUTION_SHIFT: u8 = 6;
+const CARRIER_FLOAT: u8 = 1;
+const CARRIER_FIXED: u8 = 2;
+
+const DEAD_RECKONING_ONLY: u8 = 1;
+const FIX_2D: u8 = 2;
+const FIX_3D: u8 = 3;
+const GNSS_AND_DEAD_RECKONING: u8 = 4;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageId {
+    pub class: u8,
+    pub id: u8,
+}
+
+impl MessageId {
+    pub const NAV_PVT: Self = Self { class: 0x01, id: 0x07 };
+    pub const ACK_NAK: Self = Self { class: 0x05, id: 0x00 };
+    pub const ACK_ACK: Self = Self { class: 0x05, id: 0x01 };
+    pub const CFG_MSG: Self = Self { class: 0x06, id: 0x01 };
+    pub const CFG_RATE: Self = Self { class: 0x06, id: 0x08 };
+    pub const CFG_NAV5: Self = Self { class: 0x06, id: 0x24 };
+}
+
+/// Dynamic platform model, bounding the accelerations the navigation filter expects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DynamicModel {
+    Portable = 0,
+    Stationary = 2,
+    Pedestrian = 3,
+    Automotive = 4,
+    Sea = 5,
+    Airborne1g = 6,
+    Airborne2g = 7,
+    Airborne4g = 8,
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(thiserror::Error, Debug, PartialEq, Eq, Clone)]
+pub enum UbxError {
+    #[error("Uart read error")]
+    UartRead,
+    #[error("Uart write error")]
+    UartWrite,
+    #[error("UBX checksum mismatch")]
+    Checksum,
+    #[error("UBX payload too long to send: {0} bytes")]
+    Oversized(usize),
+    #[error("Unexpected payload length for {0:?}: {1} bytes")]
+    PayloadLength(MessageId, usize),
+    #[error("Configuration rejected: {0:?}")]
+    Rejected(MessageId),
+    #[error("Configuration not acknowledged: {0:?}")]
+    AckTimeout(MessageId),
+    #[error("UTC date and time not resolved yet")]
+    TimeNotResolved,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub message: MessageId,
+    pub payload: heapless::Vec<u8, MAX_PAYLOAD_SIZE>,
+}
+
+/// 8-bit Fletcher checksum over class, id, length and payload.
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    bytes.iter().fold([0, 0], |[a, b], byte| {
+        let a = a.wrapping_add(*byte);
+        [a, b.wrapping_add(a)]
+    })
+}
+
+/// Frames `payload` into `buffer`, returning the bytes to send.
+pub fn encode<'a>(message: MessageId, payload: &[u8], buffer: &'a mut [u8; MAX_FRAME_SIZE]) -> Result<&'a [u8], UbxError> {
+    if payload.len() > MAX_PAYLOAD_SIZE {
+        return Err(UbxError::Oversized(payload.len()));
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let length = payload.len() as u16;
+    let end = HEADER_SIZE + payload.len();
+
+    buffer[..SYNC.len()].copy_from_slice(&SYNC);
+    buffer[2] = message.class;
+    buffer[3] = message.id;
+    buffer[4..HEADER_SIZE].copy_from_slice(&length.to_le_bytes());
+    buffer[HEADER_SIZE..end].copy_from_slice(payload);
+    let checksum = checksum(&buffer[SYNC.len()..end]);
+    buffer[end..end + CHECKSUM_SIZE].copy_from_slice(&checksum);
+
+    Ok(&buffer[..end + CHECKSUM_SIZE])
+}
+
+/// Reassembles frames from a byte stream, skipping whatever lies between them, e.g. NMEA sentences.
+#[derive(Default)]
+pub struct Parser {
+    buffer: heapless::Vec<u8, MAX_FRAME_SIZE>,
+}
+
+impl Parser {
+    /// Completes a frame once its last byte is pushed.
+    pub fn push(&mut self, byte: u8) -> Option<Result<Frame, UbxError>> {
+        match self.buffer.len() {
+            0 if byte != SYNC[0] => return None,
+            1 if byte != SYNC[1] => {
+                self.buffer.clear();
+                // a repeated first sync character may still start a frame
+                return if byte == SYNC[0] { self.push(byte) } else { None };
+            }
+            _ => {}
+        }
+        // cannot overflow, longer frames are dropped as soon as their length is known
+        self.buffer.push(byte).ok()?;
+
+        if self.buffer.len() < HEADER_SIZE {
+            return None;
+        }
+        if self.payload_len() > MAX_PAYLOAD_SIZE {
+            self.buffer.clear();
+            return None;
+        }
+        if self.buffer.len() < HEADER_SIZE + self.payload_len() + CHECKSUM_SIZE {
+            return None;
+        }
+
+        let frame = self.decode();
+        self.buffer.clear();
+        Some(frame)
+    }
+
+    fn payload_len(&self) -> usize {
+        usize::from(u16::from_le_bytes([self.buffer[4], self.buffer[5]]))
+    }
+
+    fn decode(&self) -> Result<Frame, UbxError> {
+        let (body, received) = self.buffer[SYNC.len()..].split_at(self.buffer.len() - SYNC.len() - CHECKSUM_SIZE);
+        if checksum(body) != received {
+            return Err(UbxError::Checksum);
+        }
+
+        let payload = &body[HEADER_SIZE - SYNC.len()..];
+        Ok(Frame {
+            message: MessageId { class: body[0], id: body[1] },
+            payload: heapless::Vec::from_slice(payload).map_err(|_| UbxError::Oversized(payload.len()))?,
+        })
+    }
+}
+
+/// Navigation solution of a NAV-PVT message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavPvt {
+    /// `None` until the receiver resolved both date and time of day.
+    pub utc: Option<NaiveDateTime>,
+    pub fix_type: FixType,
+    pub num_of_satellites: u8,
+    pub coordinates: GpsCoordinates,
+    /// Above mean sea level.
+    pub altitude: Altitude,
+    /// North, east and down.
+    pub velocity: Vector3<Velocity>,
+    pub horizontal_accuracy: Length,
+    pub vertical_accuracy: Length,
+    pub speed_accuracy: Velocity,
+}
+
+impl NavPvt {
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn parse(payload: &[u8]) -> Result<Self, UbxError> {
+        let payload: &[u8; NAV_PVT_MIN_SIZE] = payload
+            .get(..NAV_PVT_MIN_SIZE)
+            .and_then(|fields| fields.try_into().ok())
+            .ok_or(UbxError::PayloadLength(MessageId::NAV_PVT, payload.len()))?;
+        let degrees = |offset| (f64::from(i32_at(payload, offset)) * 1e-7) as f32;
+        let millimeters = Length::new::<millimeter>;
+        let velocity = |offset| Velocity::new::<millimeter_per_second>(i32_at(payload, offset) as f32);
+
+        Ok(Self {
+            utc: utc(payload),
+            fix_type: fix_type(payload[20], payload[21]),
+            num_of_satellites: payload[23],
+            coordinates: GpsCoordinates {
+                latitude: degrees(28),
+                longitude: degrees(24),
+            },
+            altitude: millimeters(i32_at(payload, 36) as f32),
+            velocity: Vector3::new(velocity(48), velocity(52), velocity(56)),
+            horizontal_accuracy: millimeters(u32_at(payload, 40) as f32),
+            vertical_accuracy: millimeters(u32_at(payload, 44) as f32),
+            speed_accuracy: Velocity::new::<millimeter_per_second>(u32_at(payload, 68) as f32),
+        })
+    }
+}
+
+impl TryFrom<NavPvt> for GpsData {
+    type Error = UbxError;
+
+    fn try_from(pvt: NavPvt) -> Result<Self, Self::Error> {
+        Ok(Self {
+            fix_time: pvt.utc.ok_or(UbxError::TimeNotResolved)?.time().into(),
+            fix_type: pvt.fix_type.into(),
+            coordinates: pvt.coordinates,
+            altitude: pvt.altitude,
+            num_of_fix_satellites: pvt.num_of_satellites,
+        })
+    }
+}
+
+const fn u32_at(payload: &[u8; NAV_PVT_MIN_SIZE], offset: usize) -> u32 {
+    u32::from_le_bytes([payload[offset], payload[offset + 1], payload[offset + 2], payload[offset + 3]])
+}
+
+const fn i32_at(payload: &[u8; NAV_PVT_MIN_SIZE], offset: usize) -> i32 {
+    i32::from_le_bytes([payload[offset], payload[offset + 1], payload[offset + 2], payload[offset + 3]])
+}
+
+fn utc(payload: &[u8; NAV_PVT_MIN_SIZE]) -> Option<NaiveDateTime> {
+    let valid = payload[11];
+    if valid & (VALID_DATE | VALID_TIME) != VALID_DATE | VALID_TIME {
+        return None;
+    }
+
+    let year = u16::from_le_bytes([payload[4], payload[5]]);
+    // the nanoseconds are signed, rounding the seconds to the nearest
+    let nanoseconds = TimeDelta::nanoseconds(i64::from(i32_at(payload, 16)));
+    NaiveDate::from_ymd_opt(i32::from(year), u32::from(payload[6]), u32::from(payload[7]))?
+        .and_hms_opt(u32::from(payload[8]), u32::from(payload[9]), u32::from(payload[10]))?
+        .checked_add_signed(nanoseconds)
+}
+
+const fn fix_type(fix: u8, flags: u8) -> FixType {
+    let solves_position = matches!(fix, FIX_2D | FIX_3D | GNSS_AND_DEAD_RECKONING);
+    if flags & GNSS_FIX_OK == 0 {
+        return FixType::Invalid;
+    }
+
+    match flags >> CARRIER_SOLUTION_SHIFT {
+        CARRIER_FIXED if solves_position => FixType::Rtk,
+        CARRIER_FLOAT if solves_position => FixType::FloatRtk,
+        _ if solves_position && flags & DIFFERENTIAL_SOLUTION != 0 => FixType::DGps,
+        _ if solves_position => FixType::Gps,
+        _ if fix == DEAD_RECKONING_ONLY => FixType::Estimated,
+        // no fix or time only
+        _ => FixType::Invalid,
+    }
+}
+
+fn rate_payload(period: Duration) -> [u8; 6] {
+    let [period_low, period_high] = u16::try_from(period.as_millis()).unwrap_or(u16::MAX).to_le_bytes();
+    let [reference_low, reference_high] = GPS_TIME_REFERENCE.to_le_bytes();
+    // one navigation solution per measurement
+    [period_low, period_high, 1, 0, reference_low, reference_high]
+}
+
+fn nav5_payload(model: DynamicModel) -> [u8; NAV5_SIZE] {
+    let mut payload = [0; NAV5_SIZE];
+    payload[..2].copy_from_slice(&NAV5_DYNAMIC_MODEL_MASK.to_le_bytes());
+    payload[2] = model as u8;
+    payload
+}
+
+/// u-blox receiver speaking UBX over a UART, configured from `UbloxConfig` at every boot.
+pub struct UbloxDevice<U>
+where
+    U: embedded_io_async::Read + embedded_io_async::Write,
+{
+    uart: U,
+    buf: [u8; READ_CHUNK_SIZE],
+    filled: usize,
+    position: usize,
+    parser: Parser,
+}
+
+impl<U> UbloxDevice<U>
+where
+    U: embedded_io_async::Read + embedded_io_async::Write,
+{
+    /// `uart` must already run at `UbloxConfig::BAUD_RATE`, the receiver keeps its stored port settings.
+    pub async fn init(uart: U) -> Result<Self, UbxError> {
+        let mut ublox = Self {
+            uart,
+            buf: [0; READ_CHUNK_SIZE],
+            filled: 0,
+            position: 0,
+            parser: Parser::default(),
+        };
+
+        // NMEA first, so the acknowledgements that follow are not delayed behind sentences
+        for sentence in UbloxConfig::DISABLED_NMEA_SENTENCES {
+            ublox.apply(MessageId::CFG_MSG, &[NMEA_CLASS, *sentence, 0]).await?;
+        }
+        // the pad rate rather than the stored CFG-RATE of 70 ms, `configure` follows the flight state from there
+        ublox.apply(MessageId::CFG_RATE, &rate_payload(Self::SAMPLE_RATES.tick_interval(FlightState::PreArmed))).await?;
+        ublox.apply(MessageId::CFG_NAV5, &nav5_payload(UbloxConfig::DYNAMIC_MODEL)).await?;
+        // on the port this message arrives at
+        ublox.apply(MessageId::CFG_MSG, &[MessageId::NAV_PVT.class, MessageId::NAV_PVT.id, 1]).await?;
+        Ok(ublox)
+    }
+
+    /// Sends a configuration message and waits for the receiver to acknowledge it.
+    async fn apply(&mut self, message: MessageId, payload: &[u8]) -> Result<(), UbxError> {
+        let mut frame = [0; MAX_FRAME_SIZE];
+        self.uart.write_all(encode(message, payload, &mut frame)?).await.map_err(|_| UbxError::UartWrite)?;
+        self.uart.flush().await.map_err(|_| UbxError::UartWrite)?;
+
+        with_timeout(UbloxConfig::ACK_TIMEOUT, self.acknowledgement(message))
+            .await
+            .map_err(|_| UbxError::AckTimeout(message))?
+    }
+
+    async fn acknowledgement(&mut self, message: MessageId) -> Result<(), UbxError> {
+        loop {
+            let frame = self.next_frame().await?;
+            let acknowledged = frame.payload[..] == [message.class, message.id];
+            match frame.message {
+                MessageId::ACK_ACK if acknowledged => return Ok(()),
+                MessageId::ACK_NAK if acknowledged => return Err(UbxError::Rejected(message)),
+                // navigation messages keep flowing while reconfiguring
+                _ => {}
+            }
+        }
+    }
+
+    async fn next_frame(&mut self) -> Result<Frame, UbxError> {
+        loop {
+            if self.position == self.filled {
+                self.filled = self.uart.read(&mut self.buf).await.map_err(|_| UbxError::UartRead)?;
+                self.position = 0;
+                // a closed stream would otherwise be polled forever
+                if self.filled == 0 {
+                    return Err(UbxError::UartRead);
+                }
+            }
+
+            let byte = self.buf[self.position];
+            self.position += 1;
+            if let Some(frame) = self.parser.push(byte) {
+                return frame;
+            }
+        }
+    }
+}
+
+impl<U> Sensor for UbloxDevice<U>
+where
+    U: embedded_io_async::Read + embedded_io_async::Write,
+{
+    type Data = GpsData;
+    type Error = UbxError;
+
+    const NAME: &'static str = "u-blox GPS";
+    const ID: SensorId = SensorId::Gps;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::GPS_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        loop {
+            let frame = self.next_frame().await?;
+            if frame.message == MessageId::NAV_PVT {
+                return NavPvt::parse(&frame.payload)?.try_into();
+            }
+        }
+    }
+
+    /// Matches the navigation rate to the phase's sample rate, fixes would otherwise queue up in the UART.
+    async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+        self.apply(MessageId::CFG_RATE, &rate_payload(Self::SAMPLE_RATES.tick_interval(state))).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use std::collections::VecDeque;
+
+    use proto::uom::si::length::meter;
+    use proto::uom::si::velocity::meter_per_second;
+
+    use crate::test_utils::ms;
+
+    use super::*;
+
+    /// NAV-PVT of a 3D fix climbing at 45 m/s, 2026-07-14 10:32:07.25 UTC. Synthetic, built by hand with
+    /// its checksum: the `gps_config` dumps hold no NAV-PVT frame, both configurations leave it off on every port.
+    const NAV_PVT: [u8; MAX_FRAME_SIZE] = [
+        0xB5, 0x62, 0x01, 0x07, 0x5C, 0x00, 0xA2, 0xAD, 0x25, 0x17, 0xEA, 0x07, 0x07, 0x0E, 0x0A, 0x20,
+        0x07, 0x07, 0x19, 0x00, 0x00, 0x00, 0x80, 0xB2, 0xE6, 0x0E, 0x03, 0x01, 0x00, 0x0B, 0x86, 0xBC,
+        0x17, 0x05, 0xA6, 0x23, 0x3D, 0x1C, 0xB4, 0x67, 0x07, 0x00, 0x60, 0xAD, 0x06, 0x00, 0x66, 0x08,
+        0x00, 0x00, 0x48, 0x0D, 0x00, 0x00, 0xE2, 0x04, 0x00, 0x00, 0xC0, 0xFE, 0xFF, 0xFF, 0x38, 0x50,
+        0xFF, 0xFF, 0x0A, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x8E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x6C, 0xB3,
+    ];
+    /// Configuration of the flight receiver, a u-blox 7, as read back after storing it to flash.
+    const CONFIGURED: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../gps_config/configured_gnss.txt"));
+    /// Its configuration as delivered.
+    const ORIGINAL: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../gps_config/original_gnss_config.txt"));
+    /// Port id of UART1, also its index among the per-port rates of CFG-MSG.
+    const UART1: u8 = 1;
+    const UBX_PROTOCOL: u8 = 0b0000_0001;
+    const GGA: &[u8] = b"$GPGGA,103207.25,4722.61322,N,00832.65002,E,1,11,1.42,437.6,M,47.7,M,,*5C\r\n";
+
+    /// Scripted UART, replaying `rx` and recording everything written.
+    struct Uart {
+        rx: VecDeque<u8>,
+        tx: Vec<u8>,
+    }
+
+    impl Uart {
+        fn new(rx: &[u8]) -> Self {
+            Self { rx: rx.iter().copied().collect(), tx: Vec::new() }
+        }
+    }
+
+    impl embedded_io_async::ErrorType for Uart {
+        type Error = Infallible;
+    }
+
+    impl embedded_io_async::Read for Uart {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let len = buf.len().min(self.rx.len());
+            for (slot, byte) in buf.iter_mut().zip(self.rx.drain(..len)) {
+                *slot = byte;
+            }
+            Ok(len)
+        }
+    }
+
+    impl embedded_io_async::Write for Uart {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.tx.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    /// Class, id, length and payload of every dumped message named `name`.
+    fn dumped(dump: &str, name: &str) -> Vec<Vec<u8>> {
+        dump.lines()
+            .filter_map(|line| line.split_once(" - "))
+            .filter(|(message, _)| *message == name)
+            .map(|(_, bytes)| bytes.split_whitespace().map(|byte| u8::from_str_radix(byte, 16).expect("Dump should be hex")).collect())
+            .collect()
+    }
+
+    /// Payload of the only dumped message named `name`.
+    fn stored(name: &str) -> Vec<u8> {
+        let mut messages = dumped(CONFIGURED, name);
+        assert_eq!(messages.len(), 1, "Dump should hold a single {name}");
+        messages.remove(0).split_off(HEADER_SIZE - SYNC.len())
+    }
+
+    /// Dumped message named `name`, framed as the receiver sent it.
+    fn stored_frame(name: &str) -> Vec<u8> {
+        let messages = dumped(CONFIGURED, name);
+        assert_eq!(messages.len(), 1, "Dump should hold a single {name}");
+        [&SYNC[..], &messages[0], &checksum(&messages[0])].concat()
+    }
+
+    /// Rate of every NMEA sentence on UART1.
+    fn nmea_rates(dump: &str) -> Vec<(u8, u8)> {
+        dumped(dump, "CFG-MSG")
+            .iter()
+            .map(|message| &message[HEADER_SIZE - SYNC.len()..])
+            .filter(|payload| payload[0] == NMEA_CLASS)
+            .map(|payload| (payload[1], payload[2 + usize::from(UART1)]))
+            .collect()
+    }
+
+    /// NAV-PVT cut to `length` bytes of payload, as older receivers send it.
+    fn nav_pvt(length: usize) -> Vec<u8> {
+        let mut frame = NAV_PVT[..HEADER_SIZE + length].to_vec();
+        frame[4..HEADER_SIZE].copy_from_slice(&u16::try_from(length).expect("Length should fit").to_le_bytes());
+        let checksum = checksum(&frame[SYNC.len()..]);
+        frame.extend_from_slice(&checksum);
+        frame
+    }
+
+    fn framed(message: MessageId, payload: &[u8]) -> Vec<u8> {
+        let mut buffer = [0; MAX_FRAME_SIZE];
+        encode(message, payload, &mut buffer).expect("Payload should fit").to_vec()
+    }
+
+    /// Everything `init` sends, in order, each paired with the receiver's answer.
+    fn configuration() -> Vec<(MessageId, Vec<u8>)> {
+        UbloxConfig::DISABLED_NMEA_SENTENCES
+            .iter()
+            .map(|sentence| (MessageId::CFG_MSG, vec![NMEA_CLASS, *sentence, 0]))
+            .chain([
+                (MessageId::CFG_RATE, rate_payload(DataAcquisitionConfig::GPS_SAMPLE_RATES.pad).to_vec()),
+                (MessageId::CFG_NAV5, nav5_payload(DynamicModel::Airborne4g).to_vec()),
+                (MessageId::CFG_MSG, vec![0x01, 0x07, 1]),
+            ])
+            .collect()
+    }
+
+    fn acknowledged(configuration: &[(MessageId, Vec<u8>)]) -> Vec<u8> {
+        configuration
+            .iter()
+            .flat_map(|(message, _)| framed(MessageId::ACK_ACK, &[message.class, message.id]))
+            .collect()
+    }
+
+    async fn initialized(rx: &[u8]) -> UbloxDevice<Uart> {
+        let mut stream = acknowledged(&configuration());
+        stream.extend_from_slice(rx);
+        UbloxDevice::init(Uart::new(&stream)).await.expect("Initialization should succeed")
+    }
+
+    #[rstest::rstest]
+    fn frames_with_checksum() {
+        // CFG-RATE of the original configuration, 1 Hz
+        assert_eq!(
+            framed(MessageId::CFG_RATE, &[0xE8, 0x03, 0x01, 0x00, 0x01, 0x00]),
+            [0xB5, 0x62, 0x06, 0x08, 0x06, 0x00, 0xE8, 0x03, 0x01, 0x00, 0x01, 0x00, 0x01, 0x39],
+        );
+    }
+
+    #[rstest::rstest]
+    #[case(NAV_PVT_SIZE)]
+    #[case(NAV_PVT_MIN_SIZE)]
+    fn parses_nav_pvt(#[case] length: usize) {
+        let pvt = NavPvt::parse(&NAV_PVT[HEADER_SIZE..HEADER_SIZE + length]).expect("Payload should parse");
+
+        let expected_utc = NaiveDate::from_ymd_opt(2026, 7, 14)
+            .and_then(|date| date.and_hms_milli_opt(10, 32, 7, 250));
+        assert_eq!(pvt.utc, expected_utc);
+        assert_eq!(pvt.fix_type, FixType::Gps);
+        assert_eq!(pvt.num_of_satellites, 11);
+        assert!((pvt.coordinates.latitude - 47.376_89).abs() < 1e-5);
+        assert!((pvt.coordinates.longitude - 8.544_167).abs() < 1e-5);
+        assert!((pvt.altitude.get::<meter>() - 437.6).abs() < 1e-3);
+        for (axis, expected) in pvt.velocity.iter().zip([1.25, -0.32, -45.0]) {
+            assert!((axis.get::<meter_per_second>() - expected).abs() < 1e-3, "Expected {expected} m/s, got {axis:?}");
+        }
+        assert!((pvt.horizontal_accuracy.get::<meter>() - 2.15).abs() < 1e-3);
+        assert!((pvt.vertical_accuracy.get::<meter>() - 3.4).abs() < 1e-3);
+        assert!((pvt.speed_accuracy.get::<meter_per_second>() - 0.18).abs() < 1e-3);
+    }
+
+    #[rstest::rstest]
+    fn rejects_truncated_nav_pvt() {
+        let truncated = &NAV_PVT[HEADER_SIZE..HEADER_SIZE + NAV_PVT_MIN_SIZE - 1];
+
+        assert_eq!(NavPvt::parse(truncated), Err(UbxError::PayloadLength(MessageId::NAV_PVT, NAV_PVT_MIN_SIZE - 1)));
+    }
+
+    #[rstest::rstest]
+    fn resyncs_after_garbage_and_corruption() {
+        let mut corrupted = NAV_PVT;
+        corrupted[30] ^= 0x01;
+        let stream = [GGA, &[SYNC[0], SYNC[0]], &corrupted, GGA, &NAV_PVT].concat();
+
+        let mut parser = Parser::default();
+        let frames: Vec<_> = stream.iter().filter_map(|byte| parser.push(*byte)).collect();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0], Err(UbxError::Checksum));
+        assert_eq!(frames[1].as_ref().map(|frame| frame.message), Ok(MessageId::NAV_PVT));
+    }
+
+    #[rstest::rstest]
+    fn skips_messages_longer_than_the_buffer() {
+        // MON-VER carries a 100 byte payload
+        let stream = [&stored_frame("MON-VER")[..], &NAV_PVT].concat();
+
+        let mut parser = Parser::default();
+        let frames: Vec<_> = stream.iter().filter_map(|byte| parser.push(*byte)).collect();
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].as_ref().map(|frame| frame.message), Ok(MessageId::NAV_PVT));
+    }
+
+    #[rstest::rstest]
+    #[case(3, 0b0000_0001, FixType::Gps)]
+    #[case(3, 0b0000_0000, FixType::Invalid)]
+    #[case(3, 0b0000_0011, FixType::DGps)]
+    #[case(3, 0b0100_0001, FixType::FloatRtk)]
+    #[case(3, 0b1000_0001, FixType::Rtk)]
+    #[case(1, 0b0000_0001, FixType::Estimated)]
+    #[case(5, 0b0000_0001, FixType::Invalid)]
+    fn maps_fix_type(#[case] fix: u8, #[case] flags: u8, #[case] expected: FixType) {
+        assert_eq!(fix_type(fix, flags), expected);
+    }
+
+    #[rstest::rstest]
+    fn stored_port_speaks_ubx_at_baud_rate() {
+        let ports = dumped(CONFIGURED, "CFG-PRT");
+        let uart1 = ports
+            .iter()
+            .map(|message| &message[HEADER_SIZE - SYNC.len()..])
+            .find(|payload| payload[0] == UART1)
+            .expect("Dump should hold UART1");
+
+        assert_eq!(u32::from_le_bytes([uart1[8], uart1[9], uart1[10], uart1[11]]), UbloxConfig::BAUD_RATE);
+        assert_eq!(uart1[12] & UBX_PROTOCOL, UBX_PROTOCOL, "UBX input");
+        assert_eq!(uart1[14] & UBX_PROTOCOL, UBX_PROTOCOL, "UBX output");
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn uploads_stored_configuration_at_init() {
+        let ublox = initialized(&[]).await;
+
+        let mut parser = Parser::default();
+        let sent = ublox.uart.tx.iter()
+            .filter_map(|byte| parser.push(*byte))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Sent frames should be valid");
+        let payloads = |message| sent.iter().filter(move |frame| frame.message == message).map(|frame| frame.payload.to_vec());
+
+        // silenced on UART1 when the configuration was stored
+        let original = nmea_rates(ORIGINAL);
+        let silenced: Vec<_> = nmea_rates(CONFIGURED)
+            .into_iter()
+            .filter(|(sentence, rate)| *rate == 0 && original.iter().any(|(original, rate)| original == sentence && *rate != 0))
+            .map(|(sentence, rate)| vec![NMEA_CLASS, sentence, rate])
+            .collect();
+        let nmea: Vec<_> = payloads(MessageId::CFG_MSG).filter(|payload| payload[0] == NMEA_CLASS).collect();
+        assert_eq!(nmea, silenced);
+        assert_eq!(silenced.len(), 5, "GLL, GSA, GSV, RMC and VTG");
+
+        let nav5 = stored("CFG-NAV5");
+        let sent_nav5: Vec<_> = payloads(MessageId::CFG_NAV5).collect();
+        assert_eq!(sent_nav5.len(), 1);
+        assert_eq!(sent_nav5[0][..2], [0x01, 0x00], "Dynamic model only");
+        assert_eq!(sent_nav5[0][2], nav5[2]);
+        assert_eq!(nav5[2], 0x08, "Airborne <4g");
+
+        // measurement rate of the pad instead of the stored 70 ms, cycles and time reference kept
+        let rate = stored("CFG-RATE");
+        let sent_rates: Vec<_> = payloads(MessageId::CFG_RATE).collect();
+        assert_eq!(sent_rates.len(), 1);
+        let sent_rate = &sent_rates[0];
+        assert_eq!(sent_rate[..2], rate_payload(DataAcquisitionConfig::GPS_SAMPLE_RATES.pad)[..2]);
+        assert_eq!(sent_rate[2..], rate[2..], "Cycles and time reference as stored");
+
+        let enabled = sent.last().expect("Frames should be sent");
+        assert_eq!((enabled.message, &enabled.payload[..]), (MessageId::CFG_MSG, &[0x01, 0x07, 1][..]), "NAV-PVT on UART1");
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn fails_init_on_rejected_configuration() {
+        let configuration = configuration();
+        let mut stream = acknowledged(&configuration[..UbloxConfig::DISABLED_NMEA_SENTENCES.len() - 1]);
+        stream.extend(framed(MessageId::ACK_NAK, &[0x06, 0x01]));
+
+        let result = UbloxDevice::init(Uart::new(&stream)).await;
+
+        assert_eq!(result.err(), Some(UbxError::Rejected(MessageId::CFG_MSG)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    #[case(NAV_PVT_SIZE)]
+    #[case(NAV_PVT_MIN_SIZE)]
+    async fn reads_nav_pvt_among_other_messages(#[case] length: usize) {
+        let stream = [GGA, &framed(MessageId::ACK_ACK, &[0x06, 0x08]), &nav_pvt(length)].concat();
+        let mut ublox = initialized(&stream).await;
+
+        let data = ublox.parse_new_data().await.expect("Reading should succeed");
+
+        assert_eq!(*data.fix_type, FixType::Gps);
+        assert_eq!(data.num_of_fix_satellites, 11);
+        assert_eq!(data.fix_time.to_string(), "10:32:07.250");
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn reports_unresolved_time() {
+        let mut unresolved = NAV_PVT;
+        unresolved[HEADER_SIZE + 11] = 0;
+        let checksum = checksum(&unresolved[SYNC.len()..HEADER_SIZE + NAV_PVT_SIZE]);
+        unresolved[HEADER_SIZE + NAV_PVT_SIZE..].copy_from_slice(&checksum);
+        let mut ublox = initialized(&unresolved).await;
+
+        assert_eq!(ublox.parse_new_data().await, Err(UbxError::TimeNotResolved));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn reconfigures_rate_per_flight_state() {
+        let ack = framed(MessageId::ACK_ACK, &[0x06, 0x08]);
+        let mut ublox = initialized(&[&NAV_PVT[..], &ack].concat()).await;
+        ublox.uart.tx.clear();
+
+        assert_eq!(ublox.configure(FlightState::Armed).await, Ok(()));
+        assert_eq!(ublox.uart.tx, framed(MessageId::CFG_RATE, &rate_payload(DataAcquisitionConfig::GPS_SAMPLE_RATES.ascent)));
+    }
+}
@@ -0,0 +1,46 @@
+use core::convert::Infallible;
+
+use embassy_time::Duration;
+
+use crate::interfaces::Watchdog;
+
+/// MCU watchdog peripheral, driven through its HAL's functions as embedded-hal has no watchdog trait.
+///
+/// e.g. `HardwareWatchdog::new(watchdog, |wdt, timeout| wdt.start(timeout.into()), |wdt| wdt.feed())`
+pub struct HardwareWatchdog<W, S, F>
+where
+    S: FnMut(&mut W, Duration),
+    F: FnMut(&mut W),
+{
+    peripheral: W,
+    start: S,
+    feed: F,
+}
+
+impl<W, S, F> HardwareWatchdog<W, S, F>
+where
+    S: FnMut(&mut W, Duration),
+    F: FnMut(&mut W),
+{
+    pub const fn new(peripheral: W, start: S, feed: F) -> Self {
+        Self { peripheral, start, feed }
+    }
+}
+
+impl<W, S, F> Watchdog for HardwareWatchdog<W, S, F>
+where
+    S: FnMut(&mut W, Duration),
+    F: FnMut(&mut W),
+{
+    type Error = Infallible;
+
+    async fn start(&mut self, timeout: Duration) -> Result<(), Self::Error> {
+        (self.start)(&mut self.peripheral, timeout);
+        Ok(())
+    }
+
+    async fn feed(&mut self) -> Result<(), Self::Error> {
+        (self.feed)(&mut self.peripheral);
+        Ok(())
+    }
+}
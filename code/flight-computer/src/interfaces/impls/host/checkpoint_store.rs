@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+use tokio::fs;
+
+use proto::flight_state::FlightCheckpoint;
+
+use crate::config::CheckpointConfig;
+use crate::interfaces::CheckpointStore;
+
+#[derive(thiserror::Error, Debug)]
+pub enum HostCheckpointError {
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialize error")]
+    Serialize(#[from] serde_json_core::ser::Error),
+    #[error("Deserialize error")]
+    Deserialize(#[from] serde_json_core::de::Error),
+}
+
+/// Checkpoint kept in a single JSON file, replaced atomically through a rename.
+pub struct HostCheckpointStore {
+    path: PathBuf,
+}
+
+impl HostCheckpointStore {
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl CheckpointStore for HostCheckpointStore {
+    type Error = HostCheckpointError;
+
+    async fn load(&mut self) -> Result<Option<FlightCheckpoint>, Self::Error> {
+        match fs::read(&self.path).await {
+            Ok(bytes) => Ok(Some(serde_json_core::from_slice(&bytes)?.0)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn store(&mut self, checkpoint: &FlightCheckpoint) -> Result<(), Self::Error> {
+        let mut buf = [0; CheckpointConfig::SLOT_SIZE];
+        let len = serde_json_core::to_slice(checkpoint, &mut buf)?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, &buf[..len]).await?;
+        fs::rename(&tmp_path, &self.path).await?;
+        Ok(())
+    }
+
+    async fn clear(&mut self) -> Result<(), Self::Error> {
+        match fs::remove_file(&self.path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
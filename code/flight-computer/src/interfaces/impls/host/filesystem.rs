@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 use tokio::fs::{self, OpenOptions, File};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::interfaces::FileSystem;
 
@@ -8,6 +8,9 @@ use crate::interfaces::FileSystem;
 /*                                 File System                                */
 /* -------------------------------------------------------------------------- */
 
+/// Size of the SD card the log directory stands in for.
+const CARD_CAPACITY: u64 = 8 * 1024 * 1024 * 1024;
+
 pub struct HostFileSystem {
     dir_path: PathBuf,
 }
@@ -41,6 +44,37 @@ impl FileSystem for HostFileSystem {
             .await
     }
 
+    async fn delete_file(&mut self, filename: &str) -> Result<(), Self::Error> {
+        fs::remove_file(self.full_path(filename)).await
+    }
+
+    async fn read_file(&mut self, filename: &str, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut file = File::open(self.full_path(filename)).await?;
+
+        let mut len = 0;
+        while len < buffer.len() {
+            match file.read(&mut buffer[len..]).await? {
+                0 => break,
+                read => len += read,
+            }
+        }
+        Ok(len)
+    }
+
+    async fn file_size(&mut self, filename: &str) -> Result<u32, Self::Error> {
+        let len = fs::metadata(self.full_path(filename)).await?.len();
+        Ok(u32::try_from(len).unwrap_or(u32::MAX))
+    }
+
+    async fn free_space(&mut self) -> Result<u64, Self::Error> {
+        let mut used = 0;
+        let mut entries = fs::read_dir(&self.dir_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            used += entry.metadata().await?.len();
+        }
+        Ok(CARD_CAPACITY.saturating_sub(used))
+    }
+
     async fn open_file_append(&mut self, filename: &str) -> Result<Self::File, Self::Error> {
         OpenOptions::new()
             .append(true)
@@ -1 +1,2 @@
-pub mod filesystem;
\ No newline at end of file
+pub mod filesystem;
+pub mod checkpoint_store;
@@ -3,4 +3,6 @@ pub mod embedded;
 #[cfg(feature = "impl_sim")]
 pub mod simulation;
 #[cfg(feature = "impl_host")]
-pub mod host;
\ No newline at end of file
+pub mod host;
+#[cfg(feature = "impl_replay")]
+pub mod replay;
\ No newline at end of file
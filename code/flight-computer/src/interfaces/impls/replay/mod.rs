@@ -0,0 +1,325 @@
+//! Peripherals re-flying a recorded flight, fed from the NDJSON records of the ground station backend
+//! or the on-board JSON log.
+
+use core::convert::Infallible;
+use std::io::BufRead;
+
+use embassy_time::{Duration, Instant, Timer};
+use proto::flight_state::{FlightCheckpoint, FlightState};
+use proto::sensor_data::{AltimeterData, GpsData, HighGAccelerometerData, ImuData};
+use proto::wire::{Record, RecordData};
+
+use crate::config::PreflightConfig;
+use crate::interfaces::{ArmingSystem, CheckpointStore, DeploymentSystem, Led};
+
+mod sensor;
+pub use sensor::{ReplayAltimeter, ReplayEnded, ReplayGps, ReplayHighGAccelerometer, ReplayImu};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ReplayError {
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {0} is not a record")]
+    Parse(usize),
+    #[error("Log holds no records")]
+    Empty,
+}
+
+/// Samples and flight state changes of a single boot, as offsets from its first record.
+pub struct ReplayLog {
+    altimeter: Vec<(Duration, AltimeterData)>,
+    gps: Vec<(Duration, GpsData)>,
+    imu: Vec<(Duration, ImuData)>,
+    high_g_accelerometer: Vec<(Duration, HighGAccelerometerData)>,
+    flight_states: Vec<(Duration, FlightState)>,
+    duration: Duration,
+}
+
+impl ReplayLog {
+    /// Reads one record per line, `tick_hz` being the tick frequency of the FC that recorded them.
+    pub fn parse(reader: impl BufRead, tick_hz: u64) -> Result<Self, ReplayError> {
+        let mut records = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (record, _) = serde_json_core::from_str::<Record>(line).map_err(|_| ReplayError::Parse(index + 1))?;
+            records.push(record);
+        }
+        Self::from_records(records, tick_hz)
+    }
+
+    pub fn from_records(records: impl IntoIterator<Item = Record>, tick_hz: u64) -> Result<Self, ReplayError> {
+        let mut records: Vec<_> = records.into_iter().map(Record::into_inner).collect();
+        // the ground station stores records as they arrive, priority records overtake samples
+        records.sort_by_key(|(timestamp, _, _)| timestamp.ticks());
+        let first = records.first().ok_or(ReplayError::Empty)?.0.ticks();
+
+        let mut log = Self {
+            altimeter: Vec::new(),
+            gps: Vec::new(),
+            imu: Vec::new(),
+            high_g_accelerometer: Vec::new(),
+            flight_states: Vec::new(),
+            duration: Duration::from_ticks(0),
+        };
+        for (timestamp, _, payload) in records {
+            let offset = offset(timestamp.ticks() - first, tick_hz);
+            log.duration = offset;
+            match payload {
+                RecordData::Altimeter(data) => log.altimeter.push((offset, data)),
+                RecordData::Gps(data) => log.gps.push((offset, data)),
+                RecordData::Imu(data) => log.imu.push((offset, data)),
+                RecordData::HighGAccelerometer(data) => log.high_g_accelerometer.push((offset, data)),
+                RecordData::FlightState(state) => log.flight_states.push((offset, state)),
+                _ => {},
+            }
+        }
+        Ok(log)
+    }
+
+    /// Flight state changes of the original flight.
+    #[must_use]
+    pub fn flight_states(&self) -> &[(Duration, FlightState)] {
+        &self.flight_states
+    }
+
+    /// Offset of the last record.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Sensors emitting the recorded samples at their offsets from `start`.
+    #[must_use]
+    pub fn sensors(&self, start: Instant) -> (ReplayAltimeter, ReplayGps, ReplayImu, ReplayHighGAccelerometer) {
+        (
+            ReplayAltimeter::new(&self.altimeter, start),
+            ReplayGps::new(&self.gps, start),
+            ReplayImu::new(&self.imu, start),
+            ReplayHighGAccelerometer::new(&self.high_g_accelerometer, start),
+        )
+    }
+
+    /// Arm command at the time the original FC armed. A log joining the flight after
+    /// arming arms right away, a log never leaving the pad never arms. A refused command is
+    /// repeated at every pre-flight check, e.g. until the replayed IMU clears the tilt lockout.
+    #[must_use]
+    pub fn arming(&self, start: Instant) -> ReplayArming {
+        let armed = self.flight_states.iter()
+            .find(|(_, state)| *state != FlightState::PreArmed)
+            .map(|(offset, state)| if *state == FlightState::Armed { start + *offset } else { start });
+        ReplayArming { armed }
+    }
+}
+
+fn offset(ticks: u64, tick_hz: u64) -> Duration {
+    let micros = u128::from(ticks) * 1_000_000 / u128::from(tick_hz);
+    Duration::from_micros(u64::try_from(micros).unwrap_or(u64::MAX))
+}
+
+pub struct ReplayArming {
+    armed: Option<Instant>,
+}
+
+impl ArmingSystem for ReplayArming {
+    type Error = Infallible;
+
+    async fn wait_arm(&mut self) -> Result<(), Self::Error> {
+        match self.armed {
+            Some(at) => Timer::at(at).await,
+            None => core::future::pending().await,
+        }
+        // only awaited again if the FSM refused to arm, an arm time already past would never yield
+        self.armed = Some(Instant::now() + PreflightConfig::CHECK_INTERVAL);
+        Ok(())
+    }
+}
+
+/// Recovery charges that always fire, the replay only compares when the FSM decided to deploy.
+pub struct ReplayRecovery;
+
+impl DeploymentSystem for ReplayRecovery {
+    type Error = Infallible;
+
+    async fn deploy(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn verify_deployment(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+pub struct ReplayLed;
+
+impl Led for ReplayLed {
+    type Error = Infallible;
+
+    async fn on(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn off(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn toggle(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Checkpoint kept in memory, every replay starts on the pad.
+#[derive(Default)]
+pub struct ReplayCheckpointStore {
+    checkpoint: Option<FlightCheckpoint>,
+}
+
+impl CheckpointStore for ReplayCheckpointStore {
+    type Error = Infallible;
+
+    async fn load(&mut self) -> Result<Option<FlightCheckpoint>, Self::Error> {
+        Ok(self.checkpoint)
+    }
+
+    async fn store(&mut self, checkpoint: &FlightCheckpoint) -> Result<(), Self::Error> {
+        self.checkpoint = Some(*checkpoint);
+        Ok(())
+    }
+
+    async fn clear(&mut self) -> Result<(), Self::Error> {
+        self.checkpoint = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::Serialize;
+    use proto::sensor_data::{Altitude, Pressure, ThermodynamicTemperature};
+    use proto::uom::si::{length::meter, pressure::pascal, thermodynamic_temperature::degree_celsius};
+
+    use crate::tasks::replay::{TransitionComparison, replay_flight_computer};
+    use crate::test_utils::filesystem::RamFileSystem;
+    use crate::test_utils::{ms, offset};
+    use crate::test_utils::sensor_data::{imu, random_altimeter_data, random_imu_data};
+
+    use super::*;
+
+    const TICK_HZ: u64 = 1_000_000;
+
+    fn json(value: &impl Serialize) -> String {
+        let mut buf = [0; 1024];
+        let len = serde_json_core::to_slice(value, &mut buf).expect("Value should serialize");
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
+
+    /// Record line as the ground station backend stores it, recorded 5 s after boot plus `ms`.
+    fn line(ms: u64, variant: &str, payload: &impl Serialize) -> String {
+        let ticks = (5_000 + ms) * 1_000;
+        format!("{{\"timestamp\":{{\"ticks\":{ticks}}},\"uid\":0,\"payload\":{{\"{variant}\":{}}}}}\n", json(payload))
+    }
+
+    fn parse(lines: &[String]) -> ReplayLog {
+        ReplayLog::parse(lines.concat().as_bytes(), TICK_HZ).expect("Log should parse")
+    }
+
+    /// Sample `meters` above a sea-level launchpad.
+    fn altimeter(meters: f32) -> AltimeterData {
+        AltimeterData {
+            pressure: Pressure::new::<pascal>(12.0f32.mul_add(-meters, 101_300.0)),
+            altitude: Altitude::new::<meter>(meters),
+            temperature: ThermodynamicTemperature::new::<degree_celsius>(15.0),
+        }
+    }
+
+    #[rstest::rstest]
+    fn sorts_records_into_offsets() {
+        let altimeter = random_altimeter_data();
+        let imu = random_imu_data();
+        let log = parse(&[
+            line(100, "FlightState", &FlightState::Armed),
+            line(0, "Altimeter", &altimeter),
+            line(50, "Imu", &imu),
+        ]);
+
+        assert_eq!(log.altimeter, [(offset(0), altimeter)]);
+        assert_eq!(log.imu, [(offset(50), imu)]);
+        assert_eq!(log.flight_states(), [(offset(100), FlightState::Armed)]);
+        assert_eq!(log.duration(), offset(100));
+    }
+
+    #[rstest::rstest]
+    fn skips_blank_lines_and_carriage_returns() {
+        let log = parse(&[
+            line(0, "FlightState", &FlightState::PreArmed).replace('\n', "\r\n"),
+            "\n".into(),
+            line(20, "Altimeter", &random_altimeter_data()),
+        ]);
+
+        assert_eq!(log.flight_states(), [(offset(0), FlightState::PreArmed)]);
+        assert_eq!(log.altimeter.len(), 1);
+    }
+
+    #[rstest::rstest]
+    #[case("", ReplayError::Empty)]
+    #[case("{\"not\":\"a record\"}\n", ReplayError::Parse(1))]
+    fn rejects_unusable_logs(#[case] ndjson: &str, #[case] expected: ReplayError) {
+        let error = ReplayLog::parse(ndjson.as_bytes(), TICK_HZ).err().expect("Log should be rejected");
+
+        assert_eq!(error.to_string(), expected.to_string());
+    }
+
+    #[rstest::rstest]
+    #[case(&[(0, FlightState::PreArmed), (1_000, FlightState::Armed)], Some(1_000))]
+    #[case(&[(0, FlightState::RecoveryActivated)], Some(0))]
+    #[case(&[(0, FlightState::PreArmed)], None)]
+    fn arms_when_the_original_did(#[case] states: &[(u64, FlightState)], #[case] expected_ms: Option<u64>) {
+        let lines: Vec<_> = states.iter().map(|(ms, state)| line(*ms, "FlightState", state)).collect();
+        let log = parse(&lines);
+        let start = Instant::from_secs(10);
+
+        assert_eq!(log.arming(start).armed, expected_ms.map(|ms| start + offset(ms)));
+    }
+
+    /// Hop armed at 1.5 s, after the first pre-flight check applied the override, lifting off at 2.5 s to
+    /// an apogee of 40 m at 3.5 s and descending at 10 m/s until the log ends. The replay runs on the
+    /// embassy clock in real time, so the log only spans the pad calibration and the apogee detector's window.
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(20_000))]
+    async fn replays_the_decisions_of_a_short_flight() {
+        let mut lines = vec![
+            line(0, "FlightState", &FlightState::PreArmed),
+            line(1_500, "FlightState", &FlightState::Armed),
+            line(6_000, "FlightState", &FlightState::RecoveryActivated),
+        ];
+        for millis in (0..=7_000u16).step_by(50) {
+            let seconds = f32::from(millis) / 1_000.0;
+            let altitude = match millis {
+                ..2_500 => 0.0,
+                ..3_500 => 40.0 * (seconds - 2.5),
+                _ => 10.0f32.mul_add(-(seconds - 3.5), 40.0),
+            };
+            lines.push(line(millis.into(), "Altimeter", &altimeter(altitude)));
+            if millis % 100 == 0 {
+                // upright throughout, clearing the tilt lockout
+                lines.push(line(millis.into(), "Imu", &imu([0.0, 0.0, 9.81], [0.0; 3])));
+            }
+        }
+        let log = parse(&lines);
+
+        let comparisons = replay_flight_computer(&log, RamFileSystem::default()).await;
+
+        let decided: Vec<_> = comparisons.iter().map(|comparison| (comparison.state, comparison.replayed.is_some())).collect();
+        assert_eq!(decided, [
+            (FlightState::Armed, true),
+            (FlightState::RecoveryActivated, true),
+            (FlightState::Touchdown, false),
+        ]);
+        assert!(comparisons.iter().all(TransitionComparison::matches), "Replay should decide as the original did: {comparisons:?}");
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::VecDeque;
+
+use embassy_time::{Duration, Instant, Timer};
+use proto::sensor_data::{AltimeterData, GpsData, HighGAccelerometerData, ImuData};
+use proto::sensor_health::SensorId;
+
+use crate::{config::{DataAcquisitionConfig, ReplayConfig}, interfaces::{ImuSensor, SampleRates, Sensor}};
+
+/// Returned once every recorded sample was read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayEnded;
+
+struct ReplaySamples<T> {
+    samples: VecDeque<(Instant, T)>,
+}
+
+impl<T: Clone> ReplaySamples<T> {
+    fn new(samples: &[(Duration, T)], start: Instant) -> Self {
+        Self {
+            samples: samples.iter().map(|(offset, data)| (start + *offset, data.clone())).collect(),
+        }
+    }
+
+    /// Waits for the next sample, then skips to the latest one due, as a sensor read slower than it was recorded.
+    async fn next(&mut self) -> Result<T, ReplayEnded> {
+        let (due, _) = self.samples.front().ok_or(ReplayEnded)?;
+        Timer::at(*due).await;
+
+        let now = Instant::now();
+        let mut latest = self.samples.pop_front().ok_or(ReplayEnded)?.1;
+        while self.samples.front().is_some_and(|(due, _)| *due <= now)
+            && let Some((_, data)) = self.samples.pop_front()
+        {
+            latest = data;
+        }
+        Ok(latest)
+    }
+}
+
+pub struct ReplayAltimeter(ReplaySamples<AltimeterData>);
+
+impl ReplayAltimeter {
+    pub(super) fn new(samples: &[(Duration, AltimeterData)], start: Instant) -> Self {
+        Self(ReplaySamples::new(samples, start))
+    }
+}
+
+impl Sensor for ReplayAltimeter {
+    type Data = AltimeterData;
+    type Error = ReplayEnded;
+
+    const NAME: &'static str = "Replayed Altimeter";
+    const ID: SensorId = SensorId::Altimeter;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::ALTIMETER_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        self.0.next().await
+    }
+}
+
+pub struct ReplayGps(ReplaySamples<GpsData>);
+
+impl ReplayGps {
+    pub(super) fn new(samples: &[(Duration, GpsData)], start: Instant) -> Self {
+        Self(ReplaySamples::new(samples, start))
+    }
+}
+
+impl Sensor for ReplayGps {
+    type Data = GpsData;
+    type Error = ReplayEnded;
+
+    const NAME: &'static str = "Replayed GPS";
+    const ID: SensorId = SensorId::Gps;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::GPS_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        self.0.next().await
+    }
+}
+
+pub struct ReplayImu(ReplaySamples<ImuData>);
+
+impl ReplayImu {
+    pub(super) fn new(samples: &[(Duration, ImuData)], start: Instant) -> Self {
+        Self(ReplaySamples::new(samples, start))
+    }
+}
+
+impl Sensor for ReplayImu {
+    type Data = ImuData;
+    type Error = ReplayEnded;
+
+    const NAME: &'static str = "Replayed IMU";
+    const ID: SensorId = SensorId::Imu;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::IMU_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        self.0.next().await
+    }
+}
+
+impl ImuSensor for ReplayImu {
+    const FULL_SCALE: f32 = ReplayConfig::IMU_FULL_SCALE;
+}
+
+pub struct ReplayHighGAccelerometer(ReplaySamples<HighGAccelerometerData>);
+
+impl ReplayHighGAccelerometer {
+    pub(super) fn new(samples: &[(Duration, HighGAccelerometerData)], start: Instant) -> Self {
+        Self(ReplaySamples::new(samples, start))
+    }
+}
+
+impl Sensor for ReplayHighGAccelerometer {
+    type Data = HighGAccelerometerData;
+    type Error = ReplayEnded;
+
+    const NAME: &'static str = "Replayed High-g Accelerometer";
+    const ID: SensorId = SensorId::HighGAccelerometer;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::HIGH_G_ACCELEROMETER_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        self.0.next().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{ms, sensor_data::random_altimeter_data};
+
+    use super::*;
+
+    fn samples(offsets_ms: &[u64]) -> Vec<(Duration, AltimeterData)> {
+        offsets_ms.iter().map(|offset| (Duration::from_millis(*offset), random_altimeter_data())).collect()
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn emits_samples_at_recorded_offsets() {
+        let recorded = samples(&[0, 20, 40]);
+        let start = Instant::now();
+        let mut altimeter = ReplayAltimeter::new(&recorded, start);
+
+        for (offset, data) in &recorded {
+            assert_eq!(altimeter.parse_new_data().await, Ok(data.clone()));
+            assert!(start.elapsed() >= *offset, "Sample at {offset:?} read after {:?}", start.elapsed());
+        }
+        assert_eq!(altimeter.parse_new_data().await, Err(ReplayEnded));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[timeout(ms(100))]
+    async fn skips_to_latest_due_sample() {
+        let recorded = samples(&[0, 10, 20, 1_000]);
+        let mut altimeter = ReplayAltimeter::new(&recorded, Instant::now());
+        Timer::after_millis(50).await;
+
+        assert_eq!(altimeter.parse_new_data().await, Ok(recorded[2].1.clone()));
+        assert_eq!(altimeter.0.samples.len(), 1);
+    }
+}
@@ -0,0 +1,43 @@
+use core::convert::Infallible;
+
+use embassy_time::{Duration, Instant};
+use proto::sensor_data::ElectricPotential;
+use proto::uom::si::electric_potential::volt;
+
+use crate::interfaces::Battery;
+
+/// The simulator has no power model: a two-cell LiPo discharging linearly from boot on.
+pub struct SimBattery {
+    started: Instant,
+}
+
+impl SimBattery {
+    const FULL: f32 = 8.4;
+    const EMPTY: f32 = 6.6;
+    const DISCHARGE_TIME: Duration = Duration::from_secs(4 * 60 * 60);
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self { started: Instant::now() }
+    }
+
+    fn voltage_at(&self, now: Instant) -> ElectricPotential {
+        let elapsed = now.saturating_duration_since(self.started).as_secs() as f32;
+        let discharged = (elapsed / Self::DISCHARGE_TIME.as_secs() as f32).min(1.0);
+        ElectricPotential::new::<volt>((Self::EMPTY - Self::FULL).mul_add(discharged, Self::FULL))
+    }
+}
+
+impl Default for SimBattery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Battery for SimBattery {
+    type Error = Infallible;
+
+    async fn voltage(&mut self) -> Result<ElectricPotential, Self::Error> {
+        Ok(self.voltage_at(Instant::now()))
+    }
+}
@@ -0,0 +1,42 @@
+use core::num::Wrapping;
+
+use postcard_rpc::server::{AsWireTxErrorKind, Sender as PostcardSender, WireTx, WireTxErrorKind};
+use postcard_rpc::header::VarSeq;
+use proto::wire::SimBuzzerTopic;
+use proto::actuator_data::ActuatorStatus;
+
+use crate::interfaces::Buzzer;
+
+pub struct SimBuzzer<'a, Tx: WireTx> {
+    tx: &'a PostcardSender<Tx>,
+    seq: Wrapping<u32>,
+}
+
+impl<'a, Tx: WireTx> SimBuzzer<'a, Tx> {
+    pub fn new(tx: &'a PostcardSender<Tx>) -> Self {
+        Self {
+            tx,
+            seq: Wrapping::default(),
+        }
+    }
+
+    async fn publish(&mut self, status: ActuatorStatus) -> Result<(), WireTxErrorKind> {
+        self.tx.publish::<SimBuzzerTopic>(VarSeq::Seq4(self.seq.0), &status)
+            .await
+            .map_err(|e| e.as_kind())?;
+        self.seq += 1;
+        Ok(())
+    }
+}
+
+impl<Tx: WireTx> Buzzer for SimBuzzer<'_, Tx> {
+    type Error = WireTxErrorKind;
+
+    async fn on(&mut self) -> Result<(), Self::Error> {
+        self.publish(ActuatorStatus::Active).await
+    }
+
+    async fn off(&mut self) -> Result<(), Self::Error> {
+        self.publish(ActuatorStatus::Inactive).await
+    }
+}
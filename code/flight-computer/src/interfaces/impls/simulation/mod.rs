@@ -1,4 +1,7 @@
 pub mod arming_system;
+pub mod battery;
+pub mod buzzer;
 pub mod deployment_system;
 pub mod led;
 pub mod sensor;
+pub mod watchdog;
\ No newline at end of file
@@ -7,6 +7,8 @@ mod gps;
 pub use gps::SimGps;
 mod imu;
 pub use imu::SimImu;
+mod high_g_accelerometer;
+pub use high_g_accelerometer::SimHighGAccelerometer;
 
 pub trait SimSensor : Sensor + Default {
     fn signal() -> &'static Signal<CriticalSectionRawMutex, Self::Data>;
@@ -20,7 +22,7 @@ pub trait SimSensor : Sensor + Default {
 mod tests {    
     use futures::FutureExt;
     use rstest::fixture;
-    use crate::test_utils::{ms, sensor_data::{random_altimeter_data, random_gps_data, random_imu_data}};
+    use crate::test_utils::{ms, sensor_data::{random_altimeter_data, random_gps_data, random_high_g_accelerometer_data, random_imu_data}};
 
     use super::*;
 
@@ -58,11 +60,13 @@ mod tests {
         #[from(sim_sensor)] mut sim_altimeter: SimAltimeter,
         #[from(sim_sensor)] mut sim_gps: SimGps,
         #[from(sim_sensor)] mut sim_imu: SimImu,
+        #[from(sim_sensor)] mut sim_high_g_accelerometer: SimHighGAccelerometer,
     ) {
         for _ in 0..updates {
             update_and_parse(random_altimeter_data(), &mut sim_altimeter).await;
             update_and_parse(random_gps_data(), &mut sim_gps).await;
             update_and_parse(random_imu_data(), &mut sim_imu).await;
+            update_and_parse(random_high_g_accelerometer_data(), &mut sim_high_g_accelerometer).await;
         }
     }
 
@@ -75,11 +79,13 @@ mod tests {
         #[from(sim_sensor)] sim_altimeter: SimAltimeter,
         #[from(sim_sensor)] sim_gps: SimGps,
         #[from(sim_sensor)] sim_imu: SimImu,
+        #[from(sim_sensor)] sim_high_g_accelerometer: SimHighGAccelerometer,
     ) {
         futures::select! {
             () = timeout_when_no_data(sim_altimeter).fuse() => (),
             () = timeout_when_no_data(sim_gps).fuse() => (),
             () = timeout_when_no_data(sim_imu).fuse() => (),
+            () = timeout_when_no_data(sim_high_g_accelerometer).fuse() => (),
         }
     }
 }
@@ -1,6 +1,7 @@
-use crate::{config::DataAcquisitionConfig, interfaces::{Sensor, impls::simulation::sensor::SimSensor}};
+use crate::{config::DataAcquisitionConfig, interfaces::{SampleRates, Sensor, impls::simulation::sensor::SimSensor}};
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
 use proto::sensor_data::AltimeterData;
+use proto::sensor_health::SensorId;
 
 static LATEST_DATA: Signal<CriticalSectionRawMutex, AltimeterData> = Signal::new();
 
@@ -17,7 +18,8 @@ impl Sensor for SimAltimeter {
     type Error = ();
 
     const NAME: &'static str = "Simulated Altimeter";
-    const TICK_INTERVAL: embassy_time::Duration = DataAcquisitionConfig::ALTIMETER_TICK_INTERVAL;
+    const ID: SensorId = SensorId::Altimeter;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::ALTIMETER_SAMPLE_RATES;
 
     async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
         Ok(LATEST_DATA.wait().await)
@@ -1,6 +1,7 @@
-use crate::{config::DataAcquisitionConfig, interfaces::{Sensor, impls::simulation::sensor::SimSensor}};
+use crate::{config::DataAcquisitionConfig, interfaces::{SampleRates, Sensor, impls::simulation::sensor::SimSensor}};
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
 use proto::sensor_data::GpsData;
+use proto::sensor_health::SensorId;
 
 static LATEST_DATA: Signal<CriticalSectionRawMutex, GpsData> = Signal::new();
 
@@ -17,7 +18,8 @@ impl Sensor for SimGps {
     type Error = ();
 
     const NAME: &'static str = "Simulated GPS";
-    const TICK_INTERVAL: embassy_time::Duration = DataAcquisitionConfig::GPS_TICK_INTERVAL;
+    const ID: SensorId = SensorId::Gps;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::GPS_SAMPLE_RATES;
 
     async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
         Ok(LATEST_DATA.wait().await)
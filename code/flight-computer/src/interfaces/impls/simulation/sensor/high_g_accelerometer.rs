@@ -0,0 +1,27 @@
+use crate::{config::DataAcquisitionConfig, interfaces::{SampleRates, Sensor, impls::simulation::sensor::SimSensor}};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
+use proto::sensor_data::HighGAccelerometerData;
+use proto::sensor_health::SensorId;
+
+static LATEST_DATA: Signal<CriticalSectionRawMutex, HighGAccelerometerData> = Signal::new();
+
+#[derive(Default)]
+pub struct SimHighGAccelerometer;
+impl SimSensor for SimHighGAccelerometer {
+    fn signal() -> &'static Signal<CriticalSectionRawMutex, Self::Data> {
+        &LATEST_DATA
+    }
+}
+
+impl Sensor for SimHighGAccelerometer {
+    type Data = HighGAccelerometerData;
+    type Error = ();
+
+    const NAME: &'static str = "Simulated High-g Accelerometer";
+    const ID: SensorId = SensorId::HighGAccelerometer;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::HIGH_G_ACCELEROMETER_SAMPLE_RATES;
+
+    async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        Ok(LATEST_DATA.wait().await)
+    }
+}
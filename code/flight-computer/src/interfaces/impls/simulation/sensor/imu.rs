@@ -1,6 +1,7 @@
-use crate::{config::DataAcquisitionConfig, interfaces::{Sensor, impls::simulation::sensor::SimSensor}};
+use crate::{config::DataAcquisitionConfig, interfaces::{ImuSensor, SampleRates, Sensor, impls::simulation::sensor::SimSensor}, sync::{IMU_CALIBRATION_STATUS_SIGNAL, finish_imu_calibration, imu_calibration_requested}};
 use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
-use proto::sensor_data::ImuData;
+use proto::sensor_data::{ImuCalibrationStatus, ImuData};
+use proto::sensor_health::SensorId;
 
 static LATEST_DATA: Signal<CriticalSectionRawMutex, ImuData> = Signal::new();
 
@@ -17,9 +18,22 @@ impl Sensor for SimImu {
     type Error = ();
 
     const NAME: &'static str = "Simulated IMU";
-    const TICK_INTERVAL: embassy_time::Duration = DataAcquisitionConfig::IMU_TICK_INTERVAL;
+    const ID: SensorId = SensorId::Imu;
+    const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::IMU_SAMPLE_RATES;
 
     async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+        // simulated data needs no calibration, guided calibrations finish right away
+        if imu_calibration_requested() {
+            let level = ImuCalibrationStatus::FULLY_CALIBRATED;
+            IMU_CALIBRATION_STATUS_SIGNAL.signal(ImuCalibrationStatus { system: level, gyroscope: level, accelerometer: level, magnetometer: level });
+            finish_imu_calibration();
+        }
+
         Ok(LATEST_DATA.wait().await)
     }
 }
+
+impl ImuSensor for SimImu {
+    /// As the simulator clips it, see its `SimulatorConfig::imu_accelerometer_range`.
+    const FULL_SCALE: f32 = 4.0;
+}
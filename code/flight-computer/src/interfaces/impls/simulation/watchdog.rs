@@ -0,0 +1,54 @@
+use core::convert::Infallible;
+
+use embassy_time::{Duration, Instant};
+
+use crate::interfaces::Watchdog;
+use crate::log::error;
+
+/// Stands in for the MCU watchdog: a missed feed is logged instead of resetting the process,
+/// so the simulation keeps running and the stall can be inspected.
+///
+/// Expiry is only noticed at the next feed, or never if the supervisor itself stalls.
+#[derive(Default)]
+pub struct SimWatchdog {
+    timeout: Option<Duration>,
+    last_fed: Option<Instant>,
+    expirations: u32,
+}
+
+impl SimWatchdog {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            timeout: None,
+            last_fed: None,
+            expirations: 0,
+        }
+    }
+}
+
+impl Watchdog for SimWatchdog {
+    type Error = Infallible;
+
+    async fn start(&mut self, timeout: Duration) -> Result<(), Self::Error> {
+        self.timeout = Some(timeout);
+        self.last_fed = Some(Instant::now());
+        Ok(())
+    }
+
+    async fn feed(&mut self) -> Result<(), Self::Error> {
+        let now = Instant::now();
+        if let (Some(timeout), Some(last_fed)) = (self.timeout, self.last_fed)
+            && now - last_fed > timeout
+        {
+            self.expirations = self.expirations.saturating_add(1);
+            error!(
+                "SimWatchdog: Not fed for {} ms, the MCU would have reset ({} times so far)",
+                (now - last_fed).as_millis(),
+                self.expirations,
+            );
+        }
+        self.last_fed = Some(now);
+        Ok(())
+    }
+}
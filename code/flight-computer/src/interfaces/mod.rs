@@ -12,5 +12,17 @@ pub use sensor::*;
 mod led;
 pub use led::*;
 
+mod buzzer;
+pub use buzzer::*;
+
 mod arming_system;
-pub use arming_system::*;
\ No newline at end of file
+pub use arming_system::*;
+
+mod checkpoint_store;
+pub use checkpoint_store::*;
+
+mod watchdog;
+pub use watchdog::*;
+
+mod battery;
+pub use battery::*;
@@ -1,15 +1,92 @@
+use embassy_time::Duration;
+use proto::flight_state::FlightState;
+use proto::sensor_data::ImuData;
+use proto::sensor_health::SensorId;
 use proto::wire::Record;
 
+/// Tick interval of a sensor in each flight phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SampleRates {
+    /// Waiting on the launchpad, armed or not.
+    pub pad: Duration,
+    /// From liftoff, through boost and coast.
+    pub ascent: Duration,
+    /// Under canopy.
+    pub descent: Duration,
+    pub landed: Duration,
+}
+
+impl SampleRates {
+    #[must_use]
+    pub const fn tick_interval(&self, state: FlightState) -> Duration {
+        match state {
+            FlightState::PreArmed => self.pad,
+            FlightState::Armed => self.ascent,
+            FlightState::RecoveryActivated => self.descent,
+            FlightState::Touchdown => self.landed,
+        }
+    }
+}
+
+/// Flight state the sensors sample for: an armed rocket keeps the pad rates until liftoff.
+#[must_use]
+pub const fn sampling_state(state: FlightState, launched: bool) -> FlightState {
+    match state {
+        FlightState::Armed if !launched => FlightState::PreArmed,
+        state => state,
+    }
+}
+
 pub trait Sensor {
     type Data: Into<Record> + Clone + PartialEq + core::fmt::Debug + 'static;
     type Error: core::fmt::Debug;
 
     const NAME: &'static str;
-    const TICK_INTERVAL: embassy_time::Duration;
+    /// Selects the sensor's entry of the health report.
+    const ID: SensorId;
+    const SAMPLE_RATES: SampleRates;
 
     async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error>;
 
-    fn ticker(&self) -> embassy_time::Ticker {
-        embassy_time::Ticker::every(Self::TICK_INTERVAL)
+    /// Adapts the sensor to a flight phase, e.g. its oversampling to the phase's sample rate.
+    async fn configure(&mut self, _state: FlightState) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// An IMU, blended with the high-g accelerometer as its accelerometer nears its full scale.
+pub trait ImuSensor: Sensor<Data = ImuData> {
+    /// Accelerometer full scale on each axis, in g.
+    const FULL_SCALE: f32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATES: SampleRates = SampleRates {
+        pad: Duration::from_hz(1),
+        ascent: Duration::from_hz(2),
+        descent: Duration::from_hz(3),
+        landed: Duration::from_hz(4),
+    };
+
+    #[rstest::rstest]
+    #[case(FlightState::PreArmed, RATES.pad)]
+    #[case(FlightState::Armed, RATES.ascent)]
+    #[case(FlightState::RecoveryActivated, RATES.descent)]
+    #[case(FlightState::Touchdown, RATES.landed)]
+    fn ticks_at_the_rate_of_each_state(#[case] state: FlightState, #[case] interval: Duration) {
+        assert_eq!(RATES.tick_interval(state), interval);
+    }
+
+    #[rstest::rstest]
+    #[case(FlightState::PreArmed, false, FlightState::PreArmed)]
+    #[case(FlightState::Armed, false, FlightState::PreArmed)]
+    #[case(FlightState::Armed, true, FlightState::Armed)]
+    #[case(FlightState::RecoveryActivated, true, FlightState::RecoveryActivated)]
+    #[case(FlightState::Touchdown, true, FlightState::Touchdown)]
+    fn samples_at_the_pad_rate_until_liftoff(#[case] state: FlightState, #[case] launched: bool, #[case] sampled: FlightState) {
+        assert_eq!(sampling_state(state, launched), sampled);
     }
 }
@@ -0,0 +1,9 @@
+use embassy_time::Duration;
+
+/// Resets the flight computer unless fed in time. Once started, it cannot be stopped.
+pub trait Watchdog {
+    type Error: core::fmt::Debug;
+
+    async fn start(&mut self, timeout: Duration) -> Result<(), Self::Error>;
+    async fn feed(&mut self) -> Result<(), Self::Error>;
+}
@@ -12,10 +12,17 @@
 //! | Feature | What it enables |
 //! |---|---|
 //! | `impl_embedded` | Real hardware drivers (`embedded-hal`) — used in HW firmware |
+//! | `bmp3xx` | BMP388/BMP390 altimeter driver (implies `impl_embedded`) |
+//! | `ms5611` | MS5611 altimeter driver over SPI (implies `impl_embedded`) |
+//! | `icm42688` | ICM-42688-P IMU driver over SPI (implies `impl_embedded`) |
+//! | `mpu` | MPU-6050 / MPU-9250 IMU driver (implies `impl_embedded`) |
+//! | `adxl375` | ADXL375 high-g accelerometer driver (implies `impl_embedded`) |
+//! | `ublox` | u-blox GPS driver speaking UBX, configured at boot (implies `impl_embedded`) |
 //! | `impl_sim` | Simulator-fed postcard-rpc peripheral clients — transport-agnostic; used in SIL (HOST) and PIL |
 //! | `impl_host` | `HostFileSystem` over a host directory — orthogonal to `impl_sim`; used in the HOST binary |
-//! | `host` | Convenience alias: `impl_sim` + `impl_host` + `log` + `proto/host` — everything a HOST binary needs |
-//! | `std` | Standard library (required by `impl_sim` and `impl_host`) |
+//! | `impl_replay` | Peripherals re-flying a recorded NDJSON log, see `tasks::replay` |
+//! | `host` | Convenience alias: `impl_sim` + `impl_host` + `impl_replay` + `log` + `proto/host` — everything a HOST binary needs |
+//! | `std` | Standard library (required by `impl_sim`, `impl_host` and `impl_replay`) |
 //! | `log` | Logging via the `log` crate (default for host/test builds) |
 //! | `defmt` | Logging via `defmt` (for embedded targets) |
 //!
@@ -58,5 +65,7 @@ pub(crate) mod sync;
 pub mod interfaces;
 pub mod tasks;
 
+pub use crate::core::state_machine::detectors::apogee_detection;
+
 #[cfg(test)]
 pub mod test_utils;
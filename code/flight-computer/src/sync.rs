@@ -1,45 +1,297 @@
-use crate::log::warn;
-use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel, signal::Signal, watch::Watch};
+use core::cell::Cell;
+
+use crate::log::{error, warn};
+use embassy_sync::{blocking_mutex::{Mutex, raw::CriticalSectionRawMutex}, channel::Channel, signal::Signal, watch::Watch};
+use embassy_time::Instant;
 use proto::wire::{Record, RecordData};
-use proto::sensor_data::Altitude;
+use proto::sensor_data::{Acceleration, AltimeterData, Altitude, GpsData, HighGAccelerometerData, ImuCalibrationProfile, ImuCalibrationStatus, ImuData, PadCalibration, Pressure, Vector3};
+use proto::preflight::PreflightReport;
+use proto::flight_state::{FlightState, FlightSummary};
+use proto::beacon::Beacon;
+use proto::sensor_health::{AltitudeSource, SensorHealthReport};
 use crate::config::TasksConfig;
+use crate::core::sensors::blending;
+use crate::core::tilt::TiltStatus;
 
 pub static LATEST_ALTITUDE_SIGNAL: Signal<CriticalSectionRawMutex, Altitude> = Signal::new();
+/// Pressure matching `LATEST_ALTITUDE_SIGNAL`, only signaled while the barometer is the altitude source.
+pub static LATEST_PRESSURE_SIGNAL: Signal<CriticalSectionRawMutex, Pressure> = Signal::new();
 
 pub static FLIGHT_STATE_WATCH: Watch<CriticalSectionRawMutex, Record, { TasksConfig::FLIGHT_STATE_WATCH_CONSUMERS }> = Watch::new();
 
+/// Latest state broadcast by the FSM, `None` until the first one.
+static FLIGHT_STATE: Mutex<CriticalSectionRawMutex, Cell<Option<FlightState>>> = Mutex::new(Cell::new(None));
+
+pub fn flight_state() -> Option<FlightState> {
+    FLIGHT_STATE.lock(Cell::get)
+}
+
 pub static ALTIMETER_DATA_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, Record> = Signal::new();
 pub static GPS_DATA_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, Record> = Signal::new();
 pub static IMU_DATA_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, Record> = Signal::new();
+pub static HIGH_G_ACCELEROMETER_DATA_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, Record> = Signal::new();
+
+pub static ALTIMETER_DATA_TO_HEALTH_SIGNAL: Signal<CriticalSectionRawMutex, AltimeterData> = Signal::new();
+pub static GPS_DATA_TO_HEALTH_SIGNAL: Signal<CriticalSectionRawMutex, GpsData> = Signal::new();
+pub static IMU_DATA_TO_HEALTH_SIGNAL: Signal<CriticalSectionRawMutex, ImuData> = Signal::new();
+pub static HIGH_G_ACCELEROMETER_DATA_TO_HEALTH_SIGNAL: Signal<CriticalSectionRawMutex, HighGAccelerometerData> = Signal::new();
+
+pub static ALTIMETER_DATA_TO_SUMMARY_SIGNAL: Signal<CriticalSectionRawMutex, AltimeterData> = Signal::new();
+pub static IMU_DATA_TO_SUMMARY_SIGNAL: Signal<CriticalSectionRawMutex, ImuData> = Signal::new();
+pub static FLIGHT_SUMMARY_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, Record> = Signal::new();
+
+pub static IMU_DATA_TO_ATTITUDE_SIGNAL: Signal<CriticalSectionRawMutex, ImuData> = Signal::new();
+pub static ATTITUDE_DATA_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, Record> = Signal::new();
+/// Each IMU sample with the direction of up the attitude task estimated from it.
+pub static ATTITUDE_TO_TILT_SIGNAL: Signal<CriticalSectionRawMutex, (ImuData, Option<Vector3<f32>>)> = Signal::new();
+
+pub static GPS_DATA_TO_PREFLIGHT_SIGNAL: Signal<CriticalSectionRawMutex, GpsData> = Signal::new();
+pub static GPS_DATA_TO_BEACON_SIGNAL: Signal<CriticalSectionRawMutex, GpsData> = Signal::new();
+pub static BEACON_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, Beacon> = Signal::new();
+pub static PREFLIGHT_REPORT_TO_GROUNDSTATION_SIGNAL: Signal<CriticalSectionRawMutex, PreflightReport> = Signal::new();
+
+pub static SENSOR_HEALTH_WATCH: Watch<CriticalSectionRawMutex, SensorHealthReport, { TasksConfig::SENSOR_HEALTH_WATCH_CONSUMERS }> = Watch::new();
+
+/// Sensor feeding `LATEST_ALTITUDE_SIGNAL`, selected by the sensor health task.
+static ALTITUDE_SOURCE: Mutex<CriticalSectionRawMutex, Cell<AltitudeSource>> = Mutex::new(Cell::new(AltitudeSource::Barometer));
+
+pub fn altitude_source() -> AltitudeSource {
+    ALTITUDE_SOURCE.lock(Cell::get)
+}
+
+pub fn set_altitude_source(source: AltitudeSource) {
+    ALTITUDE_SOURCE.lock(|cell| cell.set(source));
+}
+
+/// Added to GPS altitudes fed to `LATEST_ALTITUDE_SIGNAL`, see `SensorHealthMonitor::gps_altitude_offset`.
+static GPS_ALTITUDE_OFFSET: Mutex<CriticalSectionRawMutex, Cell<Option<Altitude>>> = Mutex::new(Cell::new(None));
+
+pub fn gps_altitude_offset() -> Option<Altitude> {
+    GPS_ALTITUDE_OFFSET.lock(Cell::get)
+}
+
+pub fn set_gps_altitude_offset(offset: Altitude) {
+    GPS_ALTITUDE_OFFSET.lock(|cell| cell.set(Some(offset)));
+}
+
+/// Latest high-g sample and its arrival, blended into the IMU stream while the IMU saturates.
+static HIGH_G_ACCELEROMETER_DATA: Mutex<CriticalSectionRawMutex, Cell<Option<(HighGAccelerometerData, Instant)>>> = Mutex::new(Cell::new(None));
+
+pub fn high_g_accelerometer_data() -> Option<(HighGAccelerometerData, Instant)> {
+    HIGH_G_ACCELEROMETER_DATA.lock(Cell::get)
+}
+
+/// Accelerometer full scale of the IMU in use, set as its task starts.
+static IMU_FULL_SCALE: Mutex<CriticalSectionRawMutex, Cell<Option<Acceleration>>> = Mutex::new(Cell::new(None));
+
+pub fn imu_full_scale() -> Option<Acceleration> {
+    IMU_FULL_SCALE.lock(Cell::get)
+}
+
+pub fn set_imu_full_scale(full_scale: Acceleration) {
+    IMU_FULL_SCALE.lock(|cell| cell.set(Some(full_scale)));
+}
+
+pub static ALTIMETER_DATA_TO_CALIBRATION_SIGNAL: Signal<CriticalSectionRawMutex, AltimeterData> = Signal::new();
+
+/// Set once the launchpad is calibrated, read by the altimeter drivers.
+static PAD_CALIBRATION: Mutex<CriticalSectionRawMutex, Cell<Option<PadCalibration>>> = Mutex::new(Cell::new(None));
+
+pub fn pad_calibration() -> Option<PadCalibration> {
+    PAD_CALIBRATION.lock(Cell::get)
+}
+
+pub fn set_pad_calibration(calibration: PadCalibration) {
+    PAD_CALIBRATION.lock(|cell| cell.set(Some(calibration)));
+}
+
+/// Whether storage writes and flushes succeeded since the previous flush, `None` before the first one.
+static STORAGE_WRITES_OK: Mutex<CriticalSectionRawMutex, Cell<Option<bool>>> = Mutex::new(Cell::new(None));
+
+pub fn storage_writes_ok() -> Option<bool> {
+    STORAGE_WRITES_OK.lock(Cell::get)
+}
+
+pub fn set_storage_writes_ok(ok: bool) {
+    STORAGE_WRITES_OK.lock(|cell| cell.set(Some(ok)));
+}
+
+/// Cleared once the storage task closed the log, records are no longer routed to it from then on.
+static STORAGE_OPEN: Mutex<CriticalSectionRawMutex, Cell<bool>> = Mutex::new(Cell::new(true));
+
+pub fn storage_open() -> bool {
+    STORAGE_OPEN.lock(Cell::get)
+}
+
+/// Closing also drops the records left in the storage channels.
+pub fn set_storage_open(open: bool) {
+    STORAGE_OPEN.lock(|cell| cell.set(open));
+    if !open {
+        RECORD_TO_STORAGE_CHANNEL.clear();
+        PRIORITY_RECORD_TO_STORAGE_CHANNEL.clear();
+    }
+}
+
+/// Set by `PreflightOverrideEndpoint`, allows arming despite failing pre-flight checks short of the tilt lockout.
+/// Cleared once used to arm.
+static PREFLIGHT_OVERRIDE: Mutex<CriticalSectionRawMutex, Cell<bool>> = Mutex::new(Cell::new(false));
+
+pub fn preflight_override() -> bool {
+    PREFLIGHT_OVERRIDE.lock(Cell::get)
+}
+
+pub fn set_preflight_override(overridden: bool) {
+    PREFLIGHT_OVERRIDE.lock(|cell| cell.set(overridden));
+}
+
+/// Set by the flight summary task once it detected liftoff, cleared on arming. Until then an armed
+/// rocket is still on the pad, and its sensors keep their pad rates.
+static LAUNCHED: Mutex<CriticalSectionRawMutex, Cell<bool>> = Mutex::new(Cell::new(false));
+
+pub fn launched() -> bool {
+    LAUNCHED.lock(Cell::get)
+}
+
+pub fn set_launched(launched: bool) {
+    LAUNCHED.lock(|cell| cell.set(launched));
+}
+
+/// Apogee above the launchpad, read out by the buzzer after touchdown.
+pub static APOGEE_SIGNAL: Signal<CriticalSectionRawMutex, Altitude> = Signal::new();
+
+/// Written at touchdown, served by `FlightSummaryEndpoint`.
+static FLIGHT_SUMMARY: Mutex<CriticalSectionRawMutex, Cell<Option<FlightSummary>>> = Mutex::new(Cell::new(None));
+
+pub fn flight_summary() -> Option<FlightSummary> {
+    FLIGHT_SUMMARY.lock(Cell::get)
+}
+
+pub fn set_flight_summary(summary: FlightSummary) {
+    FLIGHT_SUMMARY.lock(|cell| cell.set(Some(summary)));
+}
+
+/// Written by the tilt task on every IMU sample, read by the pre-flight checks and `TiltInhibitsEndpoint`.
+static TILT_STATUS: Mutex<CriticalSectionRawMutex, Cell<TiltStatus>> = Mutex::new(Cell::new(TiltStatus::UNKNOWN));
+
+pub fn tilt_status() -> TiltStatus {
+    TILT_STATUS.lock(Cell::get)
+}
+
+pub fn set_tilt_status(status: TiltStatus) {
+    TILT_STATUS.lock(|cell| cell.set(status));
+}
+
+/// Set by `CalibrateImuEndpoint`, cleared once the IMU reports full calibration.
+static IMU_CALIBRATION_REQUESTED: Mutex<CriticalSectionRawMutex, Cell<bool>> = Mutex::new(Cell::new(false));
+
+/// Starts a guided IMU calibration, returning `false` if one is already running or the rocket
+/// is no longer `PreArmed`: storing the profile interrupts the flight log.
+pub fn request_imu_calibration() -> bool {
+    flight_state() == Some(FlightState::PreArmed) && !IMU_CALIBRATION_REQUESTED.lock(|cell| cell.replace(true))
+}
+
+pub fn imu_calibration_requested() -> bool {
+    IMU_CALIBRATION_REQUESTED.lock(Cell::get)
+}
+
+pub fn finish_imu_calibration() {
+    IMU_CALIBRATION_REQUESTED.lock(|cell| cell.set(false));
+}
+
+/// Calibration levels while a guided calibration runs, streamed to the ground station.
+pub static IMU_CALIBRATION_STATUS_SIGNAL: Signal<CriticalSectionRawMutex, ImuCalibrationStatus> = Signal::new();
+/// Profile read at the end of a guided calibration, persisted by the storage task.
+pub static IMU_CALIBRATION_PROFILE_SIGNAL: Signal<CriticalSectionRawMutex, ImuCalibrationProfile> = Signal::new();
 
 pub static RECORD_TO_STORAGE_CHANNEL: Channel<CriticalSectionRawMutex, Record, { TasksConfig::RECORD_TO_STORAGE_CHANNEL_DEPTH }> = Channel::new();
+pub static PRIORITY_RECORD_TO_STORAGE_CHANNEL: Channel<CriticalSectionRawMutex, Record, { TasksConfig::PRIORITY_RECORD_TO_STORAGE_CHANNEL_DEPTH }> = Channel::new();
+
+/// Records that did not fit in their storage channel, since the last report.
+static DROPPED_RECORDS: Mutex<CriticalSectionRawMutex, Cell<u32>> = Mutex::new(Cell::new(0));
+
+/// Returns and resets the count of records dropped on the way to storage.
+pub fn take_dropped_records() -> u32 {
+    DROPPED_RECORDS.lock(|cell| cell.replace(0))
+}
+
+/// Records routed through [`PRIORITY_RECORD_TO_STORAGE_CHANNEL`]: rare, and needed to make sense of the rest.
+#[must_use]
+pub const fn is_priority(payload: &RecordData) -> bool {
+    match payload {
+        RecordData::FlightState(_) | RecordData::Event(_) | RecordData::Error(_) | RecordData::PadCalibration(_) | RecordData::Preflight(_) | RecordData::FlightSummary(_) => true,
+        RecordData::Altimeter(_) | RecordData::Gps(_) | RecordData::Imu(_) | RecordData::Attitude(_) | RecordData::HighGAccelerometer(_) => false,
+    }
+}
 
 pub fn broadcast_record(record: Record) {
     // groundstation is picky about records
     match record.payload() {
-        RecordData::Gps(_) =>           GPS_DATA_TO_GROUNDSTATION_SIGNAL.signal(record.clone()),
-        RecordData::Imu(_) =>           IMU_DATA_TO_GROUNDSTATION_SIGNAL.signal(record.clone()),
-        RecordData::FlightState(_) =>   FLIGHT_STATE_WATCH.sender().send(record.clone()),
+        RecordData::FlightState(state) => {
+            FLIGHT_STATE.lock(|cell| cell.set(Some(*state)));
+            FLIGHT_STATE_WATCH.sender().send(record.clone());
+        },
+        RecordData::Imu(payload) => {
+            // health judges the IMU itself, the rest wants the best acceleration at hand
+            IMU_DATA_TO_HEALTH_SIGNAL.signal(payload.clone());
+            let blended = imu_full_scale().map_or_else(
+                || payload.clone(),
+                |full_scale| blending::blend(payload, full_scale, high_g_accelerometer_data(), Instant::now()),
+            );
+            IMU_DATA_TO_SUMMARY_SIGNAL.signal(blended.clone());
+            IMU_DATA_TO_ATTITUDE_SIGNAL.signal(blended);
+            IMU_DATA_TO_GROUNDSTATION_SIGNAL.signal(record.clone());
+        },
+        RecordData::HighGAccelerometer(payload) => {
+            HIGH_G_ACCELEROMETER_DATA.lock(|cell| cell.set(Some((*payload, Instant::now()))));
+            HIGH_G_ACCELEROMETER_DATA_TO_HEALTH_SIGNAL.signal(*payload);
+            HIGH_G_ACCELEROMETER_DATA_TO_GROUNDSTATION_SIGNAL.signal(record.clone());
+        },
+        RecordData::Gps(payload) => {
+            if altitude_source() == AltitudeSource::Gps {
+                // the detectors compare against the barometric launchpad altitude
+                LATEST_ALTITUDE_SIGNAL.signal(gps_altitude_offset().map_or(payload.altitude, |offset| payload.altitude + offset));
+            }
+            GPS_DATA_TO_HEALTH_SIGNAL.signal(payload.clone());
+            GPS_DATA_TO_PREFLIGHT_SIGNAL.signal(payload.clone());
+            GPS_DATA_TO_BEACON_SIGNAL.signal(payload.clone());
+            GPS_DATA_TO_GROUNDSTATION_SIGNAL.signal(record.clone());
+        },
         RecordData::Altimeter(payload) => {
-            LATEST_ALTITUDE_SIGNAL.signal(payload.altitude);
+            if altitude_source() == AltitudeSource::Barometer {
+                LATEST_PRESSURE_SIGNAL.signal(payload.pressure);
+                LATEST_ALTITUDE_SIGNAL.signal(payload.altitude);
+            }
+            ALTIMETER_DATA_TO_HEALTH_SIGNAL.signal(payload.clone());
+            ALTIMETER_DATA_TO_CALIBRATION_SIGNAL.signal(payload.clone());
+            ALTIMETER_DATA_TO_SUMMARY_SIGNAL.signal(payload.clone());
             ALTIMETER_DATA_TO_GROUNDSTATION_SIGNAL.signal(record.clone());
         },
-        RecordData::Event(_) | RecordData::Error(_) => (), // Errors and Events are not broadcast to the ground station
+        RecordData::Preflight(report) => PREFLIGHT_REPORT_TO_GROUNDSTATION_SIGNAL.signal(*report),
+        RecordData::FlightSummary(_) => FLIGHT_SUMMARY_TO_GROUNDSTATION_SIGNAL.signal(record.clone()),
+        RecordData::Attitude(_) => ATTITUDE_DATA_TO_GROUNDSTATION_SIGNAL.signal(record.clone()),
+        RecordData::Event(_) | RecordData::Error(_) | RecordData::PadCalibration(_) => (), // only stored, not broadcast to the ground station
     }
 
-    // storage consumes all record
-    if let Err(e) = RECORD_TO_STORAGE_CHANNEL.try_send(record) {
-        // Log or handle the error gracefully
-        warn!("Failed to send record to storage channel: {:?}", e);
+    // storage consumes all record, until the log is closed
+    if !storage_open() {
+        return;
+    }
+    let sent = if is_priority(record.payload()) {
+        PRIORITY_RECORD_TO_STORAGE_CHANNEL.try_send(record).inspect_err(|e| error!("Failed to send record to priority storage channel: {:?}", e))
+    } else {
+        RECORD_TO_STORAGE_CHANNEL.try_send(record).inspect_err(|e| warn!("Failed to send record to storage channel: {:?}", e))
+    };
+    if sent.is_err() {
+        DROPPED_RECORDS.lock(|cell| cell.set(cell.get().saturating_add(1)));
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::{ms, sensor_data::{random_altimeter_data, random_gps_data, random_imu_data}};
+    use crate::test_utils::{ms, sensor_data::{random_altimeter_data, random_gps_data, random_high_g_accelerometer_data, random_imu_data}};
 
     use super::*;
-    use proto::flight_state::FlightState;
+    use proto::uom::si::length::meter;
 
     #[test_log::test(rstest::rstest)]
     #[async_std::test]
@@ -47,12 +299,13 @@ mod tests {
     #[case(random_altimeter_data(), ALTIMETER_DATA_TO_GROUNDSTATION_SIGNAL.wait())]
     #[case(random_gps_data(), GPS_DATA_TO_GROUNDSTATION_SIGNAL.wait())]
     #[case(random_imu_data(), IMU_DATA_TO_GROUNDSTATION_SIGNAL.wait())]
+    #[case(random_high_g_accelerometer_data(), HIGH_G_ACCELEROMETER_DATA_TO_GROUNDSTATION_SIGNAL.wait())]
     #[case(FlightState::default(), async { let mut rec = FLIGHT_STATE_WATCH.receiver().expect("Not enough flight state consumers"); rec.changed().await })]
     #[timeout(ms(100))]
     async fn broadcast_record_to_groundstation(
         #[case] record: impl Into<Record>, 
         #[case] #[future] receiver: Record,
-        #[values(TasksConfig::RECORD_TO_STORAGE_CHANNEL_DEPTH)] records_sent: usize,
+        #[values(TasksConfig::PRIORITY_RECORD_TO_STORAGE_CHANNEL_DEPTH)] records_sent: usize,
     ) {
         let record = record.into();
         for _ in 0..records_sent {
@@ -64,7 +317,7 @@ mod tests {
 
         // Check if the record was sent to storage
         for _ in 0..records_sent {
-            let received_record = RECORD_TO_STORAGE_CHANNEL.receiver().receive().await;
+            let received_record = receive_stored(&record).await;
             assert_eq!(received_record, record);
         }
     }
@@ -92,6 +345,40 @@ mod tests {
         assert!(LATEST_ALTITUDE_SIGNAL.try_take().is_none(), "LATEST_ALTITUDE_SIGNAL should be empty after takes");
     }
 
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(100))]
+    async fn gps_failover_altitude_is_on_the_barometric_scale() {
+        let gps = GpsData { altitude: Altitude::new::<meter>(450.0), ..random_gps_data() };
+        set_gps_altitude_offset(Altitude::new::<meter>(-30.0));
+        set_altitude_source(AltitudeSource::Gps);
+        LATEST_ALTITUDE_SIGNAL.reset();
+
+        broadcast_record(gps.into());
+        let altitude = LATEST_ALTITUDE_SIGNAL.wait().await;
+
+        set_altitude_source(AltitudeSource::Barometer);
+        RECORD_TO_STORAGE_CHANNEL.clear();
+        assert!((altitude.get::<meter>() - 420.0).abs() < 1e-3, "Expected 420 m, got {altitude:?}");
+    }
+
+    async fn receive_stored(record: &Record) -> Record {
+        if is_priority(record.payload()) {
+            PRIORITY_RECORD_TO_STORAGE_CHANNEL.receive().await
+        } else {
+            RECORD_TO_STORAGE_CHANNEL.receive().await
+        }
+    }
+
+    const fn storage_channel_depth(record: &Record) -> usize {
+        if is_priority(record.payload()) {
+            TasksConfig::PRIORITY_RECORD_TO_STORAGE_CHANNEL_DEPTH
+        } else {
+            TasksConfig::RECORD_TO_STORAGE_CHANNEL_DEPTH
+        }
+    }
+
     #[test_log::test(rstest::rstest)]
     #[async_std::test]
     #[serial_test::serial]
@@ -104,17 +391,81 @@ mod tests {
         #[case] record: impl Into<Record>, 
     ) {
         let record = record.into();
+        let depth = storage_channel_depth(&record);
+        take_dropped_records();
 
         // First, fill the channel to capacity
-        for _ in 0..(2*TasksConfig::RECORD_TO_STORAGE_CHANNEL_DEPTH) {
+        for _ in 0..(2*depth) {
             broadcast_record(record.clone());
         }
 
         // Now, receive all records and ensure they are correct
-        for _ in 0..TasksConfig::RECORD_TO_STORAGE_CHANNEL_DEPTH {
-            let received_record = RECORD_TO_STORAGE_CHANNEL.receiver().receive().await;
+        for _ in 0..depth {
+            let received_record = receive_stored(&record).await;
             assert_eq!(received_record, record);
         }
         assert!(RECORD_TO_STORAGE_CHANNEL.receiver().try_receive().is_err(), "Channel should be empty after receiving all records");
-    }    
+        assert!(PRIORITY_RECORD_TO_STORAGE_CHANNEL.receiver().try_receive().is_err(), "Channel should be empty after receiving all records");
+        assert_eq!(take_dropped_records(), u32::try_from(depth).expect("channel depth fits in a u32"));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(100))]
+    async fn priority_records_bypass_full_sensor_channel() {
+        let sensor_record = Record::from(random_imu_data());
+        let flight_state_record = Record::from(FlightState::RecoveryActivated);
+        take_dropped_records();
+
+        for _ in 0..=TasksConfig::RECORD_TO_STORAGE_CHANNEL_DEPTH {
+            broadcast_record(sensor_record.clone());
+        }
+        broadcast_record(flight_state_record.clone());
+
+        assert_eq!(PRIORITY_RECORD_TO_STORAGE_CHANNEL.receive().await, flight_state_record);
+        assert_eq!(take_dropped_records(), 1);
+
+        RECORD_TO_STORAGE_CHANNEL.clear();
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(100))]
+    async fn records_are_not_stored_once_storage_closed() {
+        take_dropped_records();
+        broadcast_record(random_gps_data().into());
+        set_storage_open(false);
+
+        for _ in 0..=TasksConfig::RECORD_TO_STORAGE_CHANNEL_DEPTH {
+            broadcast_record(random_gps_data().into());
+        }
+        broadcast_record(FlightState::Touchdown.into());
+        let routed = RECORD_TO_STORAGE_CHANNEL.receiver().try_receive().is_ok()
+            || PRIORITY_RECORD_TO_STORAGE_CHANNEL.receiver().try_receive().is_ok();
+
+        set_storage_open(true);
+        assert!(!routed, "Records should not be routed to a closed log");
+        assert_eq!(take_dropped_records(), 0);
+        assert!(GPS_DATA_TO_GROUNDSTATION_SIGNAL.try_take().is_some(), "Records should still reach the ground station");
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(100))]
+    async fn imu_calibration_only_starts_pre_armed() {
+        for state in [FlightState::Armed, FlightState::RecoveryActivated, FlightState::Touchdown] {
+            broadcast_record(state.into());
+            assert!(!request_imu_calibration(), "Calibration should be refused in {state}");
+        }
+
+        broadcast_record(FlightState::PreArmed.into());
+        assert!(request_imu_calibration());
+        assert!(!request_imu_calibration(), "Calibration should already be running");
+
+        finish_imu_calibration();
+        PRIORITY_RECORD_TO_STORAGE_CHANNEL.clear();
+    }
 }
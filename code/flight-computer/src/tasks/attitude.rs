@@ -0,0 +1,25 @@
+use embassy_time::Instant;
+
+use crate::config::AhrsConfig;
+use crate::core::ahrs::Ahrs;
+use crate::sync::{ATTITUDE_TO_TILT_SIGNAL, IMU_DATA_TO_ATTITUDE_SIGNAL, broadcast_record};
+
+#[inline]
+pub async fn attitude_task() -> ! {
+    let mut ahrs = Ahrs::default();
+    let mut last_published: Option<Instant> = None;
+
+    loop {
+        let data = IMU_DATA_TO_ATTITUDE_SIGNAL.wait().await;
+        let now = Instant::now();
+        ahrs.update(&data, now);
+
+        if let Some(attitude) = ahrs.attitude()
+            && last_published.is_none_or(|last| now.duration_since(last) >= AhrsConfig::PUBLISH_INTERVAL)
+        {
+            last_published = Some(now);
+            broadcast_record(attitude.into());
+        }
+        ATTITUDE_TO_TILT_SIGNAL.signal((data, ahrs.up()));
+    }
+}
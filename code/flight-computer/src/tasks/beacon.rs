@@ -0,0 +1,37 @@
+use embassy_futures::select::{Either, select};
+use embassy_time::{Instant, Ticker, with_timeout};
+use proto::event::TaskId;
+
+use crate::config::{BeaconConfig, WatchdogConfig};
+use crate::core::beacon::BeaconTracker;
+use crate::core::watchdog::{Criticality, WatchdogToken};
+use crate::interfaces::Battery;
+use crate::log::{info, warn};
+use crate::sync::{BEACON_TO_GROUNDSTATION_SIGNAL, GPS_DATA_TO_BEACON_SIGNAL, flight_summary};
+
+/// Recovery beacon, run once everything but the GPS and the ground station link is shut down.
+#[inline]
+pub async fn beacon_task<B: Battery>(mut battery: B) -> ! {
+    let watchdog = WatchdogToken::register(TaskId::Beacon, WatchdogConfig::BEACON_DEADLINES, Criticality::NonCritical)
+        .expect("Beacon: Not enough watchdog slots");
+
+    let mut tracker = BeaconTracker::new();
+    let mut ticker = Ticker::every(BeaconConfig::INTERVAL);
+    info!("Beacon: Transmitting every {} s", BeaconConfig::INTERVAL.as_secs());
+
+    loop {
+        watchdog.feed();
+
+        match select(GPS_DATA_TO_BEACON_SIGNAL.wait(), ticker.next()).await {
+            Either::First(data) => tracker.update_gps(data, Instant::now()),
+            Either::Second(()) => {
+                let voltage = match with_timeout(BeaconConfig::BATTERY_TIMEOUT, battery.voltage()).await {
+                    Ok(Ok(voltage)) => Some(voltage),
+                    Ok(Err(e)) => { warn!("Beacon: Failed to read battery voltage: {:?}", e); None },
+                    Err(_) => { warn!("Beacon: Timed out reading battery voltage"); None },
+                };
+                BEACON_TO_GROUNDSTATION_SIGNAL.signal(tracker.beacon(Instant::now(), voltage, flight_summary()));
+            },
+        }
+    }
+}
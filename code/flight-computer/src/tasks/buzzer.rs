@@ -0,0 +1,57 @@
+use embassy_futures::select::{Either3, select3};
+use proto::flight_state::FlightState;
+use proto::sensor_health::SensorHealthReport;
+use proto::wire::RecordData;
+
+use crate::core::beep_code::{beep_code, play};
+use crate::interfaces::Buzzer;
+use crate::log::{info, warn};
+use crate::sync::{APOGEE_SIGNAL, FLIGHT_STATE_WATCH, SENSOR_HEALTH_WATCH};
+
+#[inline]
+pub async fn buzzer_task<B: Buzzer>(mut buzzer: B) -> ! {
+    let mut flight_state_receiver = FLIGHT_STATE_WATCH.receiver()
+        .expect("Buzzer: Not enough flight state consumers");
+    let mut health_receiver = SENSOR_HEALTH_WATCH.receiver()
+        .expect("Buzzer: Not enough sensor health consumers");
+
+    let mut state = FlightState::default();
+    let mut health = SensorHealthReport::default();
+    let mut apogee = None;
+
+    loop {
+        let code = beep_code(state, &health, apogee);
+
+        let beeping = async {
+            match code {
+                Some(code) => loop {
+                    if let Err(e) = play(&mut buzzer, code).await {
+                        warn!("Buzzer: Failed to beep: {:?}", e);
+                    }
+                },
+                None => core::future::pending::<()>().await,
+            }
+        };
+
+        // a new status interrupts the running code
+        match select3(flight_state_receiver.changed(), health_receiver.changed(), beeping).await {
+            Either3::First(record) => {
+                if let RecordData::FlightState(new_state) = record.payload() {
+                    state = *new_state;
+                }
+            },
+            Either3::Second(report) => health = report,
+            Either3::Third(()) => {},
+        }
+
+        if let Some(altitude) = APOGEE_SIGNAL.try_take() {
+            apogee = Some(altitude);
+        }
+        let next_code = beep_code(state, &health, apogee);
+        if next_code != code {
+            info!("Buzzer: Beeping {:?}", next_code);
+        }
+
+        buzzer.off().await.unwrap_or_else(|e| warn!("Buzzer: Failed to silence: {:?}", e));
+    }
+}
@@ -1,42 +1,86 @@
-use crate::log::info;
-use proto::flight_state::FlightState;
+use embassy_time::with_timeout;
+use crate::log::{error, info};
+use proto::event::TaskId;
+use proto::flight_state::{FlightCheckpoint, FlightState};
 
-use crate::{core::state_machine::FlightStateMachine, interfaces::{ArmingSystem, DeploymentSystem, Led}, sync::broadcast_record};
+use crate::config::{CheckpointConfig, WatchdogConfig};
+use crate::core::watchdog::{Criticality, WatchdogToken};
+use crate::{core::state_machine::{FlightStateMachine, ResumedFlightStateMachine, detectors::ApogeeDetection}, interfaces::{ArmingSystem, CheckpointStore, DeploymentSystem, Led}, sync::broadcast_record};
 
 #[inline]
-pub async fn finite_state_machine_task<A, LedA, D, LedD>(
+pub async fn finite_state_machine_task<A, LedA, D, LedD, AD, CS>(
     arm_button: A,
     arm_button_led: LedA,
     deployment_system: D,
     deployment_system_led: LedD,
+    apogee_detection: AD,
+    mut checkpoint_store: CS,
 )
 where
     A: ArmingSystem,
     LedA: Led,
     D: DeploymentSystem,
     LedD: Led,
+    AD: ApogeeDetection,
+    CS: CheckpointStore,
 {
-    let fsm = FlightStateMachine::new(
-        arm_button,
-        arm_button_led,
-        deployment_system,
-        deployment_system_led,
-    ).await;
-    update_flight_state(FlightState::default());
+    // unregistered once landed
+    let watchdog = WatchdogToken::register(TaskId::FlightStateMachine, WatchdogConfig::FSM_DEADLINES, Criticality::FlightCritical)
+        .expect("FSM: Not enough watchdog slots");
 
-    let fsm = fsm.wait_arm().await;
-    update_flight_state(FlightState::Armed);
+    watchdog.supervise(async move {
+        let checkpoint = match with_timeout(CheckpointConfig::LOAD_TIMEOUT, checkpoint_store.load()).await {
+            Ok(Ok(checkpoint)) => checkpoint,
+            Ok(Err(e)) => { error!("FSM: Failed to load checkpoint: {:?}", e); None },
+            Err(_) => { error!("FSM: Timed out loading checkpoint"); None },
+        };
 
-    let fsm = fsm.wait_activate_recovery().await;
-    update_flight_state(FlightState::RecoveryActivated);
+        let fsm = FlightStateMachine::new(
+            arm_button,
+            arm_button_led,
+            deployment_system,
+            deployment_system_led,
+            apogee_detection,
+        ).await;
 
-    let fsm = fsm.wait_touchdown().await;
-    update_flight_state(FlightState::Touchdown);
+        let fsm = match fsm.resume(checkpoint).await {
+            ResumedFlightStateMachine::PreArmed(fsm) => {
+                update_flight_state(fsm.checkpoint(), &mut checkpoint_store).await;
 
-    fsm.shutdown().await;
+                let fsm = fsm.wait_arm().await;
+                update_flight_state(fsm.checkpoint(), &mut checkpoint_store).await;
+
+                fsm.wait_activate_recovery().await
+            },
+            ResumedFlightStateMachine::Armed(fsm) => {
+                update_flight_state(fsm.checkpoint(), &mut checkpoint_store).await;
+
+                fsm.wait_activate_recovery().await
+            },
+            ResumedFlightStateMachine::RecoveryActivated(fsm) => fsm,
+        };
+        update_flight_state(fsm.checkpoint(), &mut checkpoint_store).await;
+
+        let fsm = fsm.wait_touchdown().await;
+        update_flight_state(fsm.checkpoint(), &mut checkpoint_store).await;
+
+        fsm.shutdown().await;
+    }).await;
 }
 
-fn update_flight_state(state: FlightState) {
-    broadcast_record(state.into());
-    info!("Flight Computer {}", state);
+async fn update_flight_state<CS: CheckpointStore>(checkpoint: FlightCheckpoint, checkpoint_store: &mut CS) {
+    broadcast_record(checkpoint.state.into());
+    info!("Flight Computer {}", checkpoint.state);
+
+    // a landed flight has nothing left to resume
+    let stored = if checkpoint.state == FlightState::Touchdown {
+        with_timeout(CheckpointConfig::STORE_TIMEOUT, checkpoint_store.clear()).await
+    } else {
+        with_timeout(CheckpointConfig::STORE_TIMEOUT, checkpoint_store.store(&checkpoint)).await
+    };
+    match stored {
+        Ok(Ok(())) => {},
+        Ok(Err(e)) => error!("FSM: Failed to store checkpoint: {:?}", e),
+        Err(_) => error!("FSM: Timed out storing checkpoint"),
+    }
 }
@@ -0,0 +1,55 @@
+use embassy_futures::select::{Either3, select3};
+use embassy_time::Instant;
+use proto::flight_state::FlightState;
+use proto::uom::si::{acceleration::meter_per_second_squared, length::meter, velocity::meter_per_second};
+use proto::wire::RecordData;
+
+use crate::core::flight_summary::FlightSummaryTracker;
+use crate::log::info;
+use crate::sync::{ALTIMETER_DATA_TO_SUMMARY_SIGNAL, FLIGHT_STATE_WATCH, IMU_DATA_TO_SUMMARY_SIGNAL, broadcast_record, launched, pad_calibration, set_flight_summary, set_launched};
+
+#[inline]
+pub async fn flight_summary_task() -> ! {
+    let mut flight_state_receiver = FLIGHT_STATE_WATCH.receiver()
+        .expect("FlightSummary: Not enough flight state consumers");
+
+    let mut tracker = FlightSummaryTracker::new();
+
+    loop {
+        match select3(
+            flight_state_receiver.changed(),
+            ALTIMETER_DATA_TO_SUMMARY_SIGNAL.wait(),
+            IMU_DATA_TO_SUMMARY_SIGNAL.wait(),
+        ).await {
+            Either3::First(record) => match record.payload() {
+                RecordData::FlightState(FlightState::Armed) => {
+                    tracker.arm(Instant::now(), pad_calibration().map(|calibration| calibration.ground_altitude));
+                },
+                RecordData::FlightState(FlightState::Touchdown) => {
+                    tracker.touchdown(Instant::now());
+                    let summary = tracker.summary();
+                    info!(
+                        "FlightSummary: Apogee {} m, max velocity {} m/s, max acceleration {} m/s^2",
+                        summary.apogee.map_or(f32::NAN, |apogee| apogee.get::<meter>()),
+                        summary.max_velocity.map_or(f32::NAN, |velocity| velocity.get::<meter_per_second>()),
+                        summary.max_acceleration.map_or(f32::NAN, |acceleration| acceleration.get::<meter_per_second_squared>()),
+                    );
+
+                    set_flight_summary(summary);
+                    broadcast_record(summary.into());
+                },
+                _ => {},
+            },
+            Either3::Second(data) => tracker.update_altimeter(&data, Instant::now()),
+            Either3::Third(data) => tracker.update_imu(&data, Instant::now()),
+        }
+
+        // cleared again by the next arming
+        if tracker.launched() != launched() {
+            if tracker.launched() {
+                info!("FlightSummary: Liftoff detected");
+            }
+            set_launched(tracker.launched());
+        }
+    }
+}
@@ -9,39 +9,54 @@ use embassy_time::Ticker;
 use embassy_time::with_timeout;
 use postcard_rpc::header::VarSeq;
 use postcard_rpc::server::AsWireTxErrorKind;
+use postcard_rpc::Topic;
 use postcard_rpc::server::{Sender as PostcardSender, WireTx};
-use proto::wire::{Record, RecordTopic};
+use proto::Serialize;
+use proto::event::TaskId;
+use proto::flight_state::FlightState;
+use proto::wire::{BeaconTopic, ImuCalibrationTopic, PreflightReportTopic, RecordData, RecordTopic};
 
 use crate::log::warn;
-use crate::config::GroundStationConfig;
+use crate::config::{GroundStationConfig, WatchdogConfig};
+use crate::core::led_pattern::{PatternLed, link_pattern};
+use crate::core::watchdog::{Criticality, WatchdogToken};
 use crate::interfaces::Led;
 use crate::sync::ALTIMETER_DATA_TO_GROUNDSTATION_SIGNAL;
+use crate::sync::ATTITUDE_DATA_TO_GROUNDSTATION_SIGNAL;
+use crate::sync::BEACON_TO_GROUNDSTATION_SIGNAL;
 use crate::sync::FLIGHT_STATE_WATCH;
+use crate::sync::FLIGHT_SUMMARY_TO_GROUNDSTATION_SIGNAL;
 use crate::sync::GPS_DATA_TO_GROUNDSTATION_SIGNAL;
+use crate::sync::HIGH_G_ACCELEROMETER_DATA_TO_GROUNDSTATION_SIGNAL;
+use crate::sync::IMU_CALIBRATION_STATUS_SIGNAL;
 use crate::sync::IMU_DATA_TO_GROUNDSTATION_SIGNAL;
+use crate::sync::PREFLIGHT_REPORT_TO_GROUNDSTATION_SIGNAL;
 
 static UID_COUNTER: AtomicU32 = AtomicU32::new(0);
 
+/// Returns whether the message was published.
 #[inline]
-async fn send_to_ground_station<Tx>(postcard_sender: &PostcardSender<Tx>, msg: &Record)
+async fn send_to_ground_station<Tx, T>(postcard_sender: &PostcardSender<Tx>, msg: &T::Message) -> bool
 where
     Tx: WireTx,
+    T: Topic,
+    T::Message: Serialize,
 {
     match with_timeout(
         GroundStationConfig::PUBLISH_TIMEOUT,
-        postcard_sender.publish::<RecordTopic>(
+        postcard_sender.publish::<T>(
             VarSeq::Seq4(UID_COUNTER.fetch_add(1, Ordering::Relaxed)),
             msg,
         ),
     ).await {
-        Err(_) => warn!("GroundStation: Timed out sending record to ground station"),
-        Ok(Err(err)) => warn!("GroundStation: Failed to send record to ground station: {:?}", Debug2Format(&err.as_kind())),
-        Ok(Ok(())) => (),
+        Err(_) => { warn!("GroundStation: Timed out sending {} to ground station", T::PATH); false },
+        Ok(Err(err)) => { warn!("GroundStation: Failed to send {} to ground station: {:?}", T::PATH, Debug2Format(&err.as_kind())); false },
+        Ok(Ok(())) => true,
     }
 }
 
 #[inline]
-pub async fn groundstation_task<Tx, LED>(postcard_sender: &PostcardSender<Tx>, mut led: LED) -> !
+pub async fn groundstation_task<Tx, LED>(postcard_sender: &PostcardSender<Tx>, led: LED) -> !
 where
     Tx: WireTx,
     LED: Led,
@@ -50,31 +65,61 @@ where
 
     let mut sensor_data_ticker = Ticker::every(GroundStationConfig::SEND_SENSOR_DATA_TICK_INTERVAL);
 
+    let mut led = PatternLed::new(led);
+    led.set(link_pattern(false));
+
+    let watchdog = WatchdogToken::register(TaskId::GroundStation, WatchdogConfig::GROUND_STATION_DEADLINES, Criticality::NonCritical)
+        .expect("GroundStation: Not enough watchdog slots");
+
     loop {
-        let result = select(
+        watchdog.feed();
+
+        let result = led.play_until(select(
             flight_state_receiver.changed(),
             sensor_data_ticker.next(),
-        ).await;
-
-        led.on().await.unwrap_or_else(|e| warn!("GroundStation: Status Led error: {:?}", e));
+        )).await;
 
+        // a link without anything to send keeps its last pattern
+        let mut sent = None;
         match result {
             Either::First(state) => {
-                send_to_ground_station(postcard_sender, &state).await;
+                if matches!(state.payload(), RecordData::FlightState(FlightState::Touchdown)) {
+                    sensor_data_ticker = Ticker::every(GroundStationConfig::LANDED_SEND_SENSOR_DATA_TICK_INTERVAL);
+                }
+                sent = Some(send_to_ground_station::<_, RecordTopic>(postcard_sender, &state).await);
             },
+            // the link counts as down as soon as one publish fails
             Either::Second(()) => {
                 for signal in [
                     &GPS_DATA_TO_GROUNDSTATION_SIGNAL,
                     &IMU_DATA_TO_GROUNDSTATION_SIGNAL,
+                    &HIGH_G_ACCELEROMETER_DATA_TO_GROUNDSTATION_SIGNAL,
                     &ALTIMETER_DATA_TO_GROUNDSTATION_SIGNAL,
+                    &ATTITUDE_DATA_TO_GROUNDSTATION_SIGNAL,
+                    &FLIGHT_SUMMARY_TO_GROUNDSTATION_SIGNAL,
                 ] {
                     if let Some(record) = signal.try_take() {
-                        send_to_ground_station(postcard_sender, &record).await;
+                        let published = send_to_ground_station::<_, RecordTopic>(postcard_sender, &record).await;
+                        sent = Some(sent.unwrap_or(true) && published);
                     }
                 }
+                if let Some(status) = IMU_CALIBRATION_STATUS_SIGNAL.try_take() {
+                    let published = send_to_ground_station::<_, ImuCalibrationTopic>(postcard_sender, &status).await;
+                    sent = Some(sent.unwrap_or(true) && published);
+                }
+                if let Some(report) = PREFLIGHT_REPORT_TO_GROUNDSTATION_SIGNAL.try_take() {
+                    let published = send_to_ground_station::<_, PreflightReportTopic>(postcard_sender, &report).await;
+                    sent = Some(sent.unwrap_or(true) && published);
+                }
+                if let Some(beacon) = BEACON_TO_GROUNDSTATION_SIGNAL.try_take() {
+                    let published = send_to_ground_station::<_, BeaconTopic>(postcard_sender, &beacon).await;
+                    sent = Some(sent.unwrap_or(true) && published);
+                }
             },
         }
 
-        led.off().await.unwrap_or_else(|e| warn!("GroundStation: Status Led error: {:?}", e));
+        if let Some(connected) = sent {
+            led.set(link_pattern(connected));
+        }
     }
 }
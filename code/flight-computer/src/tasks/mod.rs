@@ -1,17 +1,31 @@
 use core::future::Future;
 
 use crate::log::info;
-use embassy_futures::join::{join, join3};
+use embassy_futures::join::{join, join3, join4};
 use embassy_futures::select::{Either, select};
 
 mod finite_state_machine;
 pub use finite_state_machine::finite_state_machine_task;
 mod sensor;
-pub use sensor::sensor_task;
+pub use sensor::{imu_sensor_task, sensor_task};
 mod storage;
 pub use storage::storage_task;
 mod groundstation;
 pub use groundstation::groundstation_task;
+mod sensor_health;
+pub use sensor_health::sensor_health_task;
+mod flight_summary;
+pub use flight_summary::flight_summary_task;
+mod tilt;
+pub use tilt::tilt_task;
+mod attitude;
+pub use attitude::attitude_task;
+mod beacon;
+pub use beacon::beacon_task;
+mod buzzer;
+pub use buzzer::buzzer_task;
+mod watchdog;
+pub use watchdog::watchdog_task;
 pub mod postcard;
 pub use postcard::{postcard_server_task, postcard_server_task_oneshot};
 
@@ -21,6 +35,15 @@ pub use simulation::postcard_sim_server_task;
 #[cfg(feature = "impl_sim")]
 pub mod simulation;
 
+#[cfg(feature = "impl_replay")]
+pub mod replay;
+
+/// Runs the flight, then the recovery beacon once the FSM and storage tasks completed.
+///
+/// Landed, only the GPS, the links, the buzzer and the watchdog keep running next to the beacon:
+/// everything else is shut down to save the battery until the rocket is found.
+///
+/// Boards without a high-g accelerometer pass [`core::future::pending`] as `high_g_accelerometer_task`.
 #[inline]
 #[allow(clippy::too_many_arguments)]
 pub async fn run_flight_computer(
@@ -30,26 +53,98 @@ pub async fn run_flight_computer(
     altimeter_task: impl Future,
     gps_task: impl Future,
     imu_task: impl Future,
+    high_g_accelerometer_task: impl Future,
     groundstation_task: impl Future,
+    buzzer_task: impl Future,
+    watchdog_task: impl Future,
+    beacon_task: impl Future,
     #[cfg(feature = "impl_sim")] flight_state_sim_publisher_task: impl Future,
 ) {
+    let essential = join3(
+        join(postcard_task, groundstation_task),
+        gps_task,
+        join(buzzer_task, watchdog_task),
+    );
+
     #[cfg(feature = "impl_sim")]
-    let always_on = join(
-        join3(postcard_task, altimeter_task, gps_task),
-        join3(imu_task, groundstation_task, flight_state_sim_publisher_task),
+    let in_flight = join3(
+        join3(altimeter_task, imu_task, high_g_accelerometer_task),
+        flight_state_sim_publisher_task,
+        join4(sensor_health_task(), flight_summary_task(), tilt_task(), attitude_task()),
     );
     #[cfg(not(feature = "impl_sim"))]
-    let always_on = join(
-        join3(postcard_task, altimeter_task, gps_task),
-        join3(imu_task, groundstation_task, core::future::pending::<()>()),
+    let in_flight = join3(
+        join3(altimeter_task, imu_task, high_g_accelerometer_task),
+        core::future::pending::<()>(),
+        join4(sensor_health_task(), flight_summary_task(), tilt_task(), attitude_task()),
     );
 
-    if matches!(
-        select(join(finite_state_machine_task, storage_task), always_on).await,
-        Either::First(((), ()))
-    ) {
-        info!("Flight Computer: Finite state machine and storage tasks completed");
-    }
+    let flight = async {
+        if matches!(
+            select(join(finite_state_machine_task, storage_task), in_flight).await,
+            Either::First(((), ()))
+        ) {
+            info!("Flight Computer: Finite state machine and storage tasks completed");
+        }
+
+        info!("Flight Computer: Entering recovery beacon mode");
+        beacon_task.await;
+    };
+
+    select(flight, essential).await;
 
     info!("Flight Computer: Shutdown complete");
-}
\ No newline at end of file
+}
+
+// the simulator build adds the flight state publisher to the in-flight tasks
+#[cfg(all(test, feature = "impl_sim"))]
+mod tests {
+    use core::cell::Cell;
+    use core::future::pending;
+    use core::pin::pin;
+
+    use crate::config::{BeaconConfig, StorageConfig};
+    use crate::test_utils::clock::SimClock;
+
+    use super::*;
+
+    const TOUCHDOWN_MS: u64 = 60_000;
+    const SAMPLE_MS: u64 = 100;
+
+    #[rstest::rstest]
+    #[serial_test::serial]
+    fn beacon_takes_over_once_flight_and_log_completed() {
+        let clock = SimClock::default();
+        let (altimeter, gps, beacon) = (Cell::new(0), Cell::new(0), Cell::new(0));
+        let log_closed_ms = TOUCHDOWN_MS + StorageConfig::TOUCHDOWN_HOLD_DURATION.as_millis();
+        let beacon_ms = BeaconConfig::INTERVAL.as_millis();
+
+        let mut flight_computer = pin!(run_flight_computer(
+            clock.until(TOUCHDOWN_MS),
+            clock.until(log_closed_ms),
+            pending::<()>(),
+            clock.every(SAMPLE_MS, &altimeter),
+            clock.every(SAMPLE_MS, &gps),
+            pending::<()>(),
+            pending::<()>(),
+            pending::<()>(),
+            pending::<()>(),
+            pending::<()>(),
+            clock.every(beacon_ms, &beacon),
+            pending::<()>(),
+        ));
+
+        let before_close_ms = log_closed_ms - SAMPLE_MS;
+        assert!(clock.run_until(flight_computer.as_mut(), before_close_ms, SAMPLE_MS).is_none());
+        let in_flight_samples = altimeter.get();
+        assert!(in_flight_samples > 0);
+        assert_eq!(gps.get(), in_flight_samples);
+        assert_eq!(beacon.get(), 0, "No beacon while the log is still open");
+
+        assert!(clock.run_until(flight_computer.as_mut(), log_closed_ms + 3 * beacon_ms, SAMPLE_MS).is_none());
+        let landed_samples = u32::try_from((clock.now() - before_close_ms) / SAMPLE_MS).expect("Landed samples should fit");
+        assert_eq!(altimeter.get(), in_flight_samples, "In-flight tasks should stop with the log");
+        assert_eq!(gps.get(), in_flight_samples + landed_samples, "The GPS should keep sampling");
+        assert_eq!(beacon.get(), 3);
+    }
+}
@@ -4,9 +4,12 @@ use crate::log::{debug, info, warn};
 use embassy_time::Timer;
 use postcard_rpc::{header::VarHeader, server::{Server, SpawnContext}};
 use proto::{PingRequest, PingResponse};
+use proto::trace::TraceSpan;
+use proto::flight_state::FlightSummary;
+use proto::tilt::TiltAction;
 use proto::wire::GlobalTickHz;
 
-use crate::{config::PostcardConfig, interfaces::Led};
+use crate::{config::PostcardConfig, core::{led_pattern::{PatternLed, link_pattern}, trace::pop_span}, interfaces::Led, sync::{flight_summary, request_imu_calibration, set_preflight_override, tilt_status}};
 
 #[derive(Default)]
 pub struct Context {
@@ -31,6 +34,41 @@ pub fn embassy_time_tick_hz_handler(_context: &mut Context, _header: VarHeader,
     GlobalTickHz::set_global_tick_hz(embassy_time::TICK_HZ)
 }
 
+pub fn trace_span_handler(_context: &mut Context, _header: VarHeader, _rqst: ()) -> Option<TraceSpan> {
+    pop_span()
+}
+
+pub fn calibrate_imu_handler(_context: &mut Context, _header: VarHeader, _rqst: ()) -> bool {
+    let started = request_imu_calibration();
+    if started {
+        info!("IMU calibration started");
+    } else {
+        warn!("IMU calibration refused, one is running or the rocket is no longer pre-armed");
+    }
+    started
+}
+
+pub fn preflight_override_handler(_context: &mut Context, _header: VarHeader, overridden: bool) {
+    if overridden {
+        warn!("Pre-flight checks overridden from the ground station");
+    } else {
+        info!("Pre-flight override revoked");
+    }
+    set_preflight_override(overridden);
+}
+
+pub fn flight_summary_handler(_context: &mut Context, _header: VarHeader, _rqst: ()) -> Option<FlightSummary> {
+    flight_summary()
+}
+
+pub fn tilt_inhibits_handler(_context: &mut Context, _header: VarHeader, action: TiltAction) -> bool {
+    let inhibited = tilt_status().inhibits(action);
+    if inhibited {
+        warn!("{} inhibited by the tilt lockout", action);
+    }
+    inhibited
+}
+
 /// Handles the server management for GS connections.
 /// On disconnect, waits and reconnects (GS is observational).
 ///
@@ -40,7 +78,7 @@ pub fn embassy_time_tick_hz_handler(_context: &mut Context, _header: VarHeader,
 /// an outer accept loop instead.
 pub async fn postcard_server_task<Tx, Rx, Buf, D, LED>(
     mut server: Server<Tx, Rx, Buf, D>,
-    mut led: LED,
+    led: LED,
 ) -> !
 where
     Tx: postcard_rpc::server::WireTx,
@@ -49,12 +87,13 @@ where
     D: postcard_rpc::server::Dispatch<Tx = Tx>,
     LED: Led,
 {
+    let mut led = PatternLed::new(led);
     loop {
-        led.on().await.unwrap_or_else(|e| warn!("Postcard server: Status Led error: {:?}", e));
-        let _ = server.run().await;
-        led.off().await.unwrap_or_else(|e| warn!("Postcard server: Status Led error: {:?}", e));
+        led.set(link_pattern(true));
+        let _ = led.play_until(server.run()).await;
+        led.set(link_pattern(false));
         debug!("Postcard server disconnected, waiting for reconnect...");
-        Timer::after(PostcardConfig::RECONNECT_INTERVAL).await;
+        led.play_until(Timer::after(PostcardConfig::RECONNECT_INTERVAL)).await;
     }
 }
 
@@ -62,7 +101,7 @@ where
 /// disconnects, then returns; the caller owns the outer accept loop.
 pub async fn postcard_server_task_oneshot<Tx, Rx, Buf, D, LED>(
     mut server: Server<Tx, Rx, Buf, D>,
-    mut led: LED,
+    led: LED,
 )
 where
     Tx: postcard_rpc::server::WireTx,
@@ -71,9 +110,11 @@ where
     D: postcard_rpc::server::Dispatch<Tx = Tx>,
     LED: Led,
 {
-    led.on().await.unwrap_or_else(|e| warn!("Postcard server: Status Led error: {:?}", e));
-    let _ = server.run().await;
-    led.off().await.unwrap_or_else(|e| warn!("Postcard server: Status Led error: {:?}", e));
+    let mut led = PatternLed::new(led);
+    led.set(link_pattern(true));
+    let _ = led.play_until(server.run()).await;
+    led.set(link_pattern(false));
+    led.refresh().await;
     debug!("Postcard server disconnected");
 }
 
@@ -0,0 +1,77 @@
+//! Entry point re-flying a recorded flight on the host.
+//!
+//! The FSM, storage, sensor health, flight summary, tilt and attitude tasks run unchanged against the replay
+//! peripherals, which emit the log's samples at their recorded times. The FSM's decisions are
+//! then compared with the flight state changes of the original flight.
+
+use embassy_futures::join::{join, join3, join4};
+use embassy_futures::select::{Either3, select3};
+use embassy_time::{Instant, Timer};
+use proto::wire::RecordData;
+
+pub use crate::core::replay::{Transition, TransitionComparison};
+use crate::{
+    config::ReplayConfig,
+    core::{replay::compare_transitions, state_machine::detectors::AnyApogeeDetection},
+    interfaces::{
+        impls::replay::{ReplayCheckpointStore, ReplayLed, ReplayLog, ReplayRecovery},
+        FileSystem,
+    },
+    log::info,
+    sync::{FLIGHT_STATE_WATCH, set_preflight_override},
+    tasks::{attitude_task, finite_state_machine_task, flight_summary_task, imu_sensor_task, sensor_health_task, sensor_task, storage_task, tilt_task},
+};
+
+/// Replays `log` in real time, logging to `filesystem`, and compares the decisions of both flights.
+///
+/// Runs until the FSM and storage tasks completed, or the log is out of samples for
+/// [`ReplayConfig::END_GRACE`]. Runs once per process, like the flight it replays.
+pub async fn replay_flight_computer<FS: FileSystem>(log: &ReplayLog, filesystem: FS) -> [TransitionComparison; 3] {
+    // the original flight passed its checks already, and a ground station log is too sparse to repeat them
+    set_preflight_override(true);
+
+    // a flight state left over from before the replay is none of its decisions
+    let mut flight_state_receiver = FLIGHT_STATE_WATCH.receiver()
+        .expect("Replay: Not enough flight state consumers");
+    flight_state_receiver.try_changed();
+
+    let start = Instant::now();
+    let (altimeter, gps, imu, high_g_accelerometer) = log.sensors(start);
+
+    let finite_state_machine_task = finite_state_machine_task(
+        log.arming(start),
+        ReplayLed,
+        ReplayRecovery,
+        ReplayLed,
+        AnyApogeeDetection::default(),
+        ReplayCheckpointStore::default(),
+    );
+    let storage_task = storage_task(filesystem, ReplayLed);
+    let sensor_tasks = join4(
+        sensor_task(altimeter, ReplayLed),
+        sensor_task(gps, ReplayLed),
+        imu_sensor_task(imu, ReplayLed),
+        sensor_task(high_g_accelerometer, ReplayLed),
+    );
+
+    let mut replayed: Vec<Transition> = Vec::new();
+    let observer = async {
+        loop {
+            if let RecordData::FlightState(state) = flight_state_receiver.changed().await.payload() {
+                replayed.push((start.elapsed(), *state));
+            }
+        }
+    };
+
+    match select3(
+        join(finite_state_machine_task, storage_task),
+        join4(sensor_tasks, join3(sensor_health_task(), tilt_task(), attitude_task()), flight_summary_task(), observer),
+        Timer::at(start + log.duration() + ReplayConfig::END_GRACE),
+    ).await {
+        Either3::First(((), ())) => info!("Replay: Finite state machine and storage tasks completed"),
+        Either3::Second(_) => {},
+        Either3::Third(()) => info!("Replay: Log ended"),
+    }
+
+    compare_transitions(log.flight_states(), &replayed)
+}
@@ -1,35 +1,204 @@
-use crate::log::{debug, error, warn};
+use crate::log::{debug, error, info};
 use embassy_futures::join::join;
-use embassy_time::with_timeout;
+use embassy_time::{Ticker, with_timeout};
+use proto::event::TaskId;
+use proto::flight_state::FlightState;
+use proto::sensor_data::Acceleration;
+use proto::uom::si::acceleration::standard_gravity;
+use proto::wire::RecordData;
 
-use crate::{interfaces::{Led, Sensor}, sync::broadcast_record};
+use crate::core::led_pattern::{PatternLed, sensor_health_pattern};
+use crate::core::watchdog::{Criticality, Deadlines, WatchdogToken};
+use crate::{interfaces::{ImuSensor, Led, Sensor, sampling_state}, sync::{FLIGHT_STATE_WATCH, SENSOR_HEALTH_WATCH, broadcast_record, launched, set_imu_full_scale}};
 
 #[inline]
-pub async fn sensor_task<S, LED>(mut sensor: S, mut led: LED) -> !
+pub async fn sensor_task<S, LED>(mut sensor: S, led: LED) -> !
 where
     S: Sensor,
     LED: Led,
 {
-    let mut sensor_ticker = sensor.ticker();
+    let mut flight_state_receiver = FLIGHT_STATE_WATCH.receiver()
+        .expect("Sensor: Not enough flight state consumers");
+    let mut health_receiver = SENSOR_HEALTH_WATCH.receiver()
+        .expect("Sensor: Not enough sensor health consumers");
+    let mut led = PatternLed::new(led);
+    let watchdog = WatchdogToken::register(TaskId::Sensor(S::ID), Deadlines::sensor(S::SAMPLE_RATES), Criticality::FlightCritical)
+        .expect("Sensor: Not enough watchdog slots");
+
+    let mut flight_state = FlightState::default();
+    let mut state = sampling_state(flight_state, launched());
+    configure(&mut sensor, state).await;
+    let mut sensor_ticker = Ticker::every(S::SAMPLE_RATES.tick_interval(state));
 
     loop {
-        let timeout = S::TICK_INTERVAL * 2;
-        let ((), data) = join(
+        watchdog.feed();
+
+        if let Some(record) = flight_state_receiver.try_changed()
+            && let RecordData::FlightState(new_state) = record.payload()
+        {
+            flight_state = *new_state;
+        }
+        // liftoff switches to the ascent rates without a new flight state
+        if sampling_state(flight_state, launched()) != state {
+            state = sampling_state(flight_state, launched());
+            configure(&mut sensor, state).await;
+            sensor_ticker = Ticker::every(S::SAMPLE_RATES.tick_interval(state));
+        }
+
+        if let Some(report) = health_receiver.try_changed() {
+            led.set(sensor_health_pattern(report.get(S::ID)));
+        }
+
+        let timeout = S::SAMPLE_RATES.tick_interval(state) * 2;
+        let ((), data) = led.play_until(join(
             sensor_ticker.next(),
             with_timeout(timeout, sensor.parse_new_data()),
-        ).await;
-
-        led.off().await.unwrap_or_else(|e| warn!("{}: Status Led error: {:?}", S::NAME, e));
+        )).await;
 
         match data {
             Err(_) => error!("{}: Timed out reading sensor data", S::NAME),
             Ok(Err(_)) => error!("{}: Failed to parse data", S::NAME),
             Ok(Ok(msg)) => {
                 debug!("{}: Parsed new data", S::NAME);
-                led.on().await.unwrap_or_else(|e| warn!("{}: Status Led error: {:?}", S::NAME, e));
-
                 broadcast_record(msg.into());
             },
         }
     }
 }
+
+/// Runs the IMU as any other sensor, once its full scale was handed to the acceleration blending.
+#[inline]
+pub async fn imu_sensor_task<S, LED>(sensor: S, led: LED) -> !
+where
+    S: ImuSensor,
+    LED: Led,
+{
+    set_imu_full_scale(Acceleration::new::<standard_gravity>(S::FULL_SCALE));
+    sensor_task(sensor, led).await
+}
+
+async fn configure<S: Sensor>(sensor: &mut S, state: FlightState) {
+    match sensor.configure(state).await {
+        Ok(()) => info!("{}: Sampling every {} ms for {}", S::NAME, S::SAMPLE_RATES.tick_interval(state).as_millis(), state),
+        Err(e) => error!("{}: Failed to configure for {}: {:?}", S::NAME, state, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embassy_futures::select::{Either, select};
+    use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
+    use embassy_time::{Duration, Ticker, Timer};
+    use proto::sensor_data::{GpsData, HighGAccelerometerData, ImuData, Vector3};
+    use proto::sensor_health::SensorId;
+    use proto::uom::si::acceleration::meter_per_second_squared;
+
+    use crate::config::DataAcquisitionConfig;
+    use crate::interfaces::SampleRates;
+    use crate::sync::{IMU_DATA_TO_SUMMARY_SIGNAL, set_launched};
+    use crate::test_utils::interfaces::TestLed;
+    use crate::test_utils::{ms, sensor_data::{imu, random_gps_data}};
+
+    use super::*;
+
+    /// Reads just under 4 g on the vertical axis, saturated at its own full scale but well within 16 g.
+    struct FourGImu;
+
+    impl Sensor for FourGImu {
+        type Data = ImuData;
+        type Error = Infallible;
+
+        const NAME: &'static str = "4 g IMU";
+        const ID: SensorId = SensorId::Imu;
+        const SAMPLE_RATES: SampleRates = DataAcquisitionConfig::IMU_SAMPLE_RATES;
+
+        async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+            Ok(imu([0.0, 0.0, 38.0], [0.0; 3]))
+        }
+    }
+
+    impl ImuSensor for FourGImu {
+        const FULL_SCALE: f32 = 4.0;
+    }
+
+    fn high_g_data() -> HighGAccelerometerData {
+        HighGAccelerometerData {
+            acceleration: Vector3::new(0.0, 0.0, 45.0).map(Acceleration::new::<meter_per_second_squared>),
+        }
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(500))]
+    async fn blends_at_the_full_scale_of_the_imu_in_use() {
+        IMU_DATA_TO_SUMMARY_SIGNAL.reset();
+        // a high-g sample well within `HIGH_G_MAX_AGE` whenever the IMU reads
+        let high_g = async {
+            let mut ticker = Ticker::every(Duration::from_millis(5));
+            loop {
+                broadcast_record(high_g_data().into());
+                ticker.next().await;
+            }
+        };
+
+        let Either::Second(blended) = select(join(imu_sensor_task(FourGImu, TestLed), high_g), IMU_DATA_TO_SUMMARY_SIGNAL.wait()).await;
+
+        assert_eq!(blended.acceleration, high_g_data().acceleration);
+    }
+
+    static CONFIGURED_SIGNAL: Signal<CriticalSectionRawMutex, FlightState> = Signal::new();
+
+    /// Signals each state it is configured for.
+    struct ReconfiguredGps;
+
+    impl Sensor for ReconfiguredGps {
+        type Data = GpsData;
+        type Error = Infallible;
+
+        const NAME: &'static str = "Reconfigured GPS";
+        const ID: SensorId = SensorId::Gps;
+        const SAMPLE_RATES: SampleRates = SampleRates {
+            pad: Duration::from_millis(2),
+            ascent: Duration::from_millis(1),
+            descent: Duration::from_millis(2),
+            landed: Duration::from_millis(2),
+        };
+
+        async fn parse_new_data(&mut self) -> Result<Self::Data, Self::Error> {
+            Ok(random_gps_data())
+        }
+
+        async fn configure(&mut self, state: FlightState) -> Result<(), Self::Error> {
+            CONFIGURED_SIGNAL.signal(state);
+            Ok(())
+        }
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[async_std::test]
+    #[serial_test::serial]
+    #[timeout(ms(500))]
+    async fn switches_to_the_ascent_rate_at_liftoff() {
+        CONFIGURED_SIGNAL.reset();
+        set_launched(false);
+        let liftoff = async {
+            assert_eq!(CONFIGURED_SIGNAL.wait().await, FlightState::PreArmed);
+
+            broadcast_record(FlightState::Armed.into());
+            Timer::after_millis(20).await;
+            assert!(!CONFIGURED_SIGNAL.signaled(), "Arming on the pad should keep the pad rate");
+
+            set_launched(true);
+            CONFIGURED_SIGNAL.wait().await
+        };
+
+        let Either::Second(configured) = select(sensor_task(ReconfiguredGps, TestLed), liftoff).await;
+
+        assert_eq!(configured, FlightState::Armed);
+        set_launched(false);
+        broadcast_record(FlightState::PreArmed.into());
+    }
+}
@@ -0,0 +1,64 @@
+use embassy_futures::select::{Either, Either4, select, select4};
+use embassy_time::{Instant, Ticker};
+use proto::event::{Event, SensorHealthEvent, TaskId};
+use proto::sensor_health::SensorHealth;
+
+use crate::config::{SensorHealthConfig, WatchdogConfig};
+use crate::core::sensors::health::SensorHealthMonitor;
+use crate::core::watchdog::{Criticality, WatchdogToken};
+use crate::log::{info, warn};
+use crate::sync::{ALTIMETER_DATA_TO_HEALTH_SIGNAL, GPS_DATA_TO_HEALTH_SIGNAL, HIGH_G_ACCELEROMETER_DATA_TO_HEALTH_SIGNAL, IMU_DATA_TO_HEALTH_SIGNAL, SENSOR_HEALTH_WATCH, broadcast_record, set_altitude_source, set_gps_altitude_offset};
+
+#[inline]
+pub async fn sensor_health_task() -> ! {
+    let mut monitor = SensorHealthMonitor::new(Instant::now());
+    let mut ticker = Ticker::every(SensorHealthConfig::CHECK_TICK_INTERVAL);
+    let sender = SENSOR_HEALTH_WATCH.sender();
+
+    let watchdog = WatchdogToken::register(TaskId::SensorHealth, WatchdogConfig::SENSOR_HEALTH_DEADLINES, Criticality::NonCritical)
+        .expect("SensorHealth: Not enough watchdog slots");
+
+    sender.send(monitor.report());
+
+    loop {
+        watchdog.feed();
+
+        match select4(
+            ALTIMETER_DATA_TO_HEALTH_SIGNAL.wait(),
+            GPS_DATA_TO_HEALTH_SIGNAL.wait(),
+            select(IMU_DATA_TO_HEALTH_SIGNAL.wait(), HIGH_G_ACCELEROMETER_DATA_TO_HEALTH_SIGNAL.wait()),
+            ticker.next(),
+        ).await {
+            Either4::First(data) => monitor.update_altimeter(&data, Instant::now()),
+            Either4::Second(data) => {
+                monitor.update_gps(&data, Instant::now());
+                set_gps_altitude_offset(monitor.gps_altitude_offset());
+            },
+            Either4::Third(Either::First(data)) => monitor.update_imu(&data, Instant::now()),
+            Either4::Third(Either::Second(data)) => monitor.update_high_g_accelerometer(&data, Instant::now()),
+            Either4::Fourth(()) => monitor.check_staleness(Instant::now()),
+        }
+
+        let events = monitor.poll_events();
+        if events.is_empty() {
+            continue;
+        }
+
+        sender.send(monitor.report());
+
+        for event in events {
+            match event {
+                SensorHealthEvent::HealthChanged { sensor, health: SensorHealth::Healthy, .. } =>
+                    info!("SensorHealth: {} is healthy", sensor),
+                SensorHealthEvent::HealthChanged { sensor, health, fault } =>
+                    warn!("SensorHealth: {} is {} ({:?})", sensor, health, fault),
+                SensorHealthEvent::AltitudeSourceChanged(source) => {
+                    set_altitude_source(source);
+                    warn!("SensorHealth: Altitude source switched to {}", source);
+                },
+            }
+
+            broadcast_record(Event::SensorHealth(event).into());
+        }
+    }
+}
@@ -19,27 +19,31 @@ use postcard_rpc::header::{VarHeader, VarSeq};
 use postcard_rpc::server::{Dispatch, Server, Sender, WireRx, WireTx};
 use proto::{
     actuator_data::ActuatorStatus,
-    sensor_data::{AltimeterData, GpsData, ImuData},
+    sensor_data::{AltimeterData, GpsData, HighGAccelerometerData, ImuData},
 };
 use proto::wire::{
     RecordData, SimAltimeterLedTopic, SimArmLedTopic, SimDeploymentLedTopic,
     SimFileSystemLedTopic, SimFlightStateTopic, SimGpsLedTopic, SimGroundStationLedTopic,
-    SimImuLedTopic, SimPostcardLedTopic,
+    SimHighGAccelerometerLedTopic, SimImuLedTopic, SimPostcardLedTopic,
 };
 
 use crate::{
+    core::{
+        led_pattern::{PatternLed, link_pattern},
+        state_machine::detectors::AnyApogeeDetection,
+    },
     interfaces::{
         impls::simulation::{
-            arming_system::SimArming, deployment_system::SimRecovery, led::SimLed,
-            sensor::{SimAltimeter, SimGps, SimImu, SimSensor},
+            arming_system::SimArming, battery::SimBattery, buzzer::SimBuzzer, deployment_system::SimRecovery, led::SimLed,
+            sensor::{SimAltimeter, SimGps, SimHighGAccelerometer, SimImu, SimSensor}, watchdog::SimWatchdog,
         },
-        FileSystem, Led,
+        CheckpointStore, FileSystem, Led,
     },
     log::{error, warn},
     sync::FLIGHT_STATE_WATCH,
     tasks::{
-        finite_state_machine_task, groundstation_task, postcard_server_task,
-        run_flight_computer, sensor_task, storage_task,
+        beacon_task, buzzer_task, finite_state_machine_task, groundstation_task, imu_sensor_task,
+        postcard_server_task, run_flight_computer, sensor_task, storage_task, watchdog_task,
     },
 };
 
@@ -62,6 +66,10 @@ pub fn sim_imu_update<Tx: WireTx>(_context: &mut Context, _header: VarHeader, da
     SimImu::update_data(data);
 }
 
+pub fn sim_high_g_accelerometer_update<Tx: WireTx>(_context: &mut Context, _header: VarHeader, data: HighGAccelerometerData, _out: &Sender<Tx>) {
+    SimHighGAccelerometer::update_data(data);
+}
+
 pub fn sim_arming_activate<Tx: WireTx>(_context: &mut Context, _header: VarHeader, _data: ActuatorStatus, _out: &Sender<Tx>) {
     SimArming::activate();
 }
@@ -70,7 +78,7 @@ pub fn sim_arming_activate<Tx: WireTx>(_context: &mut Context, _header: VarHeade
 /// Panics on any disconnect — FC <-> simulator desync is unrecoverable.
 pub async fn postcard_sim_server_task<Tx, Rx, Buf, D, LED>(
     mut server: Server<Tx, Rx, Buf, D>,
-    mut led: LED,
+    led: LED,
 ) -> !
 where
     Tx: postcard_rpc::server::WireTx,
@@ -79,11 +87,13 @@ where
     D: postcard_rpc::server::Dispatch<Tx = Tx>,
     LED: Led,
 {
-    led.on().await.unwrap_or_else(|e| warn!("Postcard sim server: Status Led error: {:?}", e));
+    let mut led = PatternLed::new(led);
+    led.set(link_pattern(true));
     // `ServerError` may lack Debug/Display in no_std context; just log the exit.
-    let _ = server.run().await;
+    let _ = led.play_until(server.run()).await;
     error!("sim server: run exited (connection dropped)");
-    led.off().await.unwrap_or_else(|e| warn!("Postcard sim server: Status Led error: {:?}", e));
+    led.set(link_pattern(false));
+    led.refresh().await;
     panic!("fc-sim connection closed: FC and simulator desynced");
 }
 
@@ -118,23 +128,26 @@ where
 // Entry points
 // ---------------------------------------------------------------------------
 
-/// PIL entry point — single server, caller-supplied filesystem.
+/// PIL entry point — single server, caller-supplied filesystem and checkpoint store.
 ///
 /// All peripheral instances and the groundstation task share `server.sender()`.
-/// `filesystem` is typically SD/flash in PIL.
+/// `filesystem` is typically SD/flash in PIL, `checkpoint_store` a reserved flash region.
 #[inline]
 pub async fn start_pil_flight_computer<
     FS,
+    CS,
     PostcardTx,
     PostcardRx,
     PostcardBuf,
     PostcardD,
 > (
     filesystem: FS, 
+    checkpoint_store: CS,
     gs_server: Server<PostcardTx, PostcardRx, PostcardBuf, PostcardD>,
 )
 where 
     FS: FileSystem,
+    CS: CheckpointStore,
     PostcardTx: WireTx + Clone,
     PostcardRx: WireRx,
     PostcardBuf: DerefMut<Target = [u8]>,
@@ -155,16 +168,22 @@ where
         SimGps,
         SimLed::<_, SimGpsLedTopic>::new(&postcard_sender),
     );
-    let imu_task = sensor_task(
+    let imu_task = imu_sensor_task(
         SimImu,
         SimLed::<_, SimImuLedTopic>::new(&postcard_sender),
     );
+    let high_g_accelerometer_task = sensor_task(
+        SimHighGAccelerometer,
+        SimLed::<_, SimHighGAccelerometerLedTopic>::new(&postcard_sender),
+    );
 
     let finite_state_machine_task = finite_state_machine_task(
         SimArming, 
         SimLed::<_, SimArmLedTopic>::new(&postcard_sender),
         SimRecovery::new(&postcard_sender), 
         SimLed::<_, SimDeploymentLedTopic>::new(&postcard_sender),
+        AnyApogeeDetection::default(),
+        checkpoint_store,
     );
 
     let storage_task = storage_task(
@@ -184,7 +203,11 @@ where
         altimeter_task,
         gps_task,
         imu_task,
+        high_g_accelerometer_task,
         groundstation_task,
+        buzzer_task(SimBuzzer::new(&postcard_sender)),
+        watchdog_task(SimWatchdog::new()),
+        beacon_task(SimBattery::new()),
         flight_state_sim_publisher_task(&postcard_sender),
     ).await;
 }
@@ -229,12 +252,13 @@ where
     GsD: Dispatch<Tx = GsTx>,
 {
     use crate::config::{host::HostConfig, PostcardConfig};
-    use crate::interfaces::impls::host::filesystem::HostFileSystem;
+    use crate::interfaces::impls::host::{checkpoint_store::HostCheckpointStore, filesystem::HostFileSystem};
     use embassy_futures::select::select;
     use embassy_time::Timer;
 
     let dir_path = std::path::PathBuf::from(HostConfig::STORAGE_PATH);
     let filesystem = HostFileSystem::new(dir_path).await;
+    let checkpoint_store = HostCheckpointStore::new(std::path::PathBuf::from(HostConfig::CHECKPOINT_PATH));
 
     let sim_sender = sim_server.sender();
 
@@ -251,16 +275,22 @@ where
         SimGps,
         SimLed::<_, SimGpsLedTopic>::new(&sim_sender),
     );
-    let imu_task = sensor_task(
+    let imu_task = imu_sensor_task(
         SimImu,
         SimLed::<_, SimImuLedTopic>::new(&sim_sender),
     );
+    let high_g_accelerometer_task = sensor_task(
+        SimHighGAccelerometer,
+        SimLed::<_, SimHighGAccelerometerLedTopic>::new(&sim_sender),
+    );
 
     let finite_state_machine_task = finite_state_machine_task(
         SimArming,
         SimLed::<_, SimArmLedTopic>::new(&sim_sender),
         SimRecovery::new(&sim_sender),
         SimLed::<_, SimDeploymentLedTopic>::new(&sim_sender),
+        AnyApogeeDetection::default(),
+        checkpoint_store,
     );
 
     let storage_task = storage_task(
@@ -304,7 +334,11 @@ where
         altimeter_task,
         gps_task,
         imu_task,
+        high_g_accelerometer_task,
         gs_subsystem,
+        buzzer_task(SimBuzzer::new(&sim_sender)),
+        watchdog_task(SimWatchdog::new()),
+        beacon_task(SimBattery::new()),
         flight_state_sim_publisher_task(&sim_sender),
     )
     .await;
@@ -1,23 +1,34 @@
-use embassy_futures::select::{Either4, select4};
+use embassy_futures::select::{Either, Either4, select, select4};
 use embassy_time::{Timer, Ticker, with_timeout};
 use crate::log::{trace, error, info, warn};
+use proto::event::{Event, FileSystemEvent, TaskId};
 use proto::wire::RecordData;
 use proto::flight_state::FlightState;
 use core::{future::Future, pin::Pin, task::Poll};
+use static_cell::ConstStaticCell;
 
-use crate::{config::StorageConfig, core::storage::Storage, interfaces::{FileSystem, Led}, sync::{RECORD_TO_STORAGE_CHANNEL, FLIGHT_STATE_WATCH}};
+use crate::core::led_pattern::{PatternLed, StorageStatus, storage_pattern};
+use crate::core::watchdog::{Criticality, WatchdogToken};
+use crate::{config::{StorageConfig, WatchdogConfig}, core::storage::{Storage, pre_launch::PreLaunchBuffer}, interfaces::{FileSystem, Led}, sync::{RECORD_TO_STORAGE_CHANNEL, PRIORITY_RECORD_TO_STORAGE_CHANNEL, FLIGHT_STATE_WATCH, IMU_CALIBRATION_PROFILE_SIGNAL, broadcast_record, flight_state, pad_calibration, set_storage_open, set_storage_writes_ok, take_dropped_records}};
+
+type PreLaunchRecords = PreLaunchBuffer<{ StorageConfig::PRE_LAUNCH_BUFFER_SIZE }>;
 
 #[inline]
-pub async fn storage_task<FS, LED>(filesystem: FS, mut led: LED)
+pub async fn storage_task<FS, LED>(filesystem: FS, led: LED)
 where
     FS: FileSystem,
     LED: Led,
 {
     let receiver = RECORD_TO_STORAGE_CHANNEL.receiver();
+    let priority_receiver = PRIORITY_RECORD_TO_STORAGE_CHANNEL.receiver();
 
-    let mut storage = Storage::new(filesystem)
+    static WRITE_BUFFER: ConstStaticCell<[u8; StorageConfig::WRITE_BUFFER_SIZE]> = ConstStaticCell::new([0_u8; StorageConfig::WRITE_BUFFER_SIZE]);
+    let mut storage = Storage::new(filesystem, WRITE_BUFFER.take())
         .await.expect("Storage: Initialization failed");
-    info!("Storage: Created unique log files");
+    info!("Storage: Logging flight {}", storage.flight());
+
+    static PRE_LAUNCH_BUFFER: ConstStaticCell<PreLaunchRecords> = ConstStaticCell::new(PreLaunchBuffer::new());
+    let pre_launch_buffer = PRE_LAUNCH_BUFFER.take();
 
     let mut flush_files_ticker = Ticker::every(StorageConfig::FLUSH_FILES_TICK_INTERVAL);
     let mut flight_state_receiver = FLIGHT_STATE_WATCH.receiver()
@@ -25,28 +36,64 @@ where
 
     let mut hold_timer = HoldTimer::new();
 
+    let mut led = PatternLed::new(led);
+    led.set(storage_pattern(StorageStatus::Logging));
+    let mut write_failed = false;
+
+    // unregistered on return, once the log is closed
+    let watchdog = WatchdogToken::register(TaskId::Storage, WatchdogConfig::STORAGE_DEADLINES, Criticality::NonCritical)
+        .expect("Storage: Not enough watchdog slots");
+
     loop {
-        let result = select4(
-            receiver.receive(),
+        watchdog.feed();
+
+        // polled first, so priority records are never starved by sensor bursts
+        let next_record = async {
+            match select(priority_receiver.receive(), receiver.receive()).await {
+                Either::First(record) | Either::Second(record) => record,
+            }
+        };
+
+        let result = led.play_until(select4(
+            next_record,
             flush_files_ticker.next(),
             flight_state_receiver.changed(),
             &mut hold_timer,
-        ).await;
-
-        led.on().await.unwrap_or_else(|e| warn!("Storage: Status Led error: {:?}", e));
+        )).await;
 
         match result {
             Either4::First(record) => {
-                match with_timeout(StorageConfig::WRITE_TIMEOUT, storage.append_record(&record)).await {
-                    Err(_) => error!("Storage: Timed out writing record"),
-                    Ok(res) => trace!("Storage: Logged record: {:?}", res),
+                if pre_launch_buffer.push(record, pad_calibration().map(|calibration| calibration.ground_altitude)) {
+                    info!("Storage: Launch detected, writing pre-launch records at full rate");
                 }
+                write_failed |= !write_ready_records(&mut storage, pre_launch_buffer).await;
             },
             Either4::Second(()) => {
+                let dropped = report_dropped_records();
+                if let Some(profile) = IMU_CALIBRATION_PROFILE_SIGNAL.try_take()
+                    && flight_state() == Some(FlightState::PreArmed)
+                {
+                    match with_timeout(StorageConfig::WRITE_TIMEOUT, storage.store_imu_calibration(&profile)).await {
+                        Err(_) => error!("Storage: Timed out storing IMU calibration"),
+                        Ok(Err(e)) => error!("Storage: Failed to store IMU calibration: {:?}", e),
+                        Ok(Ok(())) => info!("Storage: Stored IMU calibration"),
+                    }
+                }
                 match with_timeout(StorageConfig::FLUSH_TIMEOUT, storage.flush()).await {
-                    Err(_) => warn!("Storage: Timed out flushing file"),
+                    Err(_) => { warn!("Storage: Timed out flushing file"); write_failed = true; },
                     Ok(res) => trace!("Storage: Flushed file: {:?}", res),
                 }
+
+                let status = if write_failed {
+                    StorageStatus::WriteFailed
+                } else if dropped {
+                    StorageStatus::DroppingRecords
+                } else {
+                    StorageStatus::Logging
+                };
+                led.set(storage_pattern(status));
+                set_storage_writes_ok(!write_failed);
+                write_failed = false;
             },
             Either4::Third(record) => {
                 if hold_timer.is_running() {
@@ -59,19 +106,50 @@ where
             },
             Either4::Fourth(()) => {
                 info!("Storage: Final flush");
+                pre_launch_buffer.launch();
+                while !pre_launch_buffer.is_empty() {
+                    watchdog.feed();
+                    if !write_ready_records(&mut storage, pre_launch_buffer).await {
+                        break;
+                    }
+                }
                 match with_timeout(StorageConfig::FLUSH_TIMEOUT, storage.flush()).await {
                     Err(_) => warn!("Storage: Final flush timed out"),
                     Ok(res) => trace!("Storage: Final flush: {:?}", res),
                 }
                 info!("Storage: Exiting");
+                set_storage_open(false);
 
-                led.off().await.unwrap_or_else(|e| warn!("Storage: Status Led error: {:?}", e));
+                led.set(storage_pattern(StorageStatus::Closed));
+                led.refresh().await;
                 return;
             },
         }
+    }
+}
 
-        led.off().await.unwrap_or_else(|e| warn!("Storage: Status Led error: {:?}", e));
+/// Returns whether any records were dropped.
+fn report_dropped_records() -> bool {
+    let dropped = take_dropped_records();
+    if dropped > 0 {
+        warn!("Storage: Dropped {} records since the last report", dropped);
+        broadcast_record(Event::FileSystem(FileSystemEvent::RecordsDropped(dropped)).into());
+    }
+    dropped > 0
+}
+
+/// Writes up to [`StorageConfig::MAX_WRITES_PER_ITERATION`] ready records, stopping at the first
+/// failure. Returns whether every record was written.
+async fn write_ready_records<FS: FileSystem>(storage: &mut Storage<FS, FS::File>, pre_launch_buffer: &mut PreLaunchRecords) -> bool {
+    for _ in 0..StorageConfig::MAX_WRITES_PER_ITERATION {
+        let Some(record) = pre_launch_buffer.pop_ready() else { break };
+        match with_timeout(StorageConfig::WRITE_TIMEOUT, storage.append_record(&record)).await {
+            Err(_) => { error!("Storage: Timed out writing record"); return false; },
+            Ok(Err(e)) => { error!("Storage: Failed to write record: {:?}", e); return false; },
+            Ok(Ok(())) => trace!("Storage: Logged record"),
+        }
     }
+    true
 }
 
 struct HoldTimer(Option<Timer>);
@@ -0,0 +1,36 @@
+use embassy_futures::select::{Either, select};
+use proto::event::{Event, TiltEvent};
+use proto::flight_state::FlightState;
+use proto::uom::si::angle::degree;
+use proto::wire::RecordData;
+
+use crate::core::tilt::TiltMonitor;
+use crate::log::error;
+use crate::sync::{ATTITUDE_TO_TILT_SIGNAL, FLIGHT_STATE_WATCH, broadcast_record, set_tilt_status};
+
+#[inline]
+pub async fn tilt_task() -> ! {
+    let mut flight_state_receiver = FLIGHT_STATE_WATCH.receiver()
+        .expect("Tilt: Not enough flight state consumers");
+
+    let mut monitor = TiltMonitor::default();
+    let mut state = FlightState::PreArmed;
+
+    loop {
+        match select(flight_state_receiver.changed(), ATTITUDE_TO_TILT_SIGNAL.wait()).await {
+            Either::First(record) => {
+                if let RecordData::FlightState(new_state) = record.payload() {
+                    state = *new_state;
+                }
+            },
+            Either::Second((data, up)) => {
+                if let Some(event) = monitor.update(&data, up, state) {
+                    let TiltEvent::BoostLimitExceeded { tilt } = &event;
+                    error!("Tilt: Boost turned {} deg off vertical, past the limit", tilt.get::<degree>());
+                    broadcast_record(Event::Tilt(event).into());
+                }
+                set_tilt_status(monitor.status());
+            },
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use embassy_time::{Instant, Ticker};
+use proto::event::{Event, WatchdogEvent};
+use proto::flight_state::FlightState;
+use proto::wire::RecordData;
+
+use crate::config::WatchdogConfig;
+use crate::core::watchdog::{check_deadlines, set_flight_state};
+use crate::interfaces::{Watchdog, sampling_state};
+use crate::log::{error, info, warn};
+use crate::sync::{FLIGHT_STATE_WATCH, broadcast_record, launched};
+
+/// Supervises the tasks' watchdog tokens, and feeds `watchdog` only while every flight-critical task
+/// is within its deadline. Every stalled task is reported as a critical event, a flight-critical one
+/// then resets the flight computer once [`WatchdogConfig::HARDWARE_TIMEOUT`] passes without it recovering.
+#[inline]
+pub async fn watchdog_task<W: Watchdog>(mut watchdog: W) -> ! {
+    let mut flight_state_receiver = FLIGHT_STATE_WATCH.receiver()
+        .expect("Watchdog: Not enough flight state consumers");
+    let mut ticker = Ticker::every(WatchdogConfig::CHECK_INTERVAL);
+
+    match watchdog.start(WatchdogConfig::HARDWARE_TIMEOUT).await {
+        Ok(()) => info!("Watchdog: Started, resetting after {} ms without a feed", WatchdogConfig::HARDWARE_TIMEOUT.as_millis()),
+        Err(e) => error!("Watchdog: Failed to start: {:?}", e),
+    }
+
+    let mut flight_state = FlightState::default();
+    let mut critical_alive = true;
+    loop {
+        ticker.next().await;
+
+        if let Some(record) = flight_state_receiver.try_changed()
+            && let RecordData::FlightState(state) = record.payload()
+        {
+            flight_state = *state;
+        }
+        // the sensors' deadlines follow their sample rates
+        set_flight_state(sampling_state(flight_state, launched()));
+
+        let (missed, all_critical_alive) = check_deadlines(Instant::now());
+        for event in missed {
+            let WatchdogEvent::DeadlineMissed { task, silent_ms } = event;
+            error!("Watchdog: {:?} missed its deadline, silent for {} ms", task, silent_ms);
+            broadcast_record(Event::Watchdog(event).into());
+        }
+
+        if all_critical_alive != critical_alive {
+            critical_alive = all_critical_alive;
+            if critical_alive {
+                info!("Watchdog: Every flight-critical task is alive again, feeding resumed");
+            } else {
+                error!("Watchdog: Flight-critical task stalled, withholding the feed");
+            }
+        }
+        if critical_alive && let Err(e) = watchdog.feed().await {
+            warn!("Watchdog: Failed to feed: {:?}", e);
+        }
+    }
+}
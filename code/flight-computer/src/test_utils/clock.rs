@@ -0,0 +1,48 @@
+//! A clock advanced by hand, to step through minutes of task timeline without waiting for them.
+
+use core::cell::Cell;
+use core::future::{Future, poll_fn};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Milliseconds since the start of the test, only moving on [`SimClock::run_until`].
+///
+/// Futures on this clock are polled at every step, so they need no waker.
+#[derive(Default)]
+pub struct SimClock {
+    now_ms: Cell<u64>,
+}
+
+impl SimClock {
+    pub fn now(&self) -> u64 {
+        self.now_ms.get()
+    }
+
+    /// Completes once the clock reached `ms`.
+    pub async fn until(&self, ms: u64) {
+        poll_fn(|_| if self.now() >= ms { Poll::Ready(()) } else { Poll::Pending }).await;
+    }
+
+    /// Stands in for a periodic task, counting its iterations from the first poll on.
+    pub async fn every(&self, period_ms: u64, iterations: &Cell<u32>) -> ! {
+        let mut next = self.now() + period_ms;
+        loop {
+            self.until(next).await;
+            iterations.set(iterations.get() + 1);
+            next += period_ms;
+        }
+    }
+
+    /// Advances to `ms` in steps of `step_ms`, polling `future` at every step. Returns its output
+    /// if it completed on the way.
+    pub fn run_until<F: Future>(&self, mut future: Pin<&mut F>, ms: u64, step_ms: u64) -> Option<F::Output> {
+        let mut context = Context::from_waker(futures::task::noop_waker_ref());
+        while self.now() < ms {
+            self.now_ms.set((self.now() + step_ms).min(ms));
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return Some(output);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,88 @@
+//! In-memory stand-in for the SD card.
+
+use std::collections::BTreeMap;
+
+use crate::interfaces::FileSystem;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RamFileSystemError {
+    NotFound,
+    AlreadyExists,
+}
+
+/// Files by name, opened files are handled by name too.
+pub struct RamFileSystem {
+    pub files: BTreeMap<String, Vec<u8>>,
+    /// Bytes the files may take up together, unlimited by default.
+    pub capacity: u64,
+}
+
+impl Default for RamFileSystem {
+    fn default() -> Self {
+        Self { files: BTreeMap::new(), capacity: u64::MAX }
+    }
+}
+
+impl RamFileSystem {
+    pub fn used(&self) -> u64 {
+        self.files.values().map(|file| file.len() as u64).sum()
+    }
+
+    fn file_mut(&mut self, filename: &str) -> Result<&mut Vec<u8>, RamFileSystemError> {
+        self.files.get_mut(filename).ok_or(RamFileSystemError::NotFound)
+    }
+}
+
+impl FileSystem for RamFileSystem {
+    type File = String;
+    type Error = RamFileSystemError;
+
+    async fn exist_file(&mut self, filename: &str) -> Result<bool, Self::Error> {
+        Ok(self.files.contains_key(filename))
+    }
+
+    async fn create_file(&mut self, filename: &str) -> Result<Self::File, Self::Error> {
+        if self.files.contains_key(filename) {
+            return Err(RamFileSystemError::AlreadyExists);
+        }
+        self.files.insert(filename.into(), Vec::new());
+        Ok(filename.into())
+    }
+
+    async fn delete_file(&mut self, filename: &str) -> Result<(), Self::Error> {
+        self.files.remove(filename).map(drop).ok_or(RamFileSystemError::NotFound)
+    }
+
+    async fn read_file(&mut self, filename: &str, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+        let file = self.file_mut(filename)?;
+        let len = file.len().min(buffer.len());
+        buffer[..len].copy_from_slice(&file[..len]);
+        Ok(len)
+    }
+
+    async fn file_size(&mut self, filename: &str) -> Result<u32, Self::Error> {
+        Ok(u32::try_from(self.file_mut(filename)?.len()).unwrap_or(u32::MAX))
+    }
+
+    async fn free_space(&mut self) -> Result<u64, Self::Error> {
+        Ok(self.capacity.saturating_sub(self.used()))
+    }
+
+    async fn open_file_append(&mut self, filename: &str) -> Result<Self::File, Self::Error> {
+        self.file_mut(filename)?;
+        Ok(filename.into())
+    }
+
+    async fn close_file(&mut self, _file: Self::File) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn write_file(&mut self, file: &mut Self::File, data: &[u8]) -> Result<(), Self::Error> {
+        self.file_mut(file)?.extend_from_slice(data);
+        Ok(())
+    }
+
+    async fn flush_file(&mut self, _file: &mut Self::File) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
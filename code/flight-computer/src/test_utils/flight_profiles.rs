@@ -0,0 +1,173 @@
+//! Flight profiles replayed from the `OpenRocket` runs saved in `open rocket/Pilot V1.ork`, the airframe
+//! being flown, sampled at the apogee detector rate, and IMU samples recorded by the simulator.
+
+use embassy_time::{Duration, Instant};
+use proto::sensor_data::{Altitude, Angle, ImuData, Pressure};
+use proto::uom::si::{angle::degree, length::meter, pressure::pascal};
+
+use crate::config::ApogeeDetectorConfig;
+use crate::core::state_machine::detectors::ApogeeSample;
+use crate::test_utils::sensor_data::imu;
+
+/// Trimmed exports of the saved simulations: time (s), altitude above the launchpad (m), air pressure (Pa).
+const NOMINAL_FLIGHT: &str = include_str!("flights/pilot_v1_nominal.csv");
+const LOW_FLIGHT: &str = include_str!("flights/pilot_v1_low.csv");
+const HIGH_FLIGHT: &str = include_str!("flights/pilot_v1_high.csv");
+/// Output of the simulator's `imu_trace`: time (s), acceleration y, z (m/s²), gyro x (rad/s), true tilt (deg).
+const SIMULATED_GRAVITY_TURN: &str = include_str!("flights/simulator_gravity_turn.csv");
+
+const PAD_TIME: Duration = Duration::from_secs(5);
+const PAD_ONLY_DURATION: Duration = Duration::from_secs(60);
+const PAD_PRESSURE_PA: f32 = 101_300.0;
+/// Pressure drop per metre of climb near sea level.
+const PRESSURE_LAPSE_PA_PER_M: f32 = 12.0;
+
+pub struct FlightProfile {
+    pub samples: Vec<ApogeeSample>,
+    /// `None` for profiles that never leave the pad.
+    pub apogee_time: Option<Instant>,
+}
+
+impl FlightProfile {
+    pub fn has_pressure(&self) -> bool {
+        self.samples.iter().all(|sample| sample.pressure.is_some())
+    }
+}
+
+/// Fixed-seed noise, so every run sees the same profile.
+struct Lcg(u32);
+
+impl Lcg {
+    #[allow(clippy::cast_possible_truncation)]
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (f64::from(self.0) / f64::from(u32::MAX)).mul_add(2.0, -1.0) as f32
+    }
+}
+
+struct Row {
+    time: Duration,
+    altitude: f32,
+    pressure: f32,
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse(recording: &str) -> Vec<Row> {
+    recording.lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let mut fields = line.split(',').map(|field| field.trim().parse::<f32>().expect("Recording should only hold numbers"));
+            let mut field = || fields.next().expect("Recording rows should have three fields");
+            Row {
+                time: Duration::from_micros((field() * 1e6) as u64),
+                altitude: field(),
+                pressure: field(),
+            }
+        })
+        .collect()
+}
+
+/// Sensor readings around `altitude`, with the barometer pushed the other way.
+fn sample(time: Duration, altitude: f32, pressure: f32, noise: f32, with_pressure: bool, rng: &mut Lcg) -> ApogeeSample {
+    let error = noise * rng.next();
+    ApogeeSample {
+        altitude: Altitude::new::<meter>(altitude + error),
+        pressure: with_pressure.then(|| Pressure::new::<pascal>(error.mul_add(-PRESSURE_LAPSE_PA_PER_M, pressure))),
+        time: Instant::from_ticks(0) + time,
+    }
+}
+
+/// Replays `recording` after some time on the pad, each tick reading the latest recorded row.
+fn replay(recording: &str, noise: f32, with_pressure: bool, seed: u32) -> FlightProfile {
+    let rows = parse(recording);
+    let mut rng = Lcg(seed);
+    let tick = ApogeeDetectorConfig::DETECTOR_TICK_INTERVAL;
+
+    let mut samples = Vec::new();
+    let mut time = Duration::from_ticks(0);
+    while time < PAD_TIME {
+        samples.push(sample(time, 0.0, PAD_PRESSURE_PA, noise, with_pressure, &mut rng));
+        time += tick;
+    }
+
+    let end = rows.last().expect("Recording should not be empty").time;
+    while time <= PAD_TIME + end {
+        let row = rows.iter()
+            .take_while(|row| PAD_TIME + row.time <= time)
+            .last()
+            .unwrap_or(&rows[0]);
+        samples.push(sample(time, row.altitude, row.pressure, noise, with_pressure, &mut rng));
+        time += tick;
+    }
+
+    let apogee = rows.iter()
+        .max_by(|a, b| a.altitude.total_cmp(&b.altitude))
+        .expect("Recording should not be empty");
+
+    FlightProfile {
+        samples,
+        apogee_time: Some(Instant::from_ticks(0) + PAD_TIME + apogee.time),
+    }
+}
+
+/// ~310 m apogee, the recording as is.
+pub fn nominal_flight() -> FlightProfile {
+    replay(NOMINAL_FLIGHT, 0.0, true, 1)
+}
+
+/// Same flight seen through a noisy barometer.
+pub fn noisy_flight() -> FlightProfile {
+    replay(NOMINAL_FLIGHT, 1.5, true, 7)
+}
+
+/// ~105 m apogee on a short burn.
+pub fn low_flight() -> FlightProfile {
+    replay(LOW_FLIGHT, 0.3, true, 3)
+}
+
+/// ~550 m apogee on the longest burn.
+pub fn high_flight() -> FlightProfile {
+    replay(HIGH_FLIGHT, 0.3, true, 11)
+}
+
+/// Barometer failed, altitude comes from the GPS.
+pub fn gps_only_flight() -> FlightProfile {
+    replay(NOMINAL_FLIGHT, 2.0, false, 5)
+}
+
+/// A minute of noise on the launchpad.
+pub fn pad_only() -> FlightProfile {
+    let mut rng = Lcg(9);
+    let tick = ApogeeDetectorConfig::DETECTOR_TICK_INTERVAL;
+    let samples = (0..=PAD_ONLY_DURATION.as_ticks() / tick.as_ticks())
+        .map(|i| sample(tick * u32::try_from(i).expect("Pad duration should fit"), 0.0, PAD_PRESSURE_PA, 1.5, true, &mut rng))
+        .collect();
+
+    FlightProfile { samples, apogee_time: None }
+}
+
+/// An IMU sample of a simulated flight, with the attitude the simulator flew.
+pub struct SimulatedImuSample {
+    pub time: Instant,
+    pub data: ImuData,
+    pub tilt: Angle,
+}
+
+/// The simulator's rocket pitching over from its 3° rail in a gravity turn, without a magnetometer.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn simulated_gravity_turn() -> Vec<SimulatedImuSample> {
+    SIMULATED_GRAVITY_TURN.lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let mut fields = line.split(',').map(|field| field.trim().parse::<f32>().expect("Recording should only hold numbers"));
+            let mut field = || fields.next().expect("Recording rows should have five fields");
+            let time = Instant::from_millis((field() * 1e3).round() as u64);
+            let (y, z, rate) = (field(), field(), field());
+            SimulatedImuSample {
+                time,
+                data: imu([0.0, y, z], [rate, 0.0, 0.0]),
+                tilt: Angle::new::<degree>(field()),
+            }
+        })
+        .collect()
+}
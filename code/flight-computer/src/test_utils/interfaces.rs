@@ -0,0 +1,57 @@
+//! Stand-ins for the FSM and sensor task peripherals.
+
+use core::convert::Infallible;
+
+use crate::interfaces::{ArmingSystem, DeploymentSystem, Led};
+
+/// Led accepting every switch without showing it.
+pub struct TestLed;
+
+impl Led for TestLed {
+    type Error = Infallible;
+
+    async fn on(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn off(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn toggle(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Arm button nobody presses.
+pub struct TestArming;
+
+impl ArmingSystem for TestArming {
+    type Error = Infallible;
+
+    async fn wait_arm(&mut self) -> Result<(), Self::Error> {
+        core::future::pending().await
+    }
+}
+
+/// Charges failing their first `failures` calls to `deploy()`, then firing and verifying.
+#[derive(Default)]
+pub struct FlakyDeployment {
+    pub failures: u32,
+}
+
+impl DeploymentSystem for FlakyDeployment {
+    type Error = ();
+
+    async fn deploy(&mut self) -> Result<(), Self::Error> {
+        if self.failures == 0 {
+            return Ok(());
+        }
+        self.failures -= 1;
+        Err(())
+    }
+
+    async fn verify_deployment(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
@@ -1,14 +1,42 @@
 #![allow(dead_code)]
 #![allow(clippy::must_use_candidate)]
 
+use proto::event::{Event, FsmEvent};
+use proto::wire::RecordData;
 use rstest::fixture;
 
+use crate::sync::PRIORITY_RECORD_TO_STORAGE_CHANNEL;
+
 pub mod sensor_data;
+pub mod flight_profiles;
+pub mod filesystem;
+pub mod clock;
+pub mod interfaces;
 
 pub fn ms(ms: u32) -> std::time::Duration {
     std::time::Duration::from_millis(ms.into())
 }
 
+/// `ms` after boot, on the embassy clock.
+pub fn at(ms: u64) -> embassy_time::Instant {
+    embassy_time::Instant::from_millis(ms)
+}
+
+/// `ms` into a replayed log.
+pub fn offset(ms: u64) -> embassy_time::Duration {
+    embassy_time::Duration::from_millis(ms)
+}
+
+/// FSM decisions waiting in the priority storage channel, oldest first. Drops the other records.
+pub fn stored_fsm_events() -> Vec<FsmEvent> {
+    core::iter::from_fn(|| PRIORITY_RECORD_TO_STORAGE_CHANNEL.try_receive().ok())
+        .filter_map(|record| match record.into_inner().2 {
+            RecordData::Event(Event::Fsm(event)) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
 #[fixture]
 pub fn mock_logger() -> logtest::Logger {
     logtest::Logger::start()
@@ -1,5 +1,5 @@
 use nmea::sentences::FixType;
-use proto::{sensor_data::{Acceleration, AltimeterData, Altitude, AngularVelocity, GpsCoordinates, GpsData, ImuData, Pressure, ThermodynamicTemperature, Vector3}, uom};
+use proto::{sensor_data::{Acceleration, AltimeterData, Altitude, AngularVelocity, GpsCoordinates, GpsData, HighGAccelerometerData, ImuData, MagneticFluxDensity, Pressure, ThermodynamicTemperature, Vector3}, uom};
 use rstest::fixture;
 
 #[fixture]
@@ -50,3 +50,35 @@ pub fn random_imu_data() -> ImuData {
         temperature: ThermodynamicTemperature::new::<uom::si::thermodynamic_temperature::degree_celsius>(rand::random()),
     }
 }
+
+/// Sample in m/s² and rad/s from an IMU without a magnetometer.
+pub fn imu(acceleration: [f32; 3], rates: [f32; 3]) -> ImuData {
+    ImuData {
+        acceleration: Vector3::from(acceleration).map(Acceleration::new::<uom::si::acceleration::meter_per_second_squared>),
+        gyro: Vector3::from(rates).map(AngularVelocity::new::<uom::si::angular_velocity::radian_per_second>),
+        mag: Vector3::from([0.0; 3]).map(MagneticFluxDensity::new::<uom::si::magnetic_flux_density::microtesla>),
+        ..random_imu_data()
+    }
+}
+
+#[fixture]
+pub fn random_high_g_accelerometer_data() -> HighGAccelerometerData {
+    HighGAccelerometerData {
+        acceleration: Vector3::new(
+            Acceleration::new::<uom::si::acceleration::meter_per_second_squared>(rand::random()),
+            Acceleration::new::<uom::si::acceleration::meter_per_second_squared>(rand::random()),
+            Acceleration::new::<uom::si::acceleration::meter_per_second_squared>(rand::random()),
+        ),
+    }
+}
+
+/// `value` in raw counts of a range spanning `±full_scale`, as IMU drivers read them.
+#[allow(clippy::cast_possible_truncation)]
+pub fn counts(value: f32, full_scale: f32) -> i16 {
+    (value * 32_768.0 / full_scale) as i16
+}
+
+/// A value converted back from raw counts, within their resolution.
+pub fn assert_close(measured: f32, expected: f32) {
+    assert!((measured - expected).abs() < 0.01, "Expected {expected}, measured {measured}");
+}
@@ -21,6 +21,10 @@ impl Config {
     pub const PING_PAYLOAD: u32 = 0xdeadbeef;
     /// Delay between reconnection attempts after a FC disconnect.
     pub const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+    /// Interval between draining the FC trace span buffer.
+    pub const TRACE_POLL: Duration = Duration::from_secs(1);
+    /// Upper bound on spans pulled per poll, so a busy FC cannot starve other endpoint calls.
+    pub const TRACE_SPANS_PER_POLL: usize = 256;
 
     // -- REST server --
     pub const CTRLC: bool = true;
@@ -73,6 +73,39 @@ pub async fn run_ping_loop(state: AppState) {
     }
 }
 
+/// Periodically drain the FC trace span buffer into the session trace file.
+///
+/// Spawned as a standalone task — pulls spans one `TraceSpanEndpoint` call at a
+/// time until the FC reports none left or `Config::TRACE_SPANS_PER_POLL` is hit.
+pub async fn run_trace_loop(state: AppState) {
+    let mut ticker = tokio::time::interval(Config::TRACE_POLL);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        ticker.tick().await;
+        let Some(client) = state.conn.read().await.client.clone() else {
+            continue;
+        };
+
+        for _ in 0..Config::TRACE_SPANS_PER_POLL {
+            let span = match tokio::time::timeout(
+                Config::ENDPOINT_TIMEOUT,
+                client.service::<proto::TraceSpanEndpoint>(&()),
+            ).await {
+                Ok(Ok(Some(span))) => span,
+                Ok(Ok(None)) => break,
+                Ok(Err(e)) => { debug!("Trace span request failed: {e}"); break },
+                Err(_) => { debug!("Trace span request timed out"); break },
+            };
+
+            let mut store = state.storage.write().await;
+            if let Some(ref mut s) = *store
+                && let Err(e) = s.store_trace_span(&span) {
+                warn!(error = %e, "Failed to write trace span to storage");
+            }
+        }
+    }
+}
+
 /// Run the FC client loop with automatic reconnection.
 ///
 /// Connects to the FC host, subscribes to `RecordTopic` for telemetry,
@@ -110,21 +143,53 @@ async fn try_run_fc_client(state: &AppState) -> anyhow::Result<()> {
     let mut sub = client.subscribe::<proto::RecordTopic>().await
         .map_err(|e| anyhow::anyhow!("subscribe to RecordTopic failed: {e}"))?;
 
+    let mut calibration_sub = client.subscribe::<proto::ImuCalibrationTopic>().await
+        .map_err(|e| anyhow::anyhow!("subscribe to ImuCalibrationTopic failed: {e}"))?;
+
+    let mut preflight_sub = client.subscribe::<proto::PreflightReportTopic>().await
+        .map_err(|e| anyhow::anyhow!("subscribe to PreflightReportTopic failed: {e}"))?;
+
+    let mut beacon_sub = client.subscribe::<proto::BeaconTopic>().await
+        .map_err(|e| anyhow::anyhow!("subscribe to BeaconTopic failed: {e}"))?;
+
     info!("Subscribed to RecordTopic, waiting for telemetry...");
     state.broadcast_status().await;
 
     // Receive records until the subscription drops (FC disconnects).
-    while let Some(record) = sub.recv().await {
-        let mut store = state.storage.write().await;
-        if let Some(ref mut s) = *store
-            && let Err(e) = s.store_record(record.clone()) {
-            warn!(error = %e, "Failed to write record to storage");
-        }
-
-        // Broadcast to WebSocket clients as JSON.
-        if let Ok(json) = serde_json::to_string(&utils::status::WsMessage::Record(record.clone()))
-            && let Err(e) = state.ws_sender.send(json) {
-            debug!("Failed to broadcast record (no WS clients): {}", e);
+    loop {
+        tokio::select! {
+            record = sub.recv() => {
+                let Some(record) = record else { break };
+                let mut store = state.storage.write().await;
+                if let Some(ref mut s) = *store
+                    && let Err(e) = s.store_record(record.clone()) {
+                    warn!(error = %e, "Failed to write record to storage");
+                }
+
+                // Broadcast to WebSocket clients as JSON.
+                if let Ok(json) = serde_json::to_string(&utils::status::WsMessage::Record(record.clone()))
+                    && let Err(e) = state.ws_sender.send(json) {
+                    debug!("Failed to broadcast record (no WS clients): {}", e);
+                }
+            }
+            Some(status) = calibration_sub.recv() => {
+                if let Ok(json) = serde_json::to_string(&utils::status::WsMessage::ImuCalibration(status))
+                    && let Err(e) = state.ws_sender.send(json) {
+                    debug!("Failed to broadcast IMU calibration status (no WS clients): {}", e);
+                }
+            }
+            Some(report) = preflight_sub.recv() => {
+                if let Ok(json) = serde_json::to_string(&utils::status::WsMessage::Preflight(report))
+                    && let Err(e) = state.ws_sender.send(json) {
+                    debug!("Failed to broadcast pre-flight report (no WS clients): {}", e);
+                }
+            }
+            Some(beacon) = beacon_sub.recv() => {
+                if let Ok(json) = serde_json::to_string(&utils::status::WsMessage::Beacon(beacon))
+                    && let Err(e) = state.ws_sender.send(json) {
+                    debug!("Failed to broadcast beacon (no WS clients): {}", e);
+                }
+            }
         }
     }
 
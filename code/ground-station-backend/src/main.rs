@@ -1,8 +1,8 @@
 //! Ground-station backend — REST API + telemetry storage for the FC link.
 //!
 //! Connects to the flight-computer-host on `fc-gs.sock`, subscribes to
-//! `RecordTopic` for telemetry, stores records to NDJSON, and serves
-//! a REST/JSON API for the frontend.
+//! `RecordTopic` for telemetry, stores records and trace spans to NDJSON,
+//! and serves a REST/JSON API for the frontend.
 //!
 //! ## Config (shared constants in `utils::constants`)
 //!
@@ -59,6 +59,9 @@ async fn main() -> anyhow::Result<()> {
     // Spawn a periodic FC ping task — latency flows through WS status messages.
     tokio::spawn(fc_client::run_ping_loop(state.clone()));
 
+    // Spawn the trace span drain — spans land next to the session records.
+    tokio::spawn(fc_client::run_trace_loop(state.clone()));
+
     tracing::info!(
         "Starting REST API on {}:{}",
         c::GS_HOST,
@@ -80,7 +83,9 @@ async fn main() -> anyhow::Result<()> {
             routes::ping,
             routes::ws_events,
             routes::arm,
-            routes::ignite,
+            routes::calibrate_imu,
+            routes::preflight_override,
+            routes::flight_summary,
         ])
         .launch()
         .await?;
@@ -67,6 +67,30 @@ fn json_error(status: Status, msg: impl Into<String>) -> Custom<Json<CommandErro
     Custom(status, Json(CommandError { error: msg.into() }))
 }
 
+/// Refuses `action` with **409** while the FC's tilt lockout inhibits it.
+async fn check_tilt(client: &proto::PostcardClient, action: proto::tilt::TiltAction) -> Result<(), Custom<Json<CommandError>>> {
+    match tokio::time::timeout(
+        Config::ENDPOINT_TIMEOUT,
+        client.service::<proto::TiltInhibitsEndpoint>(&action),
+    )
+    .await
+    {
+        Ok(Ok(false)) => Ok(()),
+        Ok(Ok(true)) => {
+            warn!(%action, "command refused by the tilt lockout");
+            Err(json_error(Status::Conflict, format!("{action} inhibited: rocket not near vertical")))
+        }
+        Ok(Err(e)) => {
+            warn!("tilt_inhibits failed: {e}");
+            Err(json_error(Status::InternalServerError, format!("tilt_inhibits failed: {e}")))
+        }
+        Err(_) => {
+            warn!("tilt_inhibits timed out");
+            Err(json_error(Status::RequestTimeout, "tilt_inhibits timed out"))
+        }
+    }
+}
+
 // ---- Routes ----
 
 /// `GET /api/status` — FC connection state and session summary.
@@ -208,32 +232,118 @@ pub fn ws_events(
 /// arm endpoint is wired in M3.3 (sim-gs.sock integration).
 ///
 /// - **200** `{"status": "accepted"}` when FC is connected.
+/// - **409** `{"error": "..."}` while the FC's tilt lockout inhibits arming, which its arming gate refuses even overridden.
 /// - **503** `{"error": "..."}` when FC is disconnected.
 #[rocket::post("/commands/arm")]
 pub async fn arm(
     state: &State<AppState>,
 ) -> Result<Json<CommandSuccess>, Custom<Json<CommandError>>> {
-    let _client = state.get_fc_client().await?;
+    let client = state.get_fc_client().await?;
+    check_tilt(&client, proto::tilt::TiltAction::Arm).await?;
     info!("arm command accepted");
     Ok(Json(CommandSuccess {
         status: "accepted".into(),
     }))
 }
 
-/// `POST /api/commands/ignite` — ignite the rocket motor.
+/// `POST /api/commands/calibrate_imu` — start a guided IMU calibration.
 ///
-/// Placeholder for M3.2 — validates FC connectivity but the actual postcard-rpc
-/// ignite endpoint is wired in M3.3 (sim-gs.sock integration).
+/// Progress streams over the WebSocket as `imu_calibration` messages; the FC
+/// stores the profile once every sensor reports full calibration.
 ///
-/// - **200** `{"status": "accepted"}` when FC is connected.
+/// - **200** `{"status": "started"}` when the FC began calibrating.
+/// - **409** `{"error": "..."}` when a calibration is already running.
 /// - **503** `{"error": "..."}` when FC is disconnected.
-#[rocket::post("/commands/ignite")]
-pub async fn ignite(
+#[rocket::post("/commands/calibrate_imu")]
+pub async fn calibrate_imu(
     state: &State<AppState>,
 ) -> Result<Json<CommandSuccess>, Custom<Json<CommandError>>> {
-    let _client = state.get_fc_client().await?;
-    info!("ignite command accepted");
-    Ok(Json(CommandSuccess {
-        status: "accepted".into(),
-    }))
+    let client = state.get_fc_client().await?;
+
+    match tokio::time::timeout(
+        Config::ENDPOINT_TIMEOUT,
+        client.service::<proto::CalibrateImuEndpoint>(&()),
+    )
+    .await
+    {
+        Ok(Ok(true)) => {
+            info!("IMU calibration started");
+            Ok(Json(CommandSuccess { status: "started".into() }))
+        }
+        Ok(Ok(false)) => Err(json_error(Status::Conflict, "IMU calibration already running")),
+        Ok(Err(e)) => {
+            warn!("calibrate_imu failed: {e}");
+            Err(json_error(Status::InternalServerError, format!("calibrate_imu failed: {e}")))
+        }
+        Err(_) => {
+            warn!("calibrate_imu timed out");
+            Err(json_error(Status::RequestTimeout, "calibrate_imu timed out"))
+        }
+    }
+}
+
+/// `POST /api/commands/preflight_override?enabled=<bool>` — arm despite failing pre-flight checks, for the next arming only.
+///
+/// Only this command lifts the FC's arming gate, short of the tilt lockout; `enabled=false` restores it.
+/// The next `preflight` WebSocket message reflects the change.
+///
+/// - **200** `{"status": "overridden"}` or `{"status": "restored"}`.
+/// - **503** `{"error": "..."}` when FC is disconnected.
+#[rocket::post("/commands/preflight_override?<enabled>")]
+pub async fn preflight_override(
+    enabled: bool,
+    state: &State<AppState>,
+) -> Result<Json<CommandSuccess>, Custom<Json<CommandError>>> {
+    let client = state.get_fc_client().await?;
+
+    match tokio::time::timeout(
+        Config::ENDPOINT_TIMEOUT,
+        client.service::<proto::PreflightOverrideEndpoint>(&enabled),
+    )
+    .await
+    {
+        Ok(Ok(())) => {
+            warn!(enabled, "pre-flight override set");
+            let status = if enabled { "overridden" } else { "restored" };
+            Ok(Json(CommandSuccess { status: status.into() }))
+        }
+        Ok(Err(e)) => {
+            warn!("preflight_override failed: {e}");
+            Err(json_error(Status::InternalServerError, format!("preflight_override failed: {e}")))
+        }
+        Err(_) => {
+            warn!("preflight_override timed out");
+            Err(json_error(Status::RequestTimeout, "preflight_override timed out"))
+        }
+    }
+}
+
+/// `GET /api/flight_summary` — apogee, maxima and phase timings of the last flight.
+///
+/// - **200** the FC's `FlightSummary`, written at touchdown.
+/// - **404** `{"error": "..."}` before the FC landed.
+/// - **503** `{"error": "..."}` when FC is disconnected.
+#[rocket::get("/flight_summary")]
+pub async fn flight_summary(
+    state: &State<AppState>,
+) -> Result<Json<proto::flight_state::FlightSummary>, Custom<Json<CommandError>>> {
+    let client = state.get_fc_client().await?;
+
+    match tokio::time::timeout(
+        Config::ENDPOINT_TIMEOUT,
+        client.service::<proto::FlightSummaryEndpoint>(&()),
+    )
+    .await
+    {
+        Ok(Ok(Some(summary))) => Ok(Json(summary)),
+        Ok(Ok(None)) => Err(json_error(Status::NotFound, "no flight summary before touchdown")),
+        Ok(Err(e)) => {
+            warn!("flight_summary failed: {e}");
+            Err(json_error(Status::InternalServerError, format!("flight_summary failed: {e}")))
+        }
+        Err(_) => {
+            warn!("flight_summary timed out");
+            Err(json_error(Status::RequestTimeout, "flight_summary timed out"))
+        }
+    }
 }
@@ -2,7 +2,9 @@
 //!
 //! Writes received `Record`s to a session file at
 //! `logs/gs_records/<timestamp>/records.ndjson` and keeps an in-memory
-//! cache for REST API reads. The file is append-only within a session.
+//! cache for REST API reads. Trace spans pulled from the FC go to
+//! `trace_spans.ndjson` in the same directory. Both files are append-only
+//! within a session.
 
 use std::io::Write;
 
@@ -15,6 +17,8 @@ use crate::config::Config;
 /// Manages the NDJSON output file + in-memory record cache for one GS session.
 pub struct RecordStorage {
     file: std::io::BufWriter<std::fs::File>,
+    /// Trace spans, kept apart from telemetry for the `trace-report` tool.
+    trace_file: std::io::BufWriter<std::fs::File>,
     /// Number of records written so far.
     count: u64,
     /// Absolute session start time (UTC).
@@ -43,10 +47,14 @@ impl RecordStorage {
         let file = std::fs::File::create(&path)?;
         let file = std::io::BufWriter::new(file);
 
+        let trace_path = dir.join("trace_spans.ndjson");
+        let trace_file = std::io::BufWriter::new(std::fs::File::create(&trace_path)?);
+
         info!(path = %path.display(), "record storage opened");
 
         Ok(Self {
             file,
+            trace_file,
             count: 0,
             session_start: now_utc,
             records: Vec::new(),
@@ -61,14 +69,20 @@ impl RecordStorage {
     /// The caller should log and continue — storage failures are non-fatal
     /// for the GS process.
     pub fn append<T: Serialize>(&mut self, record: &T) -> anyhow::Result<()> {
-        let mut line = serde_json::to_vec(record)?;
-        line.push(b'\n');
-        self.file.write_all(&line)?;
-        self.file.flush()?;
+        write_line(&mut self.file, record)?;
         self.count = self.count.saturating_add(1);
         Ok(())
     }
 
+    /// Append one trace span to the session trace file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub fn store_trace_span(&mut self, span: &proto::trace::TraceSpan) -> anyhow::Result<()> {
+        write_line(&mut self.trace_file, span)
+    }
+
     pub fn count(&self) -> u64 {
         self.count
     }
@@ -95,3 +109,11 @@ impl RecordStorage {
         &self.records
     }
 }
+
+fn write_line<T: Serialize>(file: &mut std::io::BufWriter<std::fs::File>, value: &T) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    file.write_all(&line)?;
+    file.flush()?;
+    Ok(())
+}
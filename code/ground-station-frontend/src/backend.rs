@@ -22,9 +22,6 @@ pub trait BackendClient: Send + Sync {
     /// POST /api/commands/arm
     async fn arm(&self) -> anyhow::Result<()>;
 
-    /// POST /api/commands/ignite
-    async fn ignite(&self) -> anyhow::Result<()>;
-
     /// POST /api/commands/ping — returns latency in ms
     async fn ping(&self) -> anyhow::Result<Duration>;
 
@@ -76,10 +73,6 @@ impl BackendClient for WsBackend {
         self.post_json(Config::arm_url()).await
     }
 
-    async fn ignite(&self) -> anyhow::Result<()> {
-        self.post_json(Config::ignite_url()).await
-    }
-
     async fn ping(&self) -> anyhow::Result<Duration> {
         self.post_json::<PingSuccess>(Config::ping_url())
             .await
@@ -0,0 +1,48 @@
+//! Log decode — converts a binary on-board log into NDJSON records.
+//!
+//! Usage: `log-decode <log file> [output.ndjson]`
+//!
+//! Every intact record is written, one JSON object per line, to the output
+//! file (default: the input path with an `.ndjson` extension). Corrupted and
+//! truncated frames are reported on stderr and skipped.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use ground_station_frontend::log_decode::decode_log;
+use proto::record::Record;
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let input = PathBuf::from(args.next().context("usage: log-decode <log file> [output.ndjson]")?);
+    let output = args.next().map_or_else(|| input.with_extension("ndjson"), PathBuf::from);
+
+    let bytes = std::fs::read(&input)
+        .with_context(|| format!("failed to read {}", input.display()))?;
+    let log = decode_log::<Record>(&bytes);
+
+    match log.header {
+        Some(header) => eprintln!("boot {} at {} ticks/s, format v{}", header.boot_id, header.tick_hz, header.version),
+        None => eprintln!("warning: missing or corrupted log header"),
+    }
+    for (offset, e) in &log.corrupted {
+        eprintln!("warning: skipped corrupted frame at byte {offset}: {e}");
+    }
+    if log.truncated {
+        eprintln!("warning: log ends with a truncated frame");
+    }
+
+    let mut writer = std::io::BufWriter::new(
+        std::fs::File::create(&output).with_context(|| format!("failed to create {}", output.display()))?,
+    );
+    for record in &log.records {
+        serde_json::to_writer(&mut writer, record)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+
+    eprintln!("{} records written to {}", log.records.len(), output.display());
+    Ok(())
+}
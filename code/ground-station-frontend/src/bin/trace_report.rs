@@ -0,0 +1,40 @@
+//! Trace report — flame graph input and latency histograms from FC trace spans.
+//!
+//! Usage: `trace-report <trace_spans.ndjson> [tick_hz]`
+//!
+//! Writes `trace.folded` next to the input (feed it to `inferno-flamegraph`)
+//! and prints per-function latency histograms to stdout. `tick_hz` is the FC
+//! embassy-time tick rate, defaulting to [`DEFAULT_TICK_HZ`].
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use ground_station_frontend::trace_report::{folded_stacks, latency_histograms, parse_spans, render_histograms};
+
+/// embassy-time default tick rate.
+const DEFAULT_TICK_HZ: u64 = 1_000_000;
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let input = PathBuf::from(args.next().context("usage: trace-report <trace_spans.ndjson> [tick_hz]")?);
+    let tick_hz = match args.next() {
+        Some(hz) => hz.parse().context("tick_hz must be a positive integer")?,
+        None => DEFAULT_TICK_HZ,
+    };
+    anyhow::ensure!(tick_hz > 0, "tick_hz must be a positive integer");
+
+    let ndjson = std::fs::read_to_string(&input)
+        .with_context(|| format!("failed to read {}", input.display()))?;
+    let spans = parse_spans(&ndjson)
+        .map_err(|(line, e)| anyhow::anyhow!("{}:{line}: {e}", input.display()))?;
+
+    let folded_path = input.with_file_name("trace.folded");
+    std::fs::write(&folded_path, folded_stacks(&spans, tick_hz))
+        .with_context(|| format!("failed to write {}", folded_path.display()))?;
+
+    println!("{} spans, folded stacks written to {}\n", spans.len(), folded_path.display());
+    print!("{}", render_histograms(&latency_histograms(&spans, tick_hz)));
+
+    Ok(())
+}
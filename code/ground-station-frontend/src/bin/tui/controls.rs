@@ -65,36 +65,6 @@ fn render_commands(frame: &mut Frame, area: Rect, connected: bool, last_cmd: &Op
         ]));
     }
 
-    lines.push(Line::from(Span::raw("")));
-
-    // Ignite button.
-    let ignite_status = if connected {
-        ""
-    } else {
-        "  (FC disconnected)"
-    };
-    lines.push(Line::from(vec![
-        Span::styled("  [i] ", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(format!("Motor Ignition{ignite_status}")),
-    ]));
-
-    // Ignite last result.
-    if let Some(ref result) = *last_cmd
-        && result.contains("Ignite")
-    {
-        lines.push(Line::from(vec![
-            Span::raw("       Last: "),
-            Span::styled(
-                result.clone(),
-                Style::default().fg(if result.contains("OK") {
-                    Color::Green
-                } else {
-                    Color::Red
-                }),
-            ),
-        ]));
-    }
-
     let block = Block::default()
         .title(" Commands ")
         .borders(Borders::ALL);
@@ -152,7 +122,6 @@ fn render_keybinds(frame: &mut Frame, area: Rect) {
         )),
         Line::from(Span::raw("")),
         Line::from(Span::raw("  a = Arm System")),
-        Line::from(Span::raw("  i = Motor Ignition")),
         Line::from(Span::raw("  q / Ctrl+C = Quit")),
         Line::from(Span::raw("")),
         Line::from(Span::raw("  Tab / Shift+Tab = Switch tabs")),
@@ -150,6 +150,57 @@ fn format_sensor_lines(record: &proto::record::Record, _base_style: &Style) -> V
                 Style::default().fg(Color::Red),
             )));
         }
+        proto::record::RecordData::PadCalibration(data) => {
+            let alt = data.ground_altitude.value;
+            let press = data.ground_pressure.value;
+            let temp_c = data.ground_temperature.value - 273.15;
+            lines.push(Line::from(vec![
+                Span::styled("Pad Calibration:", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!("  {alt:.1} m    {press:.0} Pa    {temp_c:.1} C")),
+            ]));
+        }
+        proto::record::RecordData::Preflight(report) => {
+            let (label, color) = if report.arming_allowed { ("ready to arm", Color::Green) } else { ("not ready", Color::Red) };
+            lines.push(Line::from(vec![
+                Span::styled("Pre-flight:", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" {label} "), Style::default().fg(color)),
+            ]));
+        }
+        proto::record::RecordData::FlightSummary(summary) => {
+            let fmt = |value: Option<f32>, unit: &str| value.map_or_else(|| "--".to_string(), |v| format!("{v:.1} {unit}"));
+            lines.push(Line::from(vec![
+                Span::styled("Flight Summary:", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(
+                    "  apogee {}    max {}    {}    burn {}    descent {}",
+                    fmt(summary.apogee.map(|a| a.value), "m"),
+                    fmt(summary.max_velocity.map(|v| v.value), "m/s"),
+                    fmt(summary.max_acceleration.map(|a| a.value), "m/s²"),
+                    fmt(summary.burn_time.map(|t| t.value), "s"),
+                    fmt(summary.descent_rate.map(|v| v.value), "m/s"),
+                )),
+            ]));
+        }
+        proto::record::RecordData::Attitude(data) => {
+            let roll = data.roll.value.to_degrees();
+            let pitch = data.pitch.value.to_degrees();
+            let yaw = data.yaw.value.to_degrees();
+            let heading = if data.heading_referenced { "" } else { " (drifting)" };
+            lines.push(Line::from(vec![
+                Span::styled("Attitude:", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(
+                    "  roll {roll:.1}°    pitch {pitch:.1}°    yaw {yaw:.1}°{heading}"
+                )),
+            ]));
+        }
+        proto::record::RecordData::HighGAccelerometer(data) => {
+            let ax = data.acceleration.x.value;
+            let ay = data.acceleration.y.value;
+            let az = data.acceleration.z.value;
+            lines.push(Line::from(vec![
+                Span::styled("High-g:", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!("  accel(X: {ax:.1} Y: {ay:.1} Z: {az:.1})")),
+            ]));
+        }
     }
 
     lines
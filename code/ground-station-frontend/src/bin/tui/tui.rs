@@ -63,12 +63,6 @@ pub fn run_tui(state: &Arc<AppState<WsBackend>>) -> anyhow::Result<()> {
                             spawn_arm(state.clone());
                         }
 
-                        // Ignite.
-                        KeyCode::Char('i') | KeyCode::Char('I') => {
-                            info!("Ignite command issued");
-                            spawn_ignite(state.clone());
-                        }
-
                         _ => {}
                     }
                 }
@@ -104,8 +98,3 @@ fn spawn_arm(state: Arc<AppState<WsBackend>>) {
     let cmd_state = state.clone();
     spawn_cmd(state, "Arm", async move { cmd_state.backend.arm().await });
 }
-
-fn spawn_ignite(state: Arc<AppState<WsBackend>>) {
-    let cmd_state = state.clone();
-    spawn_cmd(state, "Ignite", async move { cmd_state.backend.ignite().await });
-}
@@ -24,6 +24,5 @@ impl Config {
 
     pub fn ws_url() -> String { Self::url(Self::WS_SCHEME, c::WS_PATH) }
     pub fn arm_url() -> String { Self::url(Self::HTTP_SCHEME, c::ARM_PATH) }
-    pub fn ignite_url() -> String { Self::url(Self::HTTP_SCHEME, c::IGNITE_PATH) }
     pub fn ping_url() -> String { Self::url(Self::HTTP_SCHEME, c::PING_PATH) }
 }
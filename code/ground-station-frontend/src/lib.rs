@@ -1,7 +1,9 @@
 pub mod backend;
 pub mod config;
 pub mod history;
+pub mod log_decode;
 pub mod state;
+pub mod trace_report;
 
 pub use backend::{BackendClient, WsBackend, WsMessage, WsStreamImpl};
 pub use config::Config;
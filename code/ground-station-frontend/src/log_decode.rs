@@ -0,0 +1,154 @@
+//! Decoder for binary on-board logs (see [`proto::log_format`]).
+//!
+//! Recovers every intact frame from a log pulled off the SD card, even when it
+//! was truncated by a power loss or has corrupted sectors: frames are split on
+//! the delimiter and each one stands or falls on its own CRC.
+
+use proto::log_format::{FRAME_DELIMITER, FrameError, LogHeader, decode_frame};
+use serde::de::DeserializeOwned;
+
+/// Everything recovered from one log file.
+#[derive(Debug)]
+pub struct DecodedLog<T> {
+    /// `None` if the first frame is missing, corrupted or not a supported header.
+    pub header: Option<LogHeader>,
+    pub records: Vec<T>,
+    /// Frames that failed to decode, with their byte offset in the file.
+    pub corrupted: Vec<(usize, FrameError)>,
+    /// The file ends in the middle of a frame.
+    pub truncated: bool,
+}
+
+/// Decodes a whole log file.
+pub fn decode_log<T: DeserializeOwned>(bytes: &[u8]) -> DecodedLog<T> {
+    let mut log = DecodedLog {
+        header: None,
+        records: Vec::new(),
+        corrupted: Vec::new(),
+        truncated: false,
+    };
+
+    let mut offset = 0;
+    let mut first_frame = true;
+    for chunk in bytes.split_inclusive(|&byte| byte == FRAME_DELIMITER) {
+        let start = offset;
+        offset += chunk.len();
+
+        let (frame, terminated) = match chunk.split_last() {
+            Some((&FRAME_DELIMITER, frame)) => (frame, true),
+            _ => (chunk, false),
+        };
+        // runs of delimiters, e.g. zero-filled sectors
+        if frame.is_empty() {
+            continue;
+        }
+
+        let mut frame = frame.to_vec();
+        if first_frame {
+            first_frame = false;
+            if let Ok(header) = decode_frame::<LogHeader>(&mut frame.clone())
+                && header.is_supported()
+            {
+                log.header = Some(header);
+                continue;
+            }
+        }
+
+        match decode_frame::<T>(&mut frame) {
+            Ok(record) => log.records.push(record),
+            Err(_) if !terminated => log.truncated = true,
+            Err(e) => log.corrupted.push((start, e)),
+        }
+    }
+
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::log_format::{encode_frame, max_frame_len};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Sample {
+        uid: u32,
+        altitude: f32,
+    }
+
+    fn frame<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut scratch = [0; 64];
+        let mut out = [0; max_frame_len(64)];
+        let len = encode_frame(value, &mut scratch, &mut out).unwrap();
+        out[..len].to_vec()
+    }
+
+    fn samples(count: u32) -> Vec<Sample> {
+        // uid 0 serializes to a zero byte, exercising COBS
+        (0..count).map(|uid| Sample { uid, altitude: uid as f32 * 1.5 }).collect()
+    }
+
+    fn log_file(header: &LogHeader, records: &[Sample]) -> Vec<u8> {
+        let mut bytes = frame(header);
+        for record in records {
+            bytes.extend(frame(record));
+        }
+        bytes
+    }
+
+    const HEADER: LogHeader = LogHeader::new(7, 1_000_000);
+
+    #[test]
+    fn decodes_intact_log() {
+        let log = decode_log::<Sample>(&log_file(&HEADER, &samples(20)));
+
+        assert_eq!(log.header, Some(HEADER));
+        assert_eq!(log.records, samples(20));
+        assert!(log.corrupted.is_empty());
+        assert!(!log.truncated);
+    }
+
+    #[test]
+    fn recovers_records_before_truncation() {
+        let mut bytes = log_file(&HEADER, &samples(5));
+        bytes.truncate(bytes.len() - 3);
+
+        let log = decode_log::<Sample>(&bytes);
+
+        assert_eq!(log.records, samples(4));
+        assert!(log.truncated);
+    }
+
+    #[test]
+    fn skips_corrupted_frames() {
+        let mut bytes = frame(&HEADER);
+        let records = samples(3);
+        bytes.extend(frame(&records[0]));
+        let corrupted_at = bytes.len();
+        let mut corrupted = frame(&records[1]);
+        // a payload byte, so only the CRC can catch it
+        corrupted[5] ^= 0x40;
+        bytes.extend(corrupted);
+        bytes.extend([0; 16]);
+        bytes.extend(frame(&records[2]));
+
+        let log = decode_log::<Sample>(&bytes);
+
+        assert_eq!(log.records.iter().map(|r| r.uid).collect::<Vec<_>>(), [0, 2]);
+        assert_eq!(log.corrupted, [(corrupted_at, FrameError::Crc)]);
+        assert!(!log.truncated);
+    }
+
+    #[test]
+    fn decodes_records_without_header() {
+        let mut bytes = log_file(&HEADER, &samples(3));
+        bytes[1] ^= 0xFF;
+
+        let log = decode_log::<Sample>(&bytes);
+
+        assert_eq!(log.header, None);
+        assert_eq!(log.records, samples(3));
+        assert_eq!(log.corrupted.len(), 1);
+    }
+}
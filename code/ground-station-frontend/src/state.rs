@@ -30,6 +30,12 @@ pub struct AppState<B: BackendClient> {
 
     // -- Events --
     pub transitions: Mutex<Vec<(Instant, proto::flight_state::FlightState)>>,
+    /// Progress of the running IMU calibration, `None` until the FC reports one.
+    pub imu_calibration: Mutex<Option<proto::sensor_data::ImuCalibrationStatus>>,
+    /// Latest pre-flight self-test, `None` until the FC reports one.
+    pub preflight: Mutex<Option<proto::preflight::PreflightReport>>,
+    /// Latest recovery beacon, `None` until the FC landed.
+    pub beacon: Mutex<Option<proto::beacon::Beacon>>,
 
     // -- Logs --
     pub log_buffer: Mutex<VecDeque<String>>,
@@ -67,6 +73,9 @@ impl<B: BackendClient> AppState<B> {
                 crate::config::Config::HISTORY_WINDOW,
             )),
             transitions: Mutex::new(Vec::new()),
+            imu_calibration: Mutex::new(None),
+            preflight: Mutex::new(None),
+            beacon: Mutex::new(None),
             log_buffer: Mutex::new(VecDeque::with_capacity(
                 crate::config::Config::LOG_BUFFER_CAPACITY,
             )),
@@ -140,6 +149,15 @@ async fn try_run_ws_reader<B: BackendClient>(
                 *state.status.lock().unwrap_or_else(|p| p.into_inner()) = status;
                 state.ping.fetch_xor(true, std::sync::atomic::Ordering::Relaxed);
             }
+            WsMessage::ImuCalibration(status) => {
+                *state.imu_calibration.lock().unwrap_or_else(|p| p.into_inner()) = Some(status);
+            }
+            WsMessage::Preflight(report) => {
+                *state.preflight.lock().unwrap_or_else(|p| p.into_inner()) = Some(report);
+            }
+            WsMessage::Beacon(beacon) => {
+                *state.beacon.lock().unwrap_or_else(|p| p.into_inner()) = Some(beacon);
+            }
             WsMessage::Log(_) => {}
         }
     }
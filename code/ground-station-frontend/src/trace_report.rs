@@ -0,0 +1,203 @@
+//! Offline analysis of FC trace spans pulled by the GS backend.
+//!
+//! Turns a session's `trace_spans.ndjson` into folded stacks (the input format
+//! of `inferno-flamegraph` / `flamegraph.pl`) and per-function latency
+//! histograms. Sync stages and await stages are always kept apart: time spent
+//! awaiting is not CPU time and would otherwise dominate every flame graph.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use proto::trace::{TraceFunction, TraceSpan};
+
+/// Whether a span measured execution or time spent awaiting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StageKind {
+    Sync,
+    Await,
+}
+
+impl StageKind {
+    pub fn of(span: &TraceSpan) -> Self {
+        if span.is_await() { Self::Await } else { Self::Sync }
+    }
+
+    pub fn frame(self) -> &'static str {
+        match self {
+            Self::Sync => "sync",
+            Self::Await => "await",
+        }
+    }
+}
+
+/// Parses one span per line, skipping blank lines.
+///
+/// # Errors
+///
+/// Returns the 1-based line number and parse error of the first malformed line.
+pub fn parse_spans(ndjson: &str) -> Result<Vec<TraceSpan>, (usize, serde_json::Error)> {
+    ndjson
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| serde_json::from_str(line).map_err(|e| (idx + 1, e)))
+        .collect()
+}
+
+fn ticks_to_micros(ticks: u64, tick_hz: u64) -> u64 {
+    u64::try_from(u128::from(ticks) * 1_000_000 / u128::from(tick_hz)).unwrap_or(u64::MAX)
+}
+
+/// Folded stacks, one `function;stage duration_us` line per function and stage kind.
+pub fn folded_stacks(spans: &[TraceSpan], tick_hz: u64) -> String {
+    let mut totals: BTreeMap<(String, StageKind), u64> = BTreeMap::new();
+    for span in spans {
+        *totals.entry((span.function.to_string(), StageKind::of(span))).or_default() +=
+            ticks_to_micros(span.duration_ticks(), tick_hz);
+    }
+
+    let mut folded = String::new();
+    for ((function, kind), micros) in totals {
+        let _ = writeln!(folded, "{function};{} {micros}", kind.frame());
+    }
+    folded
+}
+
+/// Latency distribution of one function and stage kind, in microseconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    /// Sorted durations.
+    samples: Vec<u64>,
+}
+
+impl LatencyHistogram {
+    pub fn count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Nearest-rank percentile, `p` in `0..=100`.
+    pub fn percentile(&self, p: usize) -> u64 {
+        let rank = (self.samples.len() * p).div_ceil(100).clamp(1, self.samples.len());
+        self.samples[rank - 1]
+    }
+
+    pub fn min(&self) -> u64 {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> u64 {
+        self.samples[self.samples.len() - 1]
+    }
+
+    /// Counts per power-of-two bucket: `(lower bound, count)`, the first bucket being `[0, 1)`.
+    pub fn buckets(&self) -> Vec<(u64, usize)> {
+        let mut buckets: BTreeMap<u64, usize> = BTreeMap::new();
+        for &micros in &self.samples {
+            let lower = if micros == 0 { 0 } else { 1_u64 << micros.ilog2() };
+            *buckets.entry(lower).or_default() += 1;
+        }
+        buckets.into_iter().collect()
+    }
+}
+
+/// Latency histograms per function and stage kind.
+pub fn latency_histograms(spans: &[TraceSpan], tick_hz: u64) -> BTreeMap<(TraceFunction, StageKind), LatencyHistogram> {
+    let mut histograms: BTreeMap<(TraceFunction, StageKind), LatencyHistogram> = BTreeMap::new();
+    for span in spans {
+        histograms
+            .entry((span.function, StageKind::of(span)))
+            .or_insert_with(|| LatencyHistogram { samples: Vec::new() })
+            .samples
+            .push(ticks_to_micros(span.duration_ticks(), tick_hz));
+    }
+    for histogram in histograms.values_mut() {
+        histogram.samples.sort_unstable();
+    }
+    histograms
+}
+
+/// Human-readable report of [`latency_histograms`].
+pub fn render_histograms(histograms: &BTreeMap<(TraceFunction, StageKind), LatencyHistogram>) -> String {
+    const BAR_WIDTH: usize = 40;
+
+    let mut report = String::new();
+    for ((function, kind), histogram) in histograms {
+        let _ = writeln!(
+            report,
+            "{function} [{}] n={} min={}us p50={}us p99={}us max={}us",
+            kind.frame(),
+            histogram.count(),
+            histogram.min(),
+            histogram.percentile(50),
+            histogram.percentile(99),
+            histogram.max(),
+        );
+
+        let buckets = histogram.buckets();
+        let peak = buckets.iter().map(|(_, count)| *count).max().unwrap_or(1);
+        for (lower, count) in buckets {
+            let upper = if lower == 0 { 1 } else { lower * 2 };
+            let bar = "#".repeat((count * BAR_WIDTH).div_ceil(peak));
+            let _ = writeln!(report, "  [{lower:>8}, {upper:>8}) us | {bar} {count}");
+        }
+        report.push('\n');
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICK_HZ: u64 = 1_000_000;
+
+    fn span(function: TraceFunction, stage: Option<u64>, duration: u64) -> TraceSpan {
+        TraceSpan { function, stage, start_ticks: 100, end_ticks: 100 + duration }
+    }
+
+    #[test]
+    fn parses_ndjson_spans() {
+        let spans = [span(TraceFunction::StorageFlush, Some(1), 7), span(TraceFunction::StorageNew, None, 3)];
+        let ndjson: String = spans.iter().map(|s| serde_json::to_string(s).unwrap() + "\n\n").collect();
+
+        assert_eq!(parse_spans(&ndjson).unwrap(), spans);
+        assert_eq!(parse_spans("{}\n").unwrap_err().0, 1);
+    }
+
+    #[test]
+    fn folds_sync_and_await_separately() {
+        let spans = [
+            span(TraceFunction::StorageFlush, Some(0), 10),
+            span(TraceFunction::StorageFlush, Some(1), 500),
+            span(TraceFunction::StorageFlush, Some(2), 5),
+            span(TraceFunction::StorageAppendRecord, None, 20),
+            span(TraceFunction::StorageAppendRecord, None, 22),
+        ];
+
+        assert_eq!(
+            folded_stacks(&spans, TICK_HZ),
+            "Storage::append_record;sync 42\nStorage::flush;sync 15\nStorage::flush;await 500\n",
+        );
+    }
+
+    #[test]
+    fn converts_ticks_to_micros() {
+        let spans = [span(TraceFunction::StorageNew, None, 32_768)];
+        assert_eq!(folded_stacks(&spans, 32_768), "Storage::new;sync 1000000\n");
+    }
+
+    #[test]
+    fn histogram_statistics() {
+        let spans: Vec<_> = (1..=100).map(|d| span(TraceFunction::StorageAppendRecord, None, d)).collect();
+        let histograms = latency_histograms(&spans, TICK_HZ);
+        let histogram = &histograms[&(TraceFunction::StorageAppendRecord, StageKind::Sync)];
+
+        assert_eq!(histogram.count(), 100);
+        assert_eq!((histogram.min(), histogram.max()), (1, 100));
+        assert_eq!((histogram.percentile(50), histogram.percentile(99)), (50, 99));
+        assert_eq!(
+            histogram.buckets(),
+            vec![(1, 1), (2, 2), (4, 4), (8, 8), (16, 16), (32, 32), (64, 37)],
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use core::cmp::Ordering;
+use core::time::Duration;
+
 use crate::{Serialize, Deserialize, Schema};
 
 
@@ -11,6 +14,87 @@ pub enum LedStatus {
     Off,
 }
 
+/* ------------------------------- LED Pattern ------------------------------ */
+
+/// Status encoding of a single LED (SW-8A), played by the flight computer and read back by the simulator.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LedPattern {
+    #[default]
+    Off,
+    Solid,
+    SlowBlink,
+    FastBlink,
+    /// `n` short pulses followed by a pause, for counting out codes.
+    Pulses(u8),
+    /// Double pulse once per period.
+    Heartbeat,
+}
+
+impl LedPattern {
+    /// Half period of [`Self::SlowBlink`], i.e. 1 Hz.
+    pub const SLOW_BLINK: Duration = Duration::from_millis(500);
+    /// Half period of [`Self::FastBlink`], i.e. 5 Hz.
+    pub const FAST_BLINK: Duration = Duration::from_millis(100);
+    /// Length of a single pulse of a code or heartbeat, and of the gap between pulses.
+    pub const PULSE: Duration = Duration::from_millis(150);
+    /// Dark time closing a code or heartbeat, long enough to tell where the count restarts.
+    pub const PAUSE: Duration = Duration::from_secs(1);
+
+    /// One step of the pattern's period: whether the LED is lit, and for how long.
+    fn segment(self, index: usize) -> Option<(bool, Duration)> {
+        const SLOW_BLINK: [(bool, Duration); 2] = [(true, LedPattern::SLOW_BLINK), (false, LedPattern::SLOW_BLINK)];
+        const FAST_BLINK: [(bool, Duration); 2] = [(true, LedPattern::FAST_BLINK), (false, LedPattern::FAST_BLINK)];
+        const HEARTBEAT: [(bool, Duration); 4] = [
+            (true, LedPattern::PULSE),
+            (false, LedPattern::PULSE),
+            (true, LedPattern::PULSE),
+            (false, LedPattern::PAUSE),
+        ];
+
+        match self {
+            Self::Off | Self::Solid => None,
+            Self::SlowBlink => SLOW_BLINK.get(index).copied(),
+            Self::FastBlink => FAST_BLINK.get(index).copied(),
+            Self::Heartbeat => HEARTBEAT.get(index).copied(),
+            Self::Pulses(pulses) => {
+                match index.cmp(&(2 * usize::from(pulses))) {
+                    Ordering::Less => Some((index.is_multiple_of(2), Self::PULSE)),
+                    Ordering::Equal => Some((false, Self::PAUSE)),
+                    Ordering::Greater => None,
+                }
+            },
+        }
+    }
+
+    /// The steps of one period in order, none for a steady pattern.
+    pub fn segments(self) -> impl Iterator<Item = (bool, Duration)> {
+        (0..).map_while(move |index| self.segment(index))
+    }
+
+    /// Whether the LED is lit `elapsed` after the pattern started, and the time until that changes.
+    #[must_use]
+    pub fn phase(self, elapsed: Duration) -> (bool, Option<Duration>) {
+        match self {
+            Self::Off => return (false, None),
+            Self::Solid => return (true, None),
+            _ => {},
+        }
+
+        let period: u128 = self.segments().map(|(_, duration)| duration.as_nanos()).sum();
+        let mut offset = elapsed.as_nanos() % period;
+        for (lit, duration) in self.segments() {
+            if offset < duration.as_nanos() {
+                // below a single segment, which fits in a u64 of nanoseconds
+                let offset = Duration::from_nanos(u64::try_from(offset).unwrap_or(u64::MAX));
+                return (lit, Some(duration.saturating_sub(offset)));
+            }
+            offset -= duration.as_nanos();
+        }
+        (false, None)
+    }
+}
+
 /* ----------------------------- Actuator Status ---------------------------- */
 
 #[defmt_or_log_macros::maybe_derive_format]
@@ -20,3 +104,37 @@ pub enum ActuatorStatus {
     #[default]
     Inactive,
 }
+
+#[test]
+fn steady_led_patterns_never_change() {
+    for (pattern, lit) in [(LedPattern::Off, false), (LedPattern::Solid, true)] {
+        assert_eq!(pattern.phase(Duration::ZERO), (lit, None));
+        assert_eq!(pattern.phase(Duration::from_millis(12_345)), (lit, None));
+    }
+}
+
+#[test]
+fn led_blink_repeats_every_period() {
+    let blink = LedPattern::SLOW_BLINK;
+
+    assert_eq!(LedPattern::SlowBlink.phase(Duration::ZERO), (true, Some(blink)));
+    assert_eq!(LedPattern::SlowBlink.phase(blink), (false, Some(blink)));
+    assert_eq!(LedPattern::SlowBlink.phase(blink * 2), (true, Some(blink)));
+    assert_eq!(LedPattern::SlowBlink.phase(blink * 5 + Duration::from_nanos(1)), (false, Some(blink - Duration::from_nanos(1))));
+}
+
+#[test]
+fn led_pulses_count_out_code() {
+    for pulses in [1, 3] {
+        let pattern = LedPattern::Pulses(pulses);
+
+        assert_eq!(pattern.segments().filter(|&(lit, _)| lit).count(), usize::from(pulses));
+        assert_eq!(pattern.segments().last().map(|(lit, _)| lit), Some(false));
+        assert_eq!(pattern.phase(LedPattern::PULSE * 2 * u32::from(pulses)), (false, Some(LedPattern::PAUSE)));
+    }
+}
+
+#[test]
+fn led_heartbeat_pulses_twice() {
+    assert!(LedPattern::Heartbeat.segments().map(|(lit, _)| lit).eq([true, false, true, false]));
+}
@@ -0,0 +1,15 @@
+use crate::{Deserialize, Serialize, Schema, flight_state::FlightSummary, sensor_data::{ElectricPotential, GpsData}};
+
+/// Downlinked at a low duty cycle once landed, to find the rocket.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq)]
+pub struct Beacon {
+    /// Last GPS sample with a fix.
+    pub fix: Option<GpsData>,
+    /// Milliseconds since `fix` was received.
+    pub fix_age_ms: Option<u32>,
+    /// `None` when the voltage could not be read.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub battery: Option<ElectricPotential>,
+    pub summary: Option<FlightSummary>,
+}
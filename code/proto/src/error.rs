@@ -13,8 +13,12 @@ pub enum FileSystemError {
     GetUniqueIdFailed,
     UniqueIdUnavailable,
     FileCreationFailed,
+    FailedToWriteHeader,
     FailedToSerializeRecord,
     WriteBufferTooSmall,
     FailedToWriteRecord,
     FailedToFlushFile,
+    FailedToCloseFile,
+    FailedToWriteIndex,
+    SegmentLimitReached,
 }
@@ -1,9 +1,20 @@
 use crate::{Deserialize, Serialize, Schema};
+use crate::flight_state::{FlightCheckpoint, FlightState};
+use crate::sensor_data::{Altitude, Angle, Velocity};
+use crate::sensor_health::{AltitudeSource, SensorFault, SensorHealth, SensorId};
+
+use derive_more::Display;
 
 #[defmt_or_log_macros::maybe_derive_format]
-#[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq)]
 pub enum Event {
     FileSystem(FileSystemEvent),
+    SensorHealth(SensorHealthEvent),
+    /// The flight computer was reset mid-flight and resumed from its last checkpoint.
+    FlightResumed(FlightCheckpoint),
+    Fsm(FsmEvent),
+    Watchdog(WatchdogEvent),
+    Tilt(TiltEvent),
 }
 
 #[defmt_or_log_macros::maybe_derive_format]
@@ -12,4 +23,136 @@ pub enum FileSystemEvent {
     UniqueFileCreated,
     RecordAppended,
     FileFlushed,
+    /// Records dropped since the previous report because storage fell behind.
+    RecordsDropped(u32),
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq, Eq)]
+pub enum SensorHealthEvent {
+    HealthChanged {
+        sensor: SensorId,
+        health: SensorHealth,
+        fault: Option<SensorFault>,
+    },
+    AltitudeSourceChanged(AltitudeSource),
+}
+
+/* ----------------------------- Watchdog Events ------------------------------ */
+
+/// Tasks feeding the flight computer's watchdog.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskId {
+    FlightStateMachine,
+    Sensor(SensorId),
+    Storage,
+    GroundStation,
+    SensorHealth,
+    Beacon,
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq, Eq)]
+pub enum WatchdogEvent {
+    /// `task` went `silent_ms` without feeding the watchdog, past its deadline for the flight phase.
+    DeadlineMissed {
+        task: TaskId,
+        silent_ms: u32,
+    },
+}
+
+/* ------------------------------- Tilt Events ------------------------------ */
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq)]
+pub enum TiltEvent {
+    /// The rocket turned past the boost limit, the flight lockout holds from here on.
+    BoostLimitExceeded {
+        /// From vertical.
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        tilt: Angle,
+    },
+}
+
+/* ------------------------------- FSM Events ------------------------------- */
+
+/// Altitude samples kept by the apogee detector for [`FsmEvent::ApogeeDetected`].
+pub const APOGEE_WINDOW: usize = 5;
+/// Size of the touchdown detector buffers reported in [`FsmEvent::TouchdownDetected`].
+pub const TOUCHDOWN_WINDOW: usize = 10;
+
+/// Decisions taken by the flight state machine, in the order they were taken.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq)]
+pub enum FsmEvent {
+    Transition {
+        from: FlightState,
+        to: FlightState,
+        reason: TransitionReason,
+    },
+    /// The detector started consuming altitude samples.
+    DetectorArmed(Detector),
+    ApogeeDetected {
+        /// Altitude above the launchpad that triggered the detection.
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        altitude: Altitude,
+        /// Latest altitudes above the launchpad, oldest first, `None` until the window filled up.
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        recent_altitudes: [Option<Altitude>; APOGEE_WINDOW],
+    },
+    TouchdownDetected {
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        altitude: Altitude,
+        /// Oldest first.
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        altitudes: [Altitude; TOUCHDOWN_WINDOW],
+        /// Oldest first.
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        velocities: [Velocity; TOUCHDOWN_WINDOW],
+    },
+    DeployAttempt {
+        /// Starts at 1.
+        attempt: u32,
+        outcome: DeployOutcome,
+    },
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum TransitionReason {
+    ArmCommand,
+    /// Apogee detected and deployment verified.
+    DeploymentVerified,
+    TouchdownDetected,
+    /// Restored from a checkpoint after a reset.
+    Resumed,
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum Detector {
+    Apogee,
+    Touchdown,
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum DeployOutcome {
+    /// `deploy()` was called.
+    Started,
+    DeployTimedOut,
+    DeployFailed,
+    VerifyTimedOut,
+    VerifyFailed,
+    /// `verify_deployment()` reported the deployment did not happen.
+    NotDeployed,
+    Verified,
+}
+
+impl DeployOutcome {
+    #[must_use]
+    pub const fn is_failure(self) -> bool {
+        !matches!(self, Self::Started | Self::Verified)
+    }
 }
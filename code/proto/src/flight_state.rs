@@ -1,4 +1,4 @@
-use crate::{Serialize, Deserialize, Schema};
+use crate::{Serialize, Deserialize, Schema, record::tick_hz::Timestamp, sensor_data::{Acceleration, Altitude, PadCalibration, Time, Velocity}};
 
 use derive_more::Display;
 
@@ -14,3 +14,60 @@ pub enum FlightState {
     RecoveryActivated,
     Touchdown,
 }
+
+/* ---------------------------- Flight Checkpoint --------------------------- */
+
+/// Persisted on every flight state change, so a reset mid-flight can resume where it left off.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq)]
+pub struct FlightCheckpoint {
+    pub state: FlightState,
+    /// Launchpad altitude in meters.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub launchpad_altitude: Option<Altitude>,
+    pub pad_calibration: Option<PadCalibration>,
+    /// When the rocket was armed, in ticks of the boot that wrote the checkpoint.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub armed_at: Option<Timestamp>,
+    /// When recovery was activated at apogee, in ticks of the boot that wrote the checkpoint.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub recovery_activated_at: Option<Timestamp>,
+    /// Boots that already resumed this flight, bounding a reset loop.
+    pub resumes: u8,
+}
+
+/* ----------------------------- Flight Summary ----------------------------- */
+
+/// Extrema and phase timings of a flight, written once at touchdown.
+///
+/// Each field stays `None` when the flight never produced it, e.g. no burnout without IMU data.
+/// Timestamps are ticks of the boot that tracked the flight.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Default)]
+pub struct FlightSummary {
+    /// Highest barometric altitude above the launchpad.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub apogee: Option<Altitude>,
+    /// Fastest climb rate, from the barometric altitude.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub max_velocity: Option<Velocity>,
+    /// Largest acceleration magnitude measured by the IMU.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub max_acceleration: Option<Acceleration>,
+    /// Time from launch to burnout.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub burn_time: Option<Time>,
+    /// Average descent rate from apogee to touchdown.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub descent_rate: Option<Velocity>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub armed_at: Option<Timestamp>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub launched_at: Option<Timestamp>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub burnout_at: Option<Timestamp>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub apogee_at: Option<Timestamp>,
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub touchdown_at: Option<Timestamp>,
+}
@@ -64,7 +64,13 @@ pub use uom;
 pub mod sensor_data;
 pub mod actuator_data;
 pub mod flight_state;
+pub mod sensor_health;
+pub mod preflight;
+pub mod beacon;
+pub mod tilt;
 pub mod event;
+pub mod trace;
+pub mod log_format;
 pub mod error;
 
 mod newtypes;
@@ -84,9 +90,15 @@ pub mod transport;
 
 use crate::record::Record;
 use crate::record::tick_hz::GlobalTickHz;
+use crate::trace::TraceSpan;
+use crate::sensor_data::ImuCalibrationStatus;
+use crate::preflight::PreflightReport;
+use crate::flight_state::FlightSummary;
+use crate::beacon::Beacon;
+use crate::tilt::TiltAction;
 
 #[cfg(feature = "simulator-endpoints")]
-use crate::{actuator_data::{ActuatorStatus, LedStatus}, flight_state::FlightState, sensor_data::{AltimeterData, GpsData, ImuData}};
+use crate::{actuator_data::{ActuatorStatus, LedStatus}, flight_state::FlightState, sensor_data::{AltimeterData, GpsData, HighGAccelerometerData, ImuData}};
 
 /* --------------------------------- Config --------------------------------- */
 
@@ -103,14 +115,22 @@ endpoints! {
     | ------------------------- | ----------------- | --------------------- | ------------------------- |
     | PingEndpoint              | PingRequest       | PingResponse          | "ping"                    |
     | GlobalTickHzEndpoint      | ()                | GlobalTickHz          | "embassy_time_tick_hz"    |
+    | TraceSpanEndpoint         | ()                | Option<TraceSpan>     | "trace_span"              |
+    | CalibrateImuEndpoint      | ()                | bool                  | "calibrate_imu"           |
+    | PreflightOverrideEndpoint | bool              | ()                    | "preflight_override"      |
+    | FlightSummaryEndpoint     | ()                | Option<FlightSummary> | "flight_summary"          |
+    | TiltInhibitsEndpoint      | TiltAction        | bool                  | "tilt_inhibits"           |
 }
 
 topics! {
     list = TOPICS_GS_OUT_LIST;
     direction = TopicDirection::ToClient;
-    | TopicTy                   | MessageTy         | Path                  |
-    | ------------------------- | ----------------- | --------------------- |
-    | RecordTopic               | Record            | "record"              |
+    | TopicTy                   | MessageTy            | Path                  |
+    | ------------------------- | -------------------- | --------------------- |
+    | RecordTopic               | Record               | "record"              |
+    | ImuCalibrationTopic       | ImuCalibrationStatus | "imu_calibration"     |
+    | PreflightReportTopic      | PreflightReport      | "preflight_report"    |
+    | BeaconTopic               | Beacon               | "beacon"              |
 }
 
 /* --- GS -> FC topics (empty — GS uses endpoints, not topics_in) --- */
@@ -134,6 +154,7 @@ topics! {
     | SimAltimeterTopic         | AltimeterData     | "sim_altimeter"       |
     | SimGpsTopic               | GpsData           | "sim_gps"             |
     | SimImuTopic               | ImuData           | "sim_imu"             |
+    | SimHighGAccelerometerTopic | HighGAccelerometerData | "sim_high_g_accelerometer" |
     | SimArmTopic               | ActuatorStatus    | "sim_arm"             |
 }
 
@@ -145,11 +166,13 @@ topics! {
     | ------------------------- | ----------------- | --------------------- |
     | SimDeploymentTopic        | ActuatorStatus    | "sim_deployment"      |
     | SimFlightStateTopic       | FlightState       | "sim_flight_state"    |
+    | SimBuzzerTopic            | ActuatorStatus    | "sim_buzzer"          |
     /* ------------------------------------------ LEDs ----------------------------------------------- */
     | SimPostcardLedTopic       | LedStatus         | "sim_postcard_led"    |
     | SimAltimeterLedTopic      | LedStatus         | "sim_altimeter_led"   |
     | SimGpsLedTopic            | LedStatus         | "sim_gps_led"         |
     | SimImuLedTopic            | LedStatus         | "sim_imu_led"         |
+    | SimHighGAccelerometerLedTopic | LedStatus     | "sim_high_g_accelerometer_led" |
     | SimArmLedTopic            | LedStatus         | "sim_arm_led"         |
     | SimFileSystemLedTopic     | LedStatus         | "sim_file_system_led" |
     | SimDeploymentLedTopic     | LedStatus         | "sim_deployment_led"  |
@@ -0,0 +1,163 @@
+//! Binary on-board log format.
+//!
+//! A log file is a sequence of frames, the first one holding a [`LogHeader`]
+//! and every following one a single record. Each frame is a postcard
+//! [`Frame`] of `postcard(value)` and its CRC-32, COBS-encoded and terminated
+//! by `0x00`, so after a power loss a reader can resynchronise on the next
+//! `0x00` and the CRC rejects torn or corrupted frames.
+//!
+//! The flight computer also keeps a [`LogIndex`] of the flights on the card,
+//! stored as a single frame in the same encoding.
+
+use crc::{CRC_32_ISO_HDLC, Crc};
+use derive_more::Display;
+use serde::de::Deserialize;
+
+use crate::Serialize;
+
+/// `"RLOG"`, little endian.
+pub const MAGIC: u32 = u32::from_le_bytes(*b"RLOG");
+pub const VERSION: u16 = 1;
+pub const FRAME_DELIMITER: u8 = 0x00;
+pub const CRC_SIZE: usize = 4;
+/// Longest postcard varint of a `u32`, prefixing the payload length.
+const LENGTH_PREFIX_SIZE: usize = 5;
+
+/// CRC-32/ISO-HDLC, the one used by zip and Ethernet.
+const CRC: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/* -------------------------------- Header -------------------------------- */
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogHeader {
+    pub magic: u32,
+    pub version: u16,
+    /// Distinguishes the logs of successive boots.
+    pub boot_id: u32,
+    /// embassy-time tick rate of the record timestamps.
+    pub tick_hz: u64,
+}
+
+impl LogHeader {
+    #[must_use]
+    pub const fn new(boot_id: u32, tick_hz: u64) -> Self {
+        Self {
+            magic: MAGIC,
+            version: VERSION,
+            boot_id,
+            tick_hz,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_supported(&self) -> bool {
+        self.magic == MAGIC && self.version == VERSION
+    }
+}
+
+/* -------------------------------- Index --------------------------------- */
+
+/// Flights tracked by a [`LogIndex`], the oldest being pruned to make room.
+pub const INDEX_MAX_FLIGHTS: usize = 16;
+
+/// Log files written during one flight.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlightLog {
+    pub flight: u16,
+    /// Files the log was rotated across, numbered from 0.
+    pub segments: u8,
+    /// Bytes on the card, measured at every boot; the open segment is only accounted for on rotation.
+    pub bytes: u32,
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LogIndex {
+    /// Incremented on every write, the newest of the index copies wins.
+    pub sequence: u32,
+    pub next_flight: u16,
+    /// Oldest first.
+    pub flights: [Option<FlightLog>; INDEX_MAX_FLIGHTS],
+}
+
+impl LogIndex {
+    pub fn flights(&self) -> impl Iterator<Item = &FlightLog> {
+        self.flights.iter().flatten()
+    }
+
+    #[must_use]
+    pub fn total_bytes(&self) -> u64 {
+        self.flights().map(|log| u64::from(log.bytes)).sum()
+    }
+}
+
+/* -------------------------------- Frames -------------------------------- */
+
+/// What each frame holds before COBS encoding.
+#[derive(Serialize, serde::Deserialize)]
+struct Frame<'a> {
+    /// The value, serialized with postcard.
+    payload: &'a [u8],
+    /// Little endian.
+    crc: [u8; CRC_SIZE],
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum FrameError {
+    Serialize,
+    BufferTooSmall,
+    /// Invalid COBS encoding.
+    Cobs,
+    /// Torn frame, or CRC mismatch.
+    Crc,
+    Deserialize,
+}
+
+/// Worst-case size of a frame carrying `payload_len` serialized bytes, delimiter included.
+#[must_use]
+pub const fn max_frame_len(payload_len: usize) -> usize {
+    let len = LENGTH_PREFIX_SIZE + payload_len + CRC_SIZE;
+    len + len / 254 + 2
+}
+
+const fn serialize_error(error: &postcard::Error) -> FrameError {
+    match error {
+        postcard::Error::SerializeBufferFull => FrameError::BufferTooSmall,
+        _ => FrameError::Serialize,
+    }
+}
+
+/// Encodes `value` as one frame into `out`, using `scratch` for the serialized payload.
+///
+/// # Errors
+///
+/// Returns [`FrameError::BufferTooSmall`] if either buffer cannot hold the frame,
+/// or [`FrameError::Serialize`] if `value` cannot be serialized.
+pub fn encode_frame<T: Serialize>(value: &T, scratch: &mut [u8], out: &mut [u8]) -> Result<usize, FrameError> {
+    let payload = postcard::to_slice(value, scratch).map_err(|e| serialize_error(&e))?;
+    let frame = Frame { crc: CRC.checksum(payload).to_le_bytes(), payload };
+
+    // ends with the delimiter
+    let encoded = postcard::to_slice_cobs(&frame, out).map_err(|e| serialize_error(&e))?;
+    Ok(encoded.len())
+}
+
+/// Decodes one frame, given with or without its delimiter. `frame` is COBS-decoded in place.
+///
+/// # Errors
+///
+/// Returns an error if the frame is not valid COBS, fails its CRC, or does not hold a `T`.
+pub fn decode_frame<'a, T: Deserialize<'a>>(frame: &'a mut [u8]) -> Result<T, FrameError> {
+    let frame: Frame<'a> = postcard::from_bytes_cobs(frame).map_err(|e| match e {
+        postcard::Error::DeserializeBadEncoding => FrameError::Cobs,
+        _ => FrameError::Crc,
+    })?;
+    if CRC.checksum(frame.payload).to_le_bytes() != frame.crc {
+        return Err(FrameError::Crc);
+    }
+
+    postcard::from_bytes(frame.payload).map_err(|_| FrameError::Deserialize)
+}
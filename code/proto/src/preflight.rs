@@ -0,0 +1,71 @@
+use crate::{Deserialize, Serialize, Schema};
+
+use derive_more::Display;
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum PreflightCheck {
+    Altimeter,
+    Imu,
+    Gps,
+    GpsFix,
+    Storage,
+    /// Continuity of the recovery deployment circuit.
+    Deployment,
+    /// The rocket standing near vertical.
+    Tilt,
+}
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Default, Display)]
+pub enum CheckStatus {
+    /// Not enough data yet, e.g. no sample received.
+    #[default]
+    Pending,
+    Passed,
+    Failed,
+    /// The hardware cannot perform the check.
+    Unsupported,
+}
+
+impl CheckStatus {
+    /// Passed, or could not be checked at all.
+    #[must_use]
+    pub const fn allows_arming(self) -> bool {
+        matches!(self, Self::Passed | Self::Unsupported)
+    }
+}
+
+/// Self-test results while pre-armed, published whenever one of them changes.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct PreflightReport {
+    /// Fresh and plausible data from each sensor.
+    pub altimeter: CheckStatus,
+    pub imu: CheckStatus,
+    pub gps: CheckStatus,
+    pub gps_fix: CheckStatus,
+    /// Log writes and flushes succeeding.
+    pub storage: CheckStatus,
+    pub deployment: CheckStatus,
+    pub tilt: CheckStatus,
+    /// Set from the ground station, to arm despite failing checks. Never lifts the tilt lockout.
+    pub overridden: bool,
+    /// Whether an arm command is accepted.
+    pub arming_allowed: bool,
+}
+
+impl PreflightReport {
+    #[must_use]
+    pub const fn get(&self, check: PreflightCheck) -> CheckStatus {
+        match check {
+            PreflightCheck::Altimeter => self.altimeter,
+            PreflightCheck::Imu => self.imu,
+            PreflightCheck::Gps => self.gps,
+            PreflightCheck::GpsFix => self.gps_fix,
+            PreflightCheck::Storage => self.storage,
+            PreflightCheck::Deployment => self.deployment,
+            PreflightCheck::Tilt => self.tilt,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use derive_more::From;
 
-use crate::{Serialize, Deserialize, Schema, error::Error, event::Event, flight_state::FlightState, sensor_data::{AltimeterData, GpsData, ImuData}, record::{tick_hz::Timestamp, uid::Uid}};
+use crate::{Serialize, Deserialize, Schema, error::Error, event::Event, flight_state::{FlightState, FlightSummary}, preflight::PreflightReport, sensor_data::{AltimeterData, AttitudeData, GpsData, HighGAccelerometerData, ImuData, PadCalibration}, record::{tick_hz::Timestamp, uid::Uid}};
 
 pub mod tick_hz;
 pub mod uid;
@@ -13,6 +13,11 @@ pub enum RecordData {
     FlightState(FlightState),
     Event(Event),
     Error(Error),
+    PadCalibration(PadCalibration),
+    Preflight(PreflightReport),
+    FlightSummary(FlightSummary),
+    Attitude(AttitudeData),
+    HighGAccelerometer(HighGAccelerometerData),
 }
 
 #[derive(Serialize, Deserialize, Schema, Clone, Debug, PartialEq)]
@@ -48,7 +53,7 @@ impl Record {
 mod impls {
     use crate::record::{tick_hz::Timestamp, uid::Uid};
 
-    use super::{Record, RecordData, AltimeterData, GpsData, ImuData, FlightState, Event, Error};
+    use super::{Record, RecordData, AltimeterData, AttitudeData, GpsData, HighGAccelerometerData, ImuData, FlightState, Event, Error, PadCalibration, PreflightReport, FlightSummary};
 
     impl From<AltimeterData> for Record {
         fn from(value: AltimeterData) -> Self {
@@ -109,4 +114,54 @@ mod impls {
             }
         }
     }
+
+    impl From<PadCalibration> for Record {
+        fn from(value: PadCalibration) -> Self {
+            Self {
+                timestamp: Timestamp::now(),
+                uid: Uid::generate_id(),
+                payload: RecordData::from(value),
+            }
+        }
+    }
+
+    impl From<PreflightReport> for Record {
+        fn from(value: PreflightReport) -> Self {
+            Self {
+                timestamp: Timestamp::now(),
+                uid: Uid::generate_id(),
+                payload: RecordData::from(value),
+            }
+        }
+    }
+
+    impl From<FlightSummary> for Record {
+        fn from(value: FlightSummary) -> Self {
+            Self {
+                timestamp: Timestamp::now(),
+                uid: Uid::generate_id(),
+                payload: RecordData::from(value),
+            }
+        }
+    }
+
+    impl From<AttitudeData> for Record {
+        fn from(value: AttitudeData) -> Self {
+            Self {
+                timestamp: Timestamp::now(),
+                uid: Uid::generate_id(),
+                payload: RecordData::from(value),
+            }
+        }
+    }
+
+    impl From<HighGAccelerometerData> for Record {
+        fn from(value: HighGAccelerometerData) -> Self {
+            Self {
+                timestamp: Timestamp::now(),
+                uid: Uid::generate_id(),
+                payload: RecordData::from(value),
+            }
+        }
+    }
 }
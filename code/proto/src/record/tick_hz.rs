@@ -8,6 +8,14 @@ pub struct GlobalTickHz{ hz: u64, nano_hz: u32 }
 #[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, From)]
 pub struct Timestamp{ ticks: u64 }
 
+impl Timestamp {
+    /// Raw ticks since startup, at the recording FC's tick frequency.
+    #[must_use]
+    pub const fn ticks(&self) -> u64 {
+        self.ticks
+    }
+}
+
 #[cfg(feature = "timestamp-into-duration")]
 mod into_duration_impls {
     use core::time::Duration;
@@ -1,8 +1,8 @@
 use crate::{Deserialize, Serialize, Schema, FixTypeWrapper, NaiveTimeWrapper};
 
 pub use nmea;
-pub use nalgebra::Vector3;
-pub use uom::si::f32::{Acceleration, Angle, AngularVelocity, Length, MagneticFluxDensity, Pressure, Time, ThermodynamicTemperature, Velocity};
+pub use nalgebra::{UnitQuaternion, Vector3};
+pub use uom::si::f32::{Acceleration, Angle, AngularVelocity, ElectricPotential, Length, MagneticFluxDensity, Pressure, Time, ThermodynamicTemperature, Velocity};
 
 /* ------------------------------ Type Aliases ------------------------------ */
 
@@ -72,3 +72,89 @@ pub struct ImuData {
     #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
     pub temperature: ThermodynamicTemperature,
 }
+
+/* -------------------------- High-g Accelerometer Data ----------------------- */
+
+/// Acceleration from the high-range accelerometer, in the IMU frame. Coarser than
+/// [`ImuData::acceleration`] but keeps measuring once the IMU saturates during boost.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq)]
+pub struct HighGAccelerometerData {
+    /// Acceleration vector in m/s^2 units.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub acceleration: Vector3<Acceleration>,
+}
+
+/* ------------------------------ Attitude Data ----------------------------- */
+
+/// Orientation estimated on board from [`ImuData`], in a ground frame with x towards
+/// magnetic north and z up.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq)]
+pub struct AttitudeData {
+    /// Unit quaternion rotating the IMU frame into the ground frame, as `[w, x, y, z]`.
+    pub quaternion: [f32; 4],
+    /// Rotation about the IMU x axis.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub roll: Angle,
+    /// Rotation about the IMU y axis.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub pitch: Angle,
+    /// Rotation about the IMU z axis, from magnetic north.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub yaw: Angle,
+    /// Whether the magnetometer held the yaw, otherwise it drifts with the gyro.
+    pub heading_referenced: bool,
+}
+
+/* ----------------------------- Imu Calibration ---------------------------- */
+
+/// Size of the BNO055 offset and radius registers.
+pub const IMU_CALIBRATION_PROFILE_SIZE: usize = 22;
+
+/// Calibration level of each BNO055 subsystem, from 0 (uncalibrated) to 3 (fully calibrated).
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ImuCalibrationStatus {
+    pub system: u8,
+    pub gyroscope: u8,
+    pub accelerometer: u8,
+    pub magnetometer: u8,
+}
+
+impl ImuCalibrationStatus {
+    pub const FULLY_CALIBRATED: u8 = 3;
+
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.system >= Self::FULLY_CALIBRATED
+            && self.gyroscope >= Self::FULLY_CALIBRATED
+            && self.accelerometer >= Self::FULLY_CALIBRATED
+            && self.magnetometer >= Self::FULLY_CALIBRATED
+    }
+}
+
+/// BNO055 calibration offsets, read once fully calibrated and restored at the next boot.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImuCalibrationProfile {
+    pub offsets: [u8; IMU_CALIBRATION_PROFILE_SIZE],
+}
+
+/* ----------------------------- Pad Calibration ---------------------------- */
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq)]
+pub struct PadCalibration {
+    /// Averaged pressure on the launchpad in Pascal.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub ground_pressure: Pressure,
+    /// Averaged temperature on the launchpad, stored in kelvin like every `ThermodynamicTemperature`.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub ground_temperature: ThermodynamicTemperature,
+    /// Averaged altitude of the launchpad in meters.
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub ground_altitude: Altitude,
+    /// Number of altimeter samples averaged.
+    pub samples: u16,
+}
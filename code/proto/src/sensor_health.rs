@@ -0,0 +1,80 @@
+use crate::{Serialize, Deserialize, Schema};
+
+use derive_more::Display;
+
+
+/* --------------------------------- Sensor Id -------------------------------- */
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum SensorId {
+    Altimeter,
+    Gps,
+    Imu,
+    HighGAccelerometer,
+}
+
+/* ------------------------------- Sensor Health ------------------------------ */
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Default, Display)]
+pub enum SensorHealth {
+    /// No sample received yet.
+    #[default]
+    Unknown,
+    Healthy,
+    /// Data is still used, but at least one plausibility check is failing.
+    Degraded,
+    /// Data must not be trusted.
+    Failed,
+}
+
+/// Plausibility check responsible for a health downgrade.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum SensorFault {
+    /// No new sample within the configured timeout.
+    Stale,
+    /// Bit-identical samples for too long.
+    Stuck,
+    /// Physically implausible rate of change between samples.
+    RateOfChange,
+    /// Disagrees with a redundant sensor.
+    Disagreement,
+}
+
+/* ------------------------------ Altitude Source ----------------------------- */
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Default, Display)]
+pub enum AltitudeSource {
+    #[default]
+    Barometer,
+    Gps,
+}
+
+/* ------------------------------- Health Report ------------------------------ */
+
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct SensorHealthReport {
+    pub altimeter: SensorHealth,
+    pub gps: SensorHealth,
+    pub imu: SensorHealth,
+    /// Stays [`SensorHealth::Unknown`] on boards without a high-g accelerometer.
+    pub high_g_accelerometer: SensorHealth,
+    /// Sensor currently feeding the flight detectors.
+    pub altitude_source: AltitudeSource,
+}
+
+impl SensorHealthReport {
+    #[must_use]
+    pub const fn get(&self, sensor: SensorId) -> SensorHealth {
+        match sensor {
+            SensorId::Altimeter => self.altimeter,
+            SensorId::Gps => self.gps,
+            SensorId::Imu => self.imu,
+            SensorId::HighGAccelerometer => self.high_g_accelerometer,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::{Deserialize, Serialize, Schema};
+
+use derive_more::Display;
+
+/// Actions a tilt lockout may inhibit, the flight computer's config tells which lockout inhibits which.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum TiltAction {
+    Arm,
+    /// Igniting the motor from the ground station.
+    Launch,
+    /// Igniting a sustainer or a motor cluster in flight.
+    AirStart,
+}
@@ -0,0 +1,43 @@
+use crate::{Serialize, Deserialize, Schema};
+
+use derive_more::Display;
+
+/* ----------------------------- Trace Function ----------------------------- */
+
+/// Instrumented function, kept as an enum so spans stay small on the wire.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum TraceFunction {
+    #[display("Storage::new")]
+    StorageNew,
+    #[display("Storage::append_record")]
+    StorageAppendRecord,
+    #[display("Storage::flush")]
+    StorageFlush,
+}
+
+/* ------------------------------- Trace Span ------------------------------- */
+
+/// Time spent in one stage of an instrumented function, in embassy-time ticks.
+#[defmt_or_log_macros::maybe_derive_format]
+#[derive(Serialize, Deserialize, Schema, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceSpan {
+    pub function: TraceFunction,
+    /// `None` for synchronous functions. For async functions, even stages are
+    /// synchronous execution and odd stages are time spent awaiting.
+    pub stage: Option<u64>,
+    pub start_ticks: u64,
+    pub end_ticks: u64,
+}
+
+impl TraceSpan {
+    #[must_use]
+    pub const fn duration_ticks(&self) -> u64 {
+        self.end_ticks.saturating_sub(self.start_ticks)
+    }
+
+    #[must_use]
+    pub const fn is_await(&self) -> bool {
+        matches!(self.stage, Some(stage) if stage % 2 == 1)
+    }
+}
@@ -10,18 +10,18 @@ pub use crate::record::tick_hz::Timestamp;
 pub use crate::transport::ipc::*;
 
 // Postcard-rpc Endpoints
-pub use crate::{ENDPOINT_LIST, PingEndpoint, GlobalTickHzEndpoint};
+pub use crate::{ENDPOINT_LIST, PingEndpoint, GlobalTickHzEndpoint, TraceSpanEndpoint, CalibrateImuEndpoint, PreflightOverrideEndpoint, FlightSummaryEndpoint, TiltInhibitsEndpoint};
 
 // GS-facing Topics
-pub use crate::{TOPICS_GS_IN_LIST, TOPICS_GS_OUT_LIST, RecordTopic};
+pub use crate::{TOPICS_GS_IN_LIST, TOPICS_GS_OUT_LIST, RecordTopic, ImuCalibrationTopic, PreflightReportTopic, BeaconTopic};
 
 // Simulator-facing Topics (cfg-gated)
 #[cfg(feature = "simulator-endpoints")]
 pub use crate::{
     TOPICS_SIM_IN_LIST, TOPICS_SIM_OUT_LIST,
-    SimAltimeterTopic, SimGpsTopic, SimImuTopic, SimArmTopic,
-    SimDeploymentTopic, SimFlightStateTopic,
+    SimAltimeterTopic, SimGpsTopic, SimImuTopic, SimHighGAccelerometerTopic, SimArmTopic,
+    SimDeploymentTopic, SimFlightStateTopic, SimBuzzerTopic,
     SimPostcardLedTopic, SimAltimeterLedTopic, SimGpsLedTopic,
-    SimImuLedTopic, SimArmLedTopic, SimFileSystemLedTopic,
+    SimImuLedTopic, SimHighGAccelerometerLedTopic, SimArmLedTopic, SimFileSystemLedTopic,
     SimDeploymentLedTopic, SimGroundStationLedTopic,
 };
@@ -0,0 +1,57 @@
+//! Flies the physics model headless and prints what the IMU measured as CSV, for the flight
+//! computer's attitude tests to replay.
+
+use std::sync::Arc;
+
+use proto::sensor_data::ImuData;
+use proto::uom::si::{
+    acceleration::meter_per_second_squared, angle::degree, angular_velocity::radian_per_second, time::second,
+    velocity::meter_per_second,
+};
+
+use simulator::config::SimulatorConfig;
+use simulator::physics::engine::PhysicsEngine;
+use simulator::types::ForceEvent;
+
+/// Standing on the pad before ignition.
+const PAD_TIME_S: f32 = 1.0;
+/// Recorded past apogee, under canopy.
+const AFTER_APOGEE_S: f32 = 3.0;
+
+fn main() {
+    let mut engine = PhysicsEngine::new(Arc::default());
+    let steps_per_sample = SimulatorConfig::DATA_ACQUISITION_INTERVAL.as_millis()
+        / SimulatorConfig::PHYSICS_TIME_STEP_INTERVAL.as_millis();
+
+    println!("# cargo run -p simulator --bin imu_trace: {PAD_TIME_S} s on the pad, launch, apogee plus {AFTER_APOGEE_S} s under canopy.");
+    println!("# time (s), acceleration y, z (m/s^2), gyro x (rad/s), true tilt (deg)");
+
+    let mut apogee = None;
+    for step in 0_u128.. {
+        let state = engine.state();
+        let time = state.time.get::<second>();
+
+        if state.motor_ignited.is_none() && time >= PAD_TIME_S {
+            engine.handle_force_event(ForceEvent::MotorThrust);
+        }
+        if state.motor_ignited.is_some() && !state.is_motor_burning() && state.velocity.get::<meter_per_second>() < 0.0 {
+            engine.handle_force_event(ForceEvent::Recovery);
+            apogee.get_or_insert(time);
+        }
+        if apogee.is_some_and(|apogee| time > apogee + AFTER_APOGEE_S) || state.has_touched_down() {
+            break;
+        }
+
+        if step % steps_per_sample == 0 {
+            let tilt = state.tilt.get::<degree>();
+            let imu = ImuData::from(state);
+            println!(
+                "{time:.3},{:.4},{:.4},{:.5},{tilt:.3}",
+                imu.acceleration.y.get::<meter_per_second_squared>(),
+                imu.acceleration.z.get::<meter_per_second_squared>(),
+                imu.gyro.x.get::<radian_per_second>(),
+            );
+        }
+        engine.step();
+    }
+}
@@ -1,10 +1,12 @@
+use proto::actuator_data::LedPattern;
 use proto::sensor_data::{Altitude, GpsCoordinates, Pressure, ThermodynamicTemperature};
 use proto::uom::si::pressure::pascal;
 use proto::uom::si::thermodynamic_temperature::degree_celsius;
 use tokio::time::Duration;
 
-use proto::uom::si::f32::{Acceleration, Force, Mass, Time, Velocity};
+use proto::uom::si::f32::{Acceleration, Angle, Force, Mass, Time, Velocity};
 use proto::uom::si::acceleration::meter_per_second_squared;
+use proto::uom::si::angle::degree;
 use proto::uom::si::force::newton;
 use proto::uom::si::mass::gram;
 use proto::uom::si::length::meter;
@@ -38,6 +40,16 @@ impl SimulatorConfig {
     pub fn launchpad_altitude() -> Altitude { Altitude::new::<meter>(90.0) }
     pub const LAUNCHPAD_COORDINATES: GpsCoordinates = GpsCoordinates { latitude: 47.397742, longitude: 8.545594 };
     pub fn touch_down_altitude() -> Altitude { Altitude::new::<meter>(86.0) }
+    /// Rail angle from vertical, turned further over by a gravity turn in flight. Altitude is still
+    /// integrated along vertical, only the IMU sees the attitude.
+    pub fn launch_tilt() -> Angle { Angle::new::<degree>(3.0) }
+    /// Below this the rail, and then too little airflow over the fins, keep the attitude.
+    pub fn rail_exit_velocity() -> Velocity { Velocity::new::<meter_per_second>(10.0) }
+
+    // Accelerometers, each axis clips at its full scale
+    /// The BNO055's, which the motor's thrust of about 4.6 g exceeds, so boost is read off the high-g accelerometer.
+    pub fn imu_accelerometer_range() -> Acceleration { Acceleration::new::<meter_per_second_squared>(4.0 * 9.806_65) }
+    pub fn high_g_accelerometer_range() -> Acceleration { Acceleration::new::<meter_per_second_squared>(200.0 * 9.806_65) }
 
     // Scripted scenario delays (wall-clock)
     // Set to `None` to skip the corresponding event entirely.
@@ -71,11 +83,21 @@ impl Config {
 
     // TUI
     pub const TUI_REFRESH_RATE: u64 = 60;
-    pub const PHYSICS_PANEL_HEIGHT: u16 = 9;
+    pub const PHYSICS_PANEL_HEIGHT: u16 = 10;
     pub const EVENTS_PANEL_HEIGHT: u16 = 8;
     pub const ACTUATOR_PANEL_HEIGHT: u16 = 6;
     pub const LOG_PANEL_MIN_HEIGHT: u16 = 5;
 
+    // LED pattern decoding, each threshold halfway between the `LedPattern` timings it tells apart
+    pub const LED_TRACE_PHASES: usize = 16;
+    /// Without an edge for this long the LED is steadily on or off.
+    pub const LED_STEADY_AFTER: Duration = Duration::from_millis(1_500);
+    pub fn led_fast_blink_max() -> Duration { (LedPattern::FAST_BLINK + LedPattern::PULSE) / 2 }
+    pub fn led_slow_blink_min() -> Duration { (LedPattern::PULSE + LedPattern::SLOW_BLINK) / 2 }
+    pub fn led_pause_min() -> Duration { (LedPattern::SLOW_BLINK + LedPattern::PAUSE) / 2 }
+    /// A heartbeat closes on the pause alone, a code on the gap after its last pulse and the pause.
+    pub fn led_heartbeat_pause_max() -> Duration { LedPattern::PAUSE + LedPattern::PULSE / 2 }
+
     // Connection retry
     pub const CONNECT_MAX_ATTEMPTS: u32 = 20;
     pub const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
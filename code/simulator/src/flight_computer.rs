@@ -7,12 +7,13 @@ use proto::PostcardClient;
 use proto::{
     actuator_data::ActuatorStatus,
     flight_state::FlightState,
-    sensor_data::{AltimeterData, GpsData, ImuData},
+    sensor_data::{AltimeterData, GpsData, HighGAccelerometerData, ImuData},
 };
 use proto::wire::{
-    SimAltimeterLedTopic, SimAltimeterTopic, SimArmLedTopic, SimArmTopic, SimDeploymentLedTopic,
+    SimAltimeterLedTopic, SimAltimeterTopic, SimArmLedTopic, SimArmTopic, SimBuzzerTopic, SimDeploymentLedTopic,
     SimDeploymentTopic, SimFileSystemLedTopic, SimFlightStateTopic, SimGpsLedTopic, SimGpsTopic,
-    SimGroundStationLedTopic, SimImuLedTopic, SimImuTopic, SimPostcardLedTopic,
+    SimGroundStationLedTopic, SimHighGAccelerometerLedTopic, SimHighGAccelerometerTopic, SimImuLedTopic, SimImuTopic,
+    SimPostcardLedTopic,
 };
 use tokio::{
     sync::{mpsc, watch},
@@ -133,11 +134,13 @@ async fn run_publisher(
 async fn publish_sensors(client: &PostcardClient, state: PhysicsState) -> anyhow::Result<()> {
     let altimeter: AltimeterData = state.clone().into();
     let gps: GpsData = state.clone().into();
-    let imu: ImuData = state.into();
+    let imu: ImuData = state.clone().into();
+    let high_g_accelerometer: HighGAccelerometerData = state.into();
 
     client.publish::<SimAltimeterTopic>(&altimeter).await?;
     client.publish::<SimGpsTopic>(&gps).await?;
     client.publish::<SimImuTopic>(&imu).await?;
+    client.publish::<SimHighGAccelerometerTopic>(&high_g_accelerometer).await?;
     Ok(())
 }
 
@@ -150,10 +153,12 @@ async fn run_subscriber(
 ) -> anyhow::Result<()> {
     let mut deploy_sub = client.subscribe::<SimDeploymentTopic>().await?;
     let mut flight_state_sub = client.subscribe::<SimFlightStateTopic>().await?;
+    let mut buzzer_sub = client.subscribe::<SimBuzzerTopic>().await?;
     let mut postcard_led_sub = client.subscribe::<SimPostcardLedTopic>().await?;
     let mut altimeter_led_sub = client.subscribe::<SimAltimeterLedTopic>().await?;
     let mut gps_led_sub = client.subscribe::<SimGpsLedTopic>().await?;
     let mut imu_led_sub = client.subscribe::<SimImuLedTopic>().await?;
+    let mut high_g_accelerometer_led_sub = client.subscribe::<SimHighGAccelerometerLedTopic>().await?;
     let mut arm_led_sub = client.subscribe::<SimArmLedTopic>().await?;
     let mut file_system_led_sub = client.subscribe::<SimFileSystemLedTopic>().await?;
     let mut deployment_led_sub = client.subscribe::<SimDeploymentLedTopic>().await?;
@@ -171,6 +176,23 @@ async fn run_subscriber(
         };
     }
 
+    macro_rules! recv_led {
+        ($sub:expr, $field:ident) => {
+            async {
+                $sub.recv().await
+                    .map(|val| {
+                        let now = time::Instant::now();
+                        actuator_tx.rcu(|s| {
+                            let mut s = **s;
+                            s.$field.record(val, now);
+                            s
+                        });
+                    })
+                    .context(concat!(stringify!($field), " subscription closed: FC and simulator desynced"))
+            }
+        };
+    }
+
     loop {
         tokio::select! {
             _ = cancel.cancelled() => anyhow::bail!("function cancelled"),
@@ -182,21 +204,25 @@ async fn run_subscriber(
                 physics_tx.send(ForceEvent::Recovery).await.with_context(|| "physics trigger receiver dropped")?;
             }
 
+            status = recv_actuator!(buzzer_sub, buzzer) => { status?; }
+
             // Receive LED status updates from the FC (non-blocking, writes to shared snapshot)
-            status = recv_actuator!(postcard_led_sub, postcard_led)             => { status?; }
-            status = recv_actuator!(altimeter_led_sub, altimeter_led)           => { status?; }
-            status = recv_actuator!(gps_led_sub, gps_led)                       => { status?; }
-            status = recv_actuator!(imu_led_sub, imu_led)                       => { status?; }
-            status = recv_actuator!(arm_led_sub, arm_led)                       => { status?; }
-            status = recv_actuator!(file_system_led_sub, file_system_led)       => { status?; }
-            status = recv_actuator!(deployment_led_sub, deployment_led)         => { status?; }
-            status = recv_actuator!(ground_station_led_sub, ground_station_led) => { status?; }
+            status = recv_led!(postcard_led_sub, postcard_led)             => { status?; }
+            status = recv_led!(altimeter_led_sub, altimeter_led)           => { status?; }
+            status = recv_led!(gps_led_sub, gps_led)                       => { status?; }
+            status = recv_led!(imu_led_sub, imu_led)                       => { status?; }
+            status = recv_led!(high_g_accelerometer_led_sub, high_g_accelerometer_led) => { status?; }
+            status = recv_led!(arm_led_sub, arm_led)                       => { status?; }
+            status = recv_led!(file_system_led_sub, file_system_led)       => { status?; }
+            status = recv_led!(deployment_led_sub, deployment_led)         => { status?; }
+            status = recv_led!(ground_station_led_sub, ground_station_led) => { status?; }
 
             // Forward flight state updates from the FC to the simulator
             state = flight_state_sub.recv() => {
                 let state = state.context("SimFlightStateTopic subscription closed: FC and simulator desynced")?;
 
                 info!("flight state received from FC: {state:?}");
+                actuator_tx.rcu(|s| SimActuatorSnapshot { flight_state: state, ..**s });
                 if fc_state_tx.send(state).is_err() {
                     warn!("no receivers for FC state — discarding flight state update");
                 }
@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
 use proto::uom::si::{
-    f32::{Force, Velocity},
+    acceleration::meter_per_second_squared,
+    angle::{degree, radian},
+    angular_velocity::radian_per_second,
+    f32::{Angle, AngularVelocity, Force, Velocity},
     time::second,
+    velocity::meter_per_second,
 };
 
 use crate::{
@@ -68,11 +72,15 @@ impl PhysicsEngine {
         s.velocity += s.acceleration * dt;
         s.altitude += s.velocity * dt;
 
+        s.tilt_rate = gravity_turn_rate(s);
+        s.tilt = (s.tilt + Angle::new::<radian>((s.tilt_rate * dt).value)).min(Angle::new::<degree>(90.0));
+
         let landing_alt = SimulatorConfig::touch_down_altitude();
         if s.altitude <= landing_alt {
             s.altitude = landing_alt;
             s.velocity = Velocity::default();
             s.acceleration = proto::uom::si::f32::Acceleration::default();
+            s.tilt_rate = AngularVelocity::default();
             s.touched_down = Some(s.time);
         }
 
@@ -83,3 +91,18 @@ impl PhysicsEngine {
         self.state.clone()
     }
 }
+
+/// Pitch rate of a gravity turn: once off the rail, gravity bends the climbing flight path over at
+/// `g·sin(tilt)/v`, and the rocket weathercocks along it. Under canopy the attitude is held.
+fn gravity_turn_rate(state: &PhysicsState) -> AngularVelocity {
+    let turning = state.is_flying()
+        && state.recovery_deployed.is_none()
+        && state.velocity > SimulatorConfig::rail_exit_velocity();
+    if !turning {
+        return AngularVelocity::default();
+    }
+
+    let gravity = SimulatorConfig::gravity().get::<meter_per_second_squared>();
+    let rate = gravity * state.tilt.get::<radian>().sin() / state.velocity.get::<meter_per_second>();
+    AngularVelocity::new::<radian_per_second>(rate)
+}
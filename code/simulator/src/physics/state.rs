@@ -1,12 +1,15 @@
 use chrono::Local;
 use proto::
     sensor_data::{
-        Acceleration, AltimeterData, Altitude, AngularVelocity, GpsCoordinates, GpsData, ImuData,
+        Acceleration, AltimeterData, Altitude, AngularVelocity, GpsCoordinates, GpsData, HighGAccelerometerData, ImuData,
         MagneticFluxDensity, Time, Velocity, Vector3,
         nmea::sentences::FixType,
     }
 ;
 
+use proto::uom::si::angle::radian;
+use proto::uom::si::f32::Angle;
+
 use crate::config::SimulatorConfig;
 use crate::types::ForceEvent;
 
@@ -17,6 +20,9 @@ pub struct PhysicsState {
     pub velocity: Velocity,
     pub acceleration: Acceleration,
     pub coordinates: GpsCoordinates,
+    /// Body axis from vertical, about the IMU's x axis.
+    pub tilt: Angle,
+    pub tilt_rate: AngularVelocity,
 
     pub motor_ignited: Option<Time>,
     pub recovery_deployed: Option<Time>,
@@ -53,6 +59,16 @@ impl PhysicsState {
             vec![]
         }
     }
+
+    /// What an accelerometer with full scale `range` on each axis reads, in the tilted IMU frame.
+    fn measured_acceleration(&self, range: Acceleration) -> Vector3<Acceleration> {
+        // accelerometers measure gravity as well, pointing up while standing still
+        let specific_force = self.acceleration + SimulatorConfig::gravity();
+        let (sin, cos) = self.tilt.get::<radian>().sin_cos();
+        let clip = |axis: Acceleration| axis.max(-range).min(range);
+
+        Vector3::new(Acceleration::default(), clip(specific_force * sin), clip(specific_force * cos))
+    }
 }
 
 impl Default for PhysicsState {
@@ -60,6 +76,8 @@ impl Default for PhysicsState {
         Self {
             altitude: SimulatorConfig::launchpad_altitude(),
             coordinates: SimulatorConfig::LAUNCHPAD_COORDINATES,
+            tilt: SimulatorConfig::launch_tilt(),
+            tilt_rate: AngularVelocity::default(),
             time: Time::default(),
             velocity: Velocity::default(),
             acceleration: Acceleration::default(),
@@ -84,17 +102,24 @@ impl From<PhysicsState> for ImuData {
     fn from(value: PhysicsState) -> Self {
         let gyro = AngularVelocity::default();
         let mag = MagneticFluxDensity::default();
-        let zero_accel = Acceleration::default();
 
         ImuData {
-            acceleration: Vector3::new(zero_accel, zero_accel, value.acceleration),
-            gyro: Vector3::new(gyro, gyro, gyro),
+            acceleration: value.measured_acceleration(SimulatorConfig::imu_accelerometer_range()),
+            gyro: Vector3::new(value.tilt_rate, gyro, gyro),
             mag: Vector3::new(mag, mag, mag),
             temperature: SimulatorConfig::ambient_temperature(),
         }
     }
 }
 
+impl From<PhysicsState> for HighGAccelerometerData {
+    fn from(value: PhysicsState) -> Self {
+        HighGAccelerometerData {
+            acceleration: value.measured_acceleration(SimulatorConfig::high_g_accelerometer_range()),
+        }
+    }
+}
+
 impl From<PhysicsState> for GpsData {
     fn from(value: PhysicsState) -> Self {
         GpsData {
@@ -4,34 +4,54 @@ use ratatui::{
     style::{Color, Style},
     widgets::{Block, Borders, Paragraph},
 };
-use proto::actuator_data::LedStatus;
+use proto::actuator_data::{ActuatorStatus, LedPattern, LedStatus};
+use tokio::time::Instant;
 
-use crate::types::SimActuatorSnapshot;
+use crate::types::{LedTrace, SimActuatorSnapshot};
+
+fn pattern_label(pattern: LedPattern) -> String {
+    match pattern {
+        LedPattern::Off => String::from("Off"),
+        LedPattern::Solid => String::from("Solid"),
+        LedPattern::SlowBlink => String::from("Slow blink"),
+        LedPattern::FastBlink => String::from("Fast blink"),
+        LedPattern::Pulses(pulses) => format!("{pulses} pulses"),
+        LedPattern::Heartbeat => String::from("Heartbeat"),
+    }
+}
 
 pub(super) fn render_actuators(f: &mut Frame, area: Rect, act: &SimActuatorSnapshot) {
     let block = Block::default()
-        .title(" Actuators ")
+        .title(format!(" Actuators — {} ", act.flight_state))
         .borders(Borders::ALL);
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let leds: [(&str, Color, LedStatus); 8] = [
-        ("Postcard", Color::Red, act.postcard_led),
-        ("Altimeter", Color::Red, act.altimeter_led),
-        ("GPS", Color::Red, act.gps_led),
-        ("IMU", Color::Red, act.imu_led),
-        ("Arm", Color::Green, act.arm_led),
-        ("File System", Color::Red, act.file_system_led),
-        ("Deployment", Color::Rgb(255, 165, 0), act.deployment_led),
-        ("Ground Station", Color::Red, act.ground_station_led),
+    let now = Instant::now();
+    let led = |trace: &LedTrace| {
+        let pattern = trace.pattern(now).map_or_else(|| String::from("…"), pattern_label);
+        (trace.status == LedStatus::On, pattern)
+    };
+
+    let indicators: [(&str, Color, (bool, String)); 10] = [
+        ("Postcard", Color::Red, led(&act.postcard_led)),
+        ("Altimeter", Color::Red, led(&act.altimeter_led)),
+        ("GPS", Color::Red, led(&act.gps_led)),
+        ("IMU", Color::Red, led(&act.imu_led)),
+        ("High-g", Color::Red, led(&act.high_g_accelerometer_led)),
+        ("Arm", Color::Green, led(&act.arm_led)),
+        ("File System", Color::Red, led(&act.file_system_led)),
+        ("Deployment", Color::Rgb(255, 165, 0), led(&act.deployment_led)),
+        ("Ground Station", Color::Red, led(&act.ground_station_led)),
+        ("Buzzer", Color::Yellow, (act.buzzer == ActuatorStatus::Active, String::new())),
     ];
 
     let cells = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Ratio(1, 8); 8])
+        .constraints([Constraint::Ratio(1, 10); 10])
         .split(inner);
 
-    for (i, (label, color, status)) in leds.iter().enumerate() {
+    for (i, (label, color, (lit, pattern))) in indicators.iter().enumerate() {
         let cell = cells[i];
         let led_chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -44,9 +64,9 @@ pub(super) fn render_actuators(f: &mut Frame, area: Rect, act: &SimActuatorSnaps
         let inner = block.inner(led_chunks[0]);
         f.render_widget(block, led_chunks[0]);
 
-        if *status == LedStatus::On {
-            f.render_widget(Paragraph::new("").style(Style::default().bg(*color)), inner);
-        }
+        // the pattern name sits inside the LED, dark on a lit LED
+        let style = if *lit { Style::default().fg(Color::Black).bg(*color) } else { Style::default().fg(Color::Gray) };
+        f.render_widget(Paragraph::new(pattern.as_str()).style(style).alignment(Alignment::Center), inner);
         f.render_widget(
             Paragraph::new(*label)
                 .style(Style::default().fg(Color::White))
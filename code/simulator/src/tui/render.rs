@@ -6,6 +6,8 @@ use ratatui::{
 };
 use proto::uom::si::{
     acceleration::meter_per_second_squared,
+    angle::degree,
+    angular_velocity::degree_per_second,
     length::meter,
     time::second,
     velocity::meter_per_second,
@@ -71,6 +73,7 @@ pub(super) fn render(
          Altitude  : {:.2} m\n\
          Velocity  : {:.3} m/s\n\
          Accel     : {:.3} m/s²\n\
+         Tilt      : {:.2}° ({:.2}°/s)\n\
          Ignited   : {}\n\
          Deployed  : {}\n\
          TouchDown : {}",
@@ -78,6 +81,8 @@ pub(super) fn render(
         phys.altitude.get::<meter>(),
         phys.velocity.get::<meter_per_second>(),
         phys.acceleration.get::<meter_per_second_squared>(),
+        phys.tilt.get::<degree>(),
+        phys.tilt_rate.get::<degree_per_second>(),
         phys.motor_ignited.map_or("no", |_| "YES"),
         phys.recovery_deployed.map_or("no", |_| "YES"),
         phys.touched_down.map_or("no", |_| "YES"),
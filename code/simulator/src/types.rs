@@ -2,10 +2,12 @@ use std::sync::Arc;
 
 use arc_swap::ArcSwap;
 use derive_more::Display;
-use proto::actuator_data::{ActuatorStatus, LedStatus};
+use proto::actuator_data::{ActuatorStatus, LedPattern, LedStatus};
+use proto::flight_state::FlightState;
 use proto::uom::si::f32::{Force, Time, Velocity};
+use tokio::time::{Duration, Instant};
 
-use crate::config::SimulatorConfig;
+use crate::config::{Config, SimulatorConfig};
 use crate::physics::state::PhysicsState;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
@@ -84,17 +86,140 @@ impl ActiveForceEvent {
     }
 }
 
+/// An LED's status and its latest lit and dark phases, to tell which pattern the FC is playing.
+///
+/// The FC only publishes the edges, so the pattern is read back from how long each phase lasted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LedTrace {
+    pub status: LedStatus,
+    last_edge: Option<Instant>,
+    /// Completed phases, newest last, alternating between lit and dark.
+    phases: [Duration; Config::LED_TRACE_PHASES],
+    len: usize,
+}
+
+impl LedTrace {
+    pub fn record(&mut self, status: LedStatus, now: Instant) {
+        if status == self.status {
+            return;
+        }
+        if let Some(last_edge) = self.last_edge {
+            if self.len == self.phases.len() {
+                self.phases.rotate_left(1);
+                self.len -= 1;
+            }
+            self.phases[self.len] = now - last_edge;
+            self.len += 1;
+        }
+        self.status = status;
+        self.last_edge = Some(now);
+    }
+
+    /// `None` until a full period was seen.
+    pub fn pattern(&self, now: Instant) -> Option<LedPattern> {
+        let steady = self.last_edge.is_none_or(|edge| now - edge > Config::LED_STEADY_AFTER);
+        if steady {
+            return Some(if self.status == LedStatus::On { LedPattern::Solid } else { LedPattern::Off });
+        }
+
+        // walk back from the newest phase, which was lit if the LED is now off, counting the pulses
+        // of the last group closed by a pause on both ends
+        let mut lit = self.status == LedStatus::Off;
+        let mut newest_lit = None;
+        let mut closing_pause = None;
+        let mut pulses = 0;
+        for &phase in self.phases[..self.len].iter().rev() {
+            if lit {
+                if phase >= Config::led_slow_blink_min() {
+                    return Some(LedPattern::SlowBlink);
+                }
+                newest_lit.get_or_insert(phase);
+                if closing_pause.is_some() {
+                    pulses += 1;
+                }
+            } else if phase >= Config::led_pause_min() {
+                match closing_pause {
+                    None => closing_pause = Some(phase),
+                    Some(pause) if pulses == 2 && pause < Config::led_heartbeat_pause_max() => return Some(LedPattern::Heartbeat),
+                    Some(_) => return Some(LedPattern::Pulses(pulses)),
+                }
+            }
+            lit = !lit;
+        }
+
+        let fast = closing_pause.is_none() && newest_lit.is_some_and(|phase| phase < Config::led_fast_blink_max());
+        fast.then_some(LedPattern::FastBlink)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SimActuatorSnapshot {
-    pub postcard_led: LedStatus,
-    pub altimeter_led: LedStatus,
-    pub gps_led: LedStatus,
-    pub imu_led: LedStatus,
-    pub arm_led: LedStatus,
-    pub file_system_led: LedStatus,
-    pub deployment_led: LedStatus,
-    pub ground_station_led: LedStatus,
+    pub postcard_led: LedTrace,
+    pub altimeter_led: LedTrace,
+    pub gps_led: LedTrace,
+    pub imu_led: LedTrace,
+    pub high_g_accelerometer_led: LedTrace,
+    pub arm_led: LedTrace,
+    pub file_system_led: LedTrace,
+    pub deployment_led: LedTrace,
+    pub ground_station_led: LedTrace,
     pub deployment: ActuatorStatus,
+    pub buzzer: ActuatorStatus,
+    /// Shown next to the LEDs to cross-check their patterns.
+    pub flight_state: FlightState,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays a few periods of `pattern` into a trace, edge by edge as the FC publishes them.
+    fn play(pattern: LedPattern, start: Instant) -> (LedTrace, Instant) {
+        let mut trace = LedTrace::default();
+        let mut now = start;
+        for _ in 0..3 {
+            for (lit, duration) in pattern.segments() {
+                trace.record(if lit { LedStatus::On } else { LedStatus::Off }, now);
+                now += duration;
+            }
+        }
+        (trace, now)
+    }
+
+    #[test]
+    fn decodes_every_periodic_pattern() {
+        let patterns = [
+            LedPattern::SlowBlink,
+            LedPattern::FastBlink,
+            LedPattern::Heartbeat,
+            LedPattern::Pulses(1),
+            LedPattern::Pulses(2),
+            LedPattern::Pulses(3),
+        ];
+
+        for pattern in patterns {
+            let (trace, now) = play(pattern, Instant::now());
+            assert_eq!(trace.pattern(now), Some(pattern));
+        }
+    }
+
+    #[test]
+    fn decodes_steady_patterns() {
+        let start = Instant::now();
+        let mut trace = LedTrace::default();
+        assert_eq!(trace.pattern(start), Some(LedPattern::Off));
+
+        trace.record(LedStatus::On, start);
+        assert_eq!(trace.pattern(start + Config::LED_STEADY_AFTER * 2), Some(LedPattern::Solid));
+    }
 
+    #[test]
+    fn waits_for_a_full_period() {
+        let start = Instant::now();
+        let mut trace = LedTrace::default();
+        trace.record(LedStatus::On, start);
+        trace.record(LedStatus::Off, start + LedPattern::PULSE);
+
+        assert_eq!(trace.pattern(start + LedPattern::PULSE * 2), None);
+    }
+}
@@ -26,5 +26,4 @@ pub const GS_PORT: u16 = 8000;
 pub const API_PATH: &str = "/api";
 pub const WS_PATH: &str = "/records";
 pub const ARM_PATH: &str = "/commands/arm";
-pub const IGNITE_PATH: &str = "/commands/ignite";
 pub const PING_PATH: &str = "/commands/ping";
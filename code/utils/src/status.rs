@@ -28,4 +28,7 @@ pub enum WsMessage {
     Record(proto::record::Record),
     Log(String),
     Status(Status),
+    ImuCalibration(proto::sensor_data::ImuCalibrationStatus),
+    Preflight(proto::preflight::PreflightReport),
+    Beacon(proto::beacon::Beacon),
 }